@@ -0,0 +1,30 @@
+use ccx_binance::ApiCred;
+use ccx_binance::BinanceResult;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::spot::SpotApi;
+use ccx_binance_examples_util::*;
+
+#[actix_rt::main]
+async fn main() {
+    let _ = main_().await;
+}
+
+async fn main_() -> BinanceResult<()> {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    let binance_margin = SpotApi::<ApiCred>::from_env().margin();
+
+    print_res(binance_margin.account(TimeWindow::now())?.await)?;
+    print_res(
+        binance_margin
+            .max_borrowable("USDT", None::<&str>, TimeWindow::now())?
+            .await,
+    )?;
+    print_res(
+        binance_margin
+            .max_transferable("USDT", None::<&str>, TimeWindow::now())?
+            .await,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! Parses a stored `exchangeInfo` response and validates orders against the symbol filters.
+//!
+//! Uses only DTOs and pure utilities, so it works without the `with_network` feature.
+
+use ccx_binance::api::spot::ExchangeInformation;
+use rust_decimal_macros::dec;
+
+const EXCHANGE_INFO: &str = include_str!("data/exchange_info.json");
+
+fn main() {
+    let info: ExchangeInformation =
+        serde_json::from_str(EXCHANGE_INFO).expect("Failed to parse exchangeInfo");
+    println!("Server Time: {}", info.server_time);
+
+    for symbol in &info.symbols {
+        println!(
+            "{}: {}/{} {:?}",
+            symbol.symbol, symbol.base_asset, symbol.quote_asset, symbol.status
+        );
+        for (price, qty) in [
+            (dec!(65000.01), dec!(0.001)),
+            (dec!(65000.001), dec!(0.001)),
+        ] {
+            match symbol.validate_order(price, qty) {
+                Ok(()) => println!("  {qty} @ {price}: ok"),
+                Err(violation) => println!("  {qty} @ {price}: {violation}"),
+            }
+        }
+    }
+}
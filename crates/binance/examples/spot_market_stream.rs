@@ -0,0 +1,45 @@
+use ccx_binance::ApiCred;
+use ccx_binance::BinanceResult;
+use ccx_binance::ChartInterval;
+use ccx_binance::SpotApi;
+use ccx_binance::ws_stream::WsEvent;
+use ccx_binance::ws_stream::WsStream;
+use futures::StreamExt;
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    if let Err(e) = main_binance().await {
+        log::error!("binance err: {:?}", e);
+    }
+}
+
+async fn main_binance() -> BinanceResult<()> {
+    let binance_spot = SpotApi::<ApiCred>::from_env();
+
+    let mut events = binance_spot.ws().await?.subscribe([
+        ("btcusdt", WsStream::BookTicker),
+        ("btcusdt", WsStream::AggTrade),
+        ("btcusdt", WsStream::Kline(ChartInterval::Minute1)),
+    ])?;
+
+    while let Some(event) = events.next().await {
+        match event.map(|e| e.event) {
+            Ok(WsEvent::BookTicker(ticker)) => {
+                println!(
+                    "{} book: {} / {}",
+                    ticker.symbol, ticker.best_bid_price, ticker.best_ask_price
+                );
+            }
+            Ok(WsEvent::AggTrade(trade)) => {
+                println!("{} trade: {} @ {}", trade.symbol, trade.qty, trade.price);
+            }
+            Ok(event) => log::debug!("{:?}", event),
+            Err(e) => log::warn!("stream error: {:?}", e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,298 @@
+//! Two-sided quoter skeleton for the Spot Testnet.
+//!
+//! Shows how the building blocks of the crate are meant to be composed:
+//!
+//! * config: credentials and proxy are read from `CCX_BINANCE_API_*` env vars (see
+//!   `.env-example`), quoting parameters from `QUOTER_*` env vars;
+//! * time sync: the client measures the server clock offset and stamps signed requests
+//!   with it, see `Config::with_time_sync`;
+//! * symbol registry: exchange info indexed by symbol, prices and quantities are snapped to
+//!   the symbol filters and validated locally before they are sent;
+//! * book ticker cache: shared by the quoting loop and the [`OrderGuard`], kept up to date
+//!   by the `bookTicker` stream;
+//! * user data stream supervisor: `SpotApi::start_user_data_stream` keeps the listen key
+//!   alive and recreates it when it expires;
+//! * guarded idempotent order placement: every order carries a deterministic client order id,
+//!   so a request with an unknown outcome is resolved by looking the order up instead of
+//!   sending it again;
+//! * graceful shutdown on Ctrl-C: quotes are cancelled, the listen key is closed and
+//!   the rate limiter is drained.
+//!
+//! Every clone of `SpotApi` shares the same rate limiter, so the api is cloned into
+//! background tasks rather than created again.
+//!
+//! Without `CCX_BINANCE_API_KEY` (or with `QUOTER_DRY_RUN=1`) the quoter runs in dry-run mode:
+//! only public endpoints are used and orders are printed instead of being placed.
+//!
+//! ```sh
+//! QUOTER_MAX_CYCLES=3 cargo run -p ccx-binance --example spot_quoter
+//! ```
+
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+
+use ccx_binance::ApiCred;
+use ccx_binance::Atom;
+use ccx_binance::BinanceResult;
+use ccx_binance::Decimal;
+use ccx_binance::Proxy;
+use ccx_binance::SpotApi;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::spot::API_BASE_TESTNET;
+use ccx_binance::api::spot::BookTicker;
+use ccx_binance::api::spot::NewOrderRequest;
+use ccx_binance::api::spot::OrderResponseType;
+use ccx_binance::api::spot::OrderSide;
+use ccx_binance::api::spot::OrderType;
+use ccx_binance::api::spot::STREAM_BASE_TESTNET;
+use ccx_binance::api::spot::Symbol;
+use ccx_binance::api::spot::TimeInForce;
+use ccx_binance::api::spot::WS_API_BASE_TESTNET;
+use ccx_binance::api::spot::util::BookTickerCache;
+use ccx_binance::api::spot::util::OrderGuard;
+use ccx_binance::ccx_api_lib::env_var_with_prefix;
+use ccx_binance::client::CCX_BINANCE_API_PREFIX;
+use ccx_binance::client::Config;
+use ccx_binance::ws_stream::MarketEvent;
+use ccx_binance::ws_stream::WsEvent;
+use ccx_binance::ws_stream::WsStream;
+use futures::StreamExt;
+use futures::future;
+use futures::future::Either;
+use url::Url;
+
+/// How often the client measures the server clock offset again.
+const TIME_SYNC_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+struct Settings {
+    symbol: Atom,
+    /// Relative distance of the quotes from the best bid and ask.
+    spread: Decimal,
+    /// Size of each quote in the quote asset.
+    quote_qty: Decimal,
+    interval: Duration,
+    /// Stop after this many cycles, run until Ctrl-C otherwise.
+    max_cycles: Option<u64>,
+    dry_run: bool,
+}
+
+impl Settings {
+    fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            env::var(name)
+                .ok()
+                .map(|v| v.parse().unwrap_or_else(|_| panic!("Invalid {name}: {v}")))
+                .unwrap_or(default)
+        }
+
+        let has_key = env_var_with_prefix(CCX_BINANCE_API_PREFIX, "KEY").is_some();
+        Settings {
+            symbol: Atom::from(var("QUOTER_SYMBOL", "BTCUSDT".to_string())),
+            spread: var("QUOTER_SPREAD", Decimal::new(1, 3)),
+            quote_qty: var("QUOTER_QUOTE_QTY", Decimal::from(20)),
+            interval: Duration::from_secs(var("QUOTER_INTERVAL_SECS", 5)),
+            max_cycles: env::var("QUOTER_MAX_CYCLES")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            dry_run: !has_key || var("QUOTER_DRY_RUN", 0) == 1,
+        }
+    }
+}
+
+struct Quoter {
+    api: SpotApi<ApiCred>,
+    settings: Settings,
+    symbols: HashMap<Atom, Symbol>,
+    book_tickers: BookTickerCache,
+    /// Distinguishes client order ids of different runs.
+    run_id: u64,
+}
+
+impl Quoter {
+    fn symbol(&self) -> &Symbol {
+        &self.symbols[&self.settings.symbol]
+    }
+
+    async fn cycle(&self, cycle: u64) -> BinanceResult<()> {
+        let Some(ticker) = self.book_tickers.get(&self.settings.symbol) else {
+            println!("[{cycle}] no book ticker yet");
+            return Ok(());
+        };
+
+        if !self.settings.dry_run {
+            self.api
+                .cancel_all_orders(&*self.settings.symbol, TimeWindow::now())?
+                .await
+                .map_or_else(|e| log::debug!("Nothing to cancel: {e:?}"), |_| ());
+        }
+
+        let one = Decimal::ONE;
+        let bid = ticker.bid_price * (one - self.settings.spread);
+        let ask = ticker.ask_price * (one + self.settings.spread);
+        for (side, price) in [(OrderSide::Buy, bid), (OrderSide::Sell, ask)] {
+            self.quote(cycle, side, price).await?;
+        }
+        Ok(())
+    }
+
+    async fn quote(&self, cycle: u64, side: OrderSide, price: Decimal) -> BinanceResult<()> {
+        let symbol = self.symbol();
+        let price = symbol.round_price(price);
+        let qty = symbol.round_qty(self.settings.quote_qty / price);
+        if let Err(violation) = symbol.validate_order(price, qty) {
+            println!("[{cycle}] {side:?} {qty} @ {price} skipped: {violation}");
+            return Ok(());
+        }
+
+        let side_tag = match side {
+            OrderSide::Buy => "b",
+            OrderSide::Sell => "s",
+        };
+        let client_order_id = format!("q{}-{cycle}-{side_tag}", self.run_id);
+        let order = NewOrderRequest::new(symbol.symbol.clone(), side, OrderType::Limit)
+            .time_in_force(TimeInForce::Gtc)
+            .price(price)
+            .quantity(qty)
+            .new_client_order_id(&client_order_id)
+            .new_order_resp_type(OrderResponseType::Ack);
+
+        if self.settings.dry_run {
+            println!("[{cycle}] dry run: {order:?}");
+            return Ok(());
+        }
+
+        let placed = match self.api.place_order(&order, TimeWindow::now()).await {
+            Ok(order) => order.as_ack().expect("ACK response was requested").await,
+            Err(e) => Err(e),
+        };
+        match placed {
+            Ok(ack) => println!("[{cycle}] placed {client_order_id}: {}", ack.order_id),
+            Err(e) => {
+                // The request may have reached the exchange, look it up before retrying.
+                log::warn!("[{cycle}] {client_order_id} failed: {e:?}");
+                let lookup = self
+                    .api
+                    .get_order(
+                        &*symbol.symbol,
+                        None,
+                        Some(&client_order_id),
+                        TimeWindow::now(),
+                    )?
+                    .await;
+                match lookup {
+                    Ok(order) => {
+                        println!("[{cycle}] {client_order_id} was placed: {:?}", order.status)
+                    }
+                    Err(_) => println!("[{cycle}] {client_order_id} was not placed"),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    if let Err(e) = main_().await {
+        println!("Quoter stopped with: {e:?}");
+    }
+}
+
+async fn main_() -> BinanceResult<()> {
+    let settings = Settings::from_env();
+    println!(
+        "Quoting {} on the Spot Testnet{}",
+        settings.symbol,
+        if settings.dry_run { " (dry run)" } else { "" }
+    );
+
+    let book_tickers = BookTickerCache::new();
+    let guard = OrderGuard::new()
+        .max_notional(settings.quote_qty * Decimal::from(2))
+        .max_price_deviation(Decimal::new(5, 2))
+        .book_tickers(book_tickers.clone())
+        .server_validation(true);
+    let config = Config::new(
+        ApiCred::from_env_with_prefix(CCX_BINANCE_API_PREFIX),
+        Url::parse(API_BASE_TESTNET).unwrap(),
+        Url::parse(STREAM_BASE_TESTNET).unwrap(),
+        Proxy::from_env_with_prefix(CCX_BINANCE_API_PREFIX),
+    )
+    .with_ws_api_base(Url::parse(WS_API_BASE_TESTNET).unwrap())
+    .with_time_sync(TIME_SYNC_INTERVAL);
+    let api = SpotApi::with_config(config).with_order_guard(guard);
+
+    // The book ticker cache is fed by the stream in the background.
+    let mut tickers =
+        api.market_stream([(settings.symbol.to_lowercase(), WsStream::BookTicker)])?;
+    let feed = book_tickers.clone();
+    actix_rt::spawn(async move {
+        while let Some(event) = tickers.next().await {
+            match event {
+                Ok(MarketEvent {
+                    event: WsEvent::BookTicker(ticker),
+                    ..
+                }) => feed.update(BookTicker {
+                    symbol: ticker.symbol,
+                    bid_price: ticker.best_bid_price,
+                    bid_qty: ticker.best_bid_qty,
+                    ask_price: ticker.best_ask_price,
+                    ask_qty: ticker.best_ask_qty,
+                }),
+                Ok(_) => {}
+                Err(e) => log::warn!("Book ticker stream: {e:?}"),
+            }
+        }
+    });
+
+    let symbols = api.exchange_info()?.await?.into_symbol_map();
+    if !symbols.contains_key(&settings.symbol) {
+        panic!("Unknown symbol {}", settings.symbol);
+    }
+    let user_data = if settings.dry_run {
+        None
+    } else {
+        let handle = api.start_user_data_stream().await?;
+        println!("User data stream: {}", handle.listen_key());
+        Some(handle)
+    };
+
+    let mut ticks = actix_rt::time::interval(settings.interval);
+    let quoter = Quoter {
+        api: api.clone(),
+        run_id: TimeWindow::now().timestamp() / 1000,
+        settings,
+        symbols,
+        book_tickers,
+    };
+
+    let mut shutdown = Box::pin(actix_rt::signal::ctrl_c());
+    let mut cycle = 0;
+    while quoter.settings.max_cycles.is_none_or(|max| cycle < max) {
+        if let Either::Left(_) = future::select(&mut shutdown, Box::pin(ticks.tick())).await {
+            println!("Shutting down");
+            break;
+        }
+        cycle += 1;
+        if let Err(e) = quoter.cycle(cycle).await {
+            log::error!("[{cycle}] cycle failed: {e:?}");
+        }
+    }
+
+    if !quoter.settings.dry_run {
+        let cancelled = api
+            .cancel_all_orders(&*quoter.settings.symbol, TimeWindow::now())?
+            .await;
+        println!("Cancelled quotes: {}", cancelled.map_or(0, |c| c.len()));
+    }
+    if let Some(user_data) = user_data {
+        user_data.close().await?;
+    }
+    api.shutdown().await;
+    println!("Done");
+    Ok(())
+}
@@ -70,7 +70,7 @@ async fn main() {
 
         let mut stream = Box::pin(stream.filter_map(move |e| async move {
             match e {
-                UpstreamWebsocketMessage::Event(e) => Some(X::Event(e)),
+                UpstreamWebsocketMessage::Event(e) => Some(X::Event(e.event)),
                 UpstreamWebsocketMessage::Response(e) => {
                     println!("{:?}", e);
                     None
@@ -0,0 +1,60 @@
+use ccx_binance::ApiCred;
+use ccx_binance::BinanceResult;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::um::NewOrderRequest;
+use ccx_binance::api::um::OrderSide;
+use ccx_binance::api::um::OrderType;
+use ccx_binance::api::um::TimeInForce;
+use ccx_binance::api::um::UmApi;
+use ccx_binance_examples_util::*;
+
+const BTCUSDT: &str = "BTCUSDT";
+
+#[actix_rt::main]
+async fn main() {
+    let _ = main_().await;
+}
+
+async fn main_() -> BinanceResult<()> {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    let binance_usdtm = UmApi::<ApiCred>::from_env();
+
+    print_res(
+        binance_usdtm
+            .position_risk(Some(BTCUSDT), TimeWindow::now())?
+            .await,
+    )?;
+
+    // A buy far below the market to stay on the book.
+    let order = NewOrderRequest::new(BTCUSDT, OrderSide::Buy, OrderType::Limit)
+        .time_in_force(TimeInForce::GoodTilCanceled)
+        .quantity(d("0.002"))
+        .price(d("20000"));
+    let order = print_res(binance_usdtm.new_order(&order, TimeWindow::now())?.await)?;
+
+    print_res(
+        binance_usdtm
+            .query_order(
+                BTCUSDT,
+                Some(order.order_id),
+                None::<&str>,
+                TimeWindow::now(),
+            )?
+            .await,
+    )?;
+
+    print_res(
+        binance_usdtm
+            .cancel_order(
+                BTCUSDT,
+                Some(order.order_id),
+                None::<&str>,
+                TimeWindow::now(),
+            )?
+            .await,
+    )?;
+
+    Ok(())
+}
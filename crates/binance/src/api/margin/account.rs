@@ -0,0 +1,266 @@
+use super::prelude::*;
+
+pub const SAPI_V1_MARGIN_BORROW_REPAY: &str = "/sapi/v1/margin/borrow-repay";
+pub const SAPI_V1_MARGIN_ACCOUNT: &str = "/sapi/v1/margin/account";
+pub const SAPI_V1_MARGIN_MAX_BORROWABLE: &str = "/sapi/v1/margin/maxBorrowable";
+pub const SAPI_V1_MARGIN_MAX_TRANSFERABLE: &str = "/sapi/v1/margin/maxTransferable";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MarginLoanType {
+    #[serde(rename = "BORROW")]
+    Borrow,
+    #[serde(rename = "REPAY")]
+    Repay,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTransaction {
+    pub tran_id: u64,
+}
+
+/// Cross margin account details.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAccount {
+    #[serde(default)]
+    pub created: bool,
+    pub borrow_enabled: bool,
+    /// `total_asset_of_btc / total_liability_of_btc`, see [`MarginAccount::margin_level`].
+    pub margin_level: Decimal,
+    #[serde(default)]
+    pub collateral_margin_level: Option<Decimal>,
+    pub total_asset_of_btc: Decimal,
+    pub total_liability_of_btc: Decimal,
+    pub total_net_asset_of_btc: Decimal,
+    #[serde(default, rename = "TotalCollateralValueInUSDT")]
+    pub total_collateral_value_in_usdt: Option<Decimal>,
+    #[serde(default)]
+    pub total_open_order_loss_in_usdt: Option<Decimal>,
+    pub trade_enabled: bool,
+    #[serde(default)]
+    pub transfer_in_enabled: bool,
+    #[serde(default)]
+    pub transfer_out_enabled: bool,
+    #[serde(default)]
+    pub account_type: Option<String>,
+    pub user_assets: Vec<MarginAsset>,
+}
+
+impl MarginAccount {
+    /// The margin level, or `None` if nothing is borrowed.
+    ///
+    /// Binance reports a placeholder of 999 in that case, which must not be
+    /// compared against liquidation thresholds.
+    pub fn margin_level(&self) -> Option<Decimal> {
+        if self.total_liability_of_btc.is_zero() {
+            return None;
+        }
+        Some(self.margin_level)
+    }
+
+    pub fn asset(&self, asset: &str) -> Option<&MarginAsset> {
+        self.user_assets.iter().find(|a| &*a.asset == asset)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAsset {
+    pub asset: Atom,
+    pub borrowed: Decimal,
+    pub free: Decimal,
+    pub interest: Decimal,
+    pub locked: Decimal,
+    /// `free + locked - borrowed - interest`.
+    pub net_asset: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginMaxBorrowable {
+    pub amount: Decimal,
+    /// The account's borrow limit of the asset.
+    #[serde(default)]
+    pub borrow_limit: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginMaxTransferable {
+    pub amount: Decimal,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> MarginApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Margin account borrow/repay (MARGIN)
+        ///
+        /// Weight(UID): 1500
+        ///
+        /// * `isolated_symbol` - the isolated margin pair, cross margin if omitted.
+        pub fn borrow_repay(
+            &self,
+            asset: impl Serialize,
+            amount: Decimal,
+            r#type: MarginLoanType,
+            isolated_symbol: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginTransaction>> {
+            let is_isolated = if isolated_symbol.is_some() {
+                "TRUE"
+            } else {
+                "FALSE"
+            };
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_MARGIN_BORROW_REPAY)?
+                        .signed(time_window)?
+                        .query_arg("asset", &asset)?
+                        .query_arg("isIsolated", is_isolated)?
+                        .try_query_arg("symbol", &isolated_symbol)?
+                        .decimal_arg("amount", &amount)?
+                        .query_arg("type", &r#type)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 1500)
+                .send())
+        }
+
+        /// Query Cross Margin Account Details (USER_DATA)
+        ///
+        /// Weight(IP): 10
+        pub fn account(
+            &self,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginAccount>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_ACCOUNT)?
+                        .signed(time_window)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
+                .send())
+        }
+
+        /// Query Max Borrow (USER_DATA)
+        ///
+        /// Weight(IP): 50
+        ///
+        /// * `isolated_symbol` - the isolated margin pair, cross margin if omitted.
+        pub fn max_borrowable(
+            &self,
+            asset: impl Serialize,
+            isolated_symbol: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginMaxBorrowable>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_MAX_BORROWABLE)?
+                        .signed(time_window)?
+                        .query_arg("asset", &asset)?
+                        .try_query_arg("isolatedSymbol", &isolated_symbol)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 50)
+                .send())
+        }
+
+        /// Query Max Transfer-Out Amount (USER_DATA)
+        ///
+        /// Weight(IP): 50
+        ///
+        /// * `isolated_symbol` - the isolated margin pair, cross margin if omitted.
+        pub fn max_transferable(
+            &self,
+            asset: impl Serialize,
+            isolated_symbol: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginMaxTransferable>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_MAX_TRANSFERABLE)?
+                        .signed(time_window)?
+                        .query_arg("asset", &asset)?
+                        .try_query_arg("isolatedSymbol", &isolated_symbol)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 50)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_account() {
+        let json = r#"{
+            "created": true, "borrowEnabled": true, "marginLevel": "11.64405625",
+            "collateralMarginLevel": "3.2", "totalAssetOfBtc": "6.82728457",
+            "totalLiabilityOfBtc": "0.58633215", "totalNetAssetOfBtc": "6.24095242",
+            "TotalCollateralValueInUSDT": "5.82728457", "totalOpenOrderLossInUsdt": "582.728457",
+            "tradeEnabled": true, "transferInEnabled": true, "transferOutEnabled": true,
+            "accountType": "MARGIN_1",
+            "userAssets": [
+                {"asset": "BTC", "borrowed": "0.00000000", "free": "0.00499500",
+                 "interest": "0.00000000", "locked": "0.00000000", "netAsset": "0.00499500"},
+                {"asset": "BNB", "borrowed": "201.66666672", "free": "2346.50000000",
+                 "interest": "0.00000000", "locked": "0.00000000", "netAsset": "2144.83333328"}
+            ]
+        }"#;
+        let account: MarginAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.margin_level(), Some(dec!(11.64405625)));
+        assert_eq!(
+            account.total_collateral_value_in_usdt,
+            Some(dec!(5.82728457))
+        );
+        let bnb = account.asset("BNB").unwrap();
+        assert_eq!(bnb.borrowed, dec!(201.66666672));
+        assert_eq!(bnb.net_asset, dec!(2144.83333328));
+
+        let json = r#"{
+            "borrowEnabled": true, "marginLevel": "999.00000000",
+            "totalAssetOfBtc": "0.1", "totalLiabilityOfBtc": "0.00000000",
+            "totalNetAssetOfBtc": "0.1", "tradeEnabled": true, "userAssets": []
+        }"#;
+        let account: MarginAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.margin_level, dec!(999));
+        assert_eq!(account.margin_level(), None);
+    }
+
+    #[test]
+    fn test_deserialize_limits() {
+        let json = r#"{"amount": "1.69248805", "borrowLimit": "60"}"#;
+        let max: MarginMaxBorrowable = serde_json::from_str(json).unwrap();
+        assert_eq!(max.amount, dec!(1.69248805));
+        assert_eq!(max.borrow_limit, Some(dec!(60)));
+
+        let json = r#"{"amount": "3.59498107"}"#;
+        let max: MarginMaxTransferable = serde_json::from_str(json).unwrap();
+        assert_eq!(max.amount, dec!(3.59498107));
+
+        let json = r#"{"tranId": 100000001}"#;
+        let tx: MarginTransaction = serde_json::from_str(json).unwrap();
+        assert_eq!(tx.tran_id, 100000001);
+    }
+}
@@ -0,0 +1,319 @@
+use super::prelude::*;
+use crate::api::spot::SAPI_V1_ASSET_TRANSFER;
+
+pub const SAPI_V1_MARGIN_ISOLATED_ACCOUNT: &str = "/sapi/v1/margin/isolated/account";
+
+/// At most this many symbols can be queried at once.
+pub const ISOLATED_ACCOUNT_MAX_SYMBOLS: usize = 5;
+
+/// Isolated margin account details.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAccount {
+    pub assets: Vec<IsolatedMarginSymbol>,
+    /// Only returned when the account is queried without `symbols`.
+    #[serde(default)]
+    pub total_asset_of_btc: Option<Decimal>,
+    /// Only returned when the account is queried without `symbols`.
+    #[serde(default)]
+    pub total_liability_of_btc: Option<Decimal>,
+    /// Only returned when the account is queried without `symbols`.
+    #[serde(default)]
+    pub total_net_asset_of_btc: Option<Decimal>,
+}
+
+impl IsolatedMarginAccount {
+    pub fn symbol(&self, symbol: &str) -> Option<&IsolatedMarginSymbol> {
+        self.assets.iter().find(|s| &*s.symbol == symbol)
+    }
+}
+
+/// Isolated margin account of a single pair.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginSymbol {
+    pub symbol: Atom,
+    pub base_asset: IsolatedMarginAsset,
+    pub quote_asset: IsolatedMarginAsset,
+    pub isolated_created: bool,
+    /// `false` once the account is disabled.
+    pub enabled: bool,
+    pub margin_level: Decimal,
+    pub margin_level_status: MarginLevelStatus,
+    pub margin_ratio: Decimal,
+    pub index_price: Decimal,
+    /// Zero if nothing is borrowed.
+    pub liquidate_price: Decimal,
+    pub liquidate_rate: Decimal,
+    pub trade_enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAsset {
+    pub asset: Atom,
+    pub borrow_enabled: bool,
+    pub borrowed: Decimal,
+    pub free: Decimal,
+    pub interest: Decimal,
+    pub locked: Decimal,
+    pub net_asset: Decimal,
+    pub net_asset_of_btc: Decimal,
+    pub repay_enabled: bool,
+    pub total_asset: Decimal,
+}
+
+/// Risk of the isolated margin account, from the safest to liquidation.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum MarginLevelStatus {
+    #[serde(rename = "EXCESSIVE")]
+    Excessive,
+    #[serde(rename = "NORMAL")]
+    Normal,
+    #[serde(rename = "MARGIN_CALL")]
+    MarginCall,
+    #[serde(rename = "PRE_LIQUIDATION")]
+    PreLiquidation,
+    #[serde(rename = "FORCE_LIQUIDATION")]
+    ForceLiquidation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IsolatedMarginAccountToggle {
+    pub success: bool,
+    pub symbol: Atom,
+}
+
+/// Direction of a transfer between the spot and an isolated margin account.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum IsolatedMarginTransfer {
+    /// Spot to isolated margin.
+    In,
+    /// Isolated margin to spot.
+    Out,
+}
+
+impl IsolatedMarginTransfer {
+    pub fn kind(self) -> TransferKind {
+        match self {
+            IsolatedMarginTransfer::In => TransferKind::MainIsolatedMargin,
+            IsolatedMarginTransfer::Out => TransferKind::IsolatedMarginMain,
+        }
+    }
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::super::MarginTransaction;
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> MarginApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Query Isolated Margin Account Info (USER_DATA)
+        ///
+        /// Weight(IP): 10
+        ///
+        /// * `symbols` - at most 5, all isolated margin pairs if empty.
+        pub fn isolated_account(
+            &self,
+            symbols: &[&str],
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<IsolatedMarginAccount>> {
+            if symbols.len() > ISOLATED_ACCOUNT_MAX_SYMBOLS {
+                Err(ApiError::OutOfBounds)?
+            }
+            let symbols = (!symbols.is_empty()).then(|| symbols.join(","));
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_ISOLATED_ACCOUNT)?
+                        .signed(time_window)?
+                        .try_query_arg("symbols", &symbols)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
+                .send())
+        }
+
+        /// Enable Isolated Margin Account (TRADE)
+        ///
+        /// Weight(UID): 300
+        ///
+        /// * Up to 10 isolated margin accounts can be enabled.
+        pub fn enable_isolated_account(
+            &self,
+            symbol: impl Serialize,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<IsolatedMarginAccountToggle>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_MARGIN_ISOLATED_ACCOUNT)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 300)
+                .send())
+        }
+
+        /// Disable Isolated Margin Account (TRADE)
+        ///
+        /// Weight(UID): 300
+        ///
+        /// * Only an account without assets and liabilities can be disabled,
+        ///   at most once a day.
+        pub fn disable_isolated_account(
+            &self,
+            symbol: impl Serialize,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<IsolatedMarginAccountToggle>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .delete(SAPI_V1_MARGIN_ISOLATED_ACCOUNT)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 300)
+                .send())
+        }
+
+        /// Transfer between the spot and the isolated margin account of `symbol`.
+        ///
+        /// A universal transfer (`ISOLATEDMARGIN_MAIN` / `MAIN_ISOLATEDMARGIN`), the API key
+        /// must be permitted to make them.
+        ///
+        /// Weight(UID): 900
+        pub fn isolated_transfer(
+            &self,
+            direction: IsolatedMarginTransfer,
+            symbol: impl Serialize,
+            asset: impl Serialize,
+            amount: Decimal,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginTransaction>> {
+            let symbol_arg = match direction {
+                IsolatedMarginTransfer::In => "toSymbol",
+                IsolatedMarginTransfer::Out => "fromSymbol",
+            };
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_ASSET_TRANSFER)?
+                        .signed(time_window)?
+                        .query_arg("type", &direction.kind())?
+                        .query_arg("asset", &asset)?
+                        .decimal_arg("amount", &amount)?
+                        .query_arg(symbol_arg, &symbol)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 900)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_isolated_account() {
+        let json = r#"{
+            "assets": [
+                {
+                    "baseAsset": {
+                        "asset": "BTC", "borrowEnabled": true, "borrowed": "0.00100000",
+                        "free": "0.00499500", "interest": "0.00000125", "locked": "0.00000000",
+                        "netAsset": "0.00399375", "netAssetOfBtc": "0.00399375",
+                        "repayEnabled": true, "totalAsset": "0.00499500"
+                    },
+                    "quoteAsset": {
+                        "asset": "USDT", "borrowEnabled": true, "borrowed": "0.00000000",
+                        "free": "120.00000000", "interest": "0.00000000", "locked": "0.00000000",
+                        "netAsset": "120.00000000", "netAssetOfBtc": "0.00200000",
+                        "repayEnabled": true, "totalAsset": "120.00000000"
+                    },
+                    "symbol": "BTCUSDT", "isolatedCreated": true, "enabled": true,
+                    "marginLevel": "2.56000000", "marginLevelStatus": "NORMAL",
+                    "marginRatio": "5.00000000", "indexPrice": "60000.00000000",
+                    "liquidatePrice": "41250.00000000", "liquidateRate": "1.10000000",
+                    "tradeEnabled": true
+                },
+                {
+                    "baseAsset": {
+                        "asset": "ETH", "borrowEnabled": true, "borrowed": "0.00000000",
+                        "free": "0.00000000", "interest": "0.00000000", "locked": "0.00000000",
+                        "netAsset": "0.00000000", "netAssetOfBtc": "0.00000000",
+                        "repayEnabled": true, "totalAsset": "0.00000000"
+                    },
+                    "quoteAsset": {
+                        "asset": "USDT", "borrowEnabled": true, "borrowed": "0.00000000",
+                        "free": "0.00000000", "interest": "0.00000000", "locked": "0.00000000",
+                        "netAsset": "0.00000000", "netAssetOfBtc": "0.00000000",
+                        "repayEnabled": true, "totalAsset": "0.00000000"
+                    },
+                    "symbol": "ETHUSDT", "isolatedCreated": false, "enabled": true,
+                    "marginLevel": "999.00000000", "marginLevelStatus": "EXCESSIVE",
+                    "marginRatio": "10.00000000", "indexPrice": "3000.00000000",
+                    "liquidatePrice": "0.00000000", "liquidateRate": "0.00000000",
+                    "tradeEnabled": true
+                }
+            ],
+            "totalAssetOfBtc": "0.00699500",
+            "totalLiabilityOfBtc": "0.00100125",
+            "totalNetAssetOfBtc": "0.00599375"
+        }"#;
+        let account: IsolatedMarginAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.assets.len(), 2);
+        assert_eq!(account.total_liability_of_btc, Some(dec!(0.00100125)));
+
+        let btc = account.symbol("BTCUSDT").unwrap();
+        assert_eq!(&btc.base_asset.asset, "BTC");
+        assert_eq!(btc.base_asset.borrowed, dec!(0.001));
+        assert_eq!(btc.quote_asset.net_asset, dec!(120));
+        assert_eq!(btc.liquidate_price, dec!(41250));
+        assert_eq!(btc.margin_ratio, dec!(5));
+        assert_eq!(btc.margin_level_status, MarginLevelStatus::Normal);
+
+        let eth = account.symbol("ETHUSDT").unwrap();
+        assert!(!eth.isolated_created);
+        assert_eq!(eth.liquidate_price, dec!(0));
+        assert!(eth.margin_level_status < btc.margin_level_status);
+    }
+
+    #[test]
+    fn test_deserialize_isolated_account_of_symbols() {
+        let json = r#"{"assets": []}"#;
+        let account: IsolatedMarginAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.total_asset_of_btc, None);
+
+        let json = r#"{"success": true, "symbol": "BTCUSDT"}"#;
+        let toggle: IsolatedMarginAccountToggle = serde_json::from_str(json).unwrap();
+        assert!(toggle.success);
+    }
+
+    #[test]
+    fn test_isolated_transfer_kind() {
+        assert_eq!(
+            serde_json::to_string(&IsolatedMarginTransfer::In.kind()).unwrap(),
+            r#""MAIN_ISOLATEDMARGIN""#
+        );
+        assert_eq!(
+            serde_json::to_string(&IsolatedMarginTransfer::Out.kind()).unwrap(),
+            r#""ISOLATEDMARGIN_MAIN""#
+        );
+    }
+}
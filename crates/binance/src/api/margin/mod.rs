@@ -0,0 +1,70 @@
+use crate::error::*;
+
+mod account;
+mod isolated;
+mod order;
+
+pub use self::account::*;
+pub use self::isolated::*;
+pub use self::order::*;
+
+mod prelude {
+    #[cfg(feature = "with_network")]
+    pub use super::MarginApi;
+    pub use crate::api::prelude::*;
+    pub use crate::api::spot::RL_ORDERS_PER_DAY;
+    pub use crate::api::spot::RL_ORDERS_PER_SECOND;
+    pub use crate::api::spot::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+    pub use crate::api::spot::RL_SAPI_WEIGHT_PER_MINUTE;
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::spot::SpotApi;
+    use crate::api::spot::util::SymbolCache;
+    use crate::client::BinanceSigner;
+    use crate::client::RateLimiter;
+    use crate::client::RestClient;
+
+    /// Margin endpoints (`/sapi/v1/margin/*`), see [`SpotApi::margin`].
+    ///
+    /// Shares the REST client and the rate limiter with the spot api it was created from,
+    /// so the `/sapi` weight is accounted for in one place.
+    #[derive(Clone)]
+    pub struct MarginApi<S>
+    where
+        S: BinanceSigner,
+    {
+        pub(crate) client: RestClient<S>,
+        pub(crate) rate_limiter: RateLimiter,
+        pub(crate) symbols: SymbolCache,
+    }
+
+    impl<S> SpotApi<S>
+    where
+        S: BinanceSigner,
+    {
+        /// Margin api on top of the same client, rate limiter and symbol cache.
+        pub fn margin(&self) -> MarginApi<S> {
+            MarginApi {
+                client: self.client.clone(),
+                rate_limiter: self.rate_limiter.clone(),
+                symbols: self.symbols.clone(),
+            }
+        }
+    }
+
+    impl<S> MarginApi<S>
+    where
+        S: BinanceSigner,
+    {
+        /// Rate limiter shared with the spot api.
+        pub fn rate_limiter(&self) -> &RateLimiter {
+            &self.rate_limiter
+        }
+    }
+}
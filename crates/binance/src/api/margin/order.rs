@@ -0,0 +1,715 @@
+use super::prelude::*;
+use crate::api::spot::OrderFill;
+use crate::api::spot::OrderResponseType;
+use crate::api::spot::OrderSide;
+use crate::api::spot::OrderStatus;
+use crate::api::spot::OrderType;
+use crate::api::spot::SymbolCapability;
+use crate::api::spot::TimeInForce;
+
+pub const SAPI_V1_MARGIN_ORDER: &str = "/sapi/v1/margin/order";
+pub const SAPI_V1_MARGIN_OPEN_ORDERS: &str = "/sapi/v1/margin/openOrders";
+pub const SAPI_V1_MARGIN_ORDER_OCO: &str = "/sapi/v1/margin/order/oco";
+pub const SAPI_V1_MARGIN_ORDER_LIST: &str = "/sapi/v1/margin/orderList";
+pub const SAPI_V1_MARGIN_OPEN_ORDER_LIST: &str = "/sapi/v1/margin/openOrderList";
+
+/// Borrowing or repaying done together with a margin order.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SideEffectType {
+    #[serde(rename = "NO_SIDE_EFFECT")]
+    NoSideEffect,
+    /// Borrows the missing amount to place the order.
+    #[serde(rename = "MARGIN_BUY")]
+    MarginBuy,
+    /// Repays the debt with the proceeds of the order.
+    #[serde(rename = "AUTO_REPAY")]
+    AutoRepay,
+    /// Borrows on placement and repays on fill or cancel.
+    #[serde(rename = "AUTO_BORROW_REPAY")]
+    AutoBorrowRepay,
+}
+
+/// New margin order parameters, see `MarginApi::create_order`.
+#[derive(Debug, Clone)]
+pub struct NewMarginOrderRequest {
+    pub symbol: Atom,
+    /// Isolated margin of `symbol`, cross margin otherwise.
+    pub is_isolated: bool,
+    pub side: OrderSide,
+    pub r#type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub iceberg_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+    pub new_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub side_effect_type: Option<SideEffectType>,
+}
+
+impl NewMarginOrderRequest {
+    pub fn new(symbol: impl Into<Atom>, side: OrderSide, r#type: OrderType) -> Self {
+        NewMarginOrderRequest {
+            symbol: symbol.into(),
+            is_isolated: false,
+            side,
+            r#type,
+            time_in_force: None,
+            quantity: None,
+            quote_order_qty: None,
+            iceberg_qty: None,
+            price: None,
+            stop_price: None,
+            new_client_order_id: None,
+            new_order_resp_type: None,
+            side_effect_type: None,
+        }
+    }
+
+    pub fn isolated(mut self) -> Self {
+        self.is_isolated = true;
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Decimal) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn quote_order_qty(mut self, quote_order_qty: Decimal) -> Self {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: Decimal) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: Decimal) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: impl Into<String>) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.into());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponseType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn side_effect_type(mut self, side_effect_type: SideEffectType) -> Self {
+        self.side_effect_type = Some(side_effect_type);
+        self
+    }
+}
+
+/// New margin OCO parameters, see `MarginApi::create_oco`.
+///
+/// Places a limit order at `price` and a stop-loss (limit) order at `stop_price`,
+/// the fill of one cancels the other.
+#[derive(Debug, Clone)]
+pub struct NewMarginOcoRequest {
+    pub symbol: Atom,
+    /// Isolated margin of `symbol`, cross margin otherwise.
+    pub is_isolated: bool,
+    pub side: OrderSide,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub stop_price: Decimal,
+    /// Makes the stop order a stop-loss limit order, requires `stop_limit_time_in_force`.
+    pub stop_limit_price: Option<Decimal>,
+    pub stop_limit_time_in_force: Option<TimeInForce>,
+    pub list_client_order_id: Option<String>,
+    pub limit_client_order_id: Option<String>,
+    pub stop_client_order_id: Option<String>,
+    pub limit_iceberg_qty: Option<Decimal>,
+    pub stop_iceberg_qty: Option<Decimal>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+    pub side_effect_type: Option<SideEffectType>,
+}
+
+impl NewMarginOcoRequest {
+    pub fn new(
+        symbol: impl Into<Atom>,
+        side: OrderSide,
+        quantity: Decimal,
+        price: Decimal,
+        stop_price: Decimal,
+    ) -> Self {
+        NewMarginOcoRequest {
+            symbol: symbol.into(),
+            is_isolated: false,
+            side,
+            quantity,
+            price,
+            stop_price,
+            stop_limit_price: None,
+            stop_limit_time_in_force: None,
+            list_client_order_id: None,
+            limit_client_order_id: None,
+            stop_client_order_id: None,
+            limit_iceberg_qty: None,
+            stop_iceberg_qty: None,
+            new_order_resp_type: None,
+            side_effect_type: None,
+        }
+    }
+
+    pub fn isolated(mut self) -> Self {
+        self.is_isolated = true;
+        self
+    }
+
+    pub fn stop_limit(mut self, price: Decimal, time_in_force: TimeInForce) -> Self {
+        self.stop_limit_price = Some(price);
+        self.stop_limit_time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn list_client_order_id(mut self, list_client_order_id: impl Into<String>) -> Self {
+        self.list_client_order_id = Some(list_client_order_id.into());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponseType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    pub fn side_effect_type(mut self, side_effect_type: SideEffectType) -> Self {
+        self.side_effect_type = Some(side_effect_type);
+        self
+    }
+}
+
+/// Response of a new margin order.
+///
+/// Only the ACK fields are always present, the rest come with the `RESULT` and `FULL`
+/// response types, `fills` with `FULL` only.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginNewOrder {
+    pub symbol: Atom,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub transact_time: u64,
+    #[serde(default)]
+    pub is_isolated: bool,
+    #[serde(default)]
+    pub price: Option<Decimal>,
+    #[serde(default)]
+    pub orig_qty: Option<Decimal>,
+    #[serde(default)]
+    pub executed_qty: Option<Decimal>,
+    #[serde(default)]
+    pub cummulative_quote_qty: Option<Decimal>,
+    #[serde(default)]
+    pub status: Option<OrderStatus>,
+    #[serde(default)]
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(default)]
+    pub r#type: Option<OrderType>,
+    #[serde(default)]
+    pub side: Option<OrderSide>,
+    #[serde(default)]
+    pub fills: Vec<OrderFill>,
+    /// Borrowed to place the order, with [`SideEffectType::MarginBuy`] or
+    /// [`SideEffectType::AutoBorrowRepay`].
+    #[serde(default)]
+    pub margin_buy_borrow_amount: Option<Decimal>,
+    #[serde(default)]
+    pub margin_buy_borrow_asset: Option<Atom>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCancelledOrder {
+    pub symbol: Atom,
+    #[serde(default)]
+    pub is_isolated: bool,
+    pub order_id: u64,
+    pub orig_client_order_id: String,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    pub r#type: OrderType,
+    pub side: OrderSide,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrder {
+    pub symbol: Atom,
+    #[serde(default)]
+    pub is_isolated: bool,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    pub r#type: OrderType,
+    pub side: OrderSide,
+    pub stop_price: Decimal,
+    pub iceberg_qty: Decimal,
+    pub time: u64,
+    pub update_time: u64,
+    pub is_working: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ContingencyType {
+    #[serde(rename = "OCO")]
+    Oco,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OrderListStatusType {
+    /// The list status is a response to a failed action, e.g. a rejected placement.
+    #[serde(rename = "RESPONSE")]
+    Response,
+    /// The list has been placed or there is an update to its status.
+    #[serde(rename = "EXEC_STARTED")]
+    ExecStarted,
+    /// The list has finished executing and is no longer active.
+    #[serde(rename = "ALL_DONE")]
+    AllDone,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OrderListOrderStatus {
+    #[serde(rename = "EXECUTING")]
+    Executing,
+    #[serde(rename = "ALL_DONE")]
+    AllDone,
+    #[serde(rename = "REJECT")]
+    Reject,
+}
+
+/// Margin OCO order list.
+///
+/// `order_reports` are only present in the responses to placement and cancellation,
+/// `margin_buy_borrow_*` only in the placement response with a borrowing side effect.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrderList {
+    pub order_list_id: u64,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: OrderListStatusType,
+    pub list_order_status: OrderListOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: Atom,
+    #[serde(default)]
+    pub is_isolated: bool,
+    pub orders: Vec<MarginOrderListEntry>,
+    #[serde(default)]
+    pub order_reports: Vec<MarginOrderReport>,
+    #[serde(default)]
+    pub margin_buy_borrow_amount: Option<Decimal>,
+    #[serde(default)]
+    pub margin_buy_borrow_asset: Option<Atom>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrderListEntry {
+    pub symbol: Atom,
+    pub order_id: u64,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrderReport {
+    pub symbol: Atom,
+    pub order_id: u64,
+    pub order_list_id: u64,
+    pub client_order_id: String,
+    #[serde(default)]
+    pub orig_client_order_id: Option<String>,
+    #[serde(default)]
+    pub transact_time: Option<u64>,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub status: OrderStatus,
+    pub time_in_force: TimeInForce,
+    pub r#type: OrderType,
+    pub side: OrderSide,
+    #[serde(default)]
+    pub stop_price: Option<Decimal>,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::spot::RlPriorityLevel;
+    use crate::api::spot::check_order_fields;
+    use crate::client::Task;
+
+    fn is_isolated_arg(is_isolated: bool) -> &'static str {
+        if is_isolated { "TRUE" } else { "FALSE" }
+    }
+
+    impl<S> MarginApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Margin Account New Order (TRADE)
+        ///
+        /// Weight(UID): 6
+        ///
+        /// Counts against the order rate limits like a spot order.
+        pub fn create_order(
+            &self,
+            order: &NewMarginOrderRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginNewOrder>> {
+            check_order_fields(
+                order.r#type,
+                order.time_in_force,
+                order.quantity,
+                order.quote_order_qty,
+                order.price,
+                order.stop_price,
+                None,
+            )?;
+            self.symbols.check(&order.symbol, |symbol| {
+                symbol.check_order_features(
+                    order.r#type,
+                    order.quote_order_qty.is_some(),
+                    order.iceberg_qty.is_some(),
+                    false,
+                )
+            })?;
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_MARGIN_ORDER)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &order.symbol)?
+                        .query_arg("isIsolated", is_isolated_arg(order.is_isolated))?
+                        .query_arg("side", &order.side)?
+                        .query_arg("type", &order.r#type)?
+                        .try_query_arg("timeInForce", &order.time_in_force)?
+                        .try_decimal_arg("quantity", &order.quantity)?
+                        .try_decimal_arg("quoteOrderQty", &order.quote_order_qty)?
+                        .try_decimal_arg("icebergQty", &order.iceberg_qty)?
+                        .try_decimal_arg("price", &order.price)?
+                        .try_decimal_arg("stopPrice", &order.stop_price)?
+                        .try_query_arg("newClientOrderId", &order.new_client_order_id)?
+                        .try_query_arg("newOrderRespType", &order.new_order_resp_type)?
+                        .try_query_arg("sideEffectType", &order.side_effect_type)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 6)
+                .cost(RL_ORDERS_PER_SECOND, 1)
+                .cost(RL_ORDERS_PER_DAY, 1)
+                .priority(RlPriorityLevel::High as u8)
+                .send())
+        }
+
+        /// Margin Account Cancel Order (TRADE)
+        ///
+        /// Weight(IP): 10
+        ///
+        /// Either orderId or origClientOrderId must be sent.
+        pub fn cancel_order(
+            &self,
+            symbol: impl Serialize,
+            is_isolated: bool,
+            order_id: Option<u64>,
+            orig_client_order_id: Option<impl Serialize>,
+            new_client_order_id: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginCancelledOrder>> {
+            if order_id.is_none() && orig_client_order_id.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "order_id or orig_client_order_id",
+                ))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .delete(SAPI_V1_MARGIN_ORDER)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .query_arg("isIsolated", is_isolated_arg(is_isolated))?
+                        .try_query_arg("orderId", &order_id)?
+                        .try_query_arg("origClientOrderId", &orig_client_order_id)?
+                        .try_query_arg("newClientOrderId", &new_client_order_id)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
+                .priority(RlPriorityLevel::High as u8)
+                .send())
+        }
+
+        /// Query Margin Account's Open Orders (USER_DATA)
+        ///
+        /// Weight(IP): 10
+        ///
+        /// * `symbol` is mandatory for isolated margin.
+        pub fn open_orders(
+            &self,
+            symbol: Option<impl Serialize>,
+            is_isolated: bool,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<MarginOrder>>> {
+            if is_isolated && symbol.is_none() {
+                Err(ApiError::mandatory_field_omitted("symbol"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_OPEN_ORDERS)?
+                        .signed(time_window)?
+                        .try_query_arg("symbol", &symbol)?
+                        .query_arg("isIsolated", is_isolated_arg(is_isolated))?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
+                .send())
+        }
+
+        /// Margin Account New OCO (TRADE)
+        ///
+        /// Weight(UID): 6
+        ///
+        /// Both orders of the list count against the order rate limits.
+        pub fn create_oco(
+            &self,
+            order: &NewMarginOcoRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginOrderList>> {
+            if order.stop_limit_price.is_some() != order.stop_limit_time_in_force.is_some() {
+                Err(ApiError::mandatory_field_omitted(
+                    "stop_limit_price, stop_limit_time_in_force",
+                ))?
+            }
+            self.symbols.check(&order.symbol, |symbol| {
+                symbol.check_capability(SymbolCapability::Oco)?;
+                if order.limit_iceberg_qty.is_some() || order.stop_iceberg_qty.is_some() {
+                    symbol.check_capability(SymbolCapability::Iceberg)?;
+                }
+                Ok(())
+            })?;
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_MARGIN_ORDER_OCO)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &order.symbol)?
+                        .query_arg("isIsolated", is_isolated_arg(order.is_isolated))?
+                        .try_query_arg("listClientOrderId", &order.list_client_order_id)?
+                        .query_arg("side", &order.side)?
+                        .decimal_arg("quantity", &order.quantity)?
+                        .try_query_arg("limitClientOrderId", &order.limit_client_order_id)?
+                        .decimal_arg("price", &order.price)?
+                        .try_decimal_arg("limitIcebergQty", &order.limit_iceberg_qty)?
+                        .try_query_arg("stopClientOrderId", &order.stop_client_order_id)?
+                        .decimal_arg("stopPrice", &order.stop_price)?
+                        .try_decimal_arg("stopLimitPrice", &order.stop_limit_price)?
+                        .try_decimal_arg("stopIcebergQty", &order.stop_iceberg_qty)?
+                        .try_query_arg("stopLimitTimeInForce", &order.stop_limit_time_in_force)?
+                        .try_query_arg("newOrderRespType", &order.new_order_resp_type)?
+                        .try_query_arg("sideEffectType", &order.side_effect_type)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 6)
+                .cost(RL_ORDERS_PER_SECOND, 2)
+                .cost(RL_ORDERS_PER_DAY, 2)
+                .priority(RlPriorityLevel::High as u8)
+                .send())
+        }
+
+        /// Margin Account Cancel OCO (TRADE)
+        ///
+        /// Weight(UID): 1
+        ///
+        /// Either orderListId or listClientOrderId must be sent.
+        pub fn cancel_order_list(
+            &self,
+            symbol: impl Serialize,
+            is_isolated: bool,
+            order_list_id: Option<u64>,
+            list_client_order_id: Option<impl Serialize>,
+            new_client_order_id: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<MarginOrderList>> {
+            if order_list_id.is_none() && list_client_order_id.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "order_list_id or list_client_order_id",
+                ))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .delete(SAPI_V1_MARGIN_ORDER_LIST)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .query_arg("isIsolated", is_isolated_arg(is_isolated))?
+                        .try_query_arg("orderListId", &order_list_id)?
+                        .try_query_arg("listClientOrderId", &list_client_order_id)?
+                        .try_query_arg("newClientOrderId", &new_client_order_id)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 1)
+                .priority(RlPriorityLevel::High as u8)
+                .send())
+        }
+
+        /// Query Margin Account's Open OCO (USER_DATA)
+        ///
+        /// Weight(IP): 10
+        ///
+        /// * `symbol` is mandatory for isolated margin.
+        pub fn open_order_lists(
+            &self,
+            symbol: Option<impl Serialize>,
+            is_isolated: bool,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<MarginOrderList>>> {
+            if is_isolated && symbol.is_none() {
+                Err(ApiError::mandatory_field_omitted("symbol"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_MARGIN_OPEN_ORDER_LIST)?
+                        .signed(time_window)?
+                        .query_arg("isIsolated", is_isolated_arg(is_isolated))?
+                        .try_query_arg("symbol", &symbol)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_new_order() {
+        let json = r#"{
+            "symbol": "BTCUSDT", "orderId": 28, "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+            "transactTime": 1507725176595, "price": "1.00000000", "origQty": "10.00000000",
+            "executedQty": "10.00000000", "cummulativeQuoteQty": "10.00000000",
+            "status": "FILLED", "timeInForce": "GTC", "type": "MARKET", "side": "SELL",
+            "marginBuyBorrowAmount": 5, "marginBuyBorrowAsset": "BTC", "isIsolated": true,
+            "fills": [
+                {"price": "4000.00000000", "qty": "1.00000000", "commission": "4.00000000",
+                 "commissionAsset": "USDT"}
+            ]
+        }"#;
+        let order: MarginNewOrder = serde_json::from_str(json).unwrap();
+        assert!(order.is_isolated);
+        assert_eq!(order.status, Some(OrderStatus::Filled));
+        assert_eq!(order.fills[0].qty, dec!(1));
+        assert_eq!(order.margin_buy_borrow_amount, Some(dec!(5)));
+        assert_eq!(order.margin_buy_borrow_asset.as_deref(), Some("BTC"));
+
+        let json = r#"{"symbol": "BTCUSDT", "orderId": 28, "isIsolated": false,
+            "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP", "transactTime": 1507725176595}"#;
+        let order: MarginNewOrder = serde_json::from_str(json).unwrap();
+        assert_eq!(order.status, None);
+        assert!(order.fills.is_empty());
+        assert_eq!(order.margin_buy_borrow_amount, None);
+    }
+
+    #[test]
+    fn test_deserialize_orders() {
+        let json = r#"[{
+            "clientOrderId": "qhcZw71gAkCCTv0t0k8LUK", "cummulativeQuoteQty": "0.00000000",
+            "executedQty": "0.00000000", "icebergQty": "0.00000000", "isWorking": true,
+            "orderId": 211842552, "origQty": "0.30000000", "price": "0.00475010",
+            "side": "SELL", "status": "NEW", "stopPrice": "0.00000000", "symbol": "BNBBTC",
+            "isIsolated": true, "time": 1562040170089, "timeInForce": "GTC", "type": "LIMIT",
+            "selfTradePreventionMode": "NONE", "updateTime": 1562040170089
+        }]"#;
+        let orders: Vec<MarginOrder> = serde_json::from_str(json).unwrap();
+        assert_eq!(orders[0].order_id, 211842552);
+        assert_eq!(orders[0].price, dec!(0.0047501));
+        assert_eq!(orders[0].r#type, OrderType::Limit);
+
+        let json = r#"{
+            "symbol": "LTCBTC", "isIsolated": true, "orderId": 28,
+            "origClientOrderId": "myOrder1", "clientOrderId": "cancelMyOrder1",
+            "price": "1.00000000", "origQty": "10.00000000", "executedQty": "8.00000000",
+            "cummulativeQuoteQty": "8.00000000", "status": "CANCELED", "timeInForce": "GTC",
+            "type": "LIMIT", "side": "SELL"
+        }"#;
+        let cancelled: MarginCancelledOrder = serde_json::from_str(json).unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Canceled);
+        assert_eq!(cancelled.executed_qty, dec!(8));
+    }
+
+    #[test]
+    fn test_deserialize_order_list() {
+        let json = r#"{
+            "orderListId": 0, "contingencyType": "OCO", "listStatusType": "EXEC_STARTED",
+            "listOrderStatus": "EXECUTING", "listClientOrderId": "JYVpp3F0f5CAG15DhtrqLp",
+            "transactionTime": 1563417480525, "symbol": "LTCBTC",
+            "marginBuyBorrowAmount": "5", "marginBuyBorrowAsset": "BTC", "isIsolated": false,
+            "orders": [
+                {"symbol": "LTCBTC", "orderId": 2, "clientOrderId": "Kk7sqHb9J6mJWTMDVW7Vos"},
+                {"symbol": "LTCBTC", "orderId": 3, "clientOrderId": "xTXKaGYd4bluPVp78IVRvl"}
+            ],
+            "orderReports": [
+                {"symbol": "LTCBTC", "orderId": 2, "orderListId": 0,
+                 "clientOrderId": "Kk7sqHb9J6mJWTMDVW7Vos", "transactTime": 1563417480525,
+                 "price": "0.000000", "origQty": "0.624363", "executedQty": "0.000000",
+                 "cummulativeQuoteQty": "0.000000", "status": "NEW", "timeInForce": "GTC",
+                 "type": "STOP_LOSS", "side": "BUY", "stopPrice": "0.960664"},
+                {"symbol": "LTCBTC", "orderId": 3, "orderListId": 0,
+                 "clientOrderId": "xTXKaGYd4bluPVp78IVRvl", "transactTime": 1563417480525,
+                 "price": "0.036435", "origQty": "0.624363", "executedQty": "0.000000",
+                 "cummulativeQuoteQty": "0.000000", "status": "NEW", "timeInForce": "GTC",
+                 "type": "LIMIT_MAKER", "side": "BUY"}
+            ]
+        }"#;
+        let list: MarginOrderList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.list_status_type, OrderListStatusType::ExecStarted);
+        assert_eq!(list.orders.len(), 2);
+        assert_eq!(list.order_reports[0].stop_price, Some(dec!(0.960664)));
+        assert_eq!(list.order_reports[1].r#type, OrderType::LimitMaker);
+        assert_eq!(list.margin_buy_borrow_amount, Some(dec!(5)));
+
+        assert_eq!(
+            serde_json::to_string(&SideEffectType::AutoBorrowRepay).unwrap(),
+            r#""AUTO_BORROW_REPAY""#
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod margin;
 pub mod spot;
 pub mod um;
 
@@ -2,12 +2,13 @@ use super::RL_ORDERS_PER_DAY;
 use super::RL_ORDERS_PER_SECOND;
 use super::RL_WEIGHT_PER_MINUTE;
 use super::RlPriorityLevel;
+use super::SymbolCapability;
 use super::SymbolPermission;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const API_V3_ORDER_TEST: &str = "/api/v3/order/test";
 pub const API_V3_ORDER: &str = "/api/v3/order";
+pub const API_V3_ORDER_CANCEL_REPLACE: &str = "/api/v3/order/cancelReplace";
 // TODO pub const API_V3_ORDER_OCO: &str = "/api/v3/order/oco";
 // TODO pub const API_V3_ORDER_LIST: &str = "/api/v3/orderList";
 pub const API_V3_OPEN_ORDERS: &str = "/api/v3/openOrders";
@@ -15,7 +16,9 @@ pub const API_V3_ALL_ORDERS: &str = "/api/v3/allOrders";
 // TODO pub const API_V3_ALL_ORDER_LIST: &str = "/api/v3/allOrderList";
 // TODO pub const API_V3_OPEN_ORDER_LIST: &str = "/api/v3/openOrderList";
 pub const API_V3_ACCOUNT: &str = "/api/v3/account";
+pub const API_V3_ACCOUNT_COMMISSION: &str = "/api/v3/account/commission";
 pub const API_V3_MY_TRADES: &str = "/api/v3/myTrades";
+pub const API_V3_MY_PREVENTED_MATCHES: &str = "/api/v3/myPreventedMatches";
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum OrderSide {
@@ -75,10 +78,138 @@ pub enum OrderResponseType {
 #[derive(Debug, Deserialize, Clone)]
 pub struct NewTestOrder {}
 
-pub enum NewOrder {
-    Ack(Task<NewOrderAck>),
-    Result(Task<NewOrderResult>),
-    Full(Task<NewOrderFull>),
+/// Response of the test order endpoint.
+///
+/// The endpoint returns an empty object unless `computeCommissionRates` is set.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TestOrder {
+    Commission(TestOrderCommission),
+    Empty(NewTestOrder),
+}
+
+impl TestOrder {
+    pub fn commission(&self) -> Option<&TestOrderCommission> {
+        match self {
+            TestOrder::Commission(commission) => Some(commission),
+            TestOrder::Empty(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TestOrderCommission {
+    pub standard_commission_for_order: CommissionRates,
+    pub tax_commission_for_order: CommissionRates,
+    pub discount: CommissionDiscount,
+}
+
+/// New order parameters.
+///
+/// The same request can be validated with `SpotApi::test_order` before it is placed with
+/// `SpotApi::place_order`.
+#[derive(Debug, Clone)]
+pub struct NewOrderRequest {
+    pub symbol: Atom,
+    pub side: OrderSide,
+    pub r#type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub iceberg_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+    /// Trailing delta in BIPS, makes a stop loss or take profit order a trailing stop.
+    pub trailing_delta: Option<u32>,
+    pub new_client_order_id: Option<String>,
+    pub new_order_resp_type: Option<OrderResponseType>,
+}
+
+impl NewOrderRequest {
+    pub fn new(symbol: impl Into<Atom>, side: OrderSide, r#type: OrderType) -> Self {
+        NewOrderRequest {
+            symbol: symbol.into(),
+            side,
+            r#type,
+            time_in_force: None,
+            quantity: None,
+            quote_order_qty: None,
+            iceberg_qty: None,
+            price: None,
+            stop_price: None,
+            trailing_delta: None,
+            new_client_order_id: None,
+            new_order_resp_type: None,
+        }
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Decimal) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn quote_order_qty(mut self, quote_order_qty: Decimal) -> Self {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: Decimal) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: Decimal) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn trailing_delta(mut self, trailing_delta: u32) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: impl Into<String>) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.into());
+        self
+    }
+
+    pub fn new_order_resp_type(mut self, new_order_resp_type: OrderResponseType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    /// Checks that the fields mandatory for the order type are set:
+    ///
+    /// * `LIMIT`: time_in_force, quantity, price;
+    /// * `MARKET`: quantity or quote_order_qty;
+    /// * `STOP_LOSS`, `TAKE_PROFIT`: quantity, stop_price or trailing_delta;
+    /// * `STOP_LOSS_LIMIT`, `TAKE_PROFIT_LIMIT`: time_in_force, quantity, price, stop_price or
+    ///   trailing_delta;
+    /// * `LIMIT_MAKER`: quantity, price.
+    ///
+    /// The same check is done before an order is sent.
+    pub fn validate(&self) -> BinanceResult<()> {
+        check_order_fields(
+            self.r#type,
+            self.time_in_force,
+            self.quantity,
+            self.quote_order_qty,
+            self.price,
+            self.stop_price,
+            self.trailing_delta,
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -187,6 +318,81 @@ pub struct CancelledOrder {
     pub side: OrderSide,
 }
 
+/// What to do when the cancellation of a cancel-replace request fails.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CancelReplaceMode {
+    /// The new order is not placed.
+    #[serde(rename = "STOP_ON_FAILURE")]
+    StopOnFailure,
+    /// The new order is placed anyway.
+    #[serde(rename = "ALLOW_FAILURE")]
+    AllowFailure,
+}
+
+/// Cancel-replace parameters, see `SpotApi::cancel_replace_order`.
+///
+/// Cancels an order on the symbol of `order` and places `order` instead.
+/// Either `cancel_order_id` or `cancel_orig_client_order_id` must be set.
+#[derive(Debug, Clone)]
+pub struct CancelReplaceRequest {
+    pub order: NewOrderRequest,
+    pub mode: CancelReplaceMode,
+    pub cancel_order_id: Option<u64>,
+    pub cancel_orig_client_order_id: Option<String>,
+    /// Identifies the cancellation, generated by the server by default.
+    pub cancel_new_client_order_id: Option<String>,
+}
+
+impl CancelReplaceRequest {
+    pub fn new(order: NewOrderRequest, mode: CancelReplaceMode) -> Self {
+        CancelReplaceRequest {
+            order,
+            mode,
+            cancel_order_id: None,
+            cancel_orig_client_order_id: None,
+            cancel_new_client_order_id: None,
+        }
+    }
+
+    pub fn cancel_order_id(mut self, cancel_order_id: u64) -> Self {
+        self.cancel_order_id = Some(cancel_order_id);
+        self
+    }
+
+    pub fn cancel_orig_client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.cancel_orig_client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn cancel_new_client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.cancel_new_client_order_id = Some(client_order_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CancelReplaceResult {
+    #[serde(rename = "SUCCESS")]
+    Success,
+    #[serde(rename = "FAILURE")]
+    Failure,
+    #[serde(rename = "NOT_ATTEMPTED")]
+    NotAttempted,
+}
+
+/// Response of a successful cancel-replace.
+///
+/// `V` is the new order response, see `SpotApi::cancel_replace_order`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceOrder<V> {
+    pub cancel_result: CancelReplaceResult,
+    pub new_order_result: CancelReplaceResult,
+    #[serde(default)]
+    pub cancel_response: Option<CancelledOrder>,
+    pub new_order_response: Option<V>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
@@ -229,6 +435,92 @@ pub struct AccountInformation {
     pub permissions: Vec<SymbolPermission>,
 }
 
+/// Self-trade prevention mode.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SelfTradePreventionMode {
+    None,
+    ExpireTaker,
+    ExpireMaker,
+    ExpireBoth,
+    Decrement,
+}
+
+/// Order match that was expired because of self-trade prevention.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatch {
+    pub symbol: Atom,
+    pub prevented_match_id: u64,
+    pub taker_order_id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maker_symbol: Option<Atom>,
+    pub maker_order_id: u64,
+    pub trade_group_id: i64,
+    pub self_trade_prevention_mode: SelfTradePreventionMode,
+    pub price: Decimal,
+    pub maker_prevented_quantity: Decimal,
+    pub transact_time: u64,
+}
+
+/// Checks the lookup mode of `SpotApi::my_prevented_matches` and returns its weight.
+///
+/// Exactly one of `prevented_match_id` and `order_id` must be set;
+/// `from_prevented_match_id` and `limit` page through the matches of `order_id`.
+fn prevented_matches_weight(
+    prevented_match_id: Option<u64>,
+    order_id: Option<u64>,
+    from_prevented_match_id: Option<u64>,
+    limit: Option<u16>,
+) -> BinanceResult<u32> {
+    match (prevented_match_id, order_id) {
+        (Some(_), None) if from_prevented_match_id.is_none() && limit.is_none() => Ok(2),
+        (Some(_), None) => Err(ApiError::mandatory_field_omitted("order_id"))?,
+        (None, Some(_)) => Ok(20),
+        _ => Err(ApiError::mandatory_field_omitted(
+            "exactly one of prevented_match_id or order_id",
+        ))?,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCommission {
+    pub symbol: Atom,
+    pub standard_commission: CommissionRates,
+    pub tax_commission: CommissionRates,
+    pub discount: CommissionDiscount,
+}
+
+/// Commission rates. `buyer` and `seller` are only reported for the account commission.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buyer: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seller: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: Atom,
+    pub discount: Decimal,
+}
+
+impl CommissionDiscount {
+    /// Multiplier applied to the standard commission when it is paid in `discount_asset`,
+    /// `None` unless the discount is enabled for both the account and the symbol.
+    pub fn factor(&self) -> Option<Decimal> {
+        (self.enabled_for_account && self.enabled_for_symbol).then_some(self.discount)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum AccountType {
     #[serde(rename = "SPOT")]
@@ -262,39 +554,46 @@ pub struct MyTrade {
     pub is_best_match: bool,
 }
 
-impl NewOrder {
-    pub fn is_ack(&self) -> bool {
-        matches!(self, NewOrder::Ack(_))
-    }
-
-    pub fn is_result(&self) -> bool {
-        matches!(self, NewOrder::Result(_))
-    }
-
-    pub fn is_full(&self) -> bool {
-        matches!(self, NewOrder::Full(_))
-    }
-
-    pub fn as_ack(self) -> Option<Task<NewOrderAck>> {
-        match self {
-            NewOrder::Ack(order) => Some(order),
-            _ => None,
-        }
-    }
-
-    pub fn as_result(self) -> Option<Task<NewOrderResult>> {
-        match self {
-            NewOrder::Result(order) => Some(order),
-            _ => None,
+/// Checks that the fields mandatory for the order type are set.
+///
+/// The error names the first missing field.
+pub(crate) fn check_order_fields(
+    r#type: OrderType,
+    time_in_force: Option<TimeInForce>,
+    quantity: Option<Decimal>,
+    quote_order_qty: Option<Decimal>,
+    price: Option<Decimal>,
+    stop_price: Option<Decimal>,
+    trailing_delta: Option<u32>,
+) -> BinanceResult<()> {
+    let time_in_force = ("time_in_force", time_in_force.is_some());
+    let quantity = ("quantity", quantity.is_some());
+    let price = ("price", price.is_some());
+    // A trailing stop may be sent without a stop price.
+    let stop_price = (
+        "stop_price",
+        stop_price.is_some() || trailing_delta.is_some(),
+    );
+    let required = match r#type {
+        OrderType::Limit => vec![time_in_force, quantity, price],
+        OrderType::Market => {
+            if !quantity.1 && quote_order_qty.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "quantity or quote_order_qty",
+                ))?
+            }
+            vec![]
         }
-    }
-
-    pub fn as_full(self) -> Option<Task<NewOrderFull>> {
-        match self {
-            NewOrder::Full(order) => Some(order),
-            _ => None,
+        OrderType::StopLoss | OrderType::TakeProfit => vec![quantity, stop_price],
+        OrderType::StopLossLimit | OrderType::TakeProfitLimit => {
+            vec![time_in_force, quantity, price, stop_price]
         }
+        OrderType::LimitMaker => vec![quantity, price],
+    };
+    if let Some((field, _)) = required.into_iter().find(|(_, is_set)| !is_set) {
+        Err(ApiError::mandatory_field_omitted(field))?
     }
+    Ok(())
 }
 
 #[cfg(feature = "with_network")]
@@ -302,8 +601,51 @@ pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use super::super::util::GuardedOrder;
     use super::*;
     use crate::client::RequestBuilder;
+    use crate::client::Task;
+
+    pub enum NewOrder {
+        Ack(Task<NewOrderAck>),
+        Result(Task<NewOrderResult>),
+        Full(Task<NewOrderFull>),
+    }
+
+    impl NewOrder {
+        pub fn is_ack(&self) -> bool {
+            matches!(self, NewOrder::Ack(_))
+        }
+
+        pub fn is_result(&self) -> bool {
+            matches!(self, NewOrder::Result(_))
+        }
+
+        pub fn is_full(&self) -> bool {
+            matches!(self, NewOrder::Full(_))
+        }
+
+        pub fn as_ack(self) -> Option<Task<NewOrderAck>> {
+            match self {
+                NewOrder::Ack(order) => Some(order),
+                _ => None,
+            }
+        }
+
+        pub fn as_result(self) -> Option<Task<NewOrderResult>> {
+            match self {
+                NewOrder::Result(order) => Some(order),
+                _ => None,
+            }
+        }
+
+        pub fn as_full(self) -> Option<Task<NewOrderFull>> {
+            match self {
+                NewOrder::Full(order) => Some(order),
+                _ => None,
+            }
+        }
+    }
 
     impl<S> SpotApi<S>
     where
@@ -321,7 +663,7 @@ mod with_network {
         #[allow(clippy::too_many_arguments)]
         pub fn create_order_test(
             &self,
-            symbol: impl Serialize,
+            symbol: impl AsRef<str>,
             side: OrderSide,
             r#type: OrderType,
             time_in_force: Option<TimeInForce>,
@@ -344,9 +686,10 @@ mod with_network {
                 iceberg_qty,
                 price,
                 stop_price,
+                None,
                 new_client_order_id,
                 new_order_resp_type,
-                true,
+                API_V3_ORDER_TEST,
                 time_window,
             )?;
 
@@ -357,6 +700,91 @@ mod with_network {
                 .send())
         }
 
+        /// Test New Order (TRADE)
+        ///
+        /// Validates the order without sending it into the matching engine.
+        /// With `compute_commission_rates` the response carries the commission rates
+        /// that would apply to the order.
+        ///
+        /// Weight: 1; 20 with `compute_commission_rates`
+        pub fn test_order(
+            &self,
+            order: &NewOrderRequest,
+            compute_commission_rates: bool,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<TestOrder>> {
+            let request = self
+                .prepare_order_request(
+                    &order.symbol,
+                    order.side,
+                    order.r#type,
+                    order.time_in_force,
+                    order.quantity,
+                    order.quote_order_qty,
+                    order.iceberg_qty,
+                    order.price,
+                    order.stop_price,
+                    order.trailing_delta,
+                    order.new_client_order_id.as_deref(),
+                    order.new_order_resp_type,
+                    API_V3_ORDER_TEST,
+                    time_window,
+                )?
+                .try_query_arg(
+                    "computeCommissionRates",
+                    &compute_commission_rates.then_some(true),
+                )?;
+
+            Ok(self
+                .rate_limiter
+                .task(request)
+                .cost(
+                    RL_WEIGHT_PER_MINUTE,
+                    if compute_commission_rates { 20 } else { 1 },
+                )
+                .send())
+        }
+
+        /// New Order (TRADE)
+        ///
+        /// Same as [`Self::create_order`], but takes a [`NewOrderRequest`].
+        ///
+        /// If the attached [`OrderGuard`](crate::api::spot::util::OrderGuard) has
+        /// [`server_validation`](crate::api::spot::util::OrderGuard::server_validation) enabled,
+        /// the order is validated with [`Self::test_order`] first and is not placed
+        /// if the validation fails.
+        pub async fn place_order(
+            &self,
+            order: &NewOrderRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<NewOrder> {
+            let time_window = time_window.into();
+            if self
+                .order_guard
+                .as_ref()
+                .is_some_and(|guard| guard.has_server_validation())
+            {
+                self.test_order(order, false, time_window)?.await?;
+            }
+            let request = self.prepare_order_request(
+                &order.symbol,
+                order.side,
+                order.r#type,
+                order.time_in_force,
+                order.quantity,
+                order.quote_order_qty,
+                order.iceberg_qty,
+                order.price,
+                order.stop_price,
+                order.trailing_delta,
+                order.new_client_order_id.as_deref(),
+                order.new_order_resp_type,
+                API_V3_ORDER,
+                time_window,
+            )?;
+            Ok(self.send_new_order(request, order.r#type, order.new_order_resp_type))
+        }
+
         /// New Order (TRADE)
         ///
         /// Send in a new order.
@@ -365,7 +793,7 @@ mod with_network {
         #[allow(clippy::too_many_arguments)]
         pub fn create_order(
             &self,
-            symbol: impl Serialize,
+            symbol: impl AsRef<str>,
             side: OrderSide,
             r#type: OrderType,
             time_in_force: Option<TimeInForce>,
@@ -388,12 +816,21 @@ mod with_network {
                 iceberg_qty,
                 price,
                 stop_price,
+                None,
                 new_client_order_id,
                 new_order_resp_type,
-                false,
+                API_V3_ORDER,
                 time_window,
             )?;
+            Ok(self.send_new_order(request, r#type, new_order_resp_type))
+        }
 
+        fn send_new_order(
+            &self,
+            request: RequestBuilder<S>,
+            r#type: OrderType,
+            new_order_resp_type: Option<OrderResponseType>,
+        ) -> NewOrder {
             let new_order_resp_type = new_order_resp_type.unwrap_or(match r#type {
                 OrderType::Limit | OrderType::Market => OrderResponseType::Full,
                 _ => OrderResponseType::Ack,
@@ -406,17 +843,17 @@ mod with_network {
                 .cost(RL_ORDERS_PER_DAY, 1)
                 .priority(RlPriorityLevel::High as u8);
 
-            Ok(match new_order_resp_type {
+            match new_order_resp_type {
                 OrderResponseType::Ack => NewOrder::Ack(task.send::<NewOrderAck>()),
                 OrderResponseType::Result => NewOrder::Result(task.send::<NewOrderResult>()),
                 OrderResponseType::Full => NewOrder::Full(task.send::<NewOrderFull>()),
-            })
+            }
         }
 
         #[allow(clippy::too_many_arguments)]
         fn prepare_order_request(
             &self,
-            symbol: impl Serialize,
+            symbol: impl AsRef<str>,
             side: OrderSide,
             r#type: OrderType,
             time_in_force: Option<TimeInForce>,
@@ -425,69 +862,41 @@ mod with_network {
             iceberg_qty: Option<Decimal>,
             price: Option<Decimal>,
             stop_price: Option<Decimal>,
+            trailing_delta: Option<u32>,
             new_client_order_id: Option<impl Serialize>,
             new_order_resp_type: Option<OrderResponseType>,
-            is_test: bool,
+            endpoint: &str,
             time_window: impl Into<TimeWindow>,
         ) -> BinanceResult<RequestBuilder<S>> {
-            let endpoint = if is_test {
-                API_V3_ORDER_TEST
-            } else {
-                API_V3_ORDER
-            };
-            match r#type {
-                OrderType::Limit => {
-                    if time_in_force.is_none() || quantity.is_none() || price.is_none() {
-                        Err(ApiError::mandatory_field_omitted(
-                            "time_in_force, quantity, price",
-                        ))?
-                    }
-                }
-                OrderType::Market => {
-                    if quantity.is_none() && quote_order_qty.is_none() {
-                        Err(ApiError::mandatory_field_omitted(
-                            "quantity or quote_order_qty",
-                        ))?
-                    }
-                }
-                OrderType::StopLoss => {
-                    if quantity.is_none() || stop_price.is_none() {
-                        Err(ApiError::mandatory_field_omitted("quantity, stop_price"))?
-                    }
-                }
-                OrderType::StopLossLimit => {
-                    if time_in_force.is_none()
-                        || quantity.is_none()
-                        || price.is_none()
-                        || stop_price.is_none()
-                    {
-                        Err(ApiError::mandatory_field_omitted(
-                            "time_in_force, quantity, price, stop_price",
-                        ))?
-                    }
-                }
-                OrderType::TakeProfit => {
-                    if quantity.is_none() || stop_price.is_none() {
-                        Err(ApiError::mandatory_field_omitted("quantity, stop_price"))?
-                    }
-                }
-                OrderType::TakeProfitLimit => {
-                    if time_in_force.is_none()
-                        || quantity.is_none()
-                        || price.is_none()
-                        || stop_price.is_none()
-                    {
-                        Err(ApiError::mandatory_field_omitted(
-                            "time_in_force, quantity, price, stop_price",
-                        ))?
-                    }
-                }
-                OrderType::LimitMaker => {
-                    if quantity.is_none() || price.is_none() {
-                        Err(ApiError::mandatory_field_omitted("quantity, price"))?
-                    }
-                }
-            };
+            check_order_fields(
+                r#type,
+                time_in_force,
+                quantity,
+                quote_order_qty,
+                price,
+                stop_price,
+                trailing_delta,
+            )?;
+            let symbol = symbol.as_ref();
+            self.symbols.check(symbol, |symbol| {
+                symbol.check_order_features(
+                    r#type,
+                    quote_order_qty.is_some(),
+                    iceberg_qty.is_some(),
+                    trailing_delta.is_some(),
+                )
+            })?;
+            if let Some(guard) = &self.order_guard {
+                guard.check(&GuardedOrder {
+                    symbol,
+                    side,
+                    r#type,
+                    quantity,
+                    quote_order_qty,
+                    price,
+                    stop_price,
+                })?;
+            }
             let request = self
                 .client
                 .post(endpoint)?
@@ -496,17 +905,83 @@ mod with_network {
                 .query_arg("side", &side)?
                 .query_arg("type", &r#type)?
                 .try_query_arg("timeInForce", &time_in_force)?
-                .try_query_arg("quantity", &quantity)?
-                .try_query_arg("quoteOrderQty", &quote_order_qty)?
-                .try_query_arg("icebergQty", &iceberg_qty)?
-                .try_query_arg("price", &price)?
-                .try_query_arg("stopPrice", &stop_price)?
+                .try_decimal_arg("quantity", &quantity)?
+                .try_decimal_arg("quoteOrderQty", &quote_order_qty)?
+                .try_decimal_arg("icebergQty", &iceberg_qty)?
+                .try_decimal_arg("price", &price)?
+                .try_decimal_arg("stopPrice", &stop_price)?
+                .try_query_arg("trailingDelta", &trailing_delta)?
                 .try_query_arg("newClientOrderId", &new_client_order_id)?
                 .try_query_arg("newOrderRespType", &new_order_resp_type)?;
 
             Ok(request)
         }
 
+        /// Cancel an Existing Order and Send a New Order (TRADE)
+        ///
+        /// Cancels an order and places `request.order` on the same symbol.
+        /// If the cancellation or the new order fails, the server responds with an error
+        /// that reports the outcome of both.
+        ///
+        /// `V` is one of [`NewOrderAck`], [`NewOrderResult`] or [`NewOrderFull`], matching
+        /// `new_order_resp_type` of the new order.
+        ///
+        /// Weight: 1
+        pub fn cancel_replace_order<V>(
+            &self,
+            request: &CancelReplaceRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<CancelReplaceOrder<V>>>
+        where
+            V: serde::de::DeserializeOwned + std::fmt::Debug,
+        {
+            if request.cancel_order_id.is_none() && request.cancel_orig_client_order_id.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "cancel_order_id or cancel_orig_client_order_id",
+                ))?
+            }
+            let order = &request.order;
+            self.symbols.check(&order.symbol, |symbol| {
+                symbol.check_capability(SymbolCapability::CancelReplace)
+            })?;
+            let request = self
+                .prepare_order_request(
+                    &order.symbol,
+                    order.side,
+                    order.r#type,
+                    order.time_in_force,
+                    order.quantity,
+                    order.quote_order_qty,
+                    order.iceberg_qty,
+                    order.price,
+                    order.stop_price,
+                    order.trailing_delta,
+                    order.new_client_order_id.as_deref(),
+                    order.new_order_resp_type,
+                    API_V3_ORDER_CANCEL_REPLACE,
+                    time_window,
+                )?
+                .query_arg("cancelReplaceMode", &request.mode)?
+                .try_query_arg("cancelOrderId", &request.cancel_order_id)?
+                .try_query_arg(
+                    "cancelOrigClientOrderId",
+                    &request.cancel_orig_client_order_id,
+                )?
+                .try_query_arg(
+                    "cancelNewClientOrderId",
+                    &request.cancel_new_client_order_id,
+                )?;
+
+            Ok(self
+                .rate_limiter
+                .task(request)
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_ORDERS_PER_SECOND, 1)
+                .cost(RL_ORDERS_PER_DAY, 1)
+                .priority(RlPriorityLevel::High as u8)
+                .send())
+        }
+
         /// Cancel Order (TRADE)
         ///
         /// Cancel an active order.
@@ -689,6 +1164,28 @@ mod with_network {
                 .send())
         }
 
+        /// Query Commission Rates (USER_DATA).
+        ///
+        /// Get current account commission rates.
+        ///
+        /// Weight(IP): 20
+        pub fn account_commission(
+            &self,
+            symbol: impl AsRef<str>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<AccountCommission>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_ACCOUNT_COMMISSION)?
+                        .signed(time_window)?
+                        .query_arg("symbol", symbol.as_ref())?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 20)
+                .send())
+        }
+
         /// Account Trade List (USER_DATA).
         ///
         /// Get trades for a specific account and symbol.
@@ -723,5 +1220,239 @@ mod with_network {
                 .cost(RL_WEIGHT_PER_MINUTE, 20)
                 .send())
         }
+
+        /// Query Prevented Matches (USER_DATA)
+        ///
+        /// Displays the list of orders that were expired because of self-trade prevention.
+        ///
+        /// Weight(IP): 2 when querying by `prevented_match_id`, 20 when querying by `order_id`.
+        ///
+        /// Supported lookups:
+        /// * `symbol` + `prevented_match_id`;
+        /// * `symbol` + `order_id`, optionally paged with `from_prevented_match_id` and `limit`
+        ///   (default 500; max 1000).
+        pub fn my_prevented_matches(
+            &self,
+            symbol: impl AsRef<str>,
+            prevented_match_id: Option<u64>,
+            order_id: Option<u64>,
+            from_prevented_match_id: Option<u64>,
+            limit: Option<u16>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<PreventedMatch>>> {
+            let weight = prevented_matches_weight(
+                prevented_match_id,
+                order_id,
+                from_prevented_match_id,
+                limit,
+            )?;
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_MY_PREVENTED_MATCHES)?
+                        .signed(time_window)?
+                        .query_arg("symbol", symbol.as_ref())?
+                        .try_query_arg("preventedMatchId", &prevented_match_id)?
+                        .try_query_arg("orderId", &order_id)?
+                        .try_query_arg("fromPreventedMatchId", &from_prevented_match_id)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, weight)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn omitted_field(order: &NewOrderRequest) -> Option<String> {
+        match order.validate() {
+            Ok(()) => None,
+            Err(BinanceError::ApiError(ApiError::MandatoryFieldOmitted(field))) => {
+                Some(field.into_owned())
+            }
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_new_order() {
+        let stop_loss_limit =
+            NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::StopLossLimit)
+                .quantity(dec!(0.1))
+                .price(dec!(29000));
+        assert_eq!(
+            omitted_field(&stop_loss_limit).as_deref(),
+            Some("time_in_force")
+        );
+        let stop_loss_limit = stop_loss_limit.time_in_force(TimeInForce::Gtc);
+        assert_eq!(
+            omitted_field(&stop_loss_limit).as_deref(),
+            Some("stop_price")
+        );
+        let stop_loss_limit = stop_loss_limit.stop_price(dec!(29500));
+        assert_eq!(omitted_field(&stop_loss_limit), None);
+
+        let market = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Market);
+        assert_eq!(
+            omitted_field(&market).as_deref(),
+            Some("quantity or quote_order_qty")
+        );
+        assert_eq!(omitted_field(&market.quote_order_qty(dec!(100))), None);
+
+        let take_profit = NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::TakeProfit)
+            .quantity(dec!(0.1));
+        assert_eq!(omitted_field(&take_profit).as_deref(), Some("stop_price"));
+        assert_eq!(omitted_field(&take_profit.trailing_delta(200)), None);
+
+        let limit_maker = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::LimitMaker)
+            .price(dec!(29000));
+        assert_eq!(omitted_field(&limit_maker).as_deref(), Some("quantity"));
+    }
+
+    #[test]
+    fn test_deserialize_test_order_empty() {
+        let order: TestOrder = serde_json::from_str("{}").unwrap();
+        assert!(matches!(order, TestOrder::Empty(_)));
+        assert!(order.commission().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_test_order_commission() {
+        let json = r#"{
+            "standardCommissionForOrder": {
+                "maker": "0.00000112",
+                "taker": "0.00000114"
+            },
+            "taxCommissionForOrder": {
+                "maker": "0.00000112",
+                "taker": "0.00000114"
+            },
+            "discount": {
+                "enabledForAccount": true,
+                "enabledForSymbol": true,
+                "discountAsset": "BNB",
+                "discount": "0.25000000"
+            }
+        }"#;
+        let order: TestOrder = serde_json::from_str(json).unwrap();
+        let commission = order.commission().unwrap();
+        assert_eq!(
+            commission.standard_commission_for_order,
+            CommissionRates {
+                maker: dec!(0.00000112),
+                taker: dec!(0.00000114),
+                buyer: None,
+                seller: None,
+            }
+        );
+        assert_eq!(commission.discount.discount_asset, Atom::from("BNB"));
+        assert_eq!(commission.discount.discount, dec!(0.25));
+    }
+
+    #[test]
+    fn test_deserialize_account_commission() {
+        let json = r#"{
+            "symbol": "BTCUSDT",
+            "standardCommission": {
+                "maker": "0.00000010",
+                "taker": "0.00000020",
+                "buyer": "0.00000030",
+                "seller": "0.00000040"
+            },
+            "taxCommission": {
+                "maker": "0.00000112",
+                "taker": "0.00000114",
+                "buyer": "0.00000118",
+                "seller": "0.00000116"
+            },
+            "discount": {
+                "enabledForAccount": true,
+                "enabledForSymbol": true,
+                "discountAsset": "BNB",
+                "discount": "0.75000000"
+            }
+        }"#;
+        let commission: AccountCommission = serde_json::from_str(json).unwrap();
+        assert_eq!(commission.symbol, Atom::from("BTCUSDT"));
+        assert_eq!(commission.standard_commission.buyer, Some(dec!(0.0000003)));
+        assert_eq!(commission.tax_commission.seller, Some(dec!(0.00000116)));
+        assert_eq!(commission.discount.discount_asset, Atom::from("BNB"));
+        assert_eq!(commission.discount.factor(), Some(dec!(0.75)));
+
+        let disabled = CommissionDiscount {
+            enabled_for_symbol: false,
+            ..commission.discount
+        };
+        assert_eq!(disabled.factor(), None);
+    }
+
+    #[test]
+    fn test_deserialize_prevented_matches() {
+        let json = r#"[
+            {
+                "symbol": "BTCUSDT",
+                "preventedMatchId": 1,
+                "takerOrderId": 5,
+                "makerSymbol": "BTCUSDT",
+                "makerOrderId": 3,
+                "tradeGroupId": 1,
+                "selfTradePreventionMode": "EXPIRE_MAKER",
+                "price": "1.100000",
+                "makerPreventedQuantity": "1.300000",
+                "transactTime": 1669101687094
+            }
+        ]"#;
+        let matches: Vec<PreventedMatch> = serde_json::from_str(json).unwrap();
+        assert_eq!(matches.len(), 1);
+        let prevented = &matches[0];
+        assert_eq!(prevented.prevented_match_id, 1);
+        assert_eq!(prevented.maker_order_id, 3);
+        assert_eq!(
+            prevented.self_trade_prevention_mode,
+            SelfTradePreventionMode::ExpireMaker
+        );
+        assert_eq!(prevented.price, dec!(1.1));
+        assert_eq!(prevented.maker_prevented_quantity, dec!(1.3));
+    }
+
+    #[test]
+    fn test_prevented_matches_lookup() {
+        assert_eq!(
+            prevented_matches_weight(Some(1), None, None, None).unwrap(),
+            2
+        );
+        assert_eq!(
+            prevented_matches_weight(None, Some(5), None, None).unwrap(),
+            20
+        );
+        assert_eq!(
+            prevented_matches_weight(None, Some(5), Some(1), Some(100)).unwrap(),
+            20
+        );
+
+        for (prevented_match_id, order_id, from_prevented_match_id) in [
+            (None, None, None),
+            (None, None, Some(1)),
+            (Some(1), Some(5), None),
+            (Some(1), None, Some(1)),
+        ] {
+            let err = prevented_matches_weight(
+                prevented_match_id,
+                order_id,
+                from_prevented_match_id,
+                None,
+            )
+            .unwrap_err();
+            assert!(matches!(
+                err,
+                BinanceError::ApiError(ApiError::MandatoryFieldOmitted(_))
+            ));
+        }
     }
 }
@@ -1,6 +1,5 @@
 use super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const SAPI_V1_BROKER_INFO: &str = "/sapi/v1/broker/info";
 pub const SAPI_V1_BROKER_SUB_ACCOUNT: &str = "/sapi/v1/broker/subAccount";
@@ -175,6 +174,7 @@ pub use with_network::*;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> SpotApi<S>
     where
@@ -331,7 +331,7 @@ mod with_network {
                         .try_query_arg("toId", &to_id)?
                         .try_query_arg("clientTranId", &client_tran_id)?
                         .query_arg("asset", &asset)?
-                        .query_arg("amount", &amount)?,
+                        .decimal_arg("amount", &amount)?,
                 )
                 .cost(RL_WEIGHT_PER_MINUTE, 1)
                 .send())
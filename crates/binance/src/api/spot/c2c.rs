@@ -0,0 +1,237 @@
+use serde::Deserializer;
+use serde::Serializer;
+use smart_string::SmartString;
+
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_C2C_ORDER_MATCH_LIST_USER_ORDER_HISTORY: &str =
+    "/sapi/v1/c2c/orderMatch/listUserOrderHistory";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum C2cTradeType {
+    #[serde(rename = "BUY")]
+    Buy,
+    #[serde(rename = "SELL")]
+    Sell,
+}
+
+/// Status of a C2C (P2P) order.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum C2cOrderStatus {
+    Pending,
+    Trading,
+    BuyerPayed,
+    Distributing,
+    Completed,
+    InAppeal,
+    Cancelled,
+    CancelledBySystem,
+    /// A status this version does not know about.
+    Other(SmartString),
+}
+
+impl C2cOrderStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            C2cOrderStatus::Pending => "PENDING",
+            C2cOrderStatus::Trading => "TRADING",
+            C2cOrderStatus::BuyerPayed => "BUYER_PAYED",
+            C2cOrderStatus::Distributing => "DISTRIBUTING",
+            C2cOrderStatus::Completed => "COMPLETED",
+            C2cOrderStatus::InAppeal => "IN_APPEAL",
+            C2cOrderStatus::Cancelled => "CANCELLED",
+            C2cOrderStatus::CancelledBySystem => "CANCELLED_BY_SYSTEM",
+            C2cOrderStatus::Other(status) => status.as_str(),
+        }
+    }
+
+    /// The order was either completed or cancelled.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            C2cOrderStatus::Completed
+                | C2cOrderStatus::Cancelled
+                | C2cOrderStatus::CancelledBySystem
+        )
+    }
+}
+
+impl Serialize for C2cOrderStatus {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for C2cOrderStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match &*s {
+            "PENDING" => Self::Pending,
+            "TRADING" => Self::Trading,
+            "BUYER_PAYED" => Self::BuyerPayed,
+            "DISTRIBUTING" => Self::Distributing,
+            "COMPLETED" => Self::Completed,
+            "IN_APPEAL" => Self::InAppeal,
+            "CANCELLED" => Self::Cancelled,
+            "CANCELLED_BY_SYSTEM" => Self::CancelledBySystem,
+            other => Self::Other(SmartString::from(other)),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct C2cTradeHistory {
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub data: Vec<C2cTrade>,
+    #[serde(default)]
+    pub total: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct C2cTrade {
+    pub order_number: String,
+    /// Number of the advertisement the order was placed on.
+    pub adv_no: String,
+    pub trade_type: C2cTradeType,
+    pub asset: Atom,
+    pub fiat: Atom,
+    pub fiat_symbol: String,
+    /// Amount of `asset`.
+    pub amount: Decimal,
+    /// Amount of `fiat`.
+    pub total_price: Decimal,
+    pub unit_price: Decimal,
+    pub order_status: C2cOrderStatus,
+    pub create_time: u64,
+    /// Paid in `asset`.
+    pub commission: Decimal,
+    pub counter_part_nick_name: String,
+    /// `MAKER` or `TAKER`.
+    #[serde(default)]
+    pub advertisement_role: Option<String>,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Get C2C Trade History (USER_DATA)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * If start_timestamp and end_timestamp are not sent, the recent 30 days are
+        ///   returned.
+        /// * The max interval between start_timestamp and end_timestamp is 30 days.
+        /// * page - Default: 1.
+        /// * rows - Default: 100 Max: 100.
+        pub fn c2c_trade_history(
+            &self,
+            trade_type: C2cTradeType,
+            start_timestamp: Option<u64>,
+            end_timestamp: Option<u64>,
+            page: Option<u32>,
+            rows: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<C2cTradeHistory>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_C2C_ORDER_MATCH_LIST_USER_ORDER_HISTORY)?
+                        .signed(time_window)?
+                        .query_arg("tradeType", &trade_type)?
+                        .try_query_arg("startTimestamp", &start_timestamp)?
+                        .try_query_arg("endTimestamp", &end_timestamp)?
+                        .try_query_arg("page", &page)?
+                        .try_query_arg("rows", &rows)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_trade_history() {
+        let json = r#"{
+            "code": "000000",
+            "message": "success",
+            "data": [{
+                "orderNumber": "20219644646554779648",
+                "advNo": "11218246497340923904",
+                "tradeType": "SELL",
+                "asset": "BUSD",
+                "fiat": "CNY",
+                "fiatSymbol": "￥",
+                "amount": "5000.00000000",
+                "totalPrice": "33400.00000000",
+                "unitPrice": "6.68",
+                "orderStatus": "COMPLETED",
+                "createTime": 1619361369000,
+                "commission": "0",
+                "counterPartNickName": "ab***",
+                "advertisementRole": "TAKER"
+            }, {
+                "orderNumber": "20219644646554779649",
+                "advNo": "11218246497340923905",
+                "tradeType": "BUY",
+                "asset": "USDT",
+                "fiat": "EUR",
+                "fiatSymbol": "€",
+                "amount": "100.00000000",
+                "totalPrice": "92.00000000",
+                "unitPrice": "0.92",
+                "orderStatus": "PAYMENT_TIMEOUT",
+                "createTime": 1619361369000,
+                "commission": "0.1",
+                "counterPartNickName": "cd***"
+            }],
+            "total": 2,
+            "success": true
+        }"#;
+        let history: C2cTradeHistory = serde_json::from_str(json).unwrap();
+        assert_eq!(history.total, 2);
+        let trade = &history.data[0];
+        assert_eq!(trade.trade_type, C2cTradeType::Sell);
+        assert_eq!(trade.total_price, dec!(33400));
+        assert_eq!(trade.order_status, C2cOrderStatus::Completed);
+        assert!(trade.order_status.is_finished());
+        let trade = &history.data[1];
+        assert_eq!(
+            trade.order_status,
+            C2cOrderStatus::Other("PAYMENT_TIMEOUT".into())
+        );
+        assert_eq!(trade.advertisement_role, None);
+        assert_eq!(
+            serde_json::to_string(&C2cOrderStatus::BuyerPayed).unwrap(),
+            r#""BUYER_PAYED""#
+        );
+    }
+}
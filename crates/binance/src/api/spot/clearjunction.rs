@@ -1,6 +1,5 @@
 use super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const SAPI_V1_FIAT_CLEARJUNCTION_WITHDRAW: &str = "/sapi/v1/fiat/clearjunction/withdraw";
 pub const SAPI_V1_FIAT_CLEARJUNCTION_QUERY_TRANSACTION: &str =
@@ -77,6 +76,7 @@ pub use with_network::*;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> SpotApi<S>
     where
@@ -111,7 +111,7 @@ mod with_network {
                         .post(SAPI_V1_FIAT_CLEARJUNCTION_WITHDRAW)?
                         .signed(time_window)?
                         .query_arg("currency", &currency)?
-                        .query_arg("amount", &amount)?
+                        .decimal_arg("amount", &amount)?
                         .query_arg("initTime", &init_time)?,
                 )
                 .cost(RL_WEIGHT_PER_MINUTE, 1)
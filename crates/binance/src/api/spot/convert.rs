@@ -0,0 +1,341 @@
+use ccx_api_lib::serde_util::number_or_string;
+use chrono::Utc;
+
+use super::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_CONVERT_EXCHANGE_INFO: &str = "/sapi/v1/convert/exchangeInfo";
+pub const SAPI_V1_CONVERT_GET_QUOTE: &str = "/sapi/v1/convert/getQuote";
+pub const SAPI_V1_CONVERT_ACCEPT_QUOTE: &str = "/sapi/v1/convert/acceptQuote";
+pub const SAPI_V1_CONVERT_ORDER_STATUS: &str = "/sapi/v1/convert/orderStatus";
+pub const SAPI_V1_CONVERT_TRADE_FLOW: &str = "/sapi/v1/convert/tradeFlow";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertPair {
+    pub from_asset: Atom,
+    pub to_asset: Atom,
+    pub from_asset_min_amount: Decimal,
+    pub from_asset_max_amount: Decimal,
+    pub to_asset_min_amount: Decimal,
+    pub to_asset_max_amount: Decimal,
+}
+
+/// Wallet the converted funds are taken from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ConvertWalletType {
+    #[serde(rename = "SPOT")]
+    Spot,
+    #[serde(rename = "FUNDING")]
+    Funding,
+    #[serde(rename = "SPOT_FUNDING")]
+    SpotFunding,
+}
+
+/// How long a quote can be accepted.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ConvertValidTime {
+    #[serde(rename = "10s")]
+    Second10,
+    #[serde(rename = "30s")]
+    Second30,
+    #[serde(rename = "1m")]
+    Minute1,
+    #[serde(rename = "2m")]
+    Minute2,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertQuote {
+    pub quote_id: String,
+    /// `to_amount / from_amount`.
+    pub ratio: Decimal,
+    pub inverse_ratio: Decimal,
+    /// Until when the quote can be accepted, in milliseconds.
+    pub valid_timestamp: u64,
+    pub to_amount: Decimal,
+    pub from_amount: Decimal,
+}
+
+impl ConvertQuote {
+    /// Whether the quote can still be accepted at `timestamp` (in milliseconds).
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        timestamp < self.valid_timestamp
+    }
+
+    /// Whether the validity window has passed by the local clock.
+    ///
+    /// Keep some margin for the request to reach the server when accepting a quote
+    /// close to its expiry.
+    pub fn is_expired(&self) -> bool {
+        !self.is_valid_at(Utc::now().timestamp_millis() as u64)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ConvertOrderStatus {
+    #[serde(rename = "PROCESS")]
+    Process,
+    #[serde(rename = "ACCEPT_SUCCESS")]
+    AcceptSuccess,
+    #[serde(rename = "SUCCESS")]
+    Success,
+    #[serde(rename = "FAIL")]
+    Fail,
+}
+
+impl ConvertOrderStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ConvertOrderStatus::Success | ConvertOrderStatus::Fail)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertAcceptedQuote {
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub order_id: u64,
+    pub create_time: u64,
+    pub order_status: ConvertOrderStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertOrder {
+    #[serde(default)]
+    pub quote_id: Option<String>,
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub order_id: u64,
+    pub order_status: ConvertOrderStatus,
+    pub from_asset: Atom,
+    pub from_amount: Decimal,
+    pub to_asset: Atom,
+    pub to_amount: Decimal,
+    pub ratio: Decimal,
+    pub inverse_ratio: Decimal,
+    pub create_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertTradeFlow {
+    pub list: Vec<ConvertOrder>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub limit: u32,
+    pub more_data: bool,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// List All Convert Pairs
+        ///
+        /// Weight(IP): 3000
+        ///
+        /// At least one of `from_asset` and `to_asset` must be sent.
+        pub fn convert_exchange_info(
+            &self,
+            from_asset: Option<impl Serialize>,
+            to_asset: Option<impl Serialize>,
+        ) -> BinanceResult<Task<Vec<ConvertPair>>> {
+            if from_asset.is_none() && to_asset.is_none() {
+                Err(ApiError::mandatory_field_omitted("from_asset or to_asset"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_CONVERT_EXCHANGE_INFO)?
+                        .try_query_arg("fromAsset", &from_asset)?
+                        .try_query_arg("toAsset", &to_asset)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 3000)
+                .send())
+        }
+
+        /// Send Quote Request (USER_DATA)
+        ///
+        /// Weight(UID): 200
+        ///
+        /// Exactly one of `from_amount` and `to_amount` must be sent, the other one
+        /// is quoted. `valid_time` defaults to 10s.
+        #[allow(clippy::too_many_arguments)]
+        pub fn convert_get_quote(
+            &self,
+            from_asset: impl Serialize,
+            to_asset: impl Serialize,
+            from_amount: Option<Decimal>,
+            to_amount: Option<Decimal>,
+            wallet_type: Option<ConvertWalletType>,
+            valid_time: Option<ConvertValidTime>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ConvertQuote>> {
+            if from_amount.is_some() == to_amount.is_some() {
+                Err(ApiError::mandatory_field_omitted(
+                    "exactly one of from_amount and to_amount",
+                ))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_CONVERT_GET_QUOTE)?
+                        .signed(time_window)?
+                        .query_arg("fromAsset", &from_asset)?
+                        .query_arg("toAsset", &to_asset)?
+                        .try_decimal_arg("fromAmount", &from_amount)?
+                        .try_decimal_arg("toAmount", &to_amount)?
+                        .try_query_arg("walletType", &wallet_type)?
+                        .try_query_arg("validTime", &valid_time)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 200)
+                .send())
+        }
+
+        /// Accept Quote (TRADE)
+        ///
+        /// Weight(UID): 500
+        ///
+        /// Check [`ConvertQuote::is_expired`] first, an expired quote is rejected.
+        pub fn convert_accept_quote(
+            &self,
+            quote_id: impl Serialize,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ConvertAcceptedQuote>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_CONVERT_ACCEPT_QUOTE)?
+                        .signed(time_window)?
+                        .query_arg("quoteId", &quote_id)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 500)
+                .send())
+        }
+
+        /// Order status (USER_DATA)
+        ///
+        /// Weight(UID): 100
+        ///
+        /// Either orderId or quoteId must be sent.
+        pub fn convert_order_status(
+            &self,
+            order_id: Option<u64>,
+            quote_id: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ConvertOrder>> {
+            if order_id.is_none() && quote_id.is_none() {
+                Err(ApiError::mandatory_field_omitted("order_id or quote_id"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_CONVERT_ORDER_STATUS)?
+                        .signed(time_window)?
+                        .try_query_arg("orderId", &order_id)?
+                        .try_query_arg("quoteId", &quote_id)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 100)
+                .send())
+        }
+
+        /// Get Convert Trade History (USER_DATA)
+        ///
+        /// Weight(UID): 3000
+        ///
+        /// * The time span between `start_time` and `end_time` is at most 30 days.
+        /// * `limit` defaults to 100, max 1000.
+        pub fn convert_trade_flow(
+            &self,
+            start_time: u64,
+            end_time: u64,
+            limit: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ConvertTradeFlow>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_CONVERT_TRADE_FLOW)?
+                        .signed(time_window)?
+                        .query_arg("startTime", &start_time)?
+                        .query_arg("endTime", &end_time)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 3000)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_quote() {
+        let json = r#"{
+            "quoteId": "12415572564", "ratio": "38163.7", "inverseRatio": "0.0000262",
+            "validTimestamp": 1623319461670, "toAmount": "3816.37", "fromAmount": "0.1"
+        }"#;
+        let quote: ConvertQuote = serde_json::from_str(json).unwrap();
+        assert_eq!(quote.ratio, dec!(38163.7));
+        assert!(quote.is_valid_at(1623319461669));
+        assert!(!quote.is_valid_at(1623319461670));
+        assert!(quote.is_expired());
+
+        let json = r#"{"orderId": "933256278426274426", "createTime": 1623381330472,
+            "orderStatus": "PROCESS"}"#;
+        let accepted: ConvertAcceptedQuote = serde_json::from_str(json).unwrap();
+        assert_eq!(accepted.order_id, 933256278426274426);
+        assert!(!accepted.order_status.is_finished());
+    }
+
+    #[test]
+    fn test_deserialize_orders() {
+        let json = r#"{
+            "orderId": 933256278426274426, "orderStatus": "SUCCESS", "fromAsset": "BTC",
+            "fromAmount": "0.00054414", "toAsset": "USDT", "toAmount": "20",
+            "ratio": "36755", "inverseRatio": "0.00002721", "createTime": 1623381330472
+        }"#;
+        let order: ConvertOrder = serde_json::from_str(json).unwrap();
+        assert_eq!(order.quote_id, None);
+        assert_eq!(order.from_amount, dec!(0.00054414));
+        assert!(order.order_status.is_finished());
+
+        let json = r#"{
+            "list": [{
+                "quoteId": "f3b91c525b2644c7bc1e1cd31b6e1aa6", "orderId": 940708407462087195,
+                "orderStatus": "SUCCESS", "fromAsset": "USDT", "fromAmount": "20",
+                "toAsset": "BNB", "toAmount": "0.06154036", "ratio": "0.00307702",
+                "inverseRatio": "324.99", "createTime": 1624248872184
+            }],
+            "startTime": 1623824139000, "endTime": 1626416139000, "limit": 100,
+            "moreData": false
+        }"#;
+        let flow: ConvertTradeFlow = serde_json::from_str(json).unwrap();
+        assert_eq!(&flow.list[0].to_asset, "BNB");
+        assert!(!flow.more_data);
+        assert_eq!(
+            serde_json::to_string(&ConvertValidTime::Minute1).unwrap(),
+            r#""1m""#
+        );
+    }
+}
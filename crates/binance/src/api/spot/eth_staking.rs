@@ -0,0 +1,343 @@
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V2_ETH_STAKING_ETH_STAKE: &str = "/sapi/v2/eth-staking/eth/stake";
+pub const SAPI_V1_ETH_STAKING_ETH_REDEEM: &str = "/sapi/v1/eth-staking/eth/redeem";
+pub const SAPI_V2_ETH_STAKING_ACCOUNT: &str = "/sapi/v2/eth-staking/account";
+pub const SAPI_V1_ETH_STAKING_STAKING_HISTORY: &str =
+    "/sapi/v1/eth-staking/eth/history/stakingHistory";
+pub const SAPI_V1_ETH_STAKING_REDEMPTION_HISTORY: &str =
+    "/sapi/v1/eth-staking/eth/history/redemptionHistory";
+pub const SAPI_V1_ETH_STAKING_RATE_HISTORY: &str = "/sapi/v1/eth-staking/eth/history/rateHistory";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum EthStakingStatus {
+    #[serde(rename = "PENDING")]
+    Pending,
+    #[serde(rename = "SUCCESS")]
+    Success,
+    #[serde(rename = "FAILED")]
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStake {
+    pub success: bool,
+    /// WBETH received.
+    pub wbeth_amount: Decimal,
+    /// ETH per WBETH.
+    pub conversion_ratio: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthRedemption {
+    pub success: bool,
+    /// ETH to be received.
+    pub eth_amount: Decimal,
+    pub conversion_ratio: Decimal,
+    pub arrival_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingAccount {
+    /// Holdings valued in ETH.
+    #[serde(rename = "holdingInETH")]
+    pub holding_in_eth: Decimal,
+    pub holdings: EthStakingHoldings,
+    #[serde(rename = "thirtyDaysProfitInETH")]
+    pub thirty_days_profit_in_eth: Decimal,
+    pub profit: EthStakingProfit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingHoldings {
+    pub wbeth_amount: Decimal,
+    pub beth_amount: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthStakingProfit {
+    #[serde(rename = "amountFromWBETH")]
+    pub amount_from_wbeth: Decimal,
+    #[serde(rename = "amountFromBETH")]
+    pub amount_from_beth: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingRecord {
+    pub time: u64,
+    pub asset: Atom,
+    /// ETH staked.
+    pub amount: Decimal,
+    pub status: EthStakingStatus,
+    /// WBETH received.
+    pub distribute_amount: Decimal,
+    pub conversion_ratio: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthRedemptionRecord {
+    pub time: u64,
+    pub arrival_time: u64,
+    pub asset: Atom,
+    /// Amount of `asset` redeemed.
+    pub amount: Decimal,
+    pub status: EthStakingStatus,
+    pub distribute_asset: Atom,
+    pub distribute_amount: Decimal,
+    pub conversion_ratio: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthStakingRate {
+    pub annual_percentage_rate: Decimal,
+    /// ETH per WBETH, used to value WBETH positions.
+    pub exchange_rate: Decimal,
+    pub time: u64,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Subscribe ETH Staking V2 (TRADE)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// Stakes ETH and receives WBETH.
+        pub fn eth_staking_stake(
+            &self,
+            amount: Decimal,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<EthStake>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V2_ETH_STAKING_ETH_STAKE)?
+                        .signed(time_window)?
+                        .decimal_arg("amount", &amount)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Redeem ETH (TRADE)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * asset - WBETH or BETH. Default: BETH.
+        pub fn eth_staking_redeem(
+            &self,
+            asset: Option<impl Serialize>,
+            amount: Decimal,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<EthRedemption>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_ETH_STAKING_ETH_REDEEM)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .decimal_arg("amount", &amount)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// ETH Staking account V2 (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        pub fn eth_staking_account(
+            &self,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<EthStakingAccount>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V2_ETH_STAKING_ACCOUNT)?
+                        .signed(time_window)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Get ETH staking history (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * The time between `start_time` and `end_time` cannot be longer than 3 months.
+        /// * If `start_time` and `end_time` are both not sent, the recent 30 days are returned.
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn eth_staking_history(
+            &self,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<EthStakingRecord>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_ETH_STAKING_STAKING_HISTORY)?
+                        .signed(time_window)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Get ETH redemption history (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * The time between `start_time` and `end_time` cannot be longer than 3 months.
+        /// * If `start_time` and `end_time` are both not sent, the recent 30 days are returned.
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn eth_staking_redemption_history(
+            &self,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<EthRedemptionRecord>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_ETH_STAKING_REDEMPTION_HISTORY)?
+                        .signed(time_window)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Get WBETH Rate History (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * The time between `start_time` and `end_time` cannot be longer than 3 months.
+        /// * If `start_time` and `end_time` are both not sent, the recent 30 days are returned.
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn eth_staking_rate_history(
+            &self,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<EthStakingRate>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_ETH_STAKING_RATE_HISTORY)?
+                        .signed(time_window)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_account() {
+        let json = r#"{
+            "holdingInETH": "1.22330928",
+            "holdings": {"wbethAmount": "1.10928781", "bethAmount": "1.90002112"},
+            "thirtyDaysProfitInETH": "0.22330928",
+            "profit": {"amountFromWBETH": "0.12330928", "amountFromBETH": "0.1"}
+        }"#;
+        let account: EthStakingAccount = serde_json::from_str(json).unwrap();
+        assert_eq!(account.holding_in_eth, dec!(1.22330928));
+        assert_eq!(account.holdings.wbeth_amount, dec!(1.10928781));
+        assert_eq!(account.profit.amount_from_wbeth, dec!(0.12330928));
+    }
+
+    #[test]
+    fn test_deserialize_history() {
+        let json = r#"{
+            "rows": [{
+                "time": 1575018510000,
+                "asset": "ETH",
+                "amount": "21312.23223",
+                "status": "SUCCESS",
+                "distributeAmount": "21286.456",
+                "conversionRatio": "1.00121234"
+            }],
+            "total": 1
+        }"#;
+        let history: Rows<EthStakingRecord> = serde_json::from_str(json).unwrap();
+        assert_eq!(history.rows[0].status, EthStakingStatus::Success);
+        assert_eq!(history.rows[0].distribute_amount, dec!(21286.456));
+
+        let json = r#"{
+            "rows": [{
+                "time": 1575018510000,
+                "arrivalTime": 1575018510000,
+                "asset": "WBETH",
+                "amount": "21312.23223",
+                "status": "PENDING",
+                "distributeAsset": "ETH",
+                "distributeAmount": "21338.0699",
+                "conversionRatio": "1.00121234"
+            }],
+            "total": 1
+        }"#;
+        let history: Rows<EthRedemptionRecord> = serde_json::from_str(json).unwrap();
+        assert_eq!(&history.rows[0].distribute_asset, "ETH");
+        assert_eq!(history.rows[0].conversion_ratio, dec!(1.00121234));
+
+        let json = r#"{
+            "rows": [{
+                "annualPercentageRate": "0.00006408",
+                "exchangeRate": "1.001212343432",
+                "time": 1577233578000
+            }],
+            "total": 1
+        }"#;
+        let rates: Rows<EthStakingRate> = serde_json::from_str(json).unwrap();
+        assert_eq!(rates.rows[0].exchange_rate, dec!(1.001212343432));
+    }
+}
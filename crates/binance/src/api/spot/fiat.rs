@@ -0,0 +1,285 @@
+use serde::Deserializer;
+use serde::Serializer;
+use smart_string::SmartString;
+
+use super::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_FIAT_ORDERS: &str = "/sapi/v1/fiat/orders";
+pub const SAPI_V1_FIAT_PAYMENTS: &str = "/sapi/v1/fiat/payments";
+
+#[derive(
+    Clone, Copy, Debug, Serialize_repr, Deserialize_repr, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[repr(u8)]
+pub enum FiatOrderType {
+    Deposit = 0,
+    Withdraw = 1,
+}
+
+#[derive(
+    Clone, Copy, Debug, Serialize_repr, Deserialize_repr, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[repr(u8)]
+pub enum FiatPaymentType {
+    Buy = 0,
+    Sell = 1,
+}
+
+/// Status of a fiat order or payment.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FiatStatus {
+    Processing,
+    Successful,
+    Finished,
+    Completed,
+    Failed,
+    Expired,
+    Refunding,
+    Refunded,
+    RefundFailed,
+    /// A status this version does not know about.
+    Other(SmartString),
+}
+
+impl FiatStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FiatStatus::Processing => "Processing",
+            FiatStatus::Successful => "Successful",
+            FiatStatus::Finished => "Finished",
+            FiatStatus::Completed => "Completed",
+            FiatStatus::Failed => "Failed",
+            FiatStatus::Expired => "Expired",
+            FiatStatus::Refunding => "Refunding",
+            FiatStatus::Refunded => "Refunded",
+            FiatStatus::RefundFailed => "Refund Failed",
+            FiatStatus::Other(status) => status.as_str(),
+        }
+    }
+}
+
+impl Serialize for FiatStatus {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FiatStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(match &*s {
+            "Processing" => Self::Processing,
+            "Successful" => Self::Successful,
+            "Finished" => Self::Finished,
+            "Completed" => Self::Completed,
+            "Failed" => Self::Failed,
+            "Expired" => Self::Expired,
+            "Refunding" => Self::Refunding,
+            "Refunded" => Self::Refunded,
+            "Refund Failed" => Self::RefundFailed,
+            other => Self::Other(SmartString::from(other)),
+        })
+    }
+}
+
+/// Fiat deposit or withdrawal.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatOrder {
+    pub order_no: String,
+    pub fiat_currency: Atom,
+    /// Amount requested.
+    pub indicated_amount: Decimal,
+    /// Amount credited or debited.
+    pub amount: Decimal,
+    pub total_fee: Decimal,
+    /// Payment method, e.g. `BankAccount`.
+    pub method: Option<String>,
+    pub status: FiatStatus,
+    pub create_time: u64,
+    pub update_time: u64,
+}
+
+/// Crypto bought or sold with fiat, e.g. by card.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FiatPayment {
+    pub order_no: String,
+    /// Fiat amount for a buy, crypto amount for a sell.
+    pub source_amount: Decimal,
+    pub fiat_currency: Atom,
+    /// Crypto amount for a buy, fiat amount for a sell.
+    pub obtain_amount: Decimal,
+    pub crypto_currency: Atom,
+    pub total_fee: Decimal,
+    pub price: Decimal,
+    pub status: FiatStatus,
+    pub payment_method: Option<String>,
+    pub create_time: u64,
+    pub update_time: u64,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Get Fiat Deposit/Withdraw History (USER_DATA)
+        ///
+        /// Weight(UID): 90000
+        ///
+        /// * If begin_time and end_time are not sent, the recent 30 days are returned.
+        /// * page - Default: 1.
+        /// * rows - Default: 100 Max: 500.
+        pub fn fiat_orders(
+            &self,
+            transaction_type: FiatOrderType,
+            begin_time: Option<u64>,
+            end_time: Option<u64>,
+            page: Option<u32>,
+            rows: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<FiatOrder>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_FIAT_ORDERS)?
+                        .signed(time_window)?
+                        .query_arg("transactionType", &transaction_type)?
+                        .try_query_arg("beginTime", &begin_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("page", &page)?
+                        .try_query_arg("rows", &rows)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 90000)
+                .send())
+        }
+
+        /// Get Fiat Payments History (USER_DATA)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * If begin_time and end_time are not sent, the recent 30 days are returned.
+        /// * page - Default: 1.
+        /// * rows - Default: 100 Max: 500.
+        pub fn fiat_payments(
+            &self,
+            transaction_type: FiatPaymentType,
+            begin_time: Option<u64>,
+            end_time: Option<u64>,
+            page: Option<u32>,
+            rows: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<FiatPayment>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_FIAT_PAYMENTS)?
+                        .signed(time_window)?
+                        .query_arg("transactionType", &transaction_type)?
+                        .try_query_arg("beginTime", &begin_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("page", &page)?
+                        .try_query_arg("rows", &rows)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_orders() {
+        let json = r#"{
+            "code": "000000",
+            "message": "success",
+            "data": [{
+                "orderNo": "7d76d611-0568-4f43-afb6-24cac7767365",
+                "fiatCurrency": "BRL",
+                "indicatedAmount": "10.00",
+                "amount": "10.00",
+                "totalFee": "0.00",
+                "method": "BankAccount",
+                "status": "Expired",
+                "createTime": 1626144956000,
+                "updateTime": 1626400907000
+            }, {
+                "orderNo": "8d76d611-0568-4f43-afb6-24cac7767365",
+                "fiatCurrency": "EUR",
+                "indicatedAmount": "100.00",
+                "amount": "98.50",
+                "totalFee": "1.50",
+                "method": "BankAccount",
+                "status": "Order Partial credit Stopped",
+                "createTime": 1626144956000,
+                "updateTime": 1626400907000
+            }],
+            "total": 2,
+            "success": true
+        }"#;
+        let orders: Rows<FiatOrder> = serde_json::from_str(json).unwrap();
+        assert_eq!(orders.total, 2);
+        assert_eq!(orders.rows[0].status, FiatStatus::Expired);
+        assert_eq!(orders.rows[1].total_fee, dec!(1.50));
+        assert_eq!(
+            orders.rows[1].status,
+            FiatStatus::Other("Order Partial credit Stopped".into())
+        );
+        assert_eq!(
+            serde_json::to_string(&FiatStatus::RefundFailed).unwrap(),
+            r#""Refund Failed""#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_payments() {
+        let json = r#"{
+            "code": "000000",
+            "message": "success",
+            "data": [{
+                "orderNo": "353fca443f06466db0c4dc89f94f027a",
+                "sourceAmount": "20.0",
+                "fiatCurrency": "EUR",
+                "obtainAmount": "4.462",
+                "cryptoCurrency": "LUNA",
+                "totalFee": "0.2",
+                "price": "4.437472",
+                "status": "Failed",
+                "paymentMethod": "Credit Card",
+                "createTime": 1624529919000,
+                "updateTime": 1624529919000
+            }],
+            "total": 1,
+            "success": true
+        }"#;
+        let payments: Rows<FiatPayment> = serde_json::from_str(json).unwrap();
+        let payment = &payments.rows[0];
+        assert_eq!(payment.status, FiatStatus::Failed);
+        assert_eq!(payment.obtain_amount, dec!(4.462));
+        assert_eq!(payment.payment_method.as_deref(), Some("Credit Card"));
+    }
+}
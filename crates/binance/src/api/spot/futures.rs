@@ -1,6 +1,5 @@
 use super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const SAPI_V1_FUTURES_TRANSFER: &str = "/sapi/v1/futures/transfer";
 pub const SAPI_V2_FUTURES_LOAN_CONFIGS: &str = "/sapi/v1/futures/loan/configs";
@@ -85,6 +84,7 @@ pub use with_network::*;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> SpotApi<S>
     where
@@ -113,7 +113,7 @@ mod with_network {
                         .post(SAPI_V1_FUTURES_TRANSFER)?
                         .signed(time_window)?
                         .query_arg("asset", &asset)?
-                        .query_arg("amount", &amount)?
+                        .decimal_arg("amount", &amount)?
                         .query_arg("type", &r#type)?,
                 )
                 .cost(RL_WEIGHT_PER_MINUTE, 1)
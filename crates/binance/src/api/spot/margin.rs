@@ -1,3 +0,0 @@
-// use super::prelude::*;
-
-// pub const SAPI_V1_MARGIN_TRANSFER: &str = "/sapi/v1/margin/transfer";
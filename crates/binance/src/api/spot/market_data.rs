@@ -1,14 +1,19 @@
+use std::borrow::Cow;
+
 use serde::de::Deserialize;
 use serde::de::Deserializer;
 use serde::de::{self};
 use serde::ser::Serialize;
 use serde::ser::Serializer;
+use smart_string::SmartString;
 
+use super::NewOrderRequest;
 use super::OrderType;
 use super::RL_WEIGHT_PER_MINUTE;
 use super::RlPriorityLevel;
 use super::prelude::*;
-use crate::client::Task;
+use super::util::FilterViolation;
+use super::util::OrderFilters;
 use crate::util::Ask;
 use crate::util::Bid;
 use crate::util::OrderBook;
@@ -21,8 +26,11 @@ pub const API_V3_TRADES: &str = "/api/v3/trades";
 pub const API_V3_HISTORICAL_TRADES: &str = "/api/v3/historicalTrades";
 pub const API_V3_AGG_TRADES: &str = "/api/v3/aggTrades";
 pub const API_V3_KLINES: &str = "/api/v3/klines";
+pub const API_V3_UI_KLINES: &str = "/api/v3/uiKlines";
 pub const API_V3_AVG_PRICE: &str = "/api/v3/avgPrice";
 pub const API_V3_TICKER_24HR: &str = "/api/v3/ticker/24hr";
+pub const API_V3_TICKER: &str = "/api/v3/ticker";
+pub const API_V3_TICKER_TRADING_DAY: &str = "/api/v3/ticker/tradingDay";
 pub const API_V3_TICKER_PRICE: &str = "/api/v3/ticker/price";
 pub const API_V3_TICKER_BOOK_TICKER: &str = "/api/v3/ticker/bookTicker";
 
@@ -41,9 +49,26 @@ pub struct ExchangeInformation {
     pub timezone: Atom,
     pub server_time: u64,
     pub rate_limits: Vec<RateLimit>,
+    #[serde(default)]
+    pub exchange_filters: Vec<Filter>,
     pub symbols: Vec<Symbol>,
 }
 
+impl ExchangeInformation {
+    /// Finds a symbol by its exact (uppercase) name, e.g. `BTCUSDT`.
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.symbol == *name)
+    }
+
+    /// Indexes symbols by name for repeated lookups.
+    pub fn into_symbol_map(self) -> HashMap<Atom, Symbol> {
+        self.symbols
+            .into_iter()
+            .map(|symbol| (symbol.symbol.clone(), symbol))
+            .collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimit {
@@ -88,11 +113,171 @@ pub struct Symbol {
     pub order_types: Vec<OrderType>,
     pub iceberg_allowed: bool,
     pub oco_allowed: bool,
+    #[serde(default)]
+    pub oto_allowed: bool,
     pub quote_order_qty_market_allowed: bool,
+    #[serde(default)]
+    pub allow_trailing_stop: bool,
+    #[serde(default)]
+    pub cancel_replace_allowed: bool,
     pub is_spot_trading_allowed: bool,
     pub is_margin_trading_allowed: bool,
     pub filters: Vec<Filter>,
+    /// Superseded by `permission_sets` and sent empty by the current API.
+    #[serde(default)]
     pub permissions: Vec<SymbolPermission>,
+    /// The symbol can be traded by an account that has all permissions of any of the sets.
+    #[serde(default)]
+    pub permission_sets: Vec<Vec<SymbolPermission>>,
+}
+
+/// Advanced order feature that a symbol may not allow.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SymbolCapability {
+    Iceberg,
+    Oco,
+    Oto,
+    QuoteOrderQtyMarket,
+    TrailingStop,
+    CancelReplace,
+}
+
+impl SymbolCapability {
+    /// Name of the `exchangeInfo` flag.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SymbolCapability::Iceberg => "icebergAllowed",
+            SymbolCapability::Oco => "ocoAllowed",
+            SymbolCapability::Oto => "otoAllowed",
+            SymbolCapability::QuoteOrderQtyMarket => "quoteOrderQtyMarketAllowed",
+            SymbolCapability::TrailingStop => "allowTrailingStop",
+            SymbolCapability::CancelReplace => "cancelReplaceAllowed",
+        }
+    }
+}
+
+impl std::fmt::Display for SymbolCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Symbol {
+    pub fn supports(&self, capability: SymbolCapability) -> bool {
+        match capability {
+            SymbolCapability::Iceberg => self.iceberg_allowed,
+            SymbolCapability::Oco => self.oco_allowed,
+            SymbolCapability::Oto => self.oto_allowed,
+            SymbolCapability::QuoteOrderQtyMarket => self.quote_order_qty_market_allowed,
+            SymbolCapability::TrailingStop => self.allow_trailing_stop,
+            SymbolCapability::CancelReplace => self.cancel_replace_allowed,
+        }
+    }
+
+    /// Fails with [`ApiError::CapabilityNotSupported`] unless the symbol allows `capability`.
+    ///
+    /// Meant to be called before sending an OCO, OTO, trailing stop or cancel-replace
+    /// request for the symbol.
+    pub fn check_capability(&self, capability: SymbolCapability) -> BinanceResult<()> {
+        if !self.supports(capability) {
+            Err(ApiError::CapabilityNotSupported {
+                symbol: self.symbol.clone(),
+                capability,
+            })?
+        }
+        Ok(())
+    }
+
+    /// Checks that the symbol allows the features used by `order`.
+    pub fn check_order_request(&self, order: &NewOrderRequest) -> BinanceResult<()> {
+        self.check_order_features(
+            order.r#type,
+            order.quote_order_qty.is_some(),
+            order.iceberg_qty.is_some(),
+            order.trailing_delta.is_some(),
+        )
+    }
+
+    pub(crate) fn check_order_features(
+        &self,
+        r#type: OrderType,
+        quote_order_qty: bool,
+        iceberg: bool,
+        trailing: bool,
+    ) -> BinanceResult<()> {
+        if iceberg {
+            self.check_capability(SymbolCapability::Iceberg)?;
+        }
+        if r#type == OrderType::Market && quote_order_qty {
+            self.check_capability(SymbolCapability::QuoteOrderQtyMarket)?;
+        }
+        if trailing {
+            self.check_capability(SymbolCapability::TrailingStop)?;
+        }
+        Ok(())
+    }
+
+    /// Checks a limit order against the symbol filters, see [`OrderFilters::validate_order`].
+    pub fn validate_order(&self, price: Decimal, qty: Decimal) -> Result<(), FilterViolation> {
+        OrderFilters::from_filters(&self.filters).validate_order(price, qty)
+    }
+
+    /// `PRICE_FILTER` tick size, `None` if the filter is absent or the rule is disabled.
+    pub fn tick_size(&self) -> Option<Decimal> {
+        self.price_filter().and_then(PriceFilter::tick_size)
+    }
+
+    /// `LOT_SIZE` step size, `None` if the filter is absent or the rule is disabled.
+    pub fn step_size(&self) -> Option<Decimal> {
+        self.lot_size_filter().and_then(LotSizeFilter::step_size)
+    }
+
+    /// Snaps `price` down to a valid `PRICE_FILTER` increment.
+    pub fn round_price(&self, price: Decimal) -> Decimal {
+        self.price_filter()
+            .map_or(price, |filter| filter.round(price))
+    }
+
+    /// Snaps `qty` down to a valid `LOT_SIZE` increment.
+    pub fn round_qty(&self, qty: Decimal) -> Decimal {
+        self.lot_size_filter()
+            .map_or(qty, |filter| filter.round(qty))
+    }
+
+    /// Minutes the average price of market orders is taken over by the symbol filters.
+    ///
+    /// The window of `NOTIONAL` or `MIN_NOTIONAL` is preferred, falling back to
+    /// `PERCENT_PRICE` and `PERCENT_PRICE_BY_SIDE`. `Some(0)` means the last price is used.
+    /// Compare with [`AvgPrice::mins`] before checking a market order notional against
+    /// the price from `SpotApi::avg_price`.
+    pub fn avg_price_mins(&self) -> Option<u64> {
+        let notional = self.filters.iter().find_map(|filter| match filter {
+            Filter::Notional(filter) => Some(filter.avg_price_mins),
+            Filter::MinNotional(filter) => Some(filter.avg_price_mins),
+            _ => None,
+        });
+        notional.or_else(|| {
+            self.filters.iter().find_map(|filter| match filter {
+                Filter::PercentPrice(filter) => Some(filter.avg_price_mins),
+                Filter::PercentPriceBySide(filter) => Some(filter.avg_price_mins),
+                _ => None,
+            })
+        })
+    }
+
+    fn price_filter(&self) -> Option<&PriceFilter> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::Price(filter) => Some(filter),
+            _ => None,
+        })
+    }
+
+    fn lot_size_filter(&self) -> Option<&LotSizeFilter> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::LotSize(filter) => Some(filter),
+            _ => None,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
@@ -144,6 +329,19 @@ pub enum Filter {
     MaxPosition(MaxPositionFilter),
     #[serde(rename = "TRAILING_DELTA")]
     TrailingDelta(TrailingDeltaFilter),
+    #[serde(rename = "MAX_NUM_ORDER_LISTS")]
+    MaxNumOrderLists(MaxNumOrderListsFilter),
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS")]
+    ExchangeMaxNumOrders(MaxNumOrdersFilter),
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS")]
+    ExchangeMaxNumAlgoOrders(MaxNumAlgoOrdersFilter),
+    #[serde(rename = "EXCHANGE_MAX_NUM_ICEBERG_ORDERS")]
+    ExchangeMaxNumIcebergOrders(MaxNumIcebergOrdersFilter),
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDER_LISTS")]
+    ExchangeMaxNumOrderLists(MaxNumOrderListsFilter),
+    /// A filter this version does not know about.
+    #[serde(other)]
+    Unknown,
 }
 
 /// The PRICE_FILTER defines the price rules for a symbol. There are 3 parts:
@@ -309,6 +507,14 @@ pub struct MaxNumIcebergOrdersFilter {
     pub max_num_iceberg_orders: u64,
 }
 
+/// The maximum number of open order lists (e.g. OCO) an account can have on a symbol,
+/// or on the exchange for `EXCHANGE_MAX_NUM_ORDER_LISTS`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxNumOrderListsFilter {
+    pub max_num_order_lists: u64,
+}
+
 /// The `MAX_POSITION` filter defines the allowed maximum position an account can have on the
 /// base asset of a symbol. An account's position defined as the sum of the account's:
 ///
@@ -348,6 +554,8 @@ pub enum SymbolPermission {
     Margin,
     Leveraged,
     TradeGroup(u16),
+    /// A permission this version does not know about.
+    Other(SmartString),
 }
 
 impl Serialize for SymbolPermission {
@@ -363,6 +571,7 @@ impl Serialize for SymbolPermission {
                 let group_num = format!("TRD_GRP_{:0>4}", group_num);
                 s.serialize_str(&group_num)
             }
+            SymbolPermission::Other(permission) => s.serialize_str(permission.as_str()),
         }
     }
 }
@@ -377,41 +586,76 @@ impl<'de> Deserialize<'de> for SymbolPermission {
             "SPOT" => Ok(Self::Spot),
             "MARGIN" => Ok(Self::Margin),
             "LEVERAGED" => Ok(Self::Leveraged),
-            trade_group if trade_group.contains("TRD_GRP") => {
-                // Format: TRD_GRP_0001
-                let group_num = trade_group.trim_start_matches("TRD_GRP_");
-                let group_num = group_num.parse::<u16>().map_err(de::Error::custom)?;
-                Ok(Self::TradeGroup(group_num))
-            }
-            _ => Err(de::Error::custom(format!("invalid type: {}", s))),
+            // Format: TRD_GRP_0001
+            other => match other.strip_prefix("TRD_GRP_").map(str::parse::<u16>) {
+                Some(Ok(group_num)) => Ok(Self::TradeGroup(group_num)),
+                _ => Ok(Self::Other(SmartString::from(other))),
+            },
         }
     }
 }
 
-// FIXME clarify: the documentation is ambiguous; only these values are listed as valid,
-//       but below it has a caution about value 0.
-//       [https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#order-book]
-//       If 0 is valid, add it and specify its weight.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// Depth of an order book snapshot.
+///
+/// Any depth from 1 to [`OrderBookLimit::MAX`] is accepted, the named variants are the ones
+/// historically listed by the API. Limits compare by their value, so `Custom(100)` equals
+/// `N100`.
+///
+/// `limit=0` (the full book) was accepted by early API versions only. The current API
+/// rejects it, so [`OrderBookLimit::try_from`] refuses `0`.
+/// Use [`OrderBookLimit::N5000`] for the deepest snapshot.
+#[derive(Debug, Copy, Clone)]
 pub enum OrderBookLimit {
-    N5 = 5,
-    N10 = 10,
-    N20 = 20,
-    N50 = 50,
-    N100 = 100,
-    N500 = 500,
-    N1000 = 1000,
-    N5000 = 5000,
+    N5,
+    N10,
+    N20,
+    N50,
+    N100,
+    N500,
+    N1000,
+    N5000,
+    /// Any other depth from 1 to [`OrderBookLimit::MAX`], checked when the request is built.
+    Custom(u16),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SpotOrderBook {
     pub last_update_id: u64,
+    /// Event time in milliseconds (not returned by every endpoint).
+    #[serde(rename = "E", default, skip_serializing_if = "Option::is_none")]
+    pub event_time: Option<u64>,
+    /// Transaction time in milliseconds (not returned by every endpoint).
+    #[serde(rename = "T", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_time: Option<u64>,
     pub bids: Vec<Bid>,
     pub asks: Vec<Ask>,
 }
 
+impl SpotOrderBook {
+    /// Best bid and ask of the snapshot.
+    pub fn top(&self, symbol: impl Into<Atom>) -> TopOfBook {
+        TopOfBook {
+            symbol: symbol.into(),
+            last_update_id: self.last_update_id,
+            bid: self.bids.first().copied(),
+            ask: self.asks.first().copied(),
+        }
+    }
+}
+
+/// Best bid and ask of an order book, see [`SpotApi::depth_top`].
+///
+/// Either side is `None` when the book side is empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct TopOfBook {
+    pub symbol: Atom,
+    pub last_update_id: u64,
+    pub bid: Option<Bid>,
+    pub ask: Option<Ask>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
@@ -452,8 +696,72 @@ pub struct AggTrade {
     pub is_best_match: bool,
 }
 
-// FIXME serialize as a tuple
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+/// Page of aggregate trades requested by [`AggTradesRange`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(not(feature = "with_network"), allow(dead_code))]
+enum AggTradesPage {
+    /// Trades within a window shorter than an hour.
+    Window { start_time: u64, end_time: u64 },
+    /// Trades starting with the aggregate trade id.
+    FromId(u64),
+}
+
+/// Walks aggregate trades of a time range page by page.
+///
+/// The range is queried by time windows shorter than an hour. A full page is followed
+/// by `fromId` pages until the end of the range.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "with_network"), allow(dead_code))]
+struct AggTradesRange {
+    end_time: u64,
+    next: Option<AggTradesPage>,
+}
+
+#[cfg_attr(not(feature = "with_network"), allow(dead_code))]
+impl AggTradesRange {
+    const LIMIT: usize = 1000;
+    /// `startTime` and `endTime` are inclusive and must be less than an hour apart.
+    const MAX_WINDOW: u64 = 60 * 60 * 1000 - 1;
+
+    fn new(start_time: u64, end_time: u64) -> Self {
+        AggTradesRange {
+            end_time,
+            next: (start_time <= end_time).then(|| Self::window(start_time, end_time)),
+        }
+    }
+
+    fn window(start_time: u64, end_time: u64) -> AggTradesPage {
+        AggTradesPage::Window {
+            start_time,
+            end_time: end_time.min(start_time.saturating_add(Self::MAX_WINDOW)),
+        }
+    }
+
+    /// Takes the page received for `self.next` and returns its trades within the range.
+    fn advance(&mut self, mut trades: Vec<AggTrade>) -> Vec<AggTrade> {
+        let Some(page) = self.next.take() else {
+            return vec![];
+        };
+        let is_full = trades.len() >= Self::LIMIT;
+        let is_past_end = trades.last().is_some_and(|t| t.time > self.end_time);
+        trades.retain(|t| t.time <= self.end_time);
+
+        self.next = match (page, trades.last()) {
+            _ if is_past_end => None,
+            (_, Some(last)) if is_full => Some(AggTradesPage::FromId(last.id + 1)),
+            (AggTradesPage::Window { end_time, .. }, _) if end_time < self.end_time => {
+                Some(Self::window(end_time + 1, self.end_time))
+            }
+            _ => None,
+        };
+        trades
+    }
+}
+
+/// Kline/candlestick bar.
+///
+/// (De)serialized as the 12-element array returned by the API.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Kline {
     pub open_time: u64,
     pub open: Decimal,
@@ -469,8 +777,103 @@ pub struct Kline {
     pub ignore: Decimal,
 }
 
+type KlineRow = (
+    u64,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    u64,
+    Decimal,
+    u64,
+    Decimal,
+    Decimal,
+    Decimal,
+);
+
+impl Serialize for Kline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let row: KlineRow = (
+            self.open_time,
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.volume,
+            self.close_time,
+            self.quote_asset_volume,
+            self.number_of_trades,
+            self.taker_buy_base_asset_volume,
+            self.taker_buy_quote_asset_volume,
+            self.ignore,
+        );
+        row.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Kline {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (
+            open_time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            close_time,
+            quote_asset_volume,
+            number_of_trades,
+            taker_buy_base_asset_volume,
+            taker_buy_quote_asset_volume,
+            ignore,
+        ) = KlineRow::deserialize(deserializer)?;
+        Ok(Kline {
+            open_time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            close_time,
+            quote_asset_volume,
+            number_of_trades,
+            taker_buy_base_asset_volume,
+            taker_buy_quote_asset_volume,
+            ignore,
+        })
+    }
+}
+
+/// Trade id that is `-1` when there were no trades in the window.
+mod trade_id {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    pub fn serialize<S>(id: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match id {
+            Some(id) => serializer.serialize_u64(*id),
+            None => serializer.serialize_i64(-1),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = i64::deserialize(deserializer)?;
+        Ok(u64::try_from(id).ok())
+    }
+}
+
+/// Current average price, see [`Symbol::avg_price_mins`] for the window the filters use.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct AvgPrice {
+    /// Minutes the price is averaged over.
     pub mins: u32,
     pub price: Decimal,
 }
@@ -494,16 +897,140 @@ pub struct TickerStats {
     pub quote_volume: Decimal,
     pub open_time: u64,
     pub close_time: u64,
-    /// First trade id.
-    // FIXME Option<u64> when value is -1
-    pub first_id: i64,
-    /// Last trade id.
-    // FIXME Option<u64> when value is -1
-    pub last_id: i64,
+    /// First trade id, `None` if there were no trades.
+    #[serde(with = "trade_id")]
+    pub first_id: Option<u64>,
+    /// Last trade id, `None` if there were no trades.
+    #[serde(with = "trade_id")]
+    pub last_id: Option<u64>,
     /// Trade count.
     pub count: u64,
 }
 
+/// Window of the rolling window price change statistics.
+///
+/// One of `1m`..`59m`, `1h`..`23h` or `1d`..`7d`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct WindowSize {
+    value: u8,
+    unit: WindowUnit,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum WindowUnit {
+    Minutes,
+    Hours,
+    Days,
+}
+
+impl WindowSize {
+    /// The window used by the exchange when none is specified.
+    pub const DEFAULT: WindowSize = WindowSize {
+        value: 1,
+        unit: WindowUnit::Days,
+    };
+
+    /// Window of 1 to 59 minutes.
+    pub fn minutes(value: u8) -> Result<Self, ApiError> {
+        Self::new(value, WindowUnit::Minutes, 59)
+    }
+
+    /// Window of 1 to 23 hours.
+    pub fn hours(value: u8) -> Result<Self, ApiError> {
+        Self::new(value, WindowUnit::Hours, 23)
+    }
+
+    /// Window of 1 to 7 days.
+    pub fn days(value: u8) -> Result<Self, ApiError> {
+        Self::new(value, WindowUnit::Days, 7)
+    }
+
+    fn new(value: u8, unit: WindowUnit, max: u8) -> Result<Self, ApiError> {
+        if !(1..=max).contains(&value) {
+            Err(ApiError::OutOfBounds)?
+        }
+        Ok(WindowSize { value, unit })
+    }
+}
+
+impl Default for WindowSize {
+    fn default() -> Self {
+        WindowSize::DEFAULT
+    }
+}
+
+impl std::fmt::Display for WindowSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = match self.unit {
+            WindowUnit::Minutes => "m",
+            WindowUnit::Hours => "h",
+            WindowUnit::Days => "d",
+        };
+        write!(f, "{}{unit}", self.value)
+    }
+}
+
+impl Serialize for WindowSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TickerType {
+    #[default]
+    Full,
+    /// Omits the price change and the weighted average price.
+    Mini,
+}
+
+/// Rolling window price change statistics.
+///
+/// Also returned by the trading day ticker, with the window anchored to the trading day.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingTickerStats {
+    pub symbol: Atom,
+    /// Absent for [`TickerType::Mini`].
+    #[serde(default)]
+    pub price_change: Option<Decimal>,
+    /// Absent for [`TickerType::Mini`].
+    #[serde(default)]
+    pub price_change_percent: Option<Decimal>,
+    /// Absent for [`TickerType::Mini`].
+    #[serde(default)]
+    pub weighted_avg_price: Option<Decimal>,
+    pub open_price: Decimal,
+    pub high_price: Decimal,
+    pub low_price: Decimal,
+    pub last_price: Decimal,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub open_time: u64,
+    pub close_time: u64,
+    /// First trade id, `None` if there were no trades.
+    #[serde(with = "trade_id")]
+    pub first_id: Option<u64>,
+    /// Last trade id, `None` if there were no trades.
+    #[serde(with = "trade_id")]
+    pub last_id: Option<u64>,
+    /// Trade count.
+    pub count: u64,
+}
+
+/// Weight of the rolling window ticker: 2 per symbol, capped at 100.
+#[cfg_attr(not(feature = "with_network"), allow(dead_code))]
+fn ticker_rolling_weight(symbols: usize) -> u32 {
+    (symbols as u32).saturating_mul(2).min(100)
+}
+
+/// Weight of the trading day ticker: 4 per symbol, capped at 200.
+#[cfg_attr(not(feature = "with_network"), allow(dead_code))]
+fn ticker_trading_day_weight(symbols: usize) -> u32 {
+    (symbols as u32).saturating_mul(4).min(200)
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct PriceTicker {
     pub symbol: Atom,
@@ -520,34 +1047,111 @@ pub struct BookTicker {
     pub ask_qty: Decimal,
 }
 
+/// Request weight by the upper bound of the depth range it applies to.
+const ORDER_BOOK_WEIGHTS: [(u16, u32); 4] = [(100, 5), (500, 25), (1000, 50), (5000, 250)];
+
 impl OrderBookLimit {
-    pub fn weight(self) -> u32 {
+    pub const MAX: u16 = 5000;
+
+    pub fn value(self) -> u16 {
         use OrderBookLimit as OBL;
 
         match self {
-            OBL::N5 | OBL::N10 | OBL::N20 | OBL::N50 | OBL::N100 => 1,
-            OBL::N500 => 5,
-            OBL::N1000 => 10,
-            OBL::N5000 => 50,
+            OBL::N5 => 5,
+            OBL::N10 => 10,
+            OBL::N20 => 20,
+            OBL::N50 => 50,
+            OBL::N100 => 100,
+            OBL::N500 => 500,
+            OBL::N1000 => 1000,
+            OBL::N5000 => 5000,
+            OBL::Custom(limit) => limit,
         }
     }
 
-    pub fn as_str(self) -> &'static str {
+    /// Fails with [`ApiError::OutOfBounds`] for a `Custom` limit outside `1..=MAX`.
+    pub fn validate(self) -> Result<Self, ApiError> {
+        if !(1..=Self::MAX).contains(&self.value()) {
+            Err(ApiError::OutOfBounds)?
+        }
+        Ok(self)
+    }
+
+    pub fn weight(self) -> u32 {
+        let value = self.value();
+        ORDER_BOOK_WEIGHTS
+            .iter()
+            .find(|(max_limit, _)| value <= *max_limit)
+            .or(ORDER_BOOK_WEIGHTS.last())
+            .map_or(5, |(_, weight)| *weight)
+    }
+
+    pub fn as_str(self) -> Cow<'static, str> {
         use OrderBookLimit as OBL;
 
         match self {
-            OBL::N5 => "5",
-            OBL::N10 => "10",
-            OBL::N20 => "20",
-            OBL::N50 => "50",
-            OBL::N100 => "100",
-            OBL::N500 => "500",
-            OBL::N1000 => "1000",
-            OBL::N5000 => "5000",
+            OBL::N5 => "5".into(),
+            OBL::N10 => "10".into(),
+            OBL::N20 => "20".into(),
+            OBL::N50 => "50".into(),
+            OBL::N100 => "100".into(),
+            OBL::N500 => "500".into(),
+            OBL::N1000 => "1000".into(),
+            OBL::N5000 => "5000".into(),
+            OBL::Custom(limit) => limit.to_string().into(),
         }
     }
 }
 
+impl PartialEq for OrderBookLimit {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Eq for OrderBookLimit {}
+
+impl PartialOrd for OrderBookLimit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderBookLimit {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+
+impl std::hash::Hash for OrderBookLimit {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value().hash(state)
+    }
+}
+
+impl TryFrom<u32> for OrderBookLimit {
+    type Error = ApiError;
+
+    fn try_from(limit: u32) -> Result<Self, Self::Error> {
+        use OrderBookLimit as OBL;
+
+        Ok(match limit {
+            5 => OBL::N5,
+            10 => OBL::N10,
+            20 => OBL::N20,
+            50 => OBL::N50,
+            100 => OBL::N100,
+            500 => OBL::N500,
+            1000 => OBL::N1000,
+            5000 => OBL::N5000,
+            _ => {
+                let limit = u16::try_from(limit).map_err(|_| ApiError::OutOfBounds)?;
+                OBL::Custom(limit).validate()?
+            }
+        })
+    }
+}
+
 impl From<SpotOrderBook> for OrderBook {
     fn from(book: SpotOrderBook) -> Self {
         OrderBook {
@@ -563,7 +1167,14 @@ pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use chrono::Utc;
+    use futures::Stream;
+    use futures::StreamExt;
+    use futures::stream;
+
+    use super::super::util::SnapshotValidator;
     use super::*;
+    use crate::client::Task;
 
     impl<S> SpotApi<S>
     where
@@ -593,6 +1204,23 @@ mod with_network {
                 .send())
         }
 
+        /// Measures the offset between the server and the local clock and applies it to
+        /// the timestamps of signed requests made through this api and its clones.
+        ///
+        /// Returns the offset in milliseconds. Meant to be called on start and then
+        /// periodically, or after a `-1021` (timestamp outside of recvWindow) error.
+        /// [`crate::client::Config::with_time_sync`] makes the client do it on its own.
+        ///
+        /// Weight: 1
+        pub async fn sync_time(&self) -> BinanceResult<i64> {
+            self.client.resync().await
+        }
+
+        /// Offset applied to the timestamps of signed requests, see [`Self::sync_time`].
+        pub fn time_offset(&self) -> i64 {
+            self.client.time_offset()
+        }
+
         /// Current exchange trading rules and symbol information.
         ///
         /// Weight: 1
@@ -604,16 +1232,26 @@ mod with_network {
                 .send())
         }
 
+        /// Loads [`Self::exchange_info`] symbols into the cache that new orders are checked
+        /// against, see [`Self::symbols`].
+        ///
+        /// Weight: 1
+        pub async fn load_symbols(&self) -> BinanceResult<()> {
+            let info = self.exchange_info()?.await?;
+            self.symbols.extend(info.symbols);
+            Ok(())
+        }
+
         /// Order book.
         ///
         /// Weight: Adjusted based on the limit:
         ///
         /// Limit | Weight
         /// | ---- | ---- |
-        /// 5, 10, 20, 50, 100 | 1
-        /// 500 | 5
-        /// 1000 | 10
-        /// 5000 | 50
+        /// 1-100 | 5
+        /// 101-500 | 25
+        /// 501-1000 | 50
+        /// 1001-5000 | 250
         ///
         /// The default `limit` value is `N100`.
         pub fn depth<SM: AsRef<str>>(
@@ -621,8 +1259,9 @@ mod with_network {
             symbol: SM,
             limit: impl Into<Option<OrderBookLimit>>,
         ) -> BinanceResult<Task<SpotOrderBook>> {
-            let limit: Option<OrderBookLimit> = limit.into();
-            let weight = limit.as_ref().map(|f| f.weight()).unwrap_or(1);
+            let limit: Option<OrderBookLimit> =
+                limit.into().map(OrderBookLimit::validate).transpose()?;
+            let weight = limit.unwrap_or(OrderBookLimit::N100).weight();
 
             Ok(self
                 .rate_limiter
@@ -630,12 +1269,45 @@ mod with_network {
                     self.client
                         .get(API_V3_DEPTH)?
                         .query_arg("symbol", symbol.as_ref())?
-                        .try_query_arg("limit", &limit.map(OrderBookLimit::as_str))?,
+                        .try_query_arg("limit", &limit.map(OrderBookLimit::value))?,
                 )
                 .cost(RL_WEIGHT_PER_MINUTE, weight)
                 .send())
         }
 
+        /// Best bid and ask of the order book.
+        ///
+        /// Requests the smallest snapshot (`N5`).
+        ///
+        /// Weight: 5
+        pub async fn depth_top<SM: AsRef<str>>(&self, symbol: SM) -> BinanceResult<TopOfBook> {
+            let book = self.depth(symbol.as_ref(), OrderBookLimit::N5)?.await?;
+            Ok(book.top(symbol.as_ref()))
+        }
+
+        /// Order books for several symbols.
+        ///
+        /// Snapshots are fetched concurrently. Snapshots older than the ones previously seen
+        /// by the `validator` (served by a lagging replica) are dropped from the result.
+        ///
+        /// Weight: same as [`Self::depth`] per symbol.
+        pub async fn depth_many<SM: AsRef<str>>(
+            &self,
+            symbols: impl IntoIterator<Item = SM>,
+            limit: impl Into<Option<OrderBookLimit>>,
+            validator: &SnapshotValidator,
+        ) -> BinanceResult<Vec<(Atom, BinanceResult<SpotOrderBook>)>> {
+            let limit: Option<OrderBookLimit> = limit.into();
+            let mut symbols_out = vec![];
+            let mut tasks = vec![];
+            for symbol in symbols {
+                tasks.push(self.depth(symbol.as_ref(), limit)?);
+                symbols_out.push(Atom::from(symbol.as_ref()));
+            }
+            let books = futures::future::join_all(tasks).await;
+            Ok(validator.retain_fresh(symbols_out.into_iter().zip(books).collect()))
+        }
+
         /// Recent trades list.
         ///
         /// Get recent trades.
@@ -740,6 +1412,52 @@ mod with_network {
                 .send())
         }
 
+        /// All aggregate trades between `start_time` and `end_time` (inclusive, in ms).
+        ///
+        /// Walks the range page by page with [`Self::agg_trades`]: the range is split into
+        /// windows shorter than an hour, and full pages are continued with `fromId` set to
+        /// the last aggregate trade id + 1. The stream ends after the first error.
+        ///
+        /// Weight: 1 per page.
+        pub fn agg_trades_range<SM: AsRef<str>>(
+            &self,
+            symbol: SM,
+            start_time: u64,
+            end_time: u64,
+        ) -> impl Stream<Item = BinanceResult<AggTrade>> + '_ {
+            let symbol = Atom::from(symbol.as_ref());
+            let range = AggTradesRange::new(start_time, end_time);
+            stream::unfold(Some(range), move |range| {
+                let symbol = symbol.clone();
+                async move {
+                    let mut range = range?;
+                    let (from_id, start_time, end_time) = match range.next? {
+                        AggTradesPage::Window {
+                            start_time,
+                            end_time,
+                        } => (None, Some(start_time), Some(end_time)),
+                        AggTradesPage::FromId(from_id) => (Some(from_id), None, None),
+                    };
+                    let limit = Some(AggTradesRange::LIMIT);
+                    let page = match self.agg_trades(&*symbol, from_id, start_time, end_time, limit)
+                    {
+                        Ok(task) => task.await,
+                        Err(e) => Err(e),
+                    };
+                    Some(match page {
+                        Ok(trades) => (Ok(range.advance(trades)), Some(range)),
+                        Err(e) => (Err(e), None),
+                    })
+                }
+            })
+            .flat_map(|page| {
+                stream::iter(match page {
+                    Ok(trades) => trades.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                })
+            })
+        }
+
         /// Kline/Candlestick data.
         ///
         /// Kline/candlestick bars for a symbol.
@@ -783,6 +1501,39 @@ mod with_network {
                 .send())
         }
 
+        /// UIKlines
+        ///
+        /// Same as [`Self::klines`], but the bars are modified for presentation of
+        /// candlestick charts.
+        ///
+        /// Weight: 2
+        ///
+        /// Data Source: Database
+        pub fn ui_klines<SM: AsRef<str>>(
+            &self,
+            symbol: SM,
+            interval: ChartInterval,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            limit: Option<usize>,
+        ) -> BinanceResult<Task<Vec<Kline>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_UI_KLINES)?
+                        .query_args(&[
+                            ("symbol", symbol.as_ref()),
+                            ("interval", interval.as_str()),
+                        ])?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 2)
+                .send())
+        }
+
         /// Current average price.
         ///
         /// Current average price for a symbol.
@@ -842,6 +1593,80 @@ mod with_network {
                 .send())
         }
 
+        /// Rolling window price change statistics.
+        ///
+        /// Unlike [`Self::ticker_24hr`], the window is of the requested size and ends
+        /// within the current minute.
+        ///
+        /// Weight: 2 per symbol, capped at 100.
+        ///
+        /// Parameters:
+        /// * `symbols` - at least one, up to 100.
+        /// * `window_size`
+        /// * `ticker_type`
+        ///
+        /// Data Source: Database
+        pub fn ticker_rolling(
+            &self,
+            symbols: &[&str],
+            window_size: WindowSize,
+            ticker_type: TickerType,
+        ) -> BinanceResult<Task<Vec<RollingTickerStats>>> {
+            if symbols.is_empty() {
+                Err(ApiError::mandatory_field_omitted("symbols"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_TICKER)?
+                        .query_arg_list("symbols", symbols)?
+                        .query_arg("windowSize", &window_size)?
+                        .query_arg("type", &ticker_type)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, ticker_rolling_weight(symbols.len()))
+                .send())
+        }
+
+        /// Trading Day Ticker
+        ///
+        /// Price change statistics for the current trading day, which starts at midnight
+        /// of `time_zone`.
+        ///
+        /// Weight: 4 per symbol, capped at 200.
+        ///
+        /// Parameters:
+        /// * `symbols` - at least one, up to 100.
+        /// * `time_zone` - hours (e.g. `"8"`) or hours and minutes (e.g. `"-05:00"`);
+        ///   UTC by default.
+        /// * `ticker_type`
+        ///
+        /// Data Source: Database
+        pub fn ticker_trading_day(
+            &self,
+            symbols: &[&str],
+            time_zone: Option<&str>,
+            ticker_type: TickerType,
+        ) -> BinanceResult<Task<Vec<RollingTickerStats>>> {
+            if symbols.is_empty() {
+                Err(ApiError::mandatory_field_omitted("symbols"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_TICKER_TRADING_DAY)?
+                        .query_arg_list("symbols", symbols)?
+                        .try_query_arg("timeZone", &time_zone)?
+                        .query_arg("type", &ticker_type)?,
+                )
+                .cost(
+                    RL_WEIGHT_PER_MINUTE,
+                    ticker_trading_day_weight(symbols.len()),
+                )
+                .send())
+        }
+
         /// Symbol price ticker.
         ///
         /// Latest price for a symbol.
@@ -864,6 +1689,33 @@ mod with_network {
                 .send())
         }
 
+        /// Price tickers for several symbols.
+        ///
+        /// Weight: 4 for any number of symbols; a single symbol is cheaper with
+        /// [`Self::ticker_price`].
+        ///
+        /// Parameters:
+        /// * `symbols` - at least one.
+        ///
+        /// Data Source: Memory
+        pub fn ticker_price_symbols(
+            &self,
+            symbols: &[&str],
+        ) -> BinanceResult<Task<Vec<PriceTicker>>> {
+            if symbols.is_empty() {
+                Err(ApiError::mandatory_field_omitted("symbols"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_TICKER_PRICE)?
+                        .query_arg_list("symbols", symbols)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 4)
+                .send())
+        }
+
         /// All symbol price tickers.
         ///
         /// Latest price for symbols.
@@ -901,6 +1753,33 @@ mod with_network {
                 .send())
         }
 
+        /// Order book tickers for several symbols.
+        ///
+        /// Weight: 4 for any number of symbols; a single symbol is cheaper with
+        /// [`Self::ticker_book`].
+        ///
+        /// Parameters:
+        /// * `symbols` - at least one.
+        ///
+        /// Data Source: Memory
+        pub fn ticker_book_symbols(
+            &self,
+            symbols: &[&str],
+        ) -> BinanceResult<Task<Vec<BookTicker>>> {
+            if symbols.is_empty() {
+                Err(ApiError::mandatory_field_omitted("symbols"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(API_V3_TICKER_BOOK_TICKER)?
+                        .query_arg_list("symbols", symbols)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 4)
+                .send())
+        }
+
         /// All symbol order book tickers.
         ///
         /// Best price/qty on the order book for symbols.
@@ -917,3 +1796,597 @@ mod with_network {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::api::spot::OrderSide;
+
+    const EXCHANGE_INFO: &str = include_str!("../../../examples/data/exchange_info.json");
+    const EXCHANGE_INFO_CURRENT: &str =
+        include_str!("../../../examples/data/exchange_info_current.json");
+
+    #[test]
+    fn test_symbol_lookup() {
+        let info: ExchangeInformation = serde_json::from_str(EXCHANGE_INFO).unwrap();
+        let symbol = info.symbol("ETHBTC").unwrap();
+        assert_eq!(&symbol.base_asset, "ETH");
+        assert_eq!(&symbol.quote_asset, "BTC");
+        assert!(info.symbol("ethbtc").is_none());
+        assert!(info.symbol("BTCETH").is_none());
+
+        let symbols = info.into_symbol_map();
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(&symbols[&Atom::from("BTCUSDT")].quote_asset, "USDT");
+        assert!(!symbols.contains_key(&Atom::from("btcusdt")));
+    }
+
+    #[test]
+    fn test_kline_array() {
+        // Example response from the API documentation.
+        let json = r#"[
+            [
+                1499040000000,
+                "0.01634790",
+                "0.80000000",
+                "0.01575800",
+                "0.01577100",
+                "148976.11427815",
+                1499644799999,
+                "2434.19055334",
+                308,
+                "1756.87402397",
+                "28.46694368",
+                "0"
+            ]
+        ]"#;
+        let klines: Vec<Kline> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            klines,
+            [Kline {
+                open_time: 1499040000000,
+                open: dec!(0.01634790),
+                high: dec!(0.80000000),
+                low: dec!(0.01575800),
+                close: dec!(0.01577100),
+                volume: dec!(148976.11427815),
+                close_time: 1499644799999,
+                quote_asset_volume: dec!(2434.19055334),
+                number_of_trades: 308,
+                taker_buy_base_asset_volume: dec!(1756.87402397),
+                taker_buy_quote_asset_volume: dec!(28.46694368),
+                ignore: dec!(0),
+            }]
+        );
+
+        let serialized = serde_json::to_string(&klines).unwrap();
+        assert_eq!(
+            serialized,
+            r#"[[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","#.to_string()
+                + r#""148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","#
+                + r#""28.46694368","0"]]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<Kline>>(&serialized).unwrap(),
+            klines
+        );
+
+        assert!(serde_json::from_str::<Kline>(r#"[1499040000000, "0.01634790"]"#).is_err());
+    }
+
+    #[test]
+    fn test_window_size() {
+        assert_eq!(WindowSize::minutes(1).unwrap().to_string(), "1m");
+        assert_eq!(WindowSize::minutes(59).unwrap().to_string(), "59m");
+        assert_eq!(WindowSize::hours(23).unwrap().to_string(), "23h");
+        assert_eq!(WindowSize::days(7).unwrap().to_string(), "7d");
+        assert_eq!(WindowSize::default().to_string(), "1d");
+        assert_eq!(
+            serde_json::to_string(&WindowSize::hours(4).unwrap()).unwrap(),
+            r#""4h""#
+        );
+        assert!(WindowSize::minutes(0).is_err());
+        assert!(WindowSize::minutes(60).is_err());
+        assert!(WindowSize::hours(24).is_err());
+        assert!(WindowSize::days(8).is_err());
+    }
+
+    #[test]
+    fn test_ticker_rolling_weight() {
+        assert_eq!(ticker_rolling_weight(1), 2);
+        assert_eq!(ticker_rolling_weight(50), 100);
+        assert_eq!(ticker_rolling_weight(51), 100);
+        assert_eq!(ticker_rolling_weight(100), 100);
+    }
+
+    #[test]
+    fn test_ticker_trading_day_weight() {
+        assert_eq!(ticker_trading_day_weight(1), 4);
+        assert_eq!(ticker_trading_day_weight(50), 200);
+        assert_eq!(ticker_trading_day_weight(100), 200);
+    }
+
+    #[test]
+    fn test_deserialize_rolling_ticker_stats() {
+        let full = r#"{
+            "symbol": "BNBBTC",
+            "priceChange": "-8.00000000",
+            "priceChangePercent": "-88.889",
+            "weightedAvgPrice": "2.60427807",
+            "openPrice": "9.00000000",
+            "highPrice": "9.00000000",
+            "lowPrice": "1.00000000",
+            "lastPrice": "1.00000000",
+            "volume": "187.00000000",
+            "quoteVolume": "487.00000000",
+            "openTime": 1641859200000,
+            "closeTime": 1642031999999,
+            "firstId": 0,
+            "lastId": 60,
+            "count": 61
+        }"#;
+        let stats: RollingTickerStats = serde_json::from_str(full).unwrap();
+        assert_eq!(stats.price_change, Some(dec!(-8)));
+        assert_eq!(stats.first_id, Some(0));
+        assert_eq!(stats.last_id, Some(60));
+        assert_eq!(stats.weighted_avg_price, Some(dec!(2.60427807)));
+        assert_eq!(stats.count, 61);
+
+        let mini = r#"{
+            "symbol": "BNBBTC",
+            "openPrice": "9.00000000",
+            "highPrice": "9.00000000",
+            "lowPrice": "1.00000000",
+            "lastPrice": "1.00000000",
+            "volume": "187.00000000",
+            "quoteVolume": "487.00000000",
+            "openTime": 1641859200000,
+            "closeTime": 1642031999999,
+            "firstId": 0,
+            "lastId": 60,
+            "count": 61
+        }"#;
+        let stats: RollingTickerStats = serde_json::from_str(mini).unwrap();
+        assert_eq!(&stats.symbol, "BNBBTC");
+        assert_eq!(stats.price_change, None);
+        assert_eq!(stats.price_change_percent, None);
+        assert_eq!(stats.weighted_avg_price, None);
+        assert_eq!(stats.last_price, dec!(1));
+    }
+
+    #[test]
+    fn test_ticker_stats_trade_ids() {
+        let traded = r#"{
+            "symbol": "BNBBTC",
+            "priceChange": "-94.99999800",
+            "priceChangePercent": "-95.960",
+            "weightedAvgPrice": "0.29628482",
+            "prevClosePrice": "0.10002000",
+            "lastPrice": "4.00000200",
+            "lastQty": "200.00000000",
+            "bidPrice": "4.00000000",
+            "bidQty": "100.00000000",
+            "askPrice": "4.00000200",
+            "askQty": "100.00000000",
+            "openPrice": "99.00000000",
+            "highPrice": "100.00000000",
+            "lowPrice": "0.10000000",
+            "volume": "8913.30000000",
+            "quoteVolume": "15.30000000",
+            "openTime": 1499783499040,
+            "closeTime": 1499869899040,
+            "firstId": 28385,
+            "lastId": 28460,
+            "count": 76
+        }"#;
+        let stats: TickerStats = serde_json::from_str(traded).unwrap();
+        assert_eq!(stats.first_id, Some(28385));
+        assert_eq!(stats.last_id, Some(28460));
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["firstId"], 28385);
+        assert_eq!(json["lastId"], 28460);
+
+        let idle = traded
+            .replace("28385", "-1")
+            .replace("28460", "-1")
+            .replace("76", "0");
+        let stats: TickerStats = serde_json::from_str(&idle).unwrap();
+        assert_eq!(stats.first_id, None);
+        assert_eq!(stats.last_id, None);
+        assert_eq!(stats.count, 0);
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["firstId"], -1);
+        assert_eq!(json["lastId"], -1);
+        assert_eq!(serde_json::from_value::<TickerStats>(json).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_deserialize_current_exchange_info() {
+        let info: ExchangeInformation = serde_json::from_str(EXCHANGE_INFO_CURRENT).unwrap();
+        assert_eq!(info.symbols.len(), 2);
+        assert!(matches!(
+            info.exchange_filters[..],
+            [
+                Filter::ExchangeMaxNumOrders(MaxNumOrdersFilter {
+                    max_num_orders: 1000
+                }),
+                Filter::ExchangeMaxNumAlgoOrders(MaxNumAlgoOrdersFilter {
+                    max_num_algo_orders: 200
+                }),
+                Filter::ExchangeMaxNumIcebergOrders(MaxNumIcebergOrdersFilter {
+                    max_num_iceberg_orders: 10000
+                }),
+                Filter::ExchangeMaxNumOrderLists(MaxNumOrderListsFilter {
+                    max_num_order_lists: 20
+                }),
+            ]
+        ));
+
+        let symbol = info.symbol("BTCUSDT").unwrap();
+        let filters = OrderFilters::from_filters(&symbol.filters);
+        assert_eq!(filters.max_num_order_lists.unwrap().max_num_order_lists, 20);
+        assert_eq!(filters.max_num_orders.unwrap().max_num_orders, 200);
+        assert!(matches!(symbol.filters.last(), Some(Filter::Unknown)));
+        assert_eq!(symbol.round_price(dec!(65000.123)), dec!(65000.12));
+    }
+
+    #[test]
+    fn test_symbol_avg_price_mins() {
+        let info: ExchangeInformation = serde_json::from_str(EXCHANGE_INFO_CURRENT).unwrap();
+        let mut symbol = info.symbol("BTCUSDT").unwrap().clone();
+        assert_eq!(symbol.avg_price_mins(), Some(5));
+
+        // Without a notional filter the percent price window is used.
+        symbol
+            .filters
+            .retain(|filter| !matches!(filter, Filter::Notional(_)));
+        symbol
+            .filters
+            .push(Filter::PercentPrice(PercentPriceFilter {
+                multiplier_up: dec!(5),
+                multiplier_down: dec!(0.2),
+                avg_price_mins: 1,
+            }));
+        assert_eq!(symbol.avg_price_mins(), Some(5));
+        symbol
+            .filters
+            .retain(|filter| !matches!(filter, Filter::PercentPriceBySide(_)));
+        assert_eq!(symbol.avg_price_mins(), Some(1));
+
+        symbol.filters.clear();
+        assert_eq!(symbol.avg_price_mins(), None);
+
+        let avg: AvgPrice = serde_json::from_str(r#"{"mins": 5, "price": "9.35751834"}"#).unwrap();
+        assert_eq!(
+            Some(u64::from(avg.mins)),
+            info.symbol("BTCUSDT").unwrap().avg_price_mins()
+        );
+    }
+
+    fn capability(res: BinanceResult<()>) -> Option<SymbolCapability> {
+        match res {
+            Err(BinanceError::ApiError(ApiError::CapabilityNotSupported {
+                capability, ..
+            })) => Some(capability),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_symbol_capabilities() {
+        let info: ExchangeInformation = serde_json::from_str(EXCHANGE_INFO).unwrap();
+        // The fixture predates `otoAllowed`, `allowTrailingStop` and `cancelReplaceAllowed`.
+        let mut symbol = info.symbol("BTCUSDT").unwrap().clone();
+        assert!(symbol.supports(SymbolCapability::Oco));
+        assert!(symbol.supports(SymbolCapability::QuoteOrderQtyMarket));
+        for missing in [
+            SymbolCapability::Oto,
+            SymbolCapability::TrailingStop,
+            SymbolCapability::CancelReplace,
+        ] {
+            assert!(!symbol.supports(missing));
+            assert_eq!(capability(symbol.check_capability(missing)), Some(missing));
+        }
+
+        symbol.oco_allowed = false;
+        let err = symbol.check_capability(SymbolCapability::Oco).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("BTCUSDT does not allow ocoAllowed")
+        );
+
+        let market = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Market)
+            .quote_order_qty(dec!(20));
+        assert!(symbol.check_order_request(&market).is_ok());
+        symbol.quote_order_qty_market_allowed = false;
+        assert_eq!(
+            capability(symbol.check_order_request(&market)),
+            Some(SymbolCapability::QuoteOrderQtyMarket)
+        );
+
+        let iceberg = NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::Limit)
+            .price(dec!(65000))
+            .quantity(dec!(1))
+            .iceberg_qty(dec!(0.1));
+        assert!(symbol.check_order_request(&iceberg).is_ok());
+        symbol.iceberg_allowed = false;
+        assert_eq!(
+            capability(symbol.check_order_request(&iceberg)),
+            Some(SymbolCapability::Iceberg)
+        );
+
+        let trailing = NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::StopLoss)
+            .quantity(dec!(1))
+            .trailing_delta(200);
+        assert_eq!(
+            capability(symbol.check_order_request(&trailing)),
+            Some(SymbolCapability::TrailingStop)
+        );
+        symbol.allow_trailing_stop = true;
+        assert!(symbol.check_order_request(&trailing).is_ok());
+
+        // A limit order without advanced features is always allowed.
+        let limit = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .price(dec!(65000))
+            .quantity(dec!(1));
+        assert!(symbol.check_order_request(&limit).is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_symbol_capabilities() {
+        let json = EXCHANGE_INFO.replace(
+            r#""ocoAllowed": true,"#,
+            r#""ocoAllowed": true, "otoAllowed": true, "allowTrailingStop": true,
+                "cancelReplaceAllowed": true,"#,
+        );
+        let info: ExchangeInformation = serde_json::from_str(&json).unwrap();
+        let symbol = info.symbol("ETHBTC").unwrap();
+        assert!(symbol.oto_allowed);
+        assert!(symbol.allow_trailing_stop);
+        assert!(symbol.cancel_replace_allowed);
+        assert!(
+            symbol
+                .check_capability(SymbolCapability::TrailingStop)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_symbol_permission_sets() {
+        let json = r#"{
+            "symbol": "ETHBTC",
+            "status": "TRADING",
+            "baseAsset": "ETH",
+            "baseAssetPrecision": 8,
+            "quoteAsset": "BTC",
+            "quotePrecision": 8,
+            "quoteAssetPrecision": 8,
+            "baseCommissionPrecision": 8,
+            "quoteCommissionPrecision": 8,
+            "orderTypes": [
+                "LIMIT",
+                "LIMIT_MAKER",
+                "MARKET",
+                "STOP_LOSS",
+                "STOP_LOSS_LIMIT",
+                "TAKE_PROFIT",
+                "TAKE_PROFIT_LIMIT"
+            ],
+            "icebergAllowed": true,
+            "ocoAllowed": true,
+            "otoAllowed": true,
+            "quoteOrderQtyMarketAllowed": true,
+            "allowTrailingStop": true,
+            "cancelReplaceAllowed": true,
+            "amendAllowed": true,
+            "isSpotTradingAllowed": true,
+            "isMarginTradingAllowed": true,
+            "filters": [
+                {
+                    "filterType": "PRICE_FILTER",
+                    "minPrice": "0.00001000",
+                    "maxPrice": "922327.00000000",
+                    "tickSize": "0.00001000"
+                },
+                {
+                    "filterType": "LOT_SIZE",
+                    "minQty": "0.00010000",
+                    "maxQty": "100000.00000000",
+                    "stepSize": "0.00010000"
+                },
+                {"filterType": "ICEBERG_PARTS", "limit": 10},
+                {
+                    "filterType": "NOTIONAL",
+                    "minNotional": "0.00010000",
+                    "applyMinToMarket": true,
+                    "maxNotional": "9000000.00000000",
+                    "applyMaxToMarket": false,
+                    "avgPriceMins": 5
+                },
+                {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200},
+                {"filterType": "MAX_NUM_ALGO_ORDERS", "maxNumAlgoOrders": 5}
+            ],
+            "permissions": [],
+            "permissionSets": [
+                [
+                    "SPOT",
+                    "MARGIN",
+                    "TRD_GRP_004",
+                    "TRD_GRP_005",
+                    "TRD_GRP_023",
+                    "TRD_GRP_CUSTOM",
+                    "PORTFOLIO_MARGIN"
+                ]
+            ],
+            "defaultSelfTradePreventionMode": "EXPIRE_MAKER",
+            "allowedSelfTradePreventionModes": ["EXPIRE_TAKER", "EXPIRE_MAKER", "EXPIRE_BOTH"]
+        }"#;
+        let symbol: Symbol = serde_json::from_str(json).unwrap();
+        assert!(symbol.permissions.is_empty());
+        let [permissions] = &symbol.permission_sets[..] else {
+            panic!("expected one permission set: {:?}", symbol.permission_sets);
+        };
+        assert!(matches!(permissions[0], SymbolPermission::Spot));
+        assert!(matches!(permissions[1], SymbolPermission::Margin));
+        assert!(matches!(permissions[2], SymbolPermission::TradeGroup(4)));
+        assert!(matches!(permissions[4], SymbolPermission::TradeGroup(23)));
+        assert!(
+            matches!(&permissions[5], SymbolPermission::Other(p) if p.as_str() == "TRD_GRP_CUSTOM")
+        );
+        assert!(
+            matches!(&permissions[6], SymbolPermission::Other(p) if p.as_str() == "PORTFOLIO_MARGIN")
+        );
+
+        // Unknown permissions are sent back unchanged.
+        assert_eq!(
+            serde_json::to_value(permissions).unwrap(),
+            serde_json::json!([
+                "SPOT",
+                "MARGIN",
+                "TRD_GRP_0004",
+                "TRD_GRP_0005",
+                "TRD_GRP_0023",
+                "TRD_GRP_CUSTOM",
+                "PORTFOLIO_MARGIN"
+            ])
+        );
+    }
+
+    fn agg_trade(id: u64, time: u64) -> AggTrade {
+        AggTrade {
+            id,
+            price: Decimal::ONE,
+            qty: Decimal::ONE,
+            first_trade_id: id,
+            last_trade_id: id,
+            time,
+            is_buyer_maker: false,
+            is_best_match: true,
+        }
+    }
+
+    #[test]
+    fn test_agg_trades_range_windows() {
+        const HOUR: u64 = 60 * 60 * 1000;
+        let mut range = AggTradesRange::new(0, 2 * HOUR + 10);
+        assert_eq!(
+            range.next,
+            Some(AggTradesPage::Window {
+                start_time: 0,
+                end_time: HOUR - 1,
+            })
+        );
+        assert_eq!(range.advance(vec![agg_trade(1, 5)]).len(), 1);
+        assert_eq!(
+            range.next,
+            Some(AggTradesPage::Window {
+                start_time: HOUR,
+                end_time: 2 * HOUR - 1,
+            })
+        );
+        // An empty window moves on to the next one.
+        assert!(range.advance(vec![]).is_empty());
+        assert_eq!(
+            range.next,
+            Some(AggTradesPage::Window {
+                start_time: 2 * HOUR,
+                end_time: 2 * HOUR + 10,
+            })
+        );
+        assert!(range.advance(vec![]).is_empty());
+        assert_eq!(range.next, None);
+    }
+
+    #[test]
+    fn test_agg_trades_range_from_id() {
+        let full_page = |first_id: u64| -> Vec<AggTrade> {
+            (first_id..first_id + AggTradesRange::LIMIT as u64)
+                .map(|id| agg_trade(id, id))
+                .collect()
+        };
+        let mut range = AggTradesRange::new(0, 2500);
+        assert_eq!(range.advance(full_page(0)).len(), 1000);
+        assert_eq!(range.next, Some(AggTradesPage::FromId(1000)));
+        assert_eq!(range.advance(full_page(1000)).len(), 1000);
+        assert_eq!(range.next, Some(AggTradesPage::FromId(2000)));
+        // Trades past the end of the range are dropped.
+        let trades = range.advance(full_page(2000));
+        assert_eq!(trades.len(), 501);
+        assert_eq!(trades.last().unwrap().id, 2500);
+        assert_eq!(range.next, None);
+
+        assert_eq!(AggTradesRange::new(10, 5).next, None);
+    }
+
+    #[test]
+    fn test_order_book_limit_try_from() {
+        assert_eq!(OrderBookLimit::try_from(5).unwrap(), OrderBookLimit::N5);
+        assert_eq!(
+            OrderBookLimit::try_from(5000).unwrap(),
+            OrderBookLimit::N5000
+        );
+        assert_eq!(
+            OrderBookLimit::try_from(7).unwrap(),
+            OrderBookLimit::Custom(7)
+        );
+        assert!(OrderBookLimit::try_from(0).is_err());
+        assert!(OrderBookLimit::try_from(5001).is_err());
+        assert!(OrderBookLimit::try_from(70000).is_err());
+    }
+
+    #[test]
+    fn test_order_book_limit_weight() {
+        for (limit, weight) in [
+            (1, 5),
+            (100, 5),
+            (101, 25),
+            (500, 25),
+            (501, 50),
+            (1000, 50),
+            (1001, 250),
+            (5000, 250),
+        ] {
+            let limit = OrderBookLimit::try_from(limit).unwrap();
+            assert_eq!(limit.weight(), weight, "limit {limit:?}");
+            assert_eq!(OrderBookLimit::Custom(limit.value()).weight(), weight);
+        }
+        assert_eq!(OrderBookLimit::N100.weight(), 5);
+        assert_eq!(OrderBookLimit::N5000.weight(), 250);
+    }
+
+    #[test]
+    fn test_order_book_limit_custom() {
+        assert_eq!(OrderBookLimit::Custom(100), OrderBookLimit::N100);
+        assert!(OrderBookLimit::Custom(101) > OrderBookLimit::N100);
+        assert_eq!(OrderBookLimit::Custom(250).as_str(), "250");
+        assert_eq!(OrderBookLimit::N500.as_str(), "500");
+        assert!(OrderBookLimit::Custom(5000).validate().is_ok());
+        assert!(OrderBookLimit::Custom(0).validate().is_err());
+        assert!(OrderBookLimit::Custom(5001).validate().is_err());
+    }
+
+    #[test]
+    fn test_order_book_top() {
+        let book: SpotOrderBook = serde_json::from_str(
+            r#"{
+                "lastUpdateId": 1027024,
+                "bids": [["4.00000000", "431.00000000"], ["3.99000000", "9.00000000"]],
+                "asks": [["4.00000200", "12.00000000"]]
+            }"#,
+        )
+        .unwrap();
+        let top = book.top("BNBBTC");
+        assert_eq!(&top.symbol, "BNBBTC");
+        assert_eq!(top.last_update_id, 1027024);
+        assert_eq!(top.bid.unwrap().price, Decimal::new(4, 0));
+        assert_eq!(top.ask.unwrap().qty, Decimal::new(12, 0));
+
+        let empty = SpotOrderBook {
+            asks: vec![],
+            ..book
+        };
+        assert_eq!(empty.top("BNBBTC").ask, None);
+    }
+}
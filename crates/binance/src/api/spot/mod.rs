@@ -1,20 +1,13 @@
-use ccx_api_lib::env_var_with_prefix;
-use url::Url;
-
-use crate::client::ApiCred;
-use crate::client::CCX_BINANCE_API_PREFIX;
-use crate::client::Config;
-use crate::client::Proxy;
-use crate::client::RateLimiterBucket;
-use crate::client::RateLimiterBuilder;
-use crate::client::RestClient;
-use crate::client::WebsocketStream;
 use crate::error::*;
 
 mod account;
 mod broker;
-mod margin;
+mod c2c;
+mod convert;
+mod eth_staking;
+mod fiat;
 mod market_data;
+mod pay;
 mod user_data_stream;
 // TODO mod error;
 // TODO mod savings;
@@ -23,13 +16,20 @@ mod futures;
 // TODO mod blvt;
 // TODO mod bswap;
 mod clearjunction;
+mod signed_request;
+mod simple_earn;
 mod subaccount;
 pub mod util;
 mod wallet;
 mod websocket_market;
+mod ws_api;
 
 pub use self::account::*;
 pub use self::broker::*;
+pub use self::c2c::*;
+pub use self::convert::*;
+pub use self::eth_staking::*;
+pub use self::fiat::*;
 // TODO pub use self::error::*;
 // TODO pub use self::savings::*;
 // TODO pub use self::mining::*;
@@ -38,21 +38,29 @@ pub use self::broker::*;
 #[cfg(feature = "experimental")]
 pub use self::clearjunction::*;
 pub use self::futures::*;
-pub use self::margin::*;
 pub use self::market_data::*;
+pub use self::pay::*;
+pub use self::signed_request::*;
+pub use self::simple_earn::*;
 pub use self::subaccount::*;
 pub use self::user_data_stream::*;
 pub use self::wallet::*;
 pub use self::websocket_market::*;
-use crate::client::BinanceSigner;
+pub use self::ws_api::*;
 
 pub const API_BASE: &str = "https://api.binance.com/";
 pub const STREAM_BASE: &str = "wss://stream.binance.com/stream";
+pub const WS_API_BASE: &str = "wss://ws-api.binance.com:443/ws-api/v3";
 
 pub const API_BASE_TESTNET: &str = "https://testnet.binance.vision/";
 pub const STREAM_BASE_TESTNET: &str = "wss://testnet.binance.vision/stream";
+pub const WS_API_BASE_TESTNET: &str = "wss://ws-api.testnet.binance.vision/ws-api/v3";
 
 pub const RL_WEIGHT_PER_MINUTE: &str = "weight_per_minute";
+/// IP weight of `/sapi` endpoints, limited separately from the `/api` weight.
+pub const RL_SAPI_WEIGHT_PER_MINUTE: &str = "sapi_weight_per_minute";
+/// UID weight of `/sapi` endpoints, limited per account rather than per IP.
+pub const RL_SAPI_UID_WEIGHT_PER_MINUTE: &str = "sapi_uid_weight_per_minute";
 pub const RL_ORDERS_PER_SECOND: &str = "orders_per_second";
 pub const RL_ORDERS_PER_DAY: &str = "orders_per_day";
 
@@ -72,8 +80,27 @@ pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use std::sync::Arc;
+
+    use ccx_api_lib::env_var_with_prefix;
+    use url::Url;
+
+    use super::util::OrderGuard;
+    use super::util::SymbolCache;
     use super::*;
+    use crate::client::ApiCred;
+    use crate::client::BinanceSigner;
+    use crate::client::CCX_BINANCE_API_PREFIX;
+    use crate::client::Config;
+    use crate::client::MarketStream;
+    use crate::client::Proxy;
     use crate::client::RateLimiter;
+    use crate::client::RateLimiterBucket;
+    use crate::client::RateLimiterBuilder;
+    use crate::client::RestClient;
+    use crate::client::TaskCosts;
+    use crate::client::WebsocketStream;
+    use crate::ws_stream::WsSubscription;
 
     #[derive(Clone)]
     pub struct SpotApi<S>
@@ -82,6 +109,8 @@ mod with_network {
     {
         pub client: RestClient<S>,
         pub(crate) rate_limiter: RateLimiter,
+        pub(crate) order_guard: Option<Arc<OrderGuard>>,
+        pub(crate) symbols: SymbolCache,
     }
 
     impl<S> SpotApi<S>
@@ -89,44 +118,96 @@ mod with_network {
         S: BinanceSigner,
     {
         pub fn new(signer: S, testnet: bool, proxy: Option<Proxy>) -> Self {
-            let (api_base, stream_base) = if testnet {
-                (
-                    Url::parse(API_BASE_TESTNET).unwrap(),
-                    Url::parse(STREAM_BASE_TESTNET).unwrap(),
-                )
+            let (api_base, stream_base, ws_api_base) = if testnet {
+                (API_BASE_TESTNET, STREAM_BASE_TESTNET, WS_API_BASE_TESTNET)
             } else {
-                (
-                    Url::parse(API_BASE).unwrap(),
-                    Url::parse(STREAM_BASE).unwrap(),
-                )
+                (API_BASE, STREAM_BASE, WS_API_BASE)
             };
+            let config = Config::new(
+                signer,
+                Url::parse(api_base).unwrap(),
+                Url::parse(stream_base).unwrap(),
+                proxy,
+            );
+            SpotApi::with_config(config.with_ws_api_base(Url::parse(ws_api_base).unwrap()))
+        }
+
+        /// Spot Testnet (`https://testnet.binance.vision`).
+        pub fn testnet(signer: S, proxy: Option<Proxy>) -> Self {
+            SpotApi::new(signer, true, proxy)
+        }
+
+        /// Custom REST and websocket base URLs, e.g. a local mock server.
+        pub fn with_base_urls(
+            signer: S,
+            api_base: Url,
+            stream_base: Url,
+            proxy: Option<Proxy>,
+        ) -> Self {
             SpotApi::with_config(Config::new(signer, api_base, stream_base, proxy))
         }
 
         /// Reads config from env vars with names like:
         /// "CCX_BINANCE_API_KEY", "CCX_BINANCE_API_SECRET", and "CCX_BINANCE_API_TESTNET"
+        ///
+        /// "CCX_BINANCE_API_BASE_URL", "CCX_BINANCE_API_STREAM_URL" and
+        /// "CCX_BINANCE_API_WS_API_URL" override the REST, websocket and websocket API
+        /// base URLs respectively.
+        ///
+        /// Requests are routed through a SOCKS5 proxy when "CCX_BINANCE_API_PROXY_HOST" and
+        /// "CCX_BINANCE_API_PROXY_PORT" are set ("CCX_BINANCE_API_PROXY_USERNAME" and
+        /// "CCX_BINANCE_API_PROXY_PASSWORD" are optional).
         pub fn from_env() -> SpotApi<ApiCred> {
-            let testnet = Config::<S>::env_var("TESTNET").as_deref() == Some("1");
-            let proxy = Proxy::from_env_with_prefix(CCX_BINANCE_API_PREFIX);
-            SpotApi::new(
-                ApiCred::from_env_with_prefix(CCX_BINANCE_API_PREFIX),
-                testnet,
-                proxy,
-            )
+            SpotApi::<ApiCred>::from_env_with_prefix(CCX_BINANCE_API_PREFIX)
         }
 
         /// Reads config from env vars with names like:
-        /// "${prefix}_KEY", "${prefix}_SECRET", and "${prefix}_TESTNET"
+        /// "${prefix}_KEY", "${prefix}_SECRET", "${prefix}_TESTNET", and "${prefix}_PROXY_*"
+        ///
+        /// "${prefix}_BASE_URL", "${prefix}_STREAM_URL" and "${prefix}_WS_API_URL" override
+        /// the REST, websocket and websocket API base URLs respectively. An invalid URL is
+        /// logged and the default base URLs are used instead, see
+        /// [`try_from_env_with_prefix`](Self::try_from_env_with_prefix).
         pub fn from_env_with_prefix(prefix: &str) -> SpotApi<ApiCred> {
+            SpotApi::<ApiCred>::try_from_env_with_prefix(prefix).unwrap_or_else(|e| {
+                log::error!("{e}, using the default base URLs");
+                let testnet = env_var_with_prefix(prefix, "TESTNET").as_deref() == Some("1");
+                let proxy = Proxy::from_env_with_prefix(prefix);
+                SpotApi::new(ApiCred::from_env_with_prefix(prefix), testnet, proxy)
+            })
+        }
+
+        /// Same as [`from_env_with_prefix`](Self::from_env_with_prefix), but fails on an
+        /// invalid base URL.
+        pub fn try_from_env_with_prefix(prefix: &str) -> BinanceResult<SpotApi<ApiCred>> {
             let testnet = env_var_with_prefix(prefix, "TESTNET").as_deref() == Some("1");
             let proxy = Proxy::from_env_with_prefix(prefix);
-            SpotApi::new(ApiCred::from_env_with_prefix(prefix), testnet, proxy)
+            let (api_base, stream_base, ws_api_base) = if testnet {
+                (API_BASE_TESTNET, STREAM_BASE_TESTNET, WS_API_BASE_TESTNET)
+            } else {
+                (API_BASE, STREAM_BASE, WS_API_BASE)
+            };
+            let base_url = |postfix: &str, default: &str| match env_var_with_prefix(prefix, postfix)
+            {
+                Some(url) => Url::parse(&url)
+                    .map_err(|e| BinanceError::other(format!("Invalid {prefix}_{postfix}: {e}"))),
+                None => Ok(Url::parse(default).unwrap()),
+            };
+            let api_base = base_url("BASE_URL", api_base)?;
+            let stream_base = base_url("STREAM_URL", stream_base)?;
+            let ws_api_base = base_url("WS_API_URL", ws_api_base)?;
+            let config = Config::new(
+                ApiCred::from_env_with_prefix(prefix),
+                api_base,
+                stream_base,
+                proxy,
+            );
+            Ok(SpotApi::with_config(config.with_ws_api_base(ws_api_base)))
         }
 
         pub fn with_config(config: Config<S>) -> Self {
             use std::time::Duration;
 
-            let client = RestClient::new(config);
             let rate_limiter = RateLimiterBuilder::default()
                 .bucket(
                     RL_WEIGHT_PER_MINUTE,
@@ -134,6 +215,18 @@ mod with_network {
                         .interval(Duration::from_secs(60))
                         .limit(1_200),
                 )
+                .bucket(
+                    RL_SAPI_WEIGHT_PER_MINUTE,
+                    RateLimiterBucket::default()
+                        .interval(Duration::from_secs(60))
+                        .limit(12_000),
+                )
+                .bucket(
+                    RL_SAPI_UID_WEIGHT_PER_MINUTE,
+                    RateLimiterBucket::default()
+                        .interval(Duration::from_secs(60))
+                        .limit(180_000),
+                )
                 .bucket(
                     RL_ORDERS_PER_SECOND,
                     RateLimiterBucket::default()
@@ -148,16 +241,86 @@ mod with_network {
                         .limit(200_000),
                 )
                 .start();
+            let client = RestClient::with_rate_limiter(
+                config,
+                rate_limiter.clone(),
+                TaskCosts::from([(RL_WEIGHT_PER_MINUTE.into(), 1)]),
+            );
 
             SpotApi {
                 client,
                 rate_limiter,
+                order_guard: None,
+                symbols: SymbolCache::new(),
+            }
+        }
+
+        /// Checks every new order against the guard before it is signed.
+        pub fn with_order_guard(mut self, guard: OrderGuard) -> Self {
+            self.order_guard = Some(Arc::new(guard));
+            self
+        }
+
+        /// Returns a copy of the api which does not check new orders against the guard.
+        ///
+        /// Meant for intentional large orders, e.g.
+        /// `api.dangerously_bypass_guard().create_order(...)`.
+        pub fn dangerously_bypass_guard(&self) -> SpotApi<S> {
+            SpotApi {
+                client: self.client.clone(),
+                rate_limiter: self.rate_limiter.clone(),
+                order_guard: None,
+                symbols: self.symbols.clone(),
             }
         }
 
+        /// Checks new orders against the capability flags of the cached symbols.
+        ///
+        /// An order using a feature its symbol does not allow (iceberg, quote quantity market,
+        /// trailing stop, OCO, cancel-replace) fails with
+        /// [`ApiError::CapabilityNotSupported`] before it is sent.
+        pub fn with_symbols(mut self, symbols: SymbolCache) -> Self {
+            self.symbols = symbols;
+            self
+        }
+
+        /// Symbol cache shared by all clones of this api, filled by
+        /// [`Self::load_symbols`] or [`Self::with_symbols`].
+        pub fn symbols(&self) -> &SymbolCache {
+            &self.symbols
+        }
+
+        /// Rate limiter shared by all clones of this api.
+        pub fn rate_limiter(&self) -> &RateLimiter {
+            &self.rate_limiter
+        }
+
+        /// Stops sending requests and waits until the queued and in-flight ones complete.
+        ///
+        /// The rate limiter is shared by all clones of this api, so requests made through
+        /// any of them fail afterwards. Meant to be called once, on graceful shutdown.
+        pub async fn shutdown(&self) {
+            self.rate_limiter.close();
+            self.rate_limiter.drain().await;
+        }
+
         /// Creates multiplexed websocket stream.
-        pub async fn ws(&self) -> BinanceResult<WebsocketStream> {
+        pub async fn ws(&self) -> BinanceResult<WebsocketStream>
+        where
+            S: 'static,
+        {
             self.client.web_socket().await
         }
+
+        /// Market events of `subscriptions`, over the raw stream endpoint for a single
+        /// stream and over the combined one otherwise, see [`MarketStream`].
+        pub fn market_stream<I>(&self, subscriptions: I) -> BinanceResult<MarketStream>
+        where
+            S: 'static,
+            I: IntoIterator,
+            I::Item: Into<WsSubscription>,
+        {
+            self.client.market_stream(subscriptions)
+        }
     }
 }
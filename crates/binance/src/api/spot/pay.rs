@@ -0,0 +1,252 @@
+use ccx_api_lib::serde_util::number_or_string;
+
+use super::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_PAY_TRANSACTIONS: &str = "/sapi/v1/pay/transactions";
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum PayOrderType {
+    /// Merchant payment.
+    #[serde(rename = "PAY")]
+    Pay,
+    #[serde(rename = "PAY_REFUND")]
+    PayRefund,
+    /// Transfer between users.
+    #[serde(rename = "C2C")]
+    C2c,
+    #[serde(rename = "CRYPTO_BOX")]
+    CryptoBox,
+    #[serde(rename = "CRYPTO_BOX_RF")]
+    CryptoBoxRefund,
+    /// Transfer to a user who is not registered yet.
+    #[serde(rename = "C2C_HOLDING")]
+    C2cHolding,
+    #[serde(rename = "C2C_HOLDING_RF")]
+    C2cHoldingRefund,
+    #[serde(rename = "PAYOUT")]
+    Payout,
+    #[serde(rename = "REMITTANCE")]
+    Remittance,
+    /// An order type this version does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayTransactions {
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub data: Vec<PayTransaction>,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayTransaction {
+    pub order_type: PayOrderType,
+    pub transaction_id: String,
+    pub transaction_time: u64,
+    /// Negative for outgoing transactions.
+    pub amount: Decimal,
+    pub currency: Atom,
+    /// 1 for the funding wallet, 2 for the spot wallet.
+    #[serde(default)]
+    pub wallet_type: Option<u8>,
+    #[serde(default)]
+    pub wallet_types: Option<Vec<u8>>,
+    /// Assets actually paid, which may differ from `currency`.
+    #[serde(default)]
+    pub funds_detail: Vec<PayFundsDetail>,
+    #[serde(default)]
+    pub payer_info: Option<PayCounterparty>,
+    #[serde(default)]
+    pub receiver_info: Option<PayCounterparty>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayFundsDetail {
+    pub currency: Atom,
+    pub amount: Decimal,
+    /// Amount taken from each wallet, keyed by wallet type.
+    #[serde(default)]
+    pub wallet_asset_cost: Vec<HashMap<String, Decimal>>,
+}
+
+/// Payer or receiver. Which fields are present depends on the order type and on the
+/// counterparty, e.g. merchants only have a name.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayCounterparty {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// `USER` or `MERCHANT`.
+    #[serde(default)]
+    pub r#type: Option<String>,
+    #[serde(default)]
+    pub binance_id: Option<String>,
+    #[serde(default, deserialize_with = "number_or_string::option::deserialize")]
+    pub account_id: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub country_code: Option<String>,
+    #[serde(default)]
+    pub phone_number: Option<String>,
+    #[serde(default)]
+    pub mobile_code: Option<String>,
+    #[serde(default)]
+    pub unmask_data: Option<bool>,
+    /// Set for payouts and remittances.
+    #[serde(default)]
+    pub extend: Option<PayCounterpartyExtend>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayCounterpartyExtend {
+    #[serde(default)]
+    pub institution_name: Option<String>,
+    #[serde(default)]
+    pub card_number: Option<String>,
+    #[serde(default)]
+    pub digital_wallet_id: Option<String>,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Get Pay Trade History (USER_DATA)
+        ///
+        /// Weight(UID): 3000
+        ///
+        /// * If start_time and end_time are not sent, the recent 90 days are returned.
+        /// * The max interval between start_time and end_time is 90 days.
+        /// * limit - Default: 100 Max: 100.
+        pub fn pay_transactions(
+            &self,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            limit: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<PayTransactions>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_PAY_TRANSACTIONS)?
+                        .signed(time_window)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 3000)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_c2c() {
+        let json = r#"{
+            "code": "000000",
+            "message": "success",
+            "data": [{
+                "orderType": "C2C",
+                "transactionId": "M_P_71505104267788288",
+                "transactionTime": 1610090460133,
+                "amount": "23.72469206",
+                "currency": "BNB",
+                "walletType": 1,
+                "walletTypes": [1, 2],
+                "fundsDetail": [{
+                    "currency": "USDT",
+                    "amount": "1.2",
+                    "walletAssetCost": [{"1": "0.6"}, {"2": "0.6"}]
+                }],
+                "payerInfo": {
+                    "name": "UserName",
+                    "type": "USER",
+                    "binanceId": "********",
+                    "accountId": 67736251
+                },
+                "receiverInfo": {
+                    "name": "UserName",
+                    "type": "USER",
+                    "email": "isn***@gmail.com",
+                    "binanceId": "********",
+                    "accountId": "21326891",
+                    "countryCode": "1",
+                    "phoneNumber": "8057651210",
+                    "mobileCode": "US",
+                    "extend": {"institutionName": "", "cardNumber": "", "digitalWalletId": ""}
+                }
+            }],
+            "success": true
+        }"#;
+        let transactions: PayTransactions = serde_json::from_str(json).unwrap();
+        let tx = &transactions.data[0];
+        assert_eq!(tx.order_type, PayOrderType::C2c);
+        assert_eq!(tx.amount, dec!(23.72469206));
+        assert_eq!(tx.funds_detail[0].wallet_asset_cost[1]["2"], dec!(0.6));
+        let payer = tx.payer_info.as_ref().unwrap();
+        assert_eq!(payer.account_id.as_deref(), Some("67736251"));
+        let receiver = tx.receiver_info.as_ref().unwrap();
+        assert_eq!(receiver.account_id.as_deref(), Some("21326891"));
+        assert_eq!(receiver.mobile_code.as_deref(), Some("US"));
+    }
+
+    #[test]
+    fn test_deserialize_merchant_pay() {
+        let json = r#"{
+            "code": "000000",
+            "message": "success",
+            "data": [{
+                "orderType": "PAY",
+                "transactionId": "P_A1UU8J6DH9MD1121",
+                "transactionTime": 1667891210000,
+                "amount": "-15.5",
+                "currency": "USDT",
+                "walletType": 2,
+                "fundsDetail": [{"currency": "USDT", "amount": "15.5"}],
+                "receiverInfo": {"name": "Merchant Ltd", "type": "MERCHANT"}
+            }, {
+                "orderType": "NEW_TYPE",
+                "transactionId": "X_1",
+                "transactionTime": 1667891210000,
+                "amount": "1",
+                "currency": "USDT"
+            }],
+            "success": true
+        }"#;
+        let transactions: PayTransactions = serde_json::from_str(json).unwrap();
+        let tx = &transactions.data[0];
+        assert_eq!(tx.order_type, PayOrderType::Pay);
+        assert_eq!(tx.amount, dec!(-15.5));
+        assert!(tx.payer_info.is_none());
+        assert!(tx.funds_detail[0].wallet_asset_cost.is_empty());
+        let receiver = tx.receiver_info.as_ref().unwrap();
+        assert_eq!(receiver.r#type.as_deref(), Some("MERCHANT"));
+        assert!(receiver.account_id.is_none());
+        assert_eq!(transactions.data[1].order_type, PayOrderType::Unknown);
+    }
+}
@@ -0,0 +1,106 @@
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::RL_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+/// Rate limit bucket of the IP weight of `path`.
+///
+/// `/sapi` endpoints are limited separately from the `/api` ones.
+pub fn weight_bucket(path: &str) -> &'static str {
+    if path.starts_with("/sapi/") {
+        RL_SAPI_WEIGHT_PER_MINUTE
+    } else {
+        RL_WEIGHT_PER_MINUTE
+    }
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use std::fmt::Debug;
+
+    use actix_http::Method;
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Signed GET of an endpoint without a typed wrapper in this crate.
+        ///
+        /// `query` is anything serializable into query args, e.g. a struct or a list of
+        /// pairs. The request is charged `weight` to the IP weight bucket of `path`, see
+        /// [`weight_bucket`].
+        pub fn signed_get<T>(
+            &self,
+            path: &str,
+            query: &impl Serialize,
+            weight: u32,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<T>>
+        where
+            T: DeserializeOwned + Debug,
+        {
+            self.signed_request(Method::GET, path, query, weight, time_window)
+        }
+
+        /// Signed POST of an endpoint without a typed wrapper in this crate.
+        ///
+        /// Arguments are sent in the query string like in the rest of the crate, see
+        /// [`SpotApi::signed_get`].
+        pub fn signed_post<T>(
+            &self,
+            path: &str,
+            query: &impl Serialize,
+            weight: u32,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<T>>
+        where
+            T: DeserializeOwned + Debug,
+        {
+            self.signed_request(Method::POST, path, query, weight, time_window)
+        }
+
+        fn signed_request<T>(
+            &self,
+            method: Method,
+            path: &str,
+            query: &impl Serialize,
+            weight: u32,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<T>>
+        where
+            T: DeserializeOwned + Debug,
+        {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .request(method, path)?
+                        .query_args(query)?
+                        .signed(time_window)?,
+                )
+                .cost(weight_bucket(path), weight)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weight_bucket() {
+        assert_eq!(weight_bucket("/api/v3/account"), RL_WEIGHT_PER_MINUTE);
+        assert_eq!(
+            weight_bucket("/sapi/v1/asset/tradeFee"),
+            RL_SAPI_WEIGHT_PER_MINUTE
+        );
+    }
+}
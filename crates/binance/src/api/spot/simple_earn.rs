@@ -0,0 +1,463 @@
+use ccx_api_lib::serde_util::number_or_string;
+
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_SIMPLE_EARN_FLEXIBLE_LIST: &str = "/sapi/v1/simple-earn/flexible/list";
+pub const SAPI_V1_SIMPLE_EARN_LOCKED_LIST: &str = "/sapi/v1/simple-earn/locked/list";
+pub const SAPI_V1_SIMPLE_EARN_FLEXIBLE_SUBSCRIBE: &str = "/sapi/v1/simple-earn/flexible/subscribe";
+pub const SAPI_V1_SIMPLE_EARN_LOCKED_SUBSCRIBE: &str = "/sapi/v1/simple-earn/locked/subscribe";
+pub const SAPI_V1_SIMPLE_EARN_FLEXIBLE_REDEEM: &str = "/sapi/v1/simple-earn/flexible/redeem";
+pub const SAPI_V1_SIMPLE_EARN_LOCKED_REDEEM: &str = "/sapi/v1/simple-earn/locked/redeem";
+pub const SAPI_V1_SIMPLE_EARN_FLEXIBLE_POSITION: &str = "/sapi/v1/simple-earn/flexible/position";
+pub const SAPI_V1_SIMPLE_EARN_LOCKED_POSITION: &str = "/sapi/v1/simple-earn/locked/position";
+
+/// Account the subscribed amount is taken from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SimpleEarnSourceAccount {
+    #[serde(rename = "SPOT")]
+    Spot,
+    #[serde(rename = "FUND")]
+    Fund,
+    #[serde(rename = "ALL")]
+    All,
+}
+
+/// Account the redeemed amount is credited to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SimpleEarnDestAccount {
+    #[serde(rename = "SPOT")]
+    Spot,
+    #[serde(rename = "FUND")]
+    Fund,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleProduct {
+    pub asset: Atom,
+    pub latest_annual_percentage_rate: Decimal,
+    /// Rate by amount tier, e.g. `"0-5BTC"`.
+    #[serde(default)]
+    pub tier_annual_percentage_rate: HashMap<String, Decimal>,
+    #[serde(default)]
+    pub air_drop_percentage_rate: Option<Decimal>,
+    pub can_purchase: bool,
+    pub can_redeem: bool,
+    pub is_sold_out: bool,
+    pub hot: bool,
+    pub min_purchase_amount: Decimal,
+    pub product_id: String,
+    pub subscription_start_time: u64,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProduct {
+    pub project_id: String,
+    pub detail: LockedProductDetail,
+    pub quota: LockedProductQuota,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProductDetail {
+    pub asset: Atom,
+    pub reward_asset: Atom,
+    /// Lock period in days.
+    pub duration: u32,
+    pub renewable: bool,
+    pub is_sold_out: bool,
+    pub apr: Decimal,
+    pub status: String,
+    pub subscription_start_time: u64,
+    #[serde(default)]
+    pub extra_reward_asset: Option<Atom>,
+    #[serde(default, rename = "extraRewardAPR")]
+    pub extra_reward_apr: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedProductQuota {
+    pub total_personal_quota: Decimal,
+    pub minimum: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleSubscription {
+    pub purchase_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedSubscription {
+    pub purchase_id: u64,
+    /// Required by [`SpotApi::simple_earn_locked_redeem`].
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub position_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SimpleEarnRedemption {
+    pub redeem_id: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexiblePosition {
+    pub asset: Atom,
+    pub product_id: String,
+    pub total_amount: Decimal,
+    pub latest_annual_percentage_rate: Decimal,
+    #[serde(default)]
+    pub tier_annual_percentage_rate: HashMap<String, Decimal>,
+    pub can_redeem: bool,
+    pub collateral_amount: Decimal,
+    pub yesterday_real_time_rewards: Decimal,
+    pub cumulative_bonus_rewards: Decimal,
+    pub cumulative_real_time_rewards: Decimal,
+    pub cumulative_total_rewards: Decimal,
+    pub auto_subscribe: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedPosition {
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub position_id: u64,
+    pub project_id: String,
+    pub asset: Atom,
+    pub amount: Decimal,
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub purchase_time: u64,
+    /// Lock period in days.
+    #[serde(deserialize_with = "number_or_string::deserialize")]
+    pub duration: u64,
+    pub reward_asset: Atom,
+    #[serde(rename = "APY")]
+    pub apy: Decimal,
+    pub reward_amt: Decimal,
+    pub can_redeem_early: bool,
+    pub auto_subscribe: bool,
+    pub status: String,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Get Simple Earn Flexible Product List (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn simple_earn_flexible_list(
+            &self,
+            asset: Option<impl Serialize>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<FlexibleProduct>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SIMPLE_EARN_FLEXIBLE_LIST)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Get Simple Earn Locked Product List (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn simple_earn_locked_list(
+            &self,
+            asset: Option<impl Serialize>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<LockedProduct>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SIMPLE_EARN_LOCKED_LIST)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Subscribe Flexible Product (TRADE)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * auto_subscribe - Default: true.
+        /// * source_account - Default: SPOT.
+        pub fn simple_earn_flexible_subscribe(
+            &self,
+            product_id: impl Serialize,
+            amount: Decimal,
+            auto_subscribe: Option<bool>,
+            source_account: Option<SimpleEarnSourceAccount>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<FlexibleSubscription>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_SIMPLE_EARN_FLEXIBLE_SUBSCRIBE)?
+                        .signed(time_window)?
+                        .query_arg("productId", &product_id)?
+                        .decimal_arg("amount", &amount)?
+                        .try_query_arg("autoSubscribe", &auto_subscribe)?
+                        .try_query_arg("sourceAccount", &source_account)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Subscribe Locked Product (TRADE)
+        ///
+        /// Weight(IP): 200
+        ///
+        /// * auto_subscribe - Default: true.
+        /// * source_account - Default: SPOT.
+        pub fn simple_earn_locked_subscribe(
+            &self,
+            project_id: impl Serialize,
+            amount: Decimal,
+            auto_subscribe: Option<bool>,
+            source_account: Option<SimpleEarnSourceAccount>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<LockedSubscription>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_SIMPLE_EARN_LOCKED_SUBSCRIBE)?
+                        .signed(time_window)?
+                        .query_arg("projectId", &project_id)?
+                        .decimal_arg("amount", &amount)?
+                        .try_query_arg("autoSubscribe", &auto_subscribe)?
+                        .try_query_arg("sourceAccount", &source_account)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 200)
+                .send())
+        }
+
+        /// Redeem Flexible Product (TRADE)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * amount - The whole position is redeemed when omitted.
+        /// * dest_account - Default: SPOT.
+        pub fn simple_earn_flexible_redeem(
+            &self,
+            product_id: impl Serialize,
+            amount: Option<Decimal>,
+            dest_account: Option<SimpleEarnDestAccount>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<SimpleEarnRedemption>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_SIMPLE_EARN_FLEXIBLE_REDEEM)?
+                        .signed(time_window)?
+                        .query_arg("productId", &product_id)?
+                        .query_arg("redeemAll", &amount.is_none())?
+                        .try_decimal_arg("amount", &amount)?
+                        .try_query_arg("destAccount", &dest_account)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Redeem Locked Product (TRADE)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * position_id - As returned by [`SpotApi::simple_earn_locked_subscribe`]
+        ///   or [`SpotApi::simple_earn_locked_position`].
+        pub fn simple_earn_locked_redeem(
+            &self,
+            position_id: u64,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<SimpleEarnRedemption>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_SIMPLE_EARN_LOCKED_REDEEM)?
+                        .signed(time_window)?
+                        .query_arg("positionId", &position_id)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Get Flexible Product Position (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn simple_earn_flexible_position(
+            &self,
+            asset: Option<impl Serialize>,
+            product_id: Option<impl Serialize>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<FlexiblePosition>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SIMPLE_EARN_FLEXIBLE_POSITION)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .try_query_arg("productId", &product_id)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+
+        /// Get Locked Product Position (USER_DATA)
+        ///
+        /// Weight(IP): 150
+        ///
+        /// * current - Start from 1. Default: 1.
+        /// * size - Default: 10 Max: 100.
+        pub fn simple_earn_locked_position(
+            &self,
+            asset: Option<impl Serialize>,
+            position_id: Option<u64>,
+            project_id: Option<impl Serialize>,
+            current: Option<u32>,
+            size: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Rows<LockedPosition>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SIMPLE_EARN_LOCKED_POSITION)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .try_query_arg("positionId", &position_id)?
+                        .try_query_arg("projectId", &project_id)?
+                        .try_query_arg("current", &current)?
+                        .try_query_arg("size", &size)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 150)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_flexible() {
+        let json = r#"{
+            "rows": [{
+                "asset": "USDT",
+                "latestAnnualPercentageRate": "0.05000000",
+                "tierAnnualPercentageRate": {"0-5BTC": 0.05, "5-10BTC": 0.03},
+                "airDropPercentageRate": "0.05000000",
+                "canPurchase": true,
+                "canRedeem": true,
+                "isSoldOut": true,
+                "hot": true,
+                "minPurchaseAmount": "0.01000000",
+                "productId": "USDT001",
+                "subscriptionStartTime": 1646182276000,
+                "status": "PURCHASING"
+            }],
+            "total": 1
+        }"#;
+        let products: Rows<FlexibleProduct> = serde_json::from_str(json).unwrap();
+        assert_eq!(products.total, 1);
+        assert_eq!(&products.rows[0].product_id, "USDT001");
+        assert_eq!(
+            products.rows[0].tier_annual_percentage_rate["0-5BTC"],
+            dec!(0.05)
+        );
+
+        let json = r#"{"purchaseId": 40607, "success": true}"#;
+        let subscription: FlexibleSubscription = serde_json::from_str(json).unwrap();
+        assert_eq!(subscription.purchase_id, 40607);
+    }
+
+    #[test]
+    fn test_deserialize_locked() {
+        let json = r#"{"purchaseId": 40607, "positionId": "12345", "success": true}"#;
+        let subscription: LockedSubscription = serde_json::from_str(json).unwrap();
+        assert_eq!(subscription.position_id, 12345);
+
+        let json = r#"{
+            "rows": [{
+                "positionId": 123123,
+                "parentPositionId": 123122,
+                "projectId": "Axs*90",
+                "asset": "AXS",
+                "amount": "122.09202928",
+                "purchaseTime": "1646182276000",
+                "duration": "60",
+                "accrualDays": "4",
+                "rewardAsset": "AXS",
+                "APY": "0.2032",
+                "rewardAmt": "5.17181528",
+                "nextPay": "1.29295383",
+                "nextPayDate": "1646998260000",
+                "canRedeemEarly": true,
+                "autoSubscribe": true,
+                "type": "AUTO",
+                "status": "HOLDING"
+            }],
+            "total": 1
+        }"#;
+        let positions: Rows<LockedPosition> = serde_json::from_str(json).unwrap();
+        let position = &positions.rows[0];
+        assert_eq!(position.position_id, 123123);
+        assert_eq!(position.purchase_time, 1646182276000);
+        assert_eq!(position.duration, 60);
+        assert_eq!(position.amount, dec!(122.09202928));
+    }
+}
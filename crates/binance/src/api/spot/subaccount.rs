@@ -1 +1,278 @@
-// use super::prelude::*;
+use super::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const SAPI_V1_SUB_ACCOUNT_LIST: &str = "/sapi/v1/sub-account/list";
+pub const SAPI_V3_SUB_ACCOUNT_ASSETS: &str = "/sapi/v3/sub-account/assets";
+pub const SAPI_V1_SUB_ACCOUNT_UNIVERSAL_TRANSFER: &str = "/sapi/v1/sub-account/universalTransfer";
+pub const SAPI_V1_SUB_ACCOUNT_SUB_TRANSFER_HISTORY: &str =
+    "/sapi/v1/sub-account/sub/transfer/history";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountList {
+    pub sub_accounts: Vec<SubAccount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccount {
+    pub email: String,
+    pub is_freeze: bool,
+    pub create_time: u64,
+    #[serde(default)]
+    pub is_managed_sub_account: bool,
+    #[serde(default)]
+    pub is_asset_management_sub_account: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountAssets {
+    pub balances: Vec<SubAccountBalance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountBalance {
+    pub asset: Atom,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SubAccountAccountType {
+    #[serde(rename = "SPOT")]
+    Spot,
+    #[serde(rename = "USDT_FUTURE")]
+    UsdtFuture,
+    #[serde(rename = "COIN_FUTURE")]
+    CoinFuture,
+    /// Cross margin.
+    #[serde(rename = "MARGIN")]
+    Margin,
+    #[serde(rename = "ISOLATED_MARGIN")]
+    IsolatedMargin,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountUniversalTransfer {
+    pub tran_id: u64,
+    #[serde(default)]
+    pub client_tran_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    pub from: String,
+    pub to: String,
+    pub asset: Atom,
+    pub qty: Decimal,
+    pub status: SubAccountTransferStatus,
+    pub tran_id: u64,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SubAccountTransferStatus {
+    #[serde(rename = "PROCESS")]
+    Process,
+    #[serde(rename = "SUCCESS")]
+    Success,
+    #[serde(rename = "FAILURE")]
+    Failure,
+}
+
+impl SubAccountTransferStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, SubAccountTransferStatus::Process)
+    }
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Query Sub-account List (For Master Account)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * `limit` defaults to 1, max 200.
+        pub fn sub_account_list(
+            &self,
+            email: Option<impl Serialize>,
+            is_freeze: Option<bool>,
+            page: Option<u32>,
+            limit: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<SubAccountList>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SUB_ACCOUNT_LIST)?
+                        .signed(time_window)?
+                        .try_query_arg("email", &email)?
+                        .try_query_arg("isFreeze", &is_freeze)?
+                        .try_query_arg("page", &page)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Query Sub-account Assets (For Master Account)
+        ///
+        /// Weight(UID): 60
+        pub fn sub_account_assets(
+            &self,
+            email: impl Serialize,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<SubAccountAssets>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V3_SUB_ACCOUNT_ASSETS)?
+                        .signed(time_window)?
+                        .query_arg("email", &email)?,
+                )
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 60)
+                .send())
+        }
+
+        /// Universal Transfer (For Master Account)
+        ///
+        /// Weight(IP): 360
+        ///
+        /// * An omitted `from_email` or `to_email` means the master account,
+        ///   but at least one of them must be sent.
+        /// * `symbol` is only used with [`SubAccountAccountType::IsolatedMargin`].
+        #[allow(clippy::too_many_arguments)]
+        pub fn sub_account_universal_transfer(
+            &self,
+            from_email: Option<&str>,
+            to_email: Option<&str>,
+            from_account_type: SubAccountAccountType,
+            to_account_type: SubAccountAccountType,
+            asset: impl Serialize,
+            amount: Decimal,
+            client_tran_id: Option<&str>,
+            symbol: Option<&str>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<SubAccountUniversalTransfer>> {
+            if from_email.is_none() && to_email.is_none() {
+                Err(ApiError::mandatory_field_omitted("from_email or to_email"))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_SUB_ACCOUNT_UNIVERSAL_TRANSFER)?
+                        .signed(time_window)?
+                        .try_query_arg("fromEmail", &from_email)?
+                        .try_query_arg("toEmail", &to_email)?
+                        .query_arg("fromAccountType", &from_account_type)?
+                        .query_arg("toAccountType", &to_account_type)?
+                        .try_query_arg("clientTranId", &client_tran_id)?
+                        .try_query_arg("symbol", &symbol)?
+                        .query_arg("asset", &asset)?
+                        .decimal_arg("amount", &amount)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 360)
+                .send())
+        }
+
+        /// Query Sub-account Spot Asset Transfer History (For Master Account)
+        ///
+        /// Weight(IP): 1
+        ///
+        /// * Without `from_email` and `to_email`, transfers of the master account are returned.
+        #[allow(clippy::too_many_arguments)]
+        pub fn sub_account_transfer_history(
+            &self,
+            from_email: Option<&str>,
+            to_email: Option<&str>,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            page: Option<u32>,
+            limit: Option<u32>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<SubAccountTransfer>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_SUB_ACCOUNT_SUB_TRANSFER_HISTORY)?
+                        .signed(time_window)?
+                        .try_query_arg("fromEmail", &from_email)?
+                        .try_query_arg("toEmail", &to_email)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("page", &page)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_sub_accounts() {
+        let json = r#"{"subAccounts": [
+            {"email": "testsub@gmail.com", "isFreeze": false, "createTime": 1544433328000,
+             "isManagedSubAccount": false, "isAssetManagementSubAccount": false},
+            {"email": "virtual@oxebmvfonoemail.com", "isFreeze": true,
+             "createTime": 1544433328000}
+        ]}"#;
+        let list: SubAccountList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.sub_accounts[0].email, "testsub@gmail.com");
+        assert!(!list.sub_accounts[0].is_freeze);
+        assert!(list.sub_accounts[1].is_freeze);
+
+        let json = r#"{"balances": [
+            {"asset": "ADA", "free": 10000, "locked": 0},
+            {"asset": "BNB", "free": 10003, "locked": 0.5}
+        ]}"#;
+        let assets: SubAccountAssets = serde_json::from_str(json).unwrap();
+        assert_eq!(assets.balances[0].free, dec!(10000));
+        assert_eq!(assets.balances[1].locked, dec!(0.5));
+    }
+
+    #[test]
+    fn test_deserialize_transfers() {
+        let json = r#"{"tranId": 11945860693, "clientTranId": "test"}"#;
+        let transfer: SubAccountUniversalTransfer = serde_json::from_str(json).unwrap();
+        assert_eq!(transfer.tran_id, 11945860693);
+        assert_eq!(transfer.client_tran_id.as_deref(), Some("test"));
+
+        let json = r#"[{"from": "aaa@test.com", "to": "bbb@test.com", "asset": "BTC",
+            "qty": "10", "status": "SUCCESS", "tranId": 6489943656, "time": 1544433328000}]"#;
+        let history: Vec<SubAccountTransfer> = serde_json::from_str(json).unwrap();
+        assert_eq!(history[0].qty, dec!(10));
+        assert_eq!(history[0].status, SubAccountTransferStatus::Success);
+        assert!(history[0].status.is_finished());
+        assert_eq!(
+            serde_json::to_string(&SubAccountAccountType::UsdtFuture).unwrap(),
+            r#""USDT_FUTURE""#
+        );
+    }
+}
@@ -1,7 +1,9 @@
+use super::Pong;
 use super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
+pub const API_V3_USER_DATA_STREAM: &str = "/api/v3/userDataStream";
+#[deprecated(note = "the v1 endpoint is legacy, use `API_V3_USER_DATA_STREAM`")]
 pub const V1_USER_DATA_STREAM: &str = "/api/v1/userDataStream";
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -15,7 +17,19 @@ pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::sync::PoisonError;
+    use std::time::Duration;
+
+    use futures::channel::mpsc;
+
     use super::*;
+    use crate::client::BinanceSigner;
+    use crate::client::Task;
+
+    /// How often [`UserDataStreamHandle`] sends a keepalive.
+    pub const LISTEN_KEY_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
     impl<S> SpotApi<S>
     where
@@ -31,9 +45,209 @@ mod with_network {
         pub fn user_data_stream(&self) -> BinanceResult<Task<ListenKey>> {
             Ok(self
                 .rate_limiter
-                .task(self.client.post(V1_USER_DATA_STREAM)?.auth_header()?)
+                .task(self.client.post(API_V3_USER_DATA_STREAM)?.auth_header()?)
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Ping/Keep-alive a listenKey.
+        ///
+        /// Keepalive a user data stream to prevent a time out.
+        /// User data streams will close after 60 minutes.
+        /// It's recommended to send a ping about every 30 minutes.
+        ///
+        /// Returns an empty object.
+        ///
+        /// Weight: 1
+        pub fn user_data_stream_keepalive(
+            &self,
+            listen_key: impl AsRef<str>,
+        ) -> BinanceResult<Task<Pong>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .put(API_V3_USER_DATA_STREAM)?
+                        .auth_header()?
+                        .query_arg("listenKey", listen_key.as_ref())?,
+                )
                 .cost(RL_WEIGHT_PER_MINUTE, 1)
                 .send())
         }
+
+        /// Close a listenKey.
+        ///
+        /// Close out a user data stream.
+        ///
+        /// Returns an empty object.
+        ///
+        /// Weight: 1
+        pub fn user_data_stream_close(
+            &self,
+            listen_key: impl AsRef<str>,
+        ) -> BinanceResult<Task<Pong>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .delete(API_V3_USER_DATA_STREAM)?
+                        .auth_header()?
+                        .query_arg("listenKey", listen_key.as_ref())?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Creates a listenKey and keeps it alive in the background.
+        ///
+        /// See [`UserDataStreamHandle`].
+        pub async fn start_user_data_stream(&self) -> BinanceResult<UserDataStreamHandle<S>>
+        where
+            S: Clone,
+        {
+            UserDataStreamHandle::start(self.clone(), LISTEN_KEY_KEEPALIVE_INTERVAL).await
+        }
+    }
+
+    /// A listenKey kept alive by a background task.
+    ///
+    /// When the keepalive reports that the key does not exist anymore, a new key is created
+    /// and sent to every [`on_key_changed`](Self::on_key_changed) receiver, so the websocket
+    /// can be resubscribed.
+    ///
+    /// [`close`](Self::close) releases the listenKey. Dropping the handle only stops the
+    /// keepalive, the key then expires after 60 minutes.
+    pub struct UserDataStreamHandle<S>
+    where
+        S: BinanceSigner + Clone + Unpin + 'static,
+    {
+        api: SpotApi<S>,
+        state: Arc<Mutex<KeyState>>,
+        keepalive: actix_rt::task::JoinHandle<()>,
+    }
+
+    struct KeyState {
+        listen_key: String,
+        subscribers: Vec<mpsc::UnboundedSender<String>>,
+    }
+
+    impl KeyState {
+        fn replace(&mut self, listen_key: String) {
+            self.subscribers
+                .retain(|tx| tx.unbounded_send(listen_key.clone()).is_ok());
+            self.listen_key = listen_key;
+        }
+    }
+
+    impl<S> UserDataStreamHandle<S>
+    where
+        S: BinanceSigner + Clone + Unpin + 'static,
+    {
+        /// Creates a listenKey and sends a keepalive for it every `interval`.
+        pub async fn start(api: SpotApi<S>, interval: Duration) -> BinanceResult<Self> {
+            let listen_key = api.user_data_stream()?.await?.listen_key;
+            let state = Arc::new(Mutex::new(KeyState {
+                listen_key,
+                subscribers: Vec::new(),
+            }));
+            let keepalive = actix_rt::spawn(keep_alive(api.clone(), state.clone(), interval));
+            Ok(UserDataStreamHandle {
+                api,
+                state,
+                keepalive,
+            })
+        }
+
+        /// The current listenKey.
+        pub fn listen_key(&self) -> String {
+            self.state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .listen_key
+                .clone()
+        }
+
+        /// Receives every listenKey created after the current one has expired.
+        pub fn on_key_changed(&self) -> mpsc::UnboundedReceiver<String> {
+            let (tx, rx) = mpsc::unbounded();
+            self.state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .subscribers
+                .push(tx);
+            rx
+        }
+
+        /// Stops the keepalive and closes the stream.
+        pub async fn close(self) -> BinanceResult<()> {
+            self.keepalive.abort();
+            self.api.user_data_stream_close(self.listen_key())?.await?;
+            Ok(())
+        }
+    }
+
+    impl<S> Drop for UserDataStreamHandle<S>
+    where
+        S: BinanceSigner + Clone + Unpin + 'static,
+    {
+        fn drop(&mut self) {
+            self.keepalive.abort();
+        }
+    }
+
+    async fn keep_alive<S>(api: SpotApi<S>, state: Arc<Mutex<KeyState>>, interval: Duration)
+    where
+        S: BinanceSigner + Unpin + 'static,
+    {
+        loop {
+            actix_rt::time::sleep(interval).await;
+            let listen_key = state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .listen_key
+                .clone();
+            let res = match api.user_data_stream_keepalive(&listen_key) {
+                Ok(task) => task.await.map(drop),
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok(()) => {}
+                Err(BinanceError::ApiError(ApiError::ListenKeyExpired)) => {
+                    log::warn!("Listen key expired, creating a new one");
+                    let res = match api.user_data_stream() {
+                        Ok(task) => task.await,
+                        Err(e) => Err(e),
+                    };
+                    match res {
+                        Ok(key) => state
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .replace(key.listen_key),
+                        Err(e) => log::error!("Failed to create a new listen key: {e:?}"),
+                    }
+                }
+                Err(e) => log::warn!("Failed to keep the listen key alive: {e:?}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_responses() {
+        let key: ListenKey = serde_json::from_str(
+            r#"{"listenKey": "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            key.listen_key,
+            "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"
+        );
+
+        // Keepalive and close respond with an empty object.
+        let _: Pong = serde_json::from_str("{}").unwrap();
     }
 }
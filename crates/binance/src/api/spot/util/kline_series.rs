@@ -0,0 +1,163 @@
+use std::ops;
+
+use chrono::DateTime;
+use chrono::Months;
+use chrono::Utc;
+
+use super::super::Kline;
+use crate::ChartInterval;
+
+fn datetime(millis: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis as i64).expect("kline time out of range")
+}
+
+impl Kline {
+    pub fn open_datetime(&self) -> DateTime<Utc> {
+        datetime(self.open_time)
+    }
+
+    pub fn close_datetime(&self) -> DateTime<Utc> {
+        datetime(self.close_time)
+    }
+}
+
+/// Klines ordered by open time, e.g. the result of `SpotApi::klines`.
+///
+/// Derefs to the slice of klines.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct KlineSeries {
+    klines: Vec<Kline>,
+}
+
+impl KlineSeries {
+    /// Sorts `klines` by open time and drops duplicates, keeping the latest one.
+    pub fn new(mut klines: Vec<Kline>) -> Self {
+        klines.reverse();
+        klines.sort_by_key(|kline| kline.open_time);
+        klines.dedup_by_key(|kline| kline.open_time);
+        KlineSeries { klines }
+    }
+
+    pub fn into_inner(self) -> Vec<Kline> {
+        self.klines
+    }
+
+    /// Open time of the first kline.
+    pub fn start(&self) -> Option<DateTime<Utc>> {
+        self.klines.first().map(Kline::open_datetime)
+    }
+
+    /// Close time of the last kline.
+    pub fn end(&self) -> Option<DateTime<Utc>> {
+        self.klines.last().map(Kline::close_datetime)
+    }
+
+    /// Open times of the klines missing between the first and the last one, assuming
+    /// the klines are `interval` apart.
+    pub fn gaps(&self, interval: ChartInterval) -> Vec<DateTime<Utc>> {
+        let mut gaps = Vec::new();
+        for pair in self.klines.windows(2) {
+            let next = pair[1].open_datetime();
+            let mut expected = next_open_time(pair[0].open_datetime(), interval);
+            while expected < next {
+                gaps.push(expected);
+                expected = next_open_time(expected, interval);
+            }
+        }
+        gaps
+    }
+}
+
+fn next_open_time(open_time: DateTime<Utc>, interval: ChartInterval) -> DateTime<Utc> {
+    match interval.duration() {
+        Some(duration) => open_time + duration,
+        None => open_time
+            .checked_add_months(Months::new(1))
+            .expect("kline time out of range"),
+    }
+}
+
+impl From<Vec<Kline>> for KlineSeries {
+    fn from(klines: Vec<Kline>) -> Self {
+        KlineSeries::new(klines)
+    }
+}
+
+impl ops::Deref for KlineSeries {
+    type Target = [Kline];
+
+    fn deref(&self) -> &Self::Target {
+        &self.klines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    const MINUTE: u64 = 60_000;
+
+    fn kline(open_time: u64, close: u32) -> Kline {
+        Kline {
+            open_time,
+            open: Decimal::ONE,
+            high: Decimal::ONE,
+            low: Decimal::ONE,
+            close: Decimal::from(close),
+            volume: Decimal::ONE,
+            close_time: open_time + MINUTE - 1,
+            quote_asset_volume: Decimal::ONE,
+            number_of_trades: 1,
+            taker_buy_base_asset_volume: Decimal::ONE,
+            taker_buy_quote_asset_volume: Decimal::ONE,
+            ignore: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_series() {
+        let start = 1_700_000_040_000;
+        let series = KlineSeries::new(vec![
+            kline(start + 3 * MINUTE, 3),
+            kline(start, 0),
+            kline(start + MINUTE, 1),
+            kline(start + 3 * MINUTE, 4),
+        ]);
+        assert_eq!(series.len(), 3);
+        assert_eq!(series.first().unwrap().close, Decimal::from(0));
+        // The later of the duplicates is kept.
+        assert_eq!(series.last().unwrap().close, Decimal::from(4));
+        assert_eq!(series.start(), Some(datetime(start)));
+        assert_eq!(series.end(), Some(datetime(start + 4 * MINUTE - 1)));
+        assert_eq!(
+            series.start().unwrap().to_rfc3339(),
+            "2023-11-14T22:14:00+00:00"
+        );
+
+        assert_eq!(
+            series.gaps(ChartInterval::Minute1),
+            [datetime(start + 2 * MINUTE)]
+        );
+        assert!(series.gaps(ChartInterval::Minute3).is_empty());
+        assert!(
+            KlineSeries::default()
+                .gaps(ChartInterval::Minute1)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_monthly_gaps() {
+        let month = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let series = KlineSeries::new(vec![
+            kline(month("2024-01-01T00:00:00Z").timestamp_millis() as u64, 1),
+            kline(month("2024-04-01T00:00:00Z").timestamp_millis() as u64, 4),
+        ]);
+        assert_eq!(
+            series.gaps(ChartInterval::Month1),
+            [month("2024-02-01T00:00:00Z"), month("2024-03-01T00:00:00Z")]
+        );
+    }
+}
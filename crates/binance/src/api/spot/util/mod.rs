@@ -1,3 +1,11 @@
+mod kline_series;
 mod order_filters;
+mod order_guard;
+mod snapshot_validator;
+mod symbol_cache;
 
+pub use self::kline_series::*;
 pub use self::order_filters::*;
+pub use self::order_guard::*;
+pub use self::snapshot_validator::*;
+pub use self::symbol_cache::*;
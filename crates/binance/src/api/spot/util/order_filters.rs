@@ -1,3 +1,5 @@
+use thiserror::Error;
+
 use super::super::*;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -13,6 +15,7 @@ pub struct OrderFilters {
     pub max_num_orders: Option<MaxNumOrdersFilter>,
     pub max_num_algo_orders: Option<MaxNumAlgoOrdersFilter>,
     pub max_num_iceberg: Option<MaxNumIcebergOrdersFilter>,
+    pub max_num_order_lists: Option<MaxNumOrderListsFilter>,
     pub max_position: Option<MaxPositionFilter>,
     pub trailing_delta: Option<TrailingDeltaFilter>,
 }
@@ -35,8 +38,314 @@ impl OrderFilters {
                 Filter::MaxNumIcebergOrders(filter) => this.max_num_iceberg = Some(filter),
                 Filter::MaxPosition(filter) => this.max_position = Some(filter),
                 Filter::TrailingDelta(filter) => this.trailing_delta = Some(filter),
+                Filter::MaxNumOrderLists(filter) => this.max_num_order_lists = Some(filter),
+                Filter::ExchangeMaxNumOrders(_)
+                | Filter::ExchangeMaxNumAlgoOrders(_)
+                | Filter::ExchangeMaxNumIcebergOrders(_)
+                | Filter::ExchangeMaxNumOrderLists(_)
+                | Filter::Unknown => {}
             }
         }
         this
     }
 }
+
+/// Order rejected by a symbol filter, see [`OrderFilters::validate_order`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+pub enum FilterViolation {
+    #[error("PRICE_FILTER: price {price} is below min price {min_price}")]
+    PriceTooLow { price: Decimal, min_price: Decimal },
+    #[error("PRICE_FILTER: price {price} is above max price {max_price}")]
+    PriceTooHigh { price: Decimal, max_price: Decimal },
+    #[error("PRICE_FILTER: price {price} is not a multiple of tick size {tick_size}")]
+    PriceTickSize { price: Decimal, tick_size: Decimal },
+    #[error("LOT_SIZE: quantity {qty} is below min quantity {min_qty}")]
+    QtyTooLow { qty: Decimal, min_qty: Decimal },
+    #[error("LOT_SIZE: quantity {qty} is above max quantity {max_qty}")]
+    QtyTooHigh { qty: Decimal, max_qty: Decimal },
+    #[error("LOT_SIZE: quantity {qty} is not a multiple of step size {step_size}")]
+    QtyStepSize { qty: Decimal, step_size: Decimal },
+    #[error("MIN_NOTIONAL: notional {notional} is below min notional {min_notional}")]
+    MinNotional {
+        notional: Decimal,
+        min_notional: Decimal,
+    },
+    #[error("NOTIONAL: notional {notional} is below min notional {min_notional}")]
+    NotionalTooLow {
+        notional: Decimal,
+        min_notional: Decimal,
+    },
+    #[error("NOTIONAL: notional {notional} is above max notional {max_notional}")]
+    NotionalTooHigh {
+        notional: Decimal,
+        max_notional: Decimal,
+    },
+}
+
+impl FilterViolation {
+    /// Name of the violated filter as reported by the exchange.
+    pub fn filter_type(&self) -> &'static str {
+        match self {
+            FilterViolation::PriceTooLow { .. }
+            | FilterViolation::PriceTooHigh { .. }
+            | FilterViolation::PriceTickSize { .. } => "PRICE_FILTER",
+            FilterViolation::QtyTooLow { .. }
+            | FilterViolation::QtyTooHigh { .. }
+            | FilterViolation::QtyStepSize { .. } => "LOT_SIZE",
+            FilterViolation::MinNotional { .. } => "MIN_NOTIONAL",
+            FilterViolation::NotionalTooLow { .. } | FilterViolation::NotionalTooHigh { .. } => {
+                "NOTIONAL"
+            }
+        }
+    }
+}
+
+impl OrderFilters {
+    /// Checks a limit order against `PRICE_FILTER`, `LOT_SIZE`, `MIN_NOTIONAL` and `NOTIONAL`.
+    ///
+    /// Rules set to zero are disabled, as on the exchange side.
+    pub fn validate_order(&self, price: Decimal, qty: Decimal) -> Result<(), FilterViolation> {
+        if let Some(filter) = &self.price {
+            filter.validate(price)?;
+        }
+        if let Some(filter) = &self.lot_size {
+            filter.validate(qty)?;
+        }
+        let notional = price * qty;
+        if let Some(filter) = self.min_notional.filter(|f| notional < f.min_notional) {
+            Err(FilterViolation::MinNotional {
+                notional,
+                min_notional: filter.min_notional,
+            })?
+        }
+        if let Some(filter) = &self.notional {
+            if notional < filter.min_notional {
+                Err(FilterViolation::NotionalTooLow {
+                    notional,
+                    min_notional: filter.min_notional,
+                })?
+            }
+            if !filter.max_notional.is_zero() && notional > filter.max_notional {
+                Err(FilterViolation::NotionalTooHigh {
+                    notional,
+                    max_notional: filter.max_notional,
+                })?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PriceFilter {
+    /// Tick size, or `None` if the rule is disabled.
+    pub fn tick_size(&self) -> Option<Decimal> {
+        (!self.tick_size.is_zero()).then_some(self.tick_size)
+    }
+
+    /// Snaps `price` down to the nearest multiple of the tick size above `min_price`.
+    pub fn round(&self, price: Decimal) -> Decimal {
+        round_down(price, self.min_price, self.tick_size)
+    }
+
+    pub fn validate(&self, price: Decimal) -> Result<(), FilterViolation> {
+        if !self.min_price.is_zero() && price < self.min_price {
+            Err(FilterViolation::PriceTooLow {
+                price,
+                min_price: self.min_price,
+            })?
+        }
+        if !self.max_price.is_zero() && price > self.max_price {
+            Err(FilterViolation::PriceTooHigh {
+                price,
+                max_price: self.max_price,
+            })?
+        }
+        if !self.tick_size.is_zero() && !((price - self.min_price) % self.tick_size).is_zero() {
+            Err(FilterViolation::PriceTickSize {
+                price,
+                tick_size: self.tick_size,
+            })?
+        }
+        Ok(())
+    }
+}
+
+impl LotSizeFilter {
+    /// Step size, or `None` if the rule is disabled.
+    pub fn step_size(&self) -> Option<Decimal> {
+        (!self.step_size.is_zero()).then_some(self.step_size)
+    }
+
+    /// Snaps `qty` down to the nearest multiple of the step size above `min_qty`.
+    pub fn round(&self, qty: Decimal) -> Decimal {
+        round_down(qty, self.min_qty, self.step_size)
+    }
+
+    pub fn validate(&self, qty: Decimal) -> Result<(), FilterViolation> {
+        if qty < self.min_qty {
+            Err(FilterViolation::QtyTooLow {
+                qty,
+                min_qty: self.min_qty,
+            })?
+        }
+        if !self.max_qty.is_zero() && qty > self.max_qty {
+            Err(FilterViolation::QtyTooHigh {
+                qty,
+                max_qty: self.max_qty,
+            })?
+        }
+        if !self.step_size.is_zero() && !((qty - self.min_qty) % self.step_size).is_zero() {
+            Err(FilterViolation::QtyStepSize {
+                qty,
+                step_size: self.step_size,
+            })?
+        }
+        Ok(())
+    }
+}
+
+/// Largest `base + n * increment` not greater than `value`; a zero increment disables rounding.
+fn round_down(value: Decimal, base: Decimal, increment: Decimal) -> Decimal {
+    if increment.is_zero() {
+        return value;
+    }
+    base + ((value - base) / increment).floor() * increment
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn filters() -> Vec<Filter> {
+        serde_json::from_str(
+            r#"[
+                {
+                    "filterType": "PRICE_FILTER",
+                    "minPrice": "0.01000000",
+                    "maxPrice": "1000000.00000000",
+                    "tickSize": "0.01000000"
+                },
+                {
+                    "filterType": "LOT_SIZE",
+                    "minQty": "0.00001000",
+                    "maxQty": "9000.00000000",
+                    "stepSize": "0.00001000"
+                },
+                {
+                    "filterType": "NOTIONAL",
+                    "minNotional": "5.00000000",
+                    "applyMinToMarket": true,
+                    "maxNotional": "9000000.00000000",
+                    "applyMaxToMarket": false,
+                    "avgPriceMins": 5
+                }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let filters = OrderFilters::from_filters(&filters());
+        assert_eq!(filters.validate_order(dec!(65000.01), dec!(0.001)), Ok(()));
+        assert_eq!(
+            filters.validate_order(dec!(65000.001), dec!(0.001)),
+            Err(FilterViolation::PriceTickSize {
+                price: dec!(65000.001),
+                tick_size: dec!(0.01),
+            })
+        );
+        assert_eq!(
+            filters.validate_order(dec!(0.001), dec!(1)),
+            Err(FilterViolation::PriceTooLow {
+                price: dec!(0.001),
+                min_price: dec!(0.01),
+            })
+        );
+        assert_eq!(
+            filters.validate_order(dec!(65000), dec!(0.000015)),
+            Err(FilterViolation::QtyStepSize {
+                qty: dec!(0.000015),
+                step_size: dec!(0.00001),
+            })
+        );
+        assert_eq!(
+            filters.validate_order(dec!(65000), dec!(9001)),
+            Err(FilterViolation::QtyTooHigh {
+                qty: dec!(9001),
+                max_qty: dec!(9000),
+            })
+        );
+        let violation = filters.validate_order(dec!(100), dec!(0.01)).unwrap_err();
+        assert_eq!(
+            violation,
+            FilterViolation::NotionalTooLow {
+                notional: dec!(1),
+                min_notional: dec!(5),
+            }
+        );
+        assert_eq!(violation.filter_type(), "NOTIONAL");
+    }
+
+    #[test]
+    fn test_min_notional() {
+        let filters = OrderFilters {
+            min_notional: Some(MinNotionalFilter {
+                min_notional: dec!(10),
+                apply_to_market: true,
+                avg_price_mins: 5,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(filters.validate_order(dec!(10), dec!(1)), Ok(()));
+        assert_eq!(
+            filters.validate_order(dec!(9.99), dec!(1)),
+            Err(FilterViolation::MinNotional {
+                notional: dec!(9.99),
+                min_notional: dec!(10),
+            })
+        );
+    }
+
+    #[test]
+    fn test_round() {
+        let filters = OrderFilters::from_filters(&filters());
+        let price = filters.price.unwrap();
+        assert_eq!(price.tick_size(), Some(dec!(0.01)));
+        assert_eq!(price.round(dec!(65000.019)), dec!(65000.01));
+        assert_eq!(price.round(dec!(65000.01)), dec!(65000.01));
+
+        let lot_size = filters.lot_size.unwrap();
+        assert_eq!(lot_size.step_size(), Some(dec!(0.00001)));
+        assert_eq!(lot_size.round(dec!(0.000015)), dec!(0.00001));
+        assert_eq!(lot_size.round(dec!(1.23456789)), dec!(1.23456));
+        assert_eq!(
+            filters.validate_order(
+                price.round(dec!(65000.019)),
+                lot_size.round(dec!(0.0012345))
+            ),
+            Ok(())
+        );
+
+        let lot_size = LotSizeFilter {
+            min_qty: dec!(0.5),
+            max_qty: dec!(100),
+            step_size: dec!(0.2),
+        };
+        assert_eq!(lot_size.round(dec!(1.0)), dec!(0.9));
+    }
+
+    #[test]
+    fn test_disabled_price_rules() {
+        let filter = PriceFilter {
+            min_price: dec!(0),
+            max_price: dec!(0),
+            tick_size: dec!(0),
+        };
+        assert_eq!(filter.validate(dec!(123.456789)), Ok(()));
+        assert_eq!(filter.tick_size(), None);
+        assert_eq!(filter.round(dec!(123.456789)), dec!(123.456789));
+    }
+}
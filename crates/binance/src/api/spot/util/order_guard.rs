@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use super::super::*;
+
+/// Rule of [`OrderGuard`] which rejected an order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GuardRule {
+    /// Order notional is above the absolute cap.
+    MaxNotional,
+    /// Order price deviates too far from the reference price.
+    PriceDeviation,
+    /// Order quantity is above the per-symbol cap.
+    MaxQuantity,
+}
+
+impl fmt::Display for GuardRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuardRule::MaxNotional => f.write_str("max notional"),
+            GuardRule::PriceDeviation => f.write_str("price deviation"),
+            GuardRule::MaxQuantity => f.write_str("max quantity"),
+        }
+    }
+}
+
+/// Latest book tickers by symbol, shared between the feed that keeps it up to date
+/// and the [`OrderGuard`].
+#[derive(Clone, Debug, Default)]
+pub struct BookTickerCache {
+    inner: Arc<RwLock<HashMap<Atom, BookTicker>>>,
+}
+
+impl BookTickerCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&self, ticker: BookTicker) {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.insert(ticker.symbol.clone(), ticker);
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<BookTicker> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.get(&Atom::from(symbol)).cloned()
+    }
+
+    /// Mid price of the cached ticker.
+    pub fn mid_price(&self, symbol: &str) -> Option<Decimal> {
+        let ticker = self.get(symbol)?;
+        if ticker.bid_price.is_zero() || ticker.ask_price.is_zero() {
+            return None;
+        }
+        Some((ticker.bid_price + ticker.ask_price) / Decimal::from(2))
+    }
+}
+
+/// Order parameters checked by [`OrderGuard`].
+#[derive(Clone, Copy, Debug)]
+pub struct GuardedOrder<'a> {
+    pub symbol: &'a str,
+    pub side: OrderSide,
+    pub r#type: OrderType,
+    pub quantity: Option<Decimal>,
+    pub quote_order_qty: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub stop_price: Option<Decimal>,
+}
+
+/// Last line of defense against fat-fingered orders.
+///
+/// Checked right before an order is signed. Every rule is optional:
+///
+/// * `max_notional` - absolute cap on `price * quantity` (or `quote_order_qty`);
+///   market orders are valued at the reference price.
+/// * `max_price_deviation` - max relative distance (e.g. `0.05` for 5%) between the order price
+///   and the reference price.
+/// * `max_quantity` - per-symbol cap on the order quantity.
+///
+/// The reference price is the one supplied with [`OrderGuard::reference_price`] or the mid price
+/// from the attached [`BookTickerCache`]. Rules that need a reference price are skipped when none
+/// is known.
+///
+/// With [`OrderGuard::server_validation`] `SpotApi::place_order` additionally validates
+/// the order with the test order endpoint before placing it.
+#[derive(Clone, Debug, Default)]
+pub struct OrderGuard {
+    max_notional: Option<Decimal>,
+    max_price_deviation: Option<Decimal>,
+    max_quantity: HashMap<Atom, Decimal>,
+    reference_prices: HashMap<Atom, Decimal>,
+    book_tickers: Option<BookTickerCache>,
+    server_validation: bool,
+}
+
+impl OrderGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_notional(mut self, max_notional: Decimal) -> Self {
+        self.max_notional = Some(max_notional);
+        self
+    }
+
+    pub fn max_price_deviation(mut self, max_deviation: Decimal) -> Self {
+        self.max_price_deviation = Some(max_deviation);
+        self
+    }
+
+    pub fn max_quantity(mut self, symbol: impl Into<Atom>, max_quantity: Decimal) -> Self {
+        self.max_quantity.insert(symbol.into(), max_quantity);
+        self
+    }
+
+    pub fn reference_price(mut self, symbol: impl Into<Atom>, price: Decimal) -> Self {
+        self.reference_prices.insert(symbol.into(), price);
+        self
+    }
+
+    pub fn book_tickers(mut self, cache: BookTickerCache) -> Self {
+        self.book_tickers = Some(cache);
+        self
+    }
+
+    pub fn server_validation(mut self, enabled: bool) -> Self {
+        self.server_validation = enabled;
+        self
+    }
+
+    pub fn has_server_validation(&self) -> bool {
+        self.server_validation
+    }
+
+    fn reference_price_of(&self, symbol: &str) -> Option<Decimal> {
+        self.reference_prices
+            .get(&Atom::from(symbol))
+            .copied()
+            .or_else(|| self.book_tickers.as_ref()?.mid_price(symbol))
+    }
+
+    pub fn check(&self, order: &GuardedOrder<'_>) -> BinanceResult<()> {
+        let reference_price = self.reference_price_of(order.symbol);
+        let order_price = order.price.or(order.stop_price);
+
+        if let Some(max_quantity) = self.max_quantity.get(&Atom::from(order.symbol)) {
+            if let Some(quantity) = order.quantity {
+                if quantity > *max_quantity {
+                    Err(ApiError::guard_rejected(
+                        GuardRule::MaxQuantity,
+                        format!(
+                            "{} quantity {} exceeds {}",
+                            order.symbol, quantity, max_quantity
+                        ),
+                    ))?
+                }
+            }
+        }
+
+        if let Some(max_notional) = self.max_notional {
+            let notional = match (order.quantity, order.quote_order_qty) {
+                (Some(quantity), _) => order_price.or(reference_price).map(|p| p * quantity),
+                (None, quote_order_qty) => quote_order_qty,
+            };
+            if let Some(notional) = notional {
+                if notional > max_notional {
+                    Err(ApiError::guard_rejected(
+                        GuardRule::MaxNotional,
+                        format!(
+                            "{} notional {} exceeds {}",
+                            order.symbol, notional, max_notional
+                        ),
+                    ))?
+                }
+            }
+        }
+
+        if let Some(max_deviation) = self.max_price_deviation {
+            if let (Some(price), Some(reference_price)) = (order_price, reference_price) {
+                if !reference_price.is_zero() {
+                    let deviation = (price - reference_price).abs() / reference_price;
+                    if deviation > max_deviation {
+                        Err(ApiError::guard_rejected(
+                            GuardRule::PriceDeviation,
+                            format!(
+                                "{} {:?} price {} deviates from reference {} by {}",
+                                order.symbol, order.side, price, reference_price, deviation
+                            ),
+                        ))?
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn limit_buy(quantity: Decimal, price: Decimal) -> GuardedOrder<'static> {
+        GuardedOrder {
+            symbol: "BTCUSDT",
+            side: OrderSide::Buy,
+            r#type: OrderType::Limit,
+            quantity: Some(quantity),
+            quote_order_qty: None,
+            price: Some(price),
+            stop_price: None,
+        }
+    }
+
+    fn rejected_rule(res: BinanceResult<()>) -> Option<GuardRule> {
+        match res {
+            Err(BinanceError::ApiError(ApiError::GuardRejected { rule, .. })) => Some(rule),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_unconfigured_guard_accepts_everything() {
+        let guard = OrderGuard::new();
+        assert!(guard.check(&limit_buy(dec!(1000000), dec!(1))).is_ok());
+    }
+
+    #[test]
+    fn test_max_notional() {
+        let guard = OrderGuard::new().max_notional(dec!(10000));
+        assert!(guard.check(&limit_buy(dec!(0.1), dec!(100000))).is_ok());
+        assert_eq!(
+            rejected_rule(guard.check(&limit_buy(dec!(0.2), dec!(100000)))),
+            Some(GuardRule::MaxNotional)
+        );
+
+        let market = GuardedOrder {
+            r#type: OrderType::Market,
+            quantity: None,
+            quote_order_qty: Some(dec!(20000)),
+            price: None,
+            ..limit_buy(dec!(0), dec!(0))
+        };
+        assert_eq!(
+            rejected_rule(guard.check(&market)),
+            Some(GuardRule::MaxNotional)
+        );
+    }
+
+    #[test]
+    fn test_max_notional_market_uses_reference_price() {
+        let guard = OrderGuard::new()
+            .max_notional(dec!(10000))
+            .reference_price("BTCUSDT", dec!(100000));
+        let market = GuardedOrder {
+            r#type: OrderType::Market,
+            price: None,
+            ..limit_buy(dec!(0.2), dec!(0))
+        };
+        assert_eq!(
+            rejected_rule(guard.check(&market)),
+            Some(GuardRule::MaxNotional)
+        );
+    }
+
+    #[test]
+    fn test_price_deviation_from_book_ticker() {
+        let cache = BookTickerCache::new();
+        let guard = OrderGuard::new()
+            .max_price_deviation(dec!(0.05))
+            .book_tickers(cache.clone());
+
+        // No reference price yet.
+        assert!(guard.check(&limit_buy(dec!(1), dec!(1))).is_ok());
+
+        cache.update(BookTicker {
+            symbol: "BTCUSDT".into(),
+            bid_price: dec!(99),
+            bid_qty: dec!(1),
+            ask_price: dec!(101),
+            ask_qty: dec!(1),
+        });
+        assert!(guard.check(&limit_buy(dec!(1), dec!(104))).is_ok());
+        assert_eq!(
+            rejected_rule(guard.check(&limit_buy(dec!(1), dec!(10)))),
+            Some(GuardRule::PriceDeviation)
+        );
+    }
+
+    #[test]
+    fn test_supplied_reference_price_takes_precedence() {
+        let cache = BookTickerCache::new();
+        cache.update(BookTicker {
+            symbol: "BTCUSDT".into(),
+            bid_price: dec!(99),
+            bid_qty: dec!(1),
+            ask_price: dec!(101),
+            ask_qty: dec!(1),
+        });
+        let guard = OrderGuard::new()
+            .max_price_deviation(dec!(0.05))
+            .reference_price("BTCUSDT", dec!(10))
+            .book_tickers(cache);
+        assert!(guard.check(&limit_buy(dec!(1), dec!(10))).is_ok());
+        assert_eq!(
+            rejected_rule(guard.check(&limit_buy(dec!(1), dec!(100)))),
+            Some(GuardRule::PriceDeviation)
+        );
+    }
+
+    #[test]
+    fn test_max_quantity_per_symbol() {
+        let guard = OrderGuard::new().max_quantity("BTCUSDT", dec!(2));
+        assert!(guard.check(&limit_buy(dec!(2), dec!(1))).is_ok());
+        assert_eq!(
+            rejected_rule(guard.check(&limit_buy(dec!(3), dec!(1)))),
+            Some(GuardRule::MaxQuantity)
+        );
+
+        let other = GuardedOrder {
+            symbol: "ETHUSDT",
+            ..limit_buy(dec!(3), dec!(1))
+        };
+        assert!(guard.check(&other).is_ok());
+    }
+
+    #[cfg(feature = "with_network")]
+    #[actix_rt::test]
+    async fn test_bypass_guard() {
+        use crate::ApiCred;
+        use crate::SpotApi;
+        use crate::TimeWindow;
+
+        let cred = ApiCred {
+            key: "key".to_string(),
+            secret: "secret".to_string(),
+        };
+        let api = SpotApi::new(cred, true, None)
+            .with_order_guard(OrderGuard::new().max_notional(dec!(10000)));
+        let create = |api: &SpotApi<ApiCred>| {
+            api.create_order_test(
+                "BTCUSDT",
+                OrderSide::Buy,
+                OrderType::Limit,
+                Some(TimeInForce::Gtc),
+                Some(dec!(1)),
+                None,
+                None,
+                Some(dec!(100000)),
+                None,
+                None::<&str>,
+                None,
+                TimeWindow::now(),
+            )
+            .map(|_| ())
+        };
+
+        assert_eq!(rejected_rule(create(&api)), Some(GuardRule::MaxNotional));
+        assert!(create(&api.dangerously_bypass_guard()).is_ok());
+        // The guard stays in place on the original api.
+        assert_eq!(rejected_rule(create(&api)), Some(GuardRule::MaxNotional));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use super::super::*;
+
+/// Order book snapshot is older than the one previously seen for the same symbol.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StaleSnapshot {
+    pub symbol: Atom,
+    pub last_update_id: u64,
+    pub last_seen_update_id: u64,
+}
+
+impl fmt::Display for StaleSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} snapshot lastUpdateId {} is older than previously seen {}",
+            self.symbol, self.last_update_id, self.last_seen_update_id
+        )
+    }
+}
+
+impl std::error::Error for StaleSnapshot {}
+
+/// Tracks the last seen `last_update_id` per symbol and rejects order book snapshots
+/// going back in time, which happens when requests hit different backend replicas.
+///
+/// Clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotValidator {
+    last_seen: Arc<Mutex<HashMap<Atom, u64>>>,
+}
+
+impl SnapshotValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the snapshot if it is not older than the last accepted one for the symbol.
+    pub fn validate(&self, symbol: &str, book: &SpotOrderBook) -> BinanceResult<()> {
+        let symbol = Atom::from(symbol);
+        let mut last_seen = self.last_seen.lock().unwrap_or_else(|e| e.into_inner());
+        match last_seen.get(&symbol) {
+            Some(&last_seen_update_id) if book.last_update_id < last_seen_update_id => {
+                Err(ApiError::StaleSnapshot(StaleSnapshot {
+                    symbol,
+                    last_update_id: book.last_update_id,
+                    last_seen_update_id,
+                }))?
+            }
+            _ => {
+                last_seen.insert(symbol, book.last_update_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Last accepted `last_update_id` for the symbol.
+    pub fn last_update_id(&self, symbol: &str) -> Option<u64> {
+        let last_seen = self.last_seen.lock().unwrap_or_else(|e| e.into_inner());
+        last_seen.get(&Atom::from(symbol)).copied()
+    }
+
+    /// Validates successfully fetched snapshots and drops the stale ones.
+    pub fn retain_fresh(
+        &self,
+        books: Vec<(Atom, BinanceResult<SpotOrderBook>)>,
+    ) -> Vec<(Atom, BinanceResult<SpotOrderBook>)> {
+        books
+            .into_iter()
+            .filter(|(symbol, book)| match book {
+                Ok(book) => match self.validate(symbol, book) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::warn!("Dropping order book snapshot: {e}");
+                        false
+                    }
+                },
+                Err(_) => true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(last_update_id: u64) -> SpotOrderBook {
+        SpotOrderBook {
+            last_update_id,
+            event_time: None,
+            transaction_time: None,
+            bids: vec![],
+            asks: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_regressing_snapshot() {
+        let validator = SnapshotValidator::new();
+        assert!(validator.validate("BTCUSDT", &book(100)).is_ok());
+        assert!(validator.validate("BTCUSDT", &book(100)).is_ok());
+        assert!(validator.validate("BTCUSDT", &book(105)).is_ok());
+
+        match validator.validate("BTCUSDT", &book(103)) {
+            Err(BinanceError::ApiError(ApiError::StaleSnapshot(stale))) => {
+                assert_eq!(
+                    stale,
+                    StaleSnapshot {
+                        symbol: "BTCUSDT".into(),
+                        last_update_id: 103,
+                        last_seen_update_id: 105,
+                    }
+                );
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+        // The stale snapshot does not move the watermark back.
+        assert_eq!(validator.last_update_id("BTCUSDT"), Some(105));
+
+        // Symbols are tracked independently.
+        assert!(validator.validate("ETHUSDT", &book(1)).is_ok());
+    }
+
+    #[test]
+    fn test_retain_fresh_drops_regressing_replica() {
+        let validator = SnapshotValidator::new();
+        let first = validator.retain_fresh(vec![
+            ("BTCUSDT".into(), Ok(book(200))),
+            ("ETHUSDT".into(), Ok(book(50))),
+        ]);
+        assert_eq!(first.len(), 2);
+
+        // BTCUSDT is served by a lagging replica this time.
+        let second = validator.retain_fresh(vec![
+            ("BTCUSDT".into(), Ok(book(190))),
+            ("ETHUSDT".into(), Ok(book(51))),
+            ("BNBUSDT".into(), Err(ApiError::OutOfBounds.into())),
+        ]);
+        let symbols: Vec<_> = second
+            .iter()
+            .map(|(symbol, _)| symbol.to_string())
+            .collect();
+        assert_eq!(symbols, ["ETHUSDT", "BNBUSDT"]);
+        assert_eq!(validator.last_update_id("BTCUSDT"), Some(200));
+        assert_eq!(validator.last_update_id("ETHUSDT"), Some(51));
+    }
+}
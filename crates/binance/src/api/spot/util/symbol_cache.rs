@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use super::super::*;
+
+/// Symbols from `exchangeInfo` whose capability flags are checked before an order is sent.
+///
+/// Shared by all clones of the api it is attached to. Orders on symbols missing from
+/// the cache are not checked and are left for the server to reject.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolCache {
+    inner: Arc<RwLock<HashMap<Atom, Symbol>>>,
+}
+
+impl SymbolCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&self, symbol: Symbol) {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.insert(symbol.symbol.clone(), symbol);
+    }
+
+    pub fn extend(&self, symbols: impl IntoIterator<Item = Symbol>) {
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        inner.extend(
+            symbols
+                .into_iter()
+                .map(|symbol| (symbol.symbol.clone(), symbol)),
+        );
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<Symbol> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.get(&Atom::from(symbol)).cloned()
+    }
+
+    /// Runs `check` against the cached symbol, passes if the symbol is unknown.
+    pub(crate) fn check(
+        &self,
+        symbol: &str,
+        check: impl FnOnce(&Symbol) -> BinanceResult<()>,
+    ) -> BinanceResult<()> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        match inner.get(&Atom::from(symbol)) {
+            Some(symbol) => check(symbol),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "with_network"))]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::ApiCred;
+    use crate::SpotApi;
+    use crate::TimeWindow;
+    use crate::api::margin::NewMarginOcoRequest;
+
+    /// A symbol without any of the advanced order features.
+    const SYMBOL: &str = r#"{
+        "symbol": "BTCUSDT",
+        "status": "TRADING",
+        "baseAsset": "BTC",
+        "baseAssetPrecision": 8,
+        "quoteAsset": "USDT",
+        "quotePrecision": 8,
+        "quoteAssetPrecision": 8,
+        "baseCommissionPrecision": 8,
+        "quoteCommissionPrecision": 8,
+        "orderTypes": ["LIMIT", "MARKET", "STOP_LOSS"],
+        "icebergAllowed": false,
+        "ocoAllowed": false,
+        "otoAllowed": false,
+        "quoteOrderQtyMarketAllowed": false,
+        "allowTrailingStop": false,
+        "cancelReplaceAllowed": false,
+        "isSpotTradingAllowed": true,
+        "isMarginTradingAllowed": true,
+        "filters": []
+    }"#;
+
+    fn capability(res: BinanceResult<impl Sized>) -> Option<SymbolCapability> {
+        match res {
+            Err(BinanceError::ApiError(ApiError::CapabilityNotSupported {
+                capability, ..
+            })) => Some(capability),
+            _ => None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_orders_checked_against_cached_symbols() {
+        let cred = ApiCred {
+            key: "key".to_string(),
+            secret: "secret".to_string(),
+        };
+        let symbols = SymbolCache::new();
+        symbols.update(serde_json::from_str(SYMBOL).unwrap());
+        let api = SpotApi::new(cred, true, None).with_symbols(symbols);
+
+        let market = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Market)
+            .quote_order_qty(dec!(20));
+        assert_eq!(
+            capability(api.place_order(&market, TimeWindow::now()).await),
+            Some(SymbolCapability::QuoteOrderQtyMarket)
+        );
+
+        let trailing = NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::StopLoss)
+            .quantity(dec!(1))
+            .trailing_delta(200);
+        assert_eq!(
+            capability(api.test_order(&trailing, false, TimeWindow::now())),
+            Some(SymbolCapability::TrailingStop)
+        );
+
+        let limit = NewOrderRequest::new("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .time_in_force(TimeInForce::Gtc)
+            .price(dec!(65000))
+            .quantity(dec!(1));
+        let replace =
+            CancelReplaceRequest::new(limit, CancelReplaceMode::StopOnFailure).cancel_order_id(1);
+        assert_eq!(
+            capability(api.cancel_replace_order::<NewOrderAck>(&replace, TimeWindow::now())),
+            Some(SymbolCapability::CancelReplace)
+        );
+
+        let oco = NewMarginOcoRequest::new(
+            "BTCUSDT",
+            OrderSide::Sell,
+            dec!(1),
+            dec!(70000),
+            dec!(60000),
+        );
+        assert_eq!(
+            capability(api.margin().create_oco(&oco, TimeWindow::now())),
+            Some(SymbolCapability::Oco)
+        );
+
+        // The cache is shared with clones, other symbols are left to the server.
+        let bypass = api.dangerously_bypass_guard();
+        assert_eq!(
+            capability(bypass.test_order(&trailing, false, TimeWindow::now())),
+            Some(SymbolCapability::TrailingStop)
+        );
+        let other = NewOrderRequest {
+            symbol: "ETHUSDT".into(),
+            ..trailing
+        };
+        assert!(api.test_order(&other, false, TimeWindow::now()).is_ok());
+    }
+}
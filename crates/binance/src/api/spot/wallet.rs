@@ -1,6 +1,7 @@
+use super::RL_SAPI_UID_WEIGHT_PER_MINUTE;
+use super::RL_SAPI_WEIGHT_PER_MINUTE;
 use super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const SAPI_V1_SYSTEM_STATUS: &str = "/sapi/v1/system/status";
 pub const SAPI_V1_CAPITAL_CONFIG_GETALL: &str = "/sapi/v1/capital/config/getall";
@@ -14,13 +15,16 @@ pub const SAPI_V1_CAPITAL_WITHDRAW_HISTORY: &str = "/sapi/v1/capital/withdraw/hi
 pub const SAPI_V1_CAPITAL_DEPOSIT_ADDRESS: &str = "/sapi/v1/capital/deposit/address";
 pub const SAPI_V1_ACCOUNT_STATUS: &str = "/sapi/v1/account/status";
 pub const SAPI_V1_ACCOUNT_TRADING_STATUS: &str = "/sapi/v1/account/apiTradingStatus";
+pub const SAPI_V1_ACCOUNT_API_RESTRICTIONS: &str = "/sapi/v1/account/apiRestrictions";
 pub const SAPI_V1_ASSET_DRIBLET: &str = "/sapi/v1/asset/dribblet";
 pub const SAPI_V1_ASSET_DUST: &str = "/sapi/v1/asset/dust";
+pub const SAPI_V1_ASSET_DUST_BTC: &str = "/sapi/v1/asset/dust-btc";
 pub const SAPI_V1_ASSET_DIVIDEND: &str = "/sapi/v1/asset/assetDividend";
 pub const SAPI_V1_ASSET_DETAIL: &str = "/sapi/v1/asset/assetDetail";
 pub const SAPI_V1_ASSET_TRADE_FEE: &str = "/sapi/v1/asset/tradeFee";
 pub const SAPI_V1_ASSET_TRANSFER: &str = "/sapi/v1/asset/transfer";
 pub const SAPI_V1_ASSET_GET_FUNDING_ASSET: &str = "/sapi/v1/asset/get-funding-asset";
+pub const SAPI_V3_ASSET_GET_USER_ASSET: &str = "/sapi/v3/asset/getUserAsset";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -82,6 +86,25 @@ pub struct NetworkInformation {
     pub withdraw_enable: bool,
     pub withdraw_fee: Decimal,
     pub withdraw_min: Decimal,
+    pub withdraw_max: Decimal,
+}
+
+impl CoinInformation {
+    /// Indexes the result of `SpotApi::all_coins_information` by coin.
+    pub fn index(coins: Vec<CoinInformation>) -> HashMap<Atom, CoinInformation> {
+        coins
+            .into_iter()
+            .map(|coin| (coin.coin.clone(), coin))
+            .collect()
+    }
+
+    pub fn network(&self, network: &str) -> Option<&NetworkInformation> {
+        self.network_list.iter().find(|n| &n.network == network)
+    }
+
+    pub fn default_network(&self) -> Option<&NetworkInformation> {
+        self.network_list.iter().find(|n| n.is_default)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -240,6 +263,19 @@ pub struct FundingAsset {
     pub btc_valuation: Decimal,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAsset {
+    pub asset: Atom,
+    pub free: Decimal,
+    pub locked: Decimal,
+    pub freeze: Decimal,
+    pub withdrawing: Decimal,
+    pub ipoable: Decimal,
+    /// Zero unless `need_btc_valuation` was requested.
+    pub btc_valuation: Decimal,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountStatus {
@@ -261,6 +297,28 @@ pub struct AccountTradingStatusData {
     pub update_time: u64,
 }
 
+/// Permissions of the API key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRestrictions {
+    pub ip_restrict: bool,
+    pub create_time: u64,
+    pub enable_reading: bool,
+    pub enable_spot_and_margin_trading: bool,
+    pub enable_withdrawals: bool,
+    pub enable_internal_transfer: bool,
+    pub enable_margin: bool,
+    pub enable_futures: bool,
+    pub permits_universal_transfer: bool,
+    #[serde(default)]
+    pub enable_vanilla_options: bool,
+    #[serde(default)]
+    pub enable_portfolio_margin_trading: bool,
+    /// Expiration time of the spot and margin trading permission, if it is limited.
+    #[serde(default)]
+    pub trading_authority_expiration_time: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetDribblet {
@@ -308,6 +366,31 @@ pub struct AssetDustResult {
     pub transfered_amount: Decimal,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DustAssets {
+    pub details: Vec<DustAsset>,
+    pub total_transfer_btc: Decimal,
+    #[serde(rename = "totalTransferBNB")]
+    pub total_transfer_bnb: Decimal,
+    pub dribblet_percentage: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DustAsset {
+    pub asset: Atom,
+    pub asset_full_name: String,
+    pub amount_free: Decimal,
+    #[serde(rename = "toBTC")]
+    pub to_btc: Decimal,
+    #[serde(rename = "toBNB")]
+    pub to_bnb: Decimal,
+    #[serde(rename = "toBNBOffExchange")]
+    pub to_bnb_off_exchange: Decimal,
+    pub exchange: Decimal,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetDividend {
@@ -318,12 +401,12 @@ pub struct AssetDividend {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetDividendRow {
-    id: u64,
-    amount: Decimal,
-    asset: String,
-    div_time: u64,
-    en_info: String,
-    tran_id: u64,
+    pub id: u64,
+    pub amount: Decimal,
+    pub asset: Atom,
+    pub div_time: u64,
+    pub en_info: String,
+    pub tran_id: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -347,7 +430,7 @@ pub struct AssetDetailInfo {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeFee {
-    pub symbol: String,
+    pub symbol: Atom,
     pub maker_commission: Decimal,
     pub taker_commission: Decimal,
 }
@@ -357,6 +440,7 @@ type NoResponse = HashMap<(), ()>;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> SpotApi<S>
     where
@@ -414,6 +498,32 @@ mod with_network {
                 .send())
         }
 
+        /// User Asset (USER_DATA)
+        ///
+        /// Get user assets, just for positive data.
+        ///
+        /// Weight(IP): 5
+        ///
+        /// * If `asset` is not sent, all assets with a positive balance are returned.
+        pub fn user_assets(
+            &self,
+            asset: Option<impl Serialize>,
+            need_btc_valuation: Option<bool>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<UserAsset>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V3_ASSET_GET_USER_ASSET)?
+                        .signed(time_window)?
+                        .try_query_arg("asset", &asset)?
+                        .try_query_arg("needBtcValuation", &need_btc_valuation)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 5)
+                .send())
+        }
+
         /// System Status (System)
         ///
         /// Fetch system status.
@@ -443,7 +553,7 @@ mod with_network {
                         .get(SAPI_V1_CAPITAL_CONFIG_GETALL)?
                         .signed(time_window)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 10)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
                 .send())
         }
 
@@ -517,7 +627,7 @@ mod with_network {
                         .query_arg("coin", &coin)?
                         .try_query_arg("network", &network)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 10)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
                 .send())
         }
 
@@ -525,9 +635,10 @@ mod with_network {
         ///
         /// Submit a withdraw request.
         ///
-        /// Weight(IP): 1
+        /// Weight(UID): 900
         ///
-        /// * withdrawOrderId - client id for withdraw
+        /// * withdrawOrderId - client id for withdraw, a repeated id is rejected, so a retried
+        ///     request does not withdraw twice
         /// * addressTag - Secondary address identifier for coins like XRP,XMR etc.
         /// * transactionFeeFlag - When making internal transfer, true for returning the fee
         ///     to the destination account; false for returning the fee back to the departure account.
@@ -561,11 +672,11 @@ mod with_network {
                         .try_query_arg("network", &network)?
                         .query_arg("address", &address)?
                         .try_query_arg("addressTag", &address_tag)?
-                        .query_arg("amount", &amount)?
+                        .decimal_arg("amount", &amount)?
                         .try_query_arg("transactionFeeFlag", &transaction_fee_flag)?
                         .try_query_arg("name", &name)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 900)
                 .send())
         }
 
@@ -607,7 +718,7 @@ mod with_network {
                         .try_query_arg("startTime", &start_time)?
                         .try_query_arg("endTime", &end_time)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
                 .send())
         }
 
@@ -647,7 +758,7 @@ mod with_network {
                         .try_query_arg("startTime", &start_time)?
                         .try_query_arg("endTime", &end_time)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
                 .send())
         }
 
@@ -691,6 +802,24 @@ mod with_network {
                 .send())
         }
 
+        /// Get API Key Permission (USER_DATA)
+        ///
+        /// Weight(IP): 1
+        pub fn api_restrictions(
+            &self,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ApiRestrictions>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(SAPI_V1_ACCOUNT_API_RESTRICTIONS)?
+                        .signed(time_window)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
         /// DustLog(USER_DATA)
         ///
         /// Weight(IP): 1
@@ -712,7 +841,25 @@ mod with_network {
                         .try_query_arg("startTime", &start_time)?
                         .try_query_arg("endTime", &end_time)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Get Assets That Can Be Converted Into BNB (USER_DATA)
+        ///
+        /// Weight(IP): 1
+        pub fn asset_dust_btc(
+            &self,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<DustAssets>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(SAPI_V1_ASSET_DUST_BTC)?
+                        .signed(time_window)?,
+                )
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
                 .send())
         }
 
@@ -724,20 +871,24 @@ mod with_network {
         ///
         /// * You need to openEnable Spot & Margin Trading permission
         ///   for the API Key which requests this endpoint.
-        pub fn asset_dust(
+        /// * Each asset is sent as a separate `asset` argument.
+        pub fn asset_dust<A: Serialize>(
             &self,
-            asset: impl Serialize,
+            assets: &[A],
             time_window: impl Into<TimeWindow>,
         ) -> BinanceResult<Task<AssetDust>> {
+            if assets.is_empty() {
+                Err(ApiError::mandatory_field_omitted("asset"))?
+            }
             Ok(self
                 .rate_limiter
                 .task(
                     self.client
                         .post(SAPI_V1_ASSET_DUST)?
                         .signed(time_window)?
-                        .query_arg("asset", &asset)?,
+                        .query_arg_repeated("asset", assets)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 10)
+                .cost(RL_SAPI_UID_WEIGHT_PER_MINUTE, 10)
                 .send())
         }
 
@@ -746,6 +897,9 @@ mod with_network {
         /// Query asset dividend record.
         ///
         /// Weight(IP): 10
+        ///
+        /// * `limit` defaults to 20, max 500.
+        /// * `total` of the response is the number of records in the requested period.
         pub fn asset_dividend(
             &self,
             asset: Option<impl Serialize>,
@@ -765,7 +919,7 @@ mod with_network {
                         .try_query_arg("startTime", &start_time)?
                         .try_query_arg("endTime", &end_time)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 10)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 10)
                 .send())
         }
 
@@ -796,7 +950,7 @@ mod with_network {
 
         /// Trade Fee (USER_DATA)
         ///
-        /// Fetch trade fee
+        /// Fetch trade fee rates per symbol, or for every symbol if `symbol` is `None`.
         ///
         /// Weight(IP): 1
         pub fn trade_fee(
@@ -812,8 +966,209 @@ mod with_network {
                         .signed(time_window)?
                         .try_query_arg("symbol", &symbol)?,
                 )
-                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_SAPI_WEIGHT_PER_MINUTE, 1)
                 .send())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_trade_fee() {
+        let json = r#"[
+            {"symbol": "ADABNB", "makerCommission": "0.001", "takerCommission": "0.001"},
+            {"symbol": "BNBBTC", "makerCommission": "0.00075", "takerCommission": "0.001"}
+        ]"#;
+        let fees: Vec<TradeFee> = serde_json::from_str(json).unwrap();
+        assert_eq!(fees.len(), 2);
+        assert_eq!(&fees[1].symbol, "BNBBTC");
+        assert_eq!(fees[1].maker_commission, dec!(0.00075));
+        assert_eq!(fees[1].taker_commission, dec!(0.001));
+    }
+
+    #[test]
+    fn test_deserialize_api_restrictions() {
+        let json = r#"{
+            "ipRestrict": false,
+            "createTime": 1623840271000,
+            "enableInternalTransfer": true,
+            "enableFutures": false,
+            "enablePortfolioMarginTrading": true,
+            "enableVanillaOptions": false,
+            "permitsUniversalTransfer": true,
+            "enableReading": true,
+            "enableSpotAndMarginTrading": true,
+            "enableWithdrawals": false,
+            "enableMargin": false,
+            "tradingAuthorityExpirationTime": 1628985600000
+        }"#;
+        let restrictions: ApiRestrictions = serde_json::from_str(json).unwrap();
+        assert!(restrictions.enable_spot_and_margin_trading);
+        assert!(!restrictions.enable_withdrawals);
+        assert!(restrictions.permits_universal_transfer);
+        assert_eq!(
+            restrictions.trading_authority_expiration_time,
+            Some(1628985600000)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_coins_information() {
+        let json = r#"[{
+            "coin": "BTC", "depositAllEnable": true, "free": "0.08074558", "freeze": "0",
+            "ipoable": "0", "ipoing": "0", "isLegalMoney": false, "locked": "0",
+            "name": "Bitcoin", "storage": "0", "trading": true, "withdrawAllEnable": true,
+            "withdrawing": "0",
+            "networkList": [
+                {
+                    "addressRegex": "^(bnb1)[0-9a-z]{38}$", "coin": "BTC",
+                    "depositDesc": "Wallet Maintenance, Deposit Suspended",
+                    "depositEnable": false, "isDefault": false,
+                    "memoRegex": "^[0-9A-Za-z\\-_]{1,120}$", "minConfirm": 1, "name": "BEP2",
+                    "network": "BNB", "resetAddressStatus": false,
+                    "specialTips": "Both a MEMO and an Address are required.",
+                    "unLockConfirm": 0,
+                    "withdrawDesc": "Wallet Maintenance, Withdrawal Suspended",
+                    "withdrawEnable": false, "withdrawFee": "0.00000220",
+                    "withdrawIntegerMultiple": "0.00000001",
+                    "withdrawMax": "9999999999.99999999", "withdrawMin": "0.00000440",
+                    "sameAddress": true, "estimatedArrivalTime": 25, "busy": false,
+                    "contractAddressUrl": "https://explorer.binance.org/asset/",
+                    "contractAddress": "BTCB-1DE"
+                },
+                {
+                    "addressRegex": "^[13][a-km-zA-HJ-NP-Z1-9]{25,34}$|^(bc1)[0-9A-Za-z]{39,59}$",
+                    "coin": "BTC", "depositEnable": true, "isDefault": true, "memoRegex": "",
+                    "minConfirm": 1, "name": "BTC", "network": "BTC",
+                    "resetAddressStatus": false, "specialTips": "", "unLockConfirm": 2,
+                    "withdrawEnable": true, "withdrawFee": "0.0005",
+                    "withdrawIntegerMultiple": "0.00000001", "withdrawMax": "750",
+                    "withdrawMin": "0.001", "sameAddress": false, "estimatedArrivalTime": 25,
+                    "busy": false
+                }
+            ]
+        }]"#;
+        let coins: Vec<CoinInformation> = serde_json::from_str(json).unwrap();
+        let coins = CoinInformation::index(coins);
+        let btc = &coins[&Atom::from("BTC")];
+        assert_eq!(btc.free, dec!(0.08074558));
+
+        let bep2 = btc.network("BNB").unwrap();
+        assert!(!bep2.withdraw_enable);
+        assert_eq!(bep2.withdraw_fee, dec!(0.0000022));
+        assert_eq!(bep2.withdraw_max, dec!(9999999999.99999999));
+        assert_eq!(&bep2.memo_regex, r"^[0-9A-Za-z\-_]{1,120}$");
+        assert_eq!(
+            bep2.deposit_desc.as_deref(),
+            Some("Wallet Maintenance, Deposit Suspended")
+        );
+
+        let default = btc.default_network().unwrap();
+        assert_eq!(&default.network, "BTC");
+        assert_eq!(default.withdraw_fee, dec!(0.0005));
+        assert_eq!(default.withdraw_min, dec!(0.001));
+        assert_eq!(default.withdraw_max, dec!(750));
+        assert_eq!(default.min_confirm, 1);
+        assert_eq!(default.insert_time, None);
+        assert!(btc.network("ETH").is_none());
+    }
+
+    #[test]
+    fn test_deserialize_dust() {
+        let json = r#"{
+            "details": [{
+                "asset": "ADA", "assetFullName": "ADA", "amountFree": "6.21",
+                "toBTC": "0.00016848", "toBNB": "0.01777302",
+                "toBNBOffExchange": "0.01741756", "exchange": "0.00035546"
+            }],
+            "totalTransferBtc": "0.00016848", "totalTransferBNB": "0.01777302",
+            "dribbletPercentage": "0.02"
+        }"#;
+        let dust: DustAssets = serde_json::from_str(json).unwrap();
+        assert_eq!(&dust.details[0].asset, "ADA");
+        assert_eq!(dust.details[0].to_bnb_off_exchange, dec!(0.01741756));
+        assert_eq!(dust.total_transfer_bnb, dec!(0.01777302));
+
+        let json = r#"{
+            "totalServiceCharge": "0.02102542", "totalTransfered": "1.05127099",
+            "transferResult": [{
+                "amount": "0.03000000", "fromAsset": "ETH", "operateTime": 1563368549307,
+                "serviceChargeAmount": "0.00500000", "tranId": 2970932918,
+                "transferedAmount": "0.25000000"
+            }]
+        }"#;
+        let transfer: AssetDust = serde_json::from_str(json).unwrap();
+        assert_eq!(transfer.total_transfered, dec!(1.05127099));
+        assert_eq!(&transfer.transfer_result[0].from_asset, "ETH");
+    }
+
+    #[test]
+    fn test_deserialize_user_assets() {
+        let json = r#"[
+            {"asset": "AVAX", "free": "1", "locked": "0", "freeze": "0", "withdrawing": "0",
+             "ipoable": "0", "btcValuation": "0"},
+            {"asset": "BNB", "free": "0.0019", "locked": "0", "freeze": "0.5",
+             "withdrawing": "0.1", "ipoable": "0", "btcValuation": "0.00002637"}
+        ]"#;
+        let assets: Vec<UserAsset> = serde_json::from_str(json).unwrap();
+        assert_eq!(&assets[1].asset, "BNB");
+        assert_eq!(assets[1].freeze, dec!(0.5));
+        assert_eq!(assets[1].btc_valuation, dec!(0.00002637));
+    }
+
+    #[test]
+    fn test_deserialize_asset_dividend() {
+        let json = r#"{
+            "rows": [
+                {"id": 1637366104, "amount": "10.00000000", "asset": "BHFT",
+                 "divTime": 1563189166000, "enInfo": "BHFT distribution",
+                 "tranId": 2968885920},
+                {"id": 1631750237, "amount": "10.00000000", "asset": "BHFT",
+                 "divTime": 1563189165000, "enInfo": "BHFT distribution",
+                 "tranId": 2968885920}
+            ],
+            "total": 2
+        }"#;
+        let dividends: AssetDividend = serde_json::from_str(json).unwrap();
+        assert_eq!(dividends.total, 2);
+        assert_eq!(dividends.rows[0].amount, dec!(10));
+        assert_eq!(dividends.rows[0].div_time, 1563189166000);
+        assert_eq!(&dividends.rows[1].en_info, "BHFT distribution");
+    }
+
+    #[test]
+    fn test_deserialize_history() {
+        let json = r#"[{
+            "id": "769800519366885376", "amount": "0.001", "coin": "BNB", "network": "BNB",
+            "status": 1, "address": "bnb136ns6lfw4zs5hg4n85vdthaad7hq5m4gtkgf23",
+            "addressTag": "101764890", "txId": "98A3EA560C6B3336D348B6C83F0F95ECE4F1F5919E94BD006E5BF3BF264FACFC",
+            "insertTime": 1661493146000, "transferType": 0, "confirmTimes": "1/1"
+        }]"#;
+        let deposits: Vec<Deposit> = serde_json::from_str(json).unwrap();
+        assert_eq!(deposits[0].amount, dec!(0.001));
+        assert!(deposits[0].status.is_success());
+        assert!(deposits[0].transfer_type.is_external());
+
+        let json = r#"[{
+            "id": "b6ae22b3aa844210a7041aee7589627c", "amount": "8.91000000",
+            "transactionFee": "0.004", "coin": "USDT", "status": 6,
+            "address": "0x94df8b352de7f46f64b01d3666bf6e936e44ce60",
+            "txId": "0xb5ef8c13b968a406cc62a93a8bd80f9e9a906ef1b3fcf20a2e48573c17659268",
+            "applyTime": "2019-10-12 11:12:02", "network": "ETH", "transferType": 0,
+            "withdrawOrderId": "WITHDRAWtest123"
+        }]"#;
+        let withdrawals: Vec<Withdraw> = serde_json::from_str(json).unwrap();
+        assert_eq!(withdrawals[0].status, WithdrawStatus::Completed);
+        assert!(withdrawals[0].status.is_finished());
+        assert_eq!(
+            withdrawals[0].withdraw_order_id.as_deref(),
+            Some("WITHDRAWtest123")
+        );
+        assert_eq!(withdrawals[0].transaction_fee, dec!(0.004));
+    }
+}
@@ -1,11 +1,186 @@
-// use super::prelude::*;
-//
-// #[cfg(feature = "with_network")]
-// pub use with_network::*;
-//
-// #[cfg(feature = "with_network")]
-// mod with_network {
-//     use super::*;
-//
-//
-// }
+use super::prelude::*;
+use crate::ws_stream::WsStream;
+
+/// Update speed of the diff depth stream.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum DepthUpdateSpeed {
+    #[default]
+    Ms1000,
+    Ms100,
+}
+
+impl DepthUpdateSpeed {
+    pub fn stream(self) -> WsStream {
+        match self {
+            DepthUpdateSpeed::Ms1000 => WsStream::Depth,
+            DepthUpdateSpeed::Ms100 => WsStream::Depth100ms,
+        }
+    }
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    use ccx_api_lib::SnapshotBackoff;
+    use futures::FutureExt;
+    use futures::Stream;
+    use futures::StreamExt;
+    use futures::channel::mpsc;
+    use futures::future::Fuse;
+    use futures::future::FusedFuture;
+    use futures::future::LocalBoxFuture;
+
+    use super::super::OrderBookLimit;
+    use super::*;
+    use crate::client::MarketStream;
+    use crate::util::OrderBook;
+    use crate::util::OrderBookState;
+    use crate::util::OrderBookUpdater;
+    use crate::ws_stream::MarketEvent;
+    use crate::ws_stream::OrderBookDiffEvent;
+    use crate::ws_stream::WsEvent;
+
+    /// Event of [`OrderBookStream`].
+    #[derive(Clone, Debug)]
+    pub enum OrderBookEvent {
+        /// The whole book once it is synced from a snapshot, replaces the book built so far.
+        Snapshot(OrderBook),
+        /// Diff applied on top of the previous event, a level with a zero quantity is removed.
+        ///
+        /// Can be passed to [`OrderBookState::update`] of a state built from the snapshot.
+        Diff(OrderBookDiffEvent),
+    }
+
+    /// Order book kept in sync with the diff depth stream, see [`SpotApi::order_book_stream`].
+    ///
+    /// Yields the whole book after a (re)sync and the applied diffs in between. Errors,
+    /// e.g. a failed snapshot request, are passed through without ending the stream.
+    pub struct OrderBookStream {
+        rx: mpsc::UnboundedReceiver<BinanceResult<OrderBookEvent>>,
+        task: actix_rt::task::JoinHandle<()>,
+    }
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Local order book of `symbol`.
+        ///
+        /// Subscribes to the diff depth stream, buffers the diffs while a
+        /// [`OrderBookLimit::N5000`] snapshot is fetched and applies them on top of it.
+        /// When a diff does not continue the book, e.g. after a reconnect, a new snapshot
+        /// is fetched. Snapshots requested one after another are delayed increasingly, up
+        /// to a minute.
+        pub async fn order_book_stream(
+            &self,
+            symbol: &str,
+            update_speed: DepthUpdateSpeed,
+        ) -> BinanceResult<OrderBookStream>
+        where
+            S: Clone,
+        {
+            let symbol = symbol.to_uppercase();
+            let events = self.market_stream([(symbol.to_lowercase(), update_speed.stream())])?;
+            let (tx, rx) = mpsc::unbounded();
+            let task = actix_rt::spawn(run_order_book_stream(self.clone(), symbol, events, tx));
+            Ok(OrderBookStream { rx, task })
+        }
+    }
+
+    async fn run_order_book_stream<S>(
+        api: SpotApi<S>,
+        symbol: String,
+        events: MarketStream,
+        tx: mpsc::UnboundedSender<BinanceResult<OrderBookEvent>>,
+    ) where
+        S: crate::client::BinanceSigner,
+        S: Clone + Unpin + 'static,
+    {
+        let mut events = events.fuse();
+        let mut updater = OrderBookUpdater::new();
+        let mut backoff = SnapshotBackoff::new();
+        // At most one snapshot is requested at a time, diffs are buffered meanwhile.
+        let mut snapshot: Fuse<LocalBoxFuture<'static, BinanceResult<OrderBook>>> =
+            Fuse::terminated();
+        loop {
+            let event = futures::select! {
+                event = events.next() => match event {
+                    Some(Ok(MarketEvent {
+                        event: WsEvent::OrderBookDiff(diff),
+                        ..
+                    })) => {
+                        let last_update_id = updater.state().map(OrderBookState::last_update_id);
+                        match updater.push_diff(diff.clone()) {
+                            Ok(()) if updater.state().map(OrderBookState::last_update_id)
+                                != last_update_id =>
+                            {
+                                Some(Ok(OrderBookEvent::Diff(diff)))
+                            }
+                            // Buffered or outdated.
+                            Ok(()) => None,
+                            Err(e) => {
+                                log::warn!("{symbol} order book: {e}, resyncing");
+                                None
+                            }
+                        }
+                    }
+                    Some(Ok(_)) => None,
+                    Some(Err(e)) => Some(Err(e)),
+                    None => return,
+                },
+                res = snapshot => match res {
+                    Ok(book) => match updater.init(book) {
+                        Ok(()) => updater
+                            .state()
+                            .map(|state| Ok(OrderBookEvent::Snapshot(state.to_order_book()))),
+                        // The snapshot is older than the buffered diffs, another one is
+                        // requested.
+                        Err(e) => {
+                            log::debug!("{symbol} order book: {e}");
+                            None
+                        }
+                    },
+                    Err(e) => Some(Err(e)),
+                },
+            };
+            if let Some(event) = event {
+                if tx.unbounded_send(event).is_err() {
+                    return;
+                }
+            }
+
+            if updater.state().is_none() && snapshot.is_terminated() {
+                let delay = backoff.next_delay();
+                let api = api.clone();
+                let symbol = symbol.clone();
+                snapshot = async move {
+                    actix_rt::time::sleep(delay).await;
+                    Ok(api.depth(symbol, OrderBookLimit::N5000)?.await?.into())
+                }
+                .boxed_local()
+                .fuse();
+            }
+        }
+    }
+
+    impl Stream for OrderBookStream {
+        type Item = BinanceResult<OrderBookEvent>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_next_unpin(cx)
+        }
+    }
+
+    impl Drop for OrderBookStream {
+        fn drop(&mut self) {
+            self.task.abort();
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::prelude::*;
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use std::sync::Arc;
+
+    use serde::de::DeserializeOwned;
+
+    use super::super::AccountInformation;
+    use super::super::CancelledOrder;
+    use super::super::NewOrderRequest;
+    use super::super::Order;
+    use super::super::util::GuardedOrder;
+    use super::super::util::OrderGuard;
+    use super::super::util::SymbolCache;
+    use super::*;
+    use crate::client::WsApiConnection;
+    use crate::client::WsApiParams;
+    use crate::client::WsApiResponse;
+
+    /// Trading over the websocket API.
+    ///
+    /// Every response carries the rate limits affected by the request. Requests are not
+    /// counted by the [`RateLimiter`](crate::client::RateLimiter) of the [`SpotApi`].
+    pub struct SpotWsApi<S>
+    where
+        S: crate::client::BinanceSigner,
+    {
+        connection: WsApiConnection<S>,
+        order_guard: Option<Arc<OrderGuard>>,
+        symbols: SymbolCache,
+    }
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::BinanceSigner,
+    {
+        /// Connects to the websocket API.
+        ///
+        /// New orders are checked against the order guard of this api, if any,
+        /// and the capabilities of its cached symbols.
+        pub async fn ws_api(&self) -> BinanceResult<SpotWsApi<S>> {
+            let url =
+                self.client.config().ws_api_base.clone().ok_or_else(|| {
+                    BinanceError::other("websocket API base URL is not configured")
+                })?;
+            let connection = WsApiConnection::connect(self.client.clone(), url).await?;
+            Ok(SpotWsApi {
+                connection,
+                order_guard: self.order_guard.clone(),
+                symbols: self.symbols.clone(),
+            })
+        }
+    }
+
+    impl<S> SpotWsApi<S>
+    where
+        S: crate::client::BinanceSigner,
+    {
+        /// Underlying connection, e.g. for methods not covered here.
+        pub fn connection(&self) -> &WsApiConnection<S> {
+            &self.connection
+        }
+
+        /// Place new order (TRADE)
+        ///
+        /// `V` is one of [`NewOrderAck`](super::super::NewOrderAck),
+        /// [`NewOrderResult`](super::super::NewOrderResult) or
+        /// [`NewOrderFull`](super::super::NewOrderFull), matching
+        /// `new_order_resp_type`.
+        ///
+        /// Weight: 1
+        pub async fn order_place<V>(
+            &self,
+            order: &NewOrderRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<WsApiResponse<V>>
+        where
+            V: DeserializeOwned,
+        {
+            order.validate()?;
+            self.symbols
+                .check(&order.symbol, |symbol| symbol.check_order_request(order))?;
+            if let Some(guard) = &self.order_guard {
+                guard.check(&GuardedOrder {
+                    symbol: &order.symbol,
+                    side: order.side,
+                    r#type: order.r#type,
+                    quantity: order.quantity,
+                    quote_order_qty: order.quote_order_qty,
+                    price: order.price,
+                    stop_price: order.stop_price,
+                })?;
+            }
+            let params = WsApiParams::new()
+                .arg("symbol", &order.symbol)?
+                .arg("side", &order.side)?
+                .arg("type", &order.r#type)?
+                .try_arg("timeInForce", &order.time_in_force)?
+                .try_decimal_arg("quantity", &order.quantity)?
+                .try_decimal_arg("quoteOrderQty", &order.quote_order_qty)?
+                .try_decimal_arg("icebergQty", &order.iceberg_qty)?
+                .try_decimal_arg("price", &order.price)?
+                .try_decimal_arg("stopPrice", &order.stop_price)?
+                .try_arg("trailingDelta", &order.trailing_delta)?
+                .try_arg("newClientOrderId", &order.new_client_order_id)?
+                .try_arg("newOrderRespType", &order.new_order_resp_type)?;
+            self.connection
+                .signed_request("order.place", params, time_window)
+                .await
+        }
+
+        /// Cancel order (TRADE)
+        ///
+        /// Either `order_id` or `orig_client_order_id` must be sent.
+        ///
+        /// Weight: 1
+        pub async fn order_cancel(
+            &self,
+            symbol: &str,
+            order_id: Option<u64>,
+            orig_client_order_id: Option<&str>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<WsApiResponse<CancelledOrder>> {
+            let params = order_params(symbol, order_id, orig_client_order_id)?;
+            self.connection
+                .signed_request("order.cancel", params, time_window)
+                .await
+        }
+
+        /// Query order (USER_DATA)
+        ///
+        /// Either `order_id` or `orig_client_order_id` must be sent.
+        ///
+        /// Weight: 4
+        pub async fn order_status(
+            &self,
+            symbol: &str,
+            order_id: Option<u64>,
+            orig_client_order_id: Option<&str>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<WsApiResponse<Order>> {
+            let params = order_params(symbol, order_id, orig_client_order_id)?;
+            self.connection
+                .signed_request("order.status", params, time_window)
+                .await
+        }
+
+        /// Account information (USER_DATA)
+        ///
+        /// Weight: 20
+        pub async fn account_status(
+            &self,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<WsApiResponse<AccountInformation>> {
+            self.connection
+                .signed_request("account.status", WsApiParams::new(), time_window)
+                .await
+        }
+    }
+
+    fn order_params(
+        symbol: &str,
+        order_id: Option<u64>,
+        orig_client_order_id: Option<&str>,
+    ) -> BinanceResult<WsApiParams> {
+        if order_id.is_none() && orig_client_order_id.is_none() {
+            Err(ApiError::mandatory_field_omitted(
+                "order_id or orig_client_order_id",
+            ))?
+        }
+        WsApiParams::new()
+            .arg("symbol", symbol)?
+            .try_arg("orderId", &order_id)?
+            .try_arg("origClientOrderId", &orig_client_order_id)
+    }
+}
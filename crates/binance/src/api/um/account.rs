@@ -0,0 +1,732 @@
+use super::OrderType;
+use super::RL_ORDERS_PER_MINUTE;
+use super::RL_WEIGHT_PER_MINUTE;
+use super::TimeInForce;
+use super::prelude::*;
+
+pub const FAPI_V1_ORDER: &str = "/fapi/v1/order";
+pub const FAPI_V2_POSITION_RISK: &str = "/fapi/v2/positionRisk";
+pub const FAPI_V3_POSITION_RISK: &str = "/fapi/v3/positionRisk";
+pub const FAPI_V1_LEVERAGE: &str = "/fapi/v1/leverage";
+pub const FAPI_V1_MARGIN_TYPE: &str = "/fapi/v1/marginType";
+pub const FAPI_V1_INCOME: &str = "/fapi/v1/income";
+
+/// Max rows of a single [`UmApi::income_history`] request.
+pub const INCOME_HISTORY_LIMIT: u16 = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OrderSide {
+    #[serde(rename = "BUY")]
+    Buy,
+    #[serde(rename = "SELL")]
+    Sell,
+}
+
+/// `Both` in One-way Mode; `Long` or `Short` in Hedge Mode.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PositionSide {
+    #[serde(rename = "BOTH")]
+    Both,
+    #[serde(rename = "LONG")]
+    Long,
+    #[serde(rename = "SHORT")]
+    Short,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OrderStatus {
+    #[serde(rename = "NEW")]
+    New,
+    #[serde(rename = "PARTIALLY_FILLED")]
+    PartiallyFilled,
+    #[serde(rename = "FILLED")]
+    Filled,
+    #[serde(rename = "CANCELED")]
+    Canceled,
+    #[serde(rename = "REJECTED")]
+    Rejected,
+    #[serde(rename = "EXPIRED")]
+    Expired,
+    /// The order was expired by the self-trade prevention.
+    #[serde(rename = "EXPIRED_IN_MATCH")]
+    ExpiredInMatch,
+}
+
+impl OrderStatus {
+    /// The order can not be filled any further.
+    pub fn is_finished(self) -> bool {
+        !matches!(self, OrderStatus::New | OrderStatus::PartiallyFilled)
+    }
+}
+
+/// New order parameters, see [`UmApi::new_order`].
+#[derive(Debug, Clone)]
+pub struct NewOrderRequest {
+    pub symbol: Atom,
+    pub side: OrderSide,
+    /// Must be sent in Hedge Mode.
+    pub position_side: Option<PositionSide>,
+    pub r#type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Decimal>,
+    pub price: Option<Decimal>,
+    /// Can not be sent in Hedge Mode.
+    pub reduce_only: Option<bool>,
+    pub stop_price: Option<Decimal>,
+    pub new_client_order_id: Option<String>,
+}
+
+impl NewOrderRequest {
+    pub fn new(symbol: impl Into<Atom>, side: OrderSide, r#type: OrderType) -> Self {
+        NewOrderRequest {
+            symbol: symbol.into(),
+            side,
+            position_side: None,
+            r#type,
+            time_in_force: None,
+            quantity: None,
+            price: None,
+            reduce_only: None,
+            stop_price: None,
+            new_client_order_id: None,
+        }
+    }
+
+    pub fn position_side(mut self, position_side: PositionSide) -> Self {
+        self.position_side = Some(position_side);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn quantity(mut self, quantity: Decimal) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = Some(reduce_only);
+        self
+    }
+
+    pub fn stop_price(mut self, stop_price: Decimal) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn new_client_order_id(mut self, new_client_order_id: impl Into<String>) -> Self {
+        self.new_client_order_id = Some(new_client_order_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UmOrder {
+    pub symbol: Atom,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub status: OrderStatus,
+    pub side: OrderSide,
+    pub position_side: PositionSide,
+    pub r#type: OrderType,
+    pub orig_type: OrderType,
+    pub time_in_force: TimeInForce,
+    pub price: Decimal,
+    pub avg_price: Decimal,
+    pub stop_price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cum_quote: Decimal,
+    pub reduce_only: bool,
+    pub close_position: bool,
+    /// Only returned when the order is queried.
+    #[serde(default)]
+    pub time: Option<u64>,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRisk {
+    pub symbol: Atom,
+    pub position_side: PositionSide,
+    /// Negative for a short position in One-way Mode.
+    pub position_amt: Decimal,
+    pub entry_price: Decimal,
+    pub break_even_price: Decimal,
+    pub mark_price: Decimal,
+    pub un_realized_profit: Decimal,
+    pub liquidation_price: Decimal,
+    pub leverage: Decimal,
+    pub max_notional_value: Decimal,
+    pub notional: Decimal,
+    pub margin_type: MarginType,
+    pub isolated_margin: Decimal,
+    pub isolated_wallet: Decimal,
+    pub update_time: u64,
+}
+
+/// Position entry of [`UmApi::position_risk_v3`].
+///
+/// Unlike V2, only symbols with a position or open orders are returned, and leverage
+/// and margin type are not included.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRiskV3 {
+    pub symbol: Atom,
+    pub position_side: PositionSide,
+    /// Negative for a short position in One-way Mode.
+    pub position_amt: Decimal,
+    pub entry_price: Decimal,
+    pub break_even_price: Decimal,
+    pub mark_price: Decimal,
+    pub un_realized_profit: Decimal,
+    /// Zero when the position can not be liquidated.
+    pub liquidation_price: Decimal,
+    pub isolated_margin: Decimal,
+    pub notional: Decimal,
+    pub margin_asset: Atom,
+    pub isolated_wallet: Decimal,
+    pub initial_margin: Decimal,
+    pub maint_margin: Decimal,
+    pub position_initial_margin: Decimal,
+    pub open_order_initial_margin: Decimal,
+    /// Auto-deleveraging queue indicator, 0 to 4.
+    pub adl: u8,
+    pub bid_notional: Decimal,
+    pub ask_notional: Decimal,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MarginType {
+    #[serde(rename = "cross")]
+    Cross,
+    #[serde(rename = "isolated")]
+    Isolated,
+}
+
+impl MarginType {
+    /// Value of the `marginType` argument, which differs from the one in responses.
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            MarginType::Cross => "CROSSED",
+            MarginType::Isolated => "ISOLATED",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Leverage {
+    pub symbol: Atom,
+    pub leverage: u8,
+    /// Max notional value of a position at this leverage.
+    pub max_notional_value: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeMarginType {
+    pub code: i32,
+    pub msg: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum IncomeType {
+    #[serde(rename = "TRANSFER")]
+    Transfer,
+    #[serde(rename = "WELCOME_BONUS")]
+    WelcomeBonus,
+    #[serde(rename = "REALIZED_PNL")]
+    RealizedPnl,
+    #[serde(rename = "FUNDING_FEE")]
+    FundingFee,
+    #[serde(rename = "COMMISSION")]
+    Commission,
+    #[serde(rename = "INSURANCE_CLEAR")]
+    InsuranceClear,
+    #[serde(rename = "REFERRAL_KICKBACK")]
+    ReferralKickback,
+    #[serde(rename = "COMMISSION_REBATE")]
+    CommissionRebate,
+    #[serde(rename = "API_REBATE")]
+    ApiRebate,
+    #[serde(rename = "CONTEST_REWARD")]
+    ContestReward,
+    #[serde(rename = "CROSS_COLLATERAL_TRANSFER")]
+    CrossCollateralTransfer,
+    #[serde(rename = "OPTIONS_PREMIUM_FEE")]
+    OptionsPremiumFee,
+    #[serde(rename = "OPTIONS_SETTLE_PROFIT")]
+    OptionsSettleProfit,
+    #[serde(rename = "INTERNAL_TRANSFER")]
+    InternalTransfer,
+    #[serde(rename = "AUTO_EXCHANGE")]
+    AutoExchange,
+    /// Spelled this way by the api.
+    #[serde(rename = "DELIVERED_SETTELMENT")]
+    DeliveredSettlement,
+    #[serde(rename = "COIN_SWAP_DEPOSIT")]
+    CoinSwapDeposit,
+    #[serde(rename = "COIN_SWAP_WITHDRAW")]
+    CoinSwapWithdraw,
+    #[serde(rename = "POSITION_LIMIT_INCREASE_FEE")]
+    PositionLimitIncreaseFee,
+    /// An income type this version does not know about. Not a valid filter.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    /// Empty for incomes not related to a symbol, e.g. transfers.
+    pub symbol: Atom,
+    pub income_type: IncomeType,
+    /// Negative for losses and fees.
+    pub income: Decimal,
+    pub asset: Atom,
+    pub info: String,
+    pub time: u64,
+    pub tran_id: u64,
+    /// Empty unless the income comes from a trade.
+    pub trade_id: String,
+}
+
+/// Appends a page of [`UmApi::income_history`] to `rows`, skipping the rows already taken
+/// from the previous page.
+///
+/// Returns the start time of the next page if `page` has `limit` rows. Pages overlap by a
+/// millisecond, as rows sharing the time of the last one might not fit into the page.
+fn append_income_page(
+    rows: &mut Vec<Income>,
+    page: Vec<Income>,
+    limit: usize,
+) -> BinanceResult<Option<u64>> {
+    let next_start_time = match (page.first(), page.last()) {
+        (Some(first), Some(last)) if page.len() >= limit => {
+            if first.time == last.time {
+                Err(LibError::other(format!(
+                    "more than {limit} incomes at {}, can not page through them",
+                    first.time
+                )))?
+            }
+            Some(last.time)
+        }
+        _ => None,
+    };
+    let taken = rows.len();
+    for row in page {
+        let is_taken = rows[..taken]
+            .iter()
+            .rev()
+            .take_while(|r| r.time == row.time)
+            .any(|r| *r == row);
+        if !is_taken {
+            rows.push(row);
+        }
+    }
+    Ok(next_start_time)
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> UmApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// New Order (TRADE)
+        ///
+        /// Send in a new order.
+        ///
+        /// Weight: 0 on IP; 1 on the order rate limit
+        pub fn new_order(
+            &self,
+            order: &NewOrderRequest,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<UmOrder>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(FAPI_V1_ORDER)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &order.symbol)?
+                        .query_arg("side", &order.side)?
+                        .try_query_arg("positionSide", &order.position_side)?
+                        .query_arg("type", &order.r#type)?
+                        .try_query_arg("timeInForce", &order.time_in_force)?
+                        .try_decimal_arg("quantity", &order.quantity)?
+                        .try_decimal_arg("price", &order.price)?
+                        .try_query_arg("reduceOnly", &order.reduce_only)?
+                        .try_decimal_arg("stopPrice", &order.stop_price)?
+                        .try_query_arg("newClientOrderId", &order.new_client_order_id)?
+                        .query_arg("newOrderRespType", "RESULT")?,
+                )
+                .cost(RL_ORDERS_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Cancel Order (TRADE)
+        ///
+        /// Either `order_id` or `orig_client_order_id` must be sent.
+        ///
+        /// Weight: 1
+        pub fn cancel_order(
+            &self,
+            symbol: impl Serialize,
+            order_id: Option<u64>,
+            orig_client_order_id: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<UmOrder>> {
+            if order_id.is_none() && orig_client_order_id.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "order_id or orig_client_order_id",
+                ))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .delete(FAPI_V1_ORDER)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .try_query_arg("orderId", &order_id)?
+                        .try_query_arg("origClientOrderId", &orig_client_order_id)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Query Order (USER_DATA)
+        ///
+        /// Either `order_id` or `orig_client_order_id` must be sent.
+        ///
+        /// Weight: 1
+        pub fn query_order(
+            &self,
+            symbol: impl Serialize,
+            order_id: Option<u64>,
+            orig_client_order_id: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<UmOrder>> {
+            if order_id.is_none() && orig_client_order_id.is_none() {
+                Err(ApiError::mandatory_field_omitted(
+                    "order_id or orig_client_order_id",
+                ))?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V1_ORDER)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .try_query_arg("orderId", &order_id)?
+                        .try_query_arg("origClientOrderId", &orig_client_order_id)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Position Information V2 (USER_DATA)
+        ///
+        /// Positions of all symbols if `symbol` is not sent.
+        ///
+        /// Weight: 5
+        pub fn position_risk(
+            &self,
+            symbol: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<PositionRisk>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V2_POSITION_RISK)?
+                        .signed(time_window)?
+                        .try_query_arg("symbol", &symbol)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 5)
+                .send())
+        }
+
+        /// Position Information V3 (USER_DATA)
+        ///
+        /// Positions of all symbols if `symbol` is not sent.
+        ///
+        /// Weight: 5
+        pub fn position_risk_v3(
+            &self,
+            symbol: Option<impl Serialize>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<PositionRiskV3>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V3_POSITION_RISK)?
+                        .signed(time_window)?
+                        .try_query_arg("symbol", &symbol)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 5)
+                .send())
+        }
+
+        /// Change Initial Leverage (TRADE)
+        ///
+        /// Weight: 1
+        ///
+        /// * leverage - From 1 to 125, the max depends on the symbol.
+        pub fn change_leverage(
+            &self,
+            symbol: impl Serialize,
+            leverage: u8,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Leverage>> {
+            if !(1..=125).contains(&leverage) {
+                Err(ApiError::OutOfBounds)?
+            }
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(FAPI_V1_LEVERAGE)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .query_arg("leverage", &leverage)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Change Margin Type (TRADE)
+        ///
+        /// Weight: 1
+        ///
+        /// * Fails with code -4046 if the margin type is already set.
+        /// * Fails while the symbol has a position or open orders.
+        pub fn change_margin_type(
+            &self,
+            symbol: impl Serialize,
+            margin_type: MarginType,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<ChangeMarginType>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .post(FAPI_V1_MARGIN_TYPE)?
+                        .signed(time_window)?
+                        .query_arg("symbol", &symbol)?
+                        .query_arg("marginType", margin_type.as_arg())?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Get Income History (USER_DATA)
+        ///
+        /// Weight: 30
+        ///
+        /// * If start_time and end_time are not sent, the recent 7 days are returned.
+        /// * Only the recent 3 months are available.
+        /// * limit - Default: 100 Max: 1000.
+        /// * Rows are in ascending order of time.
+        pub fn income_history(
+            &self,
+            symbol: Option<impl Serialize>,
+            income_type: Option<IncomeType>,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            limit: Option<u16>,
+            time_window: impl Into<TimeWindow>,
+        ) -> BinanceResult<Task<Vec<Income>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V1_INCOME)?
+                        .signed(time_window)?
+                        .try_query_arg("symbol", &symbol)?
+                        .try_query_arg("incomeType", &income_type)?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 30)
+                .send())
+        }
+
+        /// All incomes from `start_time` to `end_time`, fetched with as many
+        /// [`UmApi::income_history`] requests of [`INCOME_HISTORY_LIMIT`] rows as needed.
+        ///
+        /// Weight: 30 per request
+        pub async fn income_history_all(
+            &self,
+            symbol: Option<impl Serialize + Clone>,
+            income_type: Option<IncomeType>,
+            mut start_time: u64,
+            end_time: u64,
+            time_window: impl Into<TimeWindow> + Clone,
+        ) -> BinanceResult<Vec<Income>> {
+            let mut rows = Vec::new();
+            loop {
+                let page = self
+                    .income_history(
+                        symbol.clone(),
+                        income_type,
+                        Some(start_time),
+                        Some(end_time),
+                        Some(INCOME_HISTORY_LIMIT),
+                        time_window.clone(),
+                    )?
+                    .await?;
+                match append_income_page(&mut rows, page, INCOME_HISTORY_LIMIT.into())? {
+                    Some(next_start_time) => start_time = next_start_time,
+                    None => return Ok(rows),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_order() {
+        let json = r#"{
+            "clientOrderId": "testOrder", "cumQty": "0", "cumQuote": "0", "executedQty": "0",
+            "orderId": 22542179, "avgPrice": "0.00000", "origQty": "10", "price": "0",
+            "reduceOnly": false, "side": "BUY", "positionSide": "SHORT", "status": "NEW",
+            "stopPrice": "9300", "closePosition": false, "symbol": "BTCUSDT",
+            "timeInForce": "GTC", "type": "TRAILING_STOP_MARKET",
+            "origType": "TRAILING_STOP_MARKET", "activatePrice": "9020", "priceRate": "0.3",
+            "updateTime": 1566818724722, "workingType": "CONTRACT_PRICE", "priceProtect": false,
+            "priceMatch": "NONE", "selfTradePreventionMode": "NONE", "goodTillDate": 0
+        }"#;
+        let order: UmOrder = serde_json::from_str(json).unwrap();
+        assert_eq!(order.order_id, 22542179);
+        assert_eq!(order.position_side, PositionSide::Short);
+        assert_eq!(order.r#type, OrderType::TrailingStopMarket);
+        assert_eq!(order.stop_price, dec!(9300));
+        assert_eq!(order.time, None);
+        assert!(!order.status.is_finished());
+    }
+
+    #[test]
+    fn test_deserialize_position_risk() {
+        let json = r#"[{
+            "entryPrice": "6563.66500", "breakEvenPrice": "6570.228665", "marginType": "isolated",
+            "isAutoAddMargin": "false", "isolatedMargin": "15517.54150468", "leverage": "10",
+            "liquidationPrice": "5930.78", "markPrice": "6679.50671178",
+            "maxNotionalValue": "20000000", "positionAmt": "-20.000", "notional": "-133590.13",
+            "isolatedWallet": "17836.49", "symbol": "BTCUSDT",
+            "unRealizedProfit": "-2317.05", "positionSide": "BOTH", "updateTime": 1625474304765
+        }]"#;
+        let positions: Vec<PositionRisk> = serde_json::from_str(json).unwrap();
+        assert_eq!(positions[0].position_amt, dec!(-20));
+        assert_eq!(positions[0].margin_type, MarginType::Isolated);
+        assert_eq!(positions[0].un_realized_profit, dec!(-2317.05));
+        assert_eq!(positions[0].break_even_price, dec!(6570.228665));
+    }
+
+    #[test]
+    fn test_deserialize_position_risk_v3() {
+        let json = r#"[{
+            "symbol": "ADAUSDT", "positionSide": "LONG", "positionAmt": "30",
+            "entryPrice": "0.385", "breakEvenPrice": "0.385077", "markPrice": "0.41047590",
+            "unRealizedProfit": "0.76427700", "liquidationPrice": "0",
+            "isolatedMargin": "0", "notional": "12.31427700", "marginAsset": "USDT",
+            "isolatedWallet": "0", "initialMargin": "0.61571385", "maintMargin": "0.08004280",
+            "positionInitialMargin": "0.61571385", "openOrderInitialMargin": "0",
+            "adl": 2, "bidNotional": "0", "askNotional": "0", "updateTime": 1720736417660
+        }]"#;
+        let positions: Vec<PositionRiskV3> = serde_json::from_str(json).unwrap();
+        assert_eq!(positions[0].position_side, PositionSide::Long);
+        assert_eq!(positions[0].position_amt, dec!(30));
+        assert_eq!(positions[0].break_even_price, dec!(0.385077));
+        assert!(positions[0].liquidation_price.is_zero());
+        assert_eq!(positions[0].adl, 2);
+    }
+
+    #[test]
+    fn test_deserialize_leverage() {
+        let json = r#"{"leverage": 21, "maxNotionalValue": "1000000", "symbol": "BTCUSDT"}"#;
+        let leverage: Leverage = serde_json::from_str(json).unwrap();
+        assert_eq!(leverage.leverage, 21);
+        assert_eq!(leverage.max_notional_value, dec!(1000000));
+        assert_eq!(MarginType::Cross.as_arg(), "CROSSED");
+    }
+
+    fn income(time: u64, tran_id: u64) -> Income {
+        Income {
+            symbol: "BTCUSDT".into(),
+            income_type: IncomeType::Commission,
+            income: dec!(-0.01),
+            asset: "USDT".into(),
+            info: String::new(),
+            time,
+            tran_id,
+            trade_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_income() {
+        let json = r#"[{
+            "symbol": "", "incomeType": "TRANSFER", "income": "-0.37500000", "asset": "USDT",
+            "info": "TRANSFER", "time": 1570608000000, "tranId": 9689322392, "tradeId": ""
+        }, {
+            "symbol": "BTCUSDT", "incomeType": "STRATEGY_UMFUTURES_TRANSFER", "income": "1",
+            "asset": "USDT", "info": "", "time": 1570636800000, "tranId": 9689322393,
+            "tradeId": ""
+        }]"#;
+        let incomes: Vec<Income> = serde_json::from_str(json).unwrap();
+        assert_eq!(incomes[0].income_type, IncomeType::Transfer);
+        assert_eq!(incomes[0].income, dec!(-0.375));
+        assert_eq!(incomes[0].tran_id, 9689322392);
+        assert_eq!(incomes[1].income_type, IncomeType::Unknown);
+        assert_eq!(
+            serde_json::to_string(&IncomeType::DeliveredSettlement).unwrap(),
+            r#""DELIVERED_SETTELMENT""#
+        );
+    }
+
+    #[test]
+    fn test_append_income_page() {
+        let mut rows = vec![];
+        let page = vec![income(1, 1), income(2, 2), income(2, 3)];
+        assert_eq!(append_income_page(&mut rows, page, 3).unwrap(), Some(2));
+
+        // The next page starts at the time of the last row and repeats the rows at it.
+        let page = vec![income(2, 2), income(2, 3), income(2, 4), income(3, 5)];
+        assert_eq!(append_income_page(&mut rows, page, 5).unwrap(), None);
+        let ids: Vec<u64> = rows.iter().map(|r| r.tran_id).collect();
+        assert_eq!(ids, [1, 2, 3, 4, 5]);
+
+        let page = vec![income(4, 6), income(4, 7)];
+        assert!(append_income_page(&mut rows, page, 2).is_err());
+    }
+}
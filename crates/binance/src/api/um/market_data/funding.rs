@@ -0,0 +1,181 @@
+use ccx_api_lib::serde_util::none_as_empty_str;
+
+use super::super::RL_FUNDING_RATE_PER_5_MINUTES;
+use super::super::RL_WEIGHT_PER_MINUTE;
+use super::prelude::*;
+
+pub const FAPI_V1_PREMIUM_INDEX: &str = "/fapi/v1/premiumIndex";
+pub const FAPI_V1_FUNDING_RATE: &str = "/fapi/v1/fundingRate";
+pub const FAPI_V1_FUNDING_INFO: &str = "/fapi/v1/fundingInfo";
+
+/// Mark price and funding rate of a symbol.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct PremiumIndex {
+    pub symbol: Atom,
+    pub mark_price: Decimal,
+    pub index_price: Decimal,
+    /// Only meaningful in the last hour before the settlement.
+    pub estimated_settle_price: Decimal,
+    pub last_funding_rate: Decimal,
+    pub interest_rate: Decimal,
+    pub next_funding_time: u64,
+    pub time: u64,
+}
+
+impl PremiumIndex {
+    /// Mark price less index price.
+    pub fn basis(&self) -> Decimal {
+        self.mark_price - self.index_price
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub symbol: Atom,
+    pub funding_rate: Decimal,
+    pub funding_time: u64,
+    /// Sent empty for old records.
+    #[serde(default, with = "none_as_empty_str")]
+    pub mark_price: Option<Decimal>,
+}
+
+/// Funding parameters of a symbol which differ from the defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingInfo {
+    pub symbol: Atom,
+    pub adjusted_funding_rate_cap: Decimal,
+    pub adjusted_funding_rate_floor: Decimal,
+    pub funding_interval_hours: u32,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::client::Task;
+
+    impl<S> UmApi<S>
+    where
+        S: crate::client::BinanceSigner,
+        S: Unpin + 'static,
+    {
+        /// Mark Price and Funding Rate of a symbol.
+        ///
+        /// Weight: 1
+        pub fn premium_index(&self, symbol: impl AsRef<str>) -> BinanceResult<Task<PremiumIndex>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V1_PREMIUM_INDEX)?
+                        .query_arg("symbol", symbol.as_ref())?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Mark Price and Funding Rate of all symbols.
+        ///
+        /// Weight: 1
+        pub fn premium_index_all(&self) -> BinanceResult<Task<Vec<PremiumIndex>>> {
+            Ok(self
+                .rate_limiter
+                .task(self.client.get(FAPI_V1_PREMIUM_INDEX)?)
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .send())
+        }
+
+        /// Get Funding Rate History.
+        ///
+        /// Weight: 1, and 500 per 5 minutes shared with [`UmApi::funding_info`].
+        ///
+        /// * If start_time and end_time are not sent, the most recent records are returned.
+        /// * limit - Default: 100 Max: 1000.
+        /// * Records are in ascending order.
+        pub fn funding_rate(
+            &self,
+            symbol: Option<impl AsRef<str>>,
+            start_time: Option<u64>,
+            end_time: Option<u64>,
+            limit: Option<u16>,
+        ) -> BinanceResult<Task<Vec<FundingRate>>> {
+            Ok(self
+                .rate_limiter
+                .task(
+                    self.client
+                        .get(FAPI_V1_FUNDING_RATE)?
+                        .try_query_arg("symbol", &symbol.as_ref().map(|s| s.as_ref()))?
+                        .try_query_arg("startTime", &start_time)?
+                        .try_query_arg("endTime", &end_time)?
+                        .try_query_arg("limit", &limit)?,
+                )
+                .cost(RL_WEIGHT_PER_MINUTE, 1)
+                .cost(RL_FUNDING_RATE_PER_5_MINUTES, 1)
+                .send())
+        }
+
+        /// Get Funding Rate Info of the symbols with adjusted funding rate cap, floor or
+        /// interval.
+        ///
+        /// Weight: 0, and 500 per 5 minutes shared with [`UmApi::funding_rate`].
+        pub fn funding_info(&self) -> BinanceResult<Task<Vec<FundingInfo>>> {
+            Ok(self
+                .rate_limiter
+                .task(self.client.get(FAPI_V1_FUNDING_INFO)?)
+                .cost(RL_FUNDING_RATE_PER_5_MINUTES, 1)
+                .send())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_premium_index() {
+        let json = r#"{
+            "symbol": "BTCUSDT",
+            "markPrice": "11793.63104562",
+            "indexPrice": "11781.80495970",
+            "estimatedSettlePrice": "11781.16138815",
+            "lastFundingRate": "0.00038246",
+            "interestRate": "0.00010000",
+            "nextFundingTime": 1597392000000,
+            "time": 1597370495002
+        }"#;
+        let index: PremiumIndex = serde_json::from_str(json).unwrap();
+        assert_eq!(index.last_funding_rate, dec!(0.00038246));
+        assert_eq!(index.next_funding_time, 1597392000000);
+        assert_eq!(index.basis(), dec!(11.82608592));
+    }
+
+    #[test]
+    fn test_deserialize_funding() {
+        let json = r#"[
+            {"symbol": "BTCUSDT", "fundingRate": "-0.03750000", "fundingTime": 1570608000000, "markPrice": "34287.54619963"},
+            {"symbol": "BTCUSDT", "fundingRate": "0.00010000", "fundingTime": 1570636800000, "markPrice": ""}
+        ]"#;
+        let rates: Vec<FundingRate> = serde_json::from_str(json).unwrap();
+        assert_eq!(rates[0].funding_rate, dec!(-0.0375));
+        assert_eq!(rates[0].mark_price, Some(dec!(34287.54619963)));
+        assert_eq!(rates[1].mark_price, None);
+
+        let json = r#"[{
+            "symbol": "BLZUSDT",
+            "adjustedFundingRateCap": "0.02500000",
+            "adjustedFundingRateFloor": "-0.02500000",
+            "fundingIntervalHours": 8,
+            "disclaimer": false
+        }]"#;
+        let info: Vec<FundingInfo> = serde_json::from_str(json).unwrap();
+        assert_eq!(info[0].funding_interval_hours, 8);
+    }
+}
@@ -1,7 +1,9 @@
+mod funding;
 mod info;
 mod ping;
 mod time;
 
+pub use self::funding::*;
 pub use self::info::*;
 pub use self::ping::*;
 pub use self::time::*;
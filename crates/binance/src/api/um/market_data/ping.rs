@@ -1,6 +1,5 @@
 use super::super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const FAPI_V1_PING: &str = "/fapi/v1/ping";
 
@@ -13,6 +12,7 @@ pub use with_network::*;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> UmApi<S>
     where
@@ -1,6 +1,5 @@
 use super::super::RL_WEIGHT_PER_MINUTE;
 use super::prelude::*;
-use crate::client::Task;
 
 pub const FAPI_V1_TIME: &str = "/fapi/v1/time";
 
@@ -16,6 +15,7 @@ pub use with_network::*;
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::Task;
 
     impl<S> UmApi<S>
     where
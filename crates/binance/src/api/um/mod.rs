@@ -1,29 +1,14 @@
-use std::time::Duration;
-
-use ccx_api_lib::env_var_with_prefix;
-use url::Url;
-
-use crate::client::ApiCred;
-use crate::client::BinanceSigner;
-use crate::client::CCX_BINANCE_API_PREFIX;
-use crate::client::Config;
-use crate::client::Proxy;
-use crate::client::RateLimiter;
-use crate::client::RateLimiterBucket;
-use crate::client::RateLimiterBuilder;
-use crate::client::RestClient;
-use crate::client::WebsocketStream;
 use crate::error::*;
 
+mod account;
 mod market_data;
 // TODO mod websocket_market;
-// TODO mod account;
 // TODO mod user_data_stream;
 // TODO mod error;
 
+pub use self::account::*;
 pub use self::market_data::*;
 // pub use self::websocket_market::*;
-// pub use self::account::*;
 // pub use self::user_data_stream::*;
 
 mod prelude {
@@ -39,13 +24,32 @@ pub const API_BASE_TESTNET: &str = "https://testnet.binancefuture.com/";
 pub const STREAM_BASE_TESTNET: &str = "wss://stream.binancefuture.com/stream";
 
 pub const RL_WEIGHT_PER_MINUTE: &str = "weight_per_minute";
+pub const RL_ORDERS_PER_MINUTE: &str = "orders_per_minute";
+/// Shared by `/fapi/v1/fundingRate` and `/fapi/v1/fundingInfo`.
+pub const RL_FUNDING_RATE_PER_5_MINUTES: &str = "funding_rate_per_5_minutes";
 
 #[cfg(feature = "with_network")]
 pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use std::time::Duration;
+
+    use ccx_api_lib::env_var_with_prefix;
+    use url::Url;
+
     use super::*;
+    use crate::client::ApiCred;
+    use crate::client::BinanceSigner;
+    use crate::client::CCX_BINANCE_API_PREFIX;
+    use crate::client::Config;
+    use crate::client::Proxy;
+    use crate::client::RateLimiter;
+    use crate::client::RateLimiterBucket;
+    use crate::client::RateLimiterBuilder;
+    use crate::client::RestClient;
+    use crate::client::TaskCosts;
+    use crate::client::WebsocketStream;
 
     #[derive(Clone)]
     pub struct UmApi<S>
@@ -77,6 +81,10 @@ mod with_network {
 
         /// Reads config from env vars with names like:
         /// "CCX_BINANCE_API_KEY", "CCX_BINANCE_API_SECRET", and "CCX_BINANCE_API_TESTNET"
+        ///
+        /// Requests are routed through a SOCKS5 proxy when "CCX_BINANCE_API_PROXY_HOST" and
+        /// "CCX_BINANCE_API_PROXY_PORT" are set ("CCX_BINANCE_API_PROXY_USERNAME" and
+        /// "CCX_BINANCE_API_PROXY_PASSWORD" are optional).
         pub fn from_env() -> UmApi<ApiCred> {
             let testnet = Config::<S>::env_var("TESTNET").as_deref() == Some("1");
             let proxy = Proxy::from_env_with_prefix(CCX_BINANCE_API_PREFIX);
@@ -88,7 +96,7 @@ mod with_network {
         }
 
         /// Reads config from env vars with names like:
-        /// "${prefix}_KEY", "${prefix}_SECRET", and "${prefix}_TESTNET"
+        /// "${prefix}_KEY", "${prefix}_SECRET", "${prefix}_TESTNET", and "${prefix}_PROXY_*"
         pub fn from_env_with_prefix(prefix: &str) -> UmApi<ApiCred> {
             let testnet = env_var_with_prefix(prefix, "TESTNET").as_deref() == Some("1");
             let proxy = Proxy::from_env_with_prefix(prefix);
@@ -96,7 +104,6 @@ mod with_network {
         }
 
         pub fn with_config(config: Config<S>) -> Self {
-            let client = RestClient::new(config);
             let rate_limiter = RateLimiterBuilder::default()
                 .bucket(
                     RL_WEIGHT_PER_MINUTE,
@@ -104,15 +111,40 @@ mod with_network {
                         .interval(Duration::from_secs(60))
                         .limit(1_200),
                 )
+                .bucket(
+                    RL_ORDERS_PER_MINUTE,
+                    RateLimiterBucket::default()
+                        .interval(Duration::from_secs(60))
+                        .limit(1_200),
+                )
+                .bucket(
+                    RL_FUNDING_RATE_PER_5_MINUTES,
+                    RateLimiterBucket::default()
+                        .interval(Duration::from_secs(300))
+                        .limit(500),
+                )
                 .start();
+            let client = RestClient::with_rate_limiter(
+                config.with_time_endpoint(FAPI_V1_TIME),
+                rate_limiter.clone(),
+                TaskCosts::from([(RL_WEIGHT_PER_MINUTE.into(), 1)]),
+            );
             UmApi {
                 client,
                 rate_limiter,
             }
         }
 
+        /// Rate limiter shared by all clones of this api.
+        pub fn rate_limiter(&self) -> &RateLimiter {
+            &self.rate_limiter
+        }
+
         /// Creates multiplexed websocket stream.
-        pub async fn ws(&self) -> BinanceResult<WebsocketStream> {
+        pub async fn ws(&self) -> BinanceResult<WebsocketStream>
+        where
+            S: 'static,
+        {
             self.client.web_socket().await
         }
     }
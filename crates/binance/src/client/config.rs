@@ -1,9 +1,12 @@
 pub use ccx_api_lib::ApiCred;
 pub use ccx_api_lib::Proxy;
+use std::time::Duration;
+
 use ccx_api_lib::env_var_with_prefix;
 use url::Url;
 
 use crate::client::BinanceSigner;
+use crate::proto::RecvWindow;
 
 pub static CCX_BINANCE_API_PREFIX: &str = "CCX_BINANCE_API";
 
@@ -13,7 +16,15 @@ pub struct Config<S: BinanceSigner> {
     pub signer: S,
     pub api_base: Url,
     pub stream_base: Url,
+    /// Base URL of the websocket API, e.g. `wss://ws-api.binance.com:443/ws-api/v3`.
+    pub ws_api_base: Option<Url>,
     pub proxy: Option<Proxy>,
+    /// `recvWindow` of signed requests whose [`crate::TimeWindow`] does not set one.
+    pub recv_window: RecvWindow,
+    /// Interval of the automatic server time sync, see [`Config::with_time_sync`].
+    pub time_sync_interval: Option<Duration>,
+    /// Server time endpoint, `/api/v3/time` by default.
+    pub time_endpoint: &'static str,
 }
 
 impl<S> Config<S>
@@ -25,10 +36,58 @@ where
             signer,
             api_base,
             stream_base,
+            ws_api_base: None,
             proxy,
+            recv_window: RecvWindow::DEFAULT,
+            time_sync_interval: None,
+            time_endpoint: "/api/v3/time",
         }
     }
 
+    /// Overrides the REST base URL, e.g. `https://testnet.binance.vision/`.
+    pub fn with_api_base(mut self, api_base: Url) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
+    /// Overrides the websocket base URL independently of the REST one.
+    pub fn with_stream_base(mut self, stream_base: Url) -> Self {
+        self.stream_base = stream_base;
+        self
+    }
+
+    /// Sets the websocket API base URL.
+    pub fn with_ws_api_base(mut self, ws_api_base: Url) -> Self {
+        self.ws_api_base = Some(ws_api_base);
+        self
+    }
+
+    /// Sets the `recvWindow` of signed requests.
+    ///
+    /// A window set on the [`crate::TimeWindow`] of a request takes precedence.
+    /// See [`RecvWindow::from_duration`] to make one from a `Duration`.
+    pub fn with_recv_window(mut self, recv_window: RecvWindow) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// Enables the automatic server time sync.
+    ///
+    /// The offset between the server and the local clock is measured before the first
+    /// signed request and then again once `interval` has passed since the last sync.
+    /// It is added to the `timestamp` of signed requests, see
+    /// [`crate::client::RestClient::resync`].
+    pub fn with_time_sync(mut self, interval: Duration) -> Self {
+        self.time_sync_interval = Some(interval);
+        self
+    }
+
+    /// Sets the server time endpoint used by the time sync, e.g. `/fapi/v1/time`.
+    pub fn with_time_endpoint(mut self, time_endpoint: &'static str) -> Self {
+        self.time_endpoint = time_endpoint;
+        self
+    }
+
     pub fn env_var(postfix: &str) -> Option<String> {
         env_var_with_prefix(CCX_BINANCE_API_PREFIX, postfix)
     }
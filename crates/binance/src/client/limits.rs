@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::Duration;
 
 use actix_http::header::HeaderMap;
@@ -129,3 +130,52 @@ impl UsedRateLimits {
         u
     }
 }
+
+/// Numeric `X-MBX-*` headers by lowercase name, e.g. `x-mbx-used-weight-1m` or
+/// `x-mbx-order-count-10s`.
+pub fn used_weights(headers: &HeaderMap) -> HashMap<String, u32> {
+    static MBX_PREFIX: &str = "x-mbx-";
+
+    headers
+        .iter()
+        .filter(|(name, _)| name.as_str().starts_with(MBX_PREFIX))
+        .filter_map(|(name, value)| {
+            let value = value.to_str().ok()?.trim().parse().ok()?;
+            Some((name.as_str().to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_http::header::HeaderName;
+    use actix_http::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_used_weights() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("X-MBX-USED-WEIGHT-1M", "42"),
+            ("x-mbx-order-count-10s", "3"),
+            ("x-mbx-uuid", "6dd1e7b3-2a8f-4c1d-9d1b-2a8f4c1d9d1b"),
+            ("content-length", "100"),
+        ] {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_static(value),
+            );
+        }
+
+        let weights = used_weights(&headers);
+        assert_eq!(weights.len(), 2);
+        assert_eq!(weights["x-mbx-used-weight-1m"], 42);
+        assert_eq!(weights["x-mbx-order-count-10s"], 3);
+
+        let limits = UsedRateLimits::from_headers(&headers);
+        assert_eq!(limits.weight_per_ip[0].0.interval, Duration::from_secs(60));
+        assert_eq!(limits.weight_per_ip[0].1, 42);
+        assert_eq!(limits.order_count_per_account[0].1, 3);
+    }
+}
@@ -4,6 +4,7 @@ mod rate_limiter;
 mod rest;
 mod signer;
 mod websocket;
+mod ws_api;
 use serde::Deserialize;
 
 pub use self::config::*;
@@ -12,10 +13,10 @@ pub use self::rate_limiter::*;
 pub use self::rest::*;
 pub use self::signer::*;
 pub use self::websocket::*;
+pub use self::ws_api::*;
 
 #[derive(Debug, Deserialize)]
 struct BinanceContentError {
-    #[allow(dead_code)]
     pub code: i16,
     #[allow(dead_code)]
     pub msg: String,
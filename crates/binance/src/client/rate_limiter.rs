@@ -5,24 +5,29 @@ use std::collections::hash_map::Entry;
 use std::fmt::Debug;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
 use actix::clock::sleep;
 use futures::channel::mpsc;
 use futures::channel::oneshot;
-use futures::lock::Mutex;
 use futures::prelude::*;
 use futures::task::Context;
 use futures::task::Poll;
+use tokio::sync::Notify;
 
 use super::BinanceSigner;
 use super::RequestBuilder;
+use super::UsedRateLimits;
 use crate::BinanceResult;
 use crate::LibError;
 
-type BucketName = Cow<'static, str>;
-type TaskCosts = HashMap<BucketName, u32>;
+pub type BucketName = Cow<'static, str>;
+pub type TaskCosts = HashMap<BucketName, u32>;
 type TaskMessageResult = BinanceResult<()>;
 
 struct TaskMessage {
@@ -58,6 +63,7 @@ impl RateLimiterBuilder {
             buckets: Arc::new(buckets),
             tasks_tx,
             queue: Arc::new(Mutex::new(Queue::new())),
+            state: Default::default(),
         };
         rate_limiter.recv(tasks_rx);
         rate_limiter
@@ -65,14 +71,42 @@ impl RateLimiterBuilder {
 }
 
 #[derive(Clone)]
-pub(crate) struct RateLimiter {
+pub struct RateLimiter {
     buckets: Arc<HashMap<BucketName, Mutex<RateLimiterBucket>>>,
     tasks_tx: mpsc::UnboundedSender<TaskMessage>,
     queue: Arc<Mutex<Queue>>,
+    state: Arc<LimiterState>,
+}
+
+/// Shutdown flag and the number of tasks that have not completed yet.
+#[derive(Default)]
+struct LimiterState {
+    closed: AtomicBool,
+    pending: AtomicUsize,
+    /// Notified when `pending` drops to zero.
+    drained: Notify,
+}
+
+/// Decrements the pending counter when a task completes or is dropped.
+struct PendingGuard(Arc<LimiterState>);
+
+impl PendingGuard {
+    fn new(state: Arc<LimiterState>) -> Self {
+        state.pending.fetch_add(1, Ordering::SeqCst);
+        PendingGuard(state)
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if self.0.pending.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.drained.notify_waiters();
+        }
+    }
 }
 
 impl RateLimiter {
-    pub fn task<S>(&self, builder: RequestBuilder<S>) -> TaskBuilder<S>
+    pub(crate) fn task<S>(&self, builder: RequestBuilder<S>) -> TaskBuilder<S>
     where
         S: BinanceSigner + Unpin,
     {
@@ -81,15 +115,72 @@ impl RateLimiter {
             costs: TaskCosts::new(),
             req_builder: builder,
             tasks_tx: self.tasks_tx.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Rejects tasks sent from now on. Tasks already waiting are still released.
+    pub fn close(&self) {
+        self.state.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.closed.load(Ordering::SeqCst)
+    }
+
+    /// Number of tasks created and not yet completed or dropped, including the requests
+    /// in flight.
+    pub fn pending(&self) -> usize {
+        self.state.pending.load(Ordering::SeqCst)
+    }
+
+    /// Waits until every task created so far has completed or been dropped.
+    pub async fn drain(&self) {
+        loop {
+            // Registered before the check so that the last completion is not missed.
+            let drained = self.state.drained.notified();
+            if self.pending() == 0 {
+                return;
+            }
+            drained.await;
         }
     }
 
+    /// How long a task with `costs` (see [`Task::metadata`]) would wait if it was sent now.
+    ///
+    /// Computed from the current state of the buckets without enqueuing anything,
+    /// tasks already waiting in the queue are not taken into account. Buckets unknown
+    /// to the limiter are ignored.
+    pub fn estimated_wait(&self, costs: &TaskCosts) -> Duration {
+        let now = Instant::now();
+        costs
+            .iter()
+            .filter_map(|(name, cost)| {
+                let bucket = lock(self.buckets.get(name)?);
+                Some(bucket.wait_time(*cost, now))
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Whether a task with `costs` would have to wait for a bucket if it was sent now,
+    /// see [`Self::estimated_wait`].
+    pub fn would_exceed(&self, costs: &TaskCosts) -> bool {
+        !self.estimated_wait(costs).is_zero()
+    }
+
+    /// Waits until `costs` fit into the buckets and charges them, for requests sent
+    /// without a [`Task`] such as the time sync of [`super::RestClient`].
+    pub(crate) async fn acquire(&self, priority: u8, costs: TaskCosts) -> BinanceResult<()> {
+        enqueue(&self.tasks_tx, priority, costs).await
+    }
+
     fn recv(&self, mut rx: mpsc::UnboundedReceiver<TaskMessage>) {
         let buckets = self.buckets.clone();
         let queue = self.queue.clone();
         actix_rt::spawn(async move {
             while let Some(task_message) = rx.next().await {
-                let is_first_task = queue.lock().await.add(task_message).is_first();
+                let is_first_task = lock(&queue).add(task_message).is_first();
                 if is_first_task {
                     Self::handler(buckets.clone(), queue.clone()).await;
                 }
@@ -107,7 +198,7 @@ impl RateLimiter {
                     priority,
                     costs,
                     tx,
-                } = match queue.lock().await.next() {
+                } = match lock(&queue).next() {
                     Some(task) => task,
                     None => {
                         log::debug!("RateLimiter: stop queue handler (queue is empty)");
@@ -118,11 +209,11 @@ impl RateLimiter {
 
                 let buckets = buckets.clone();
                 let res = async move {
-                    if let Some(dur) = Self::timeout(buckets.clone(), &costs).await? {
+                    if let Some(dur) = Self::timeout(&buckets, &costs)? {
                         log::debug!("RateLimiter: sleep for {:?}", dur);
                         sleep(dur).await;
                     }
-                    Self::set_costs(buckets, &costs).await?;
+                    Self::set_costs(&buckets, &costs)?;
                     Ok(())
                 }
                 .await;
@@ -133,15 +224,15 @@ impl RateLimiter {
         });
     }
 
-    async fn timeout(
-        buckets: Arc<HashMap<BucketName, Mutex<RateLimiterBucket>>>,
+    fn timeout(
+        buckets: &HashMap<BucketName, Mutex<RateLimiterBucket>>,
         costs: &TaskCosts,
     ) -> BinanceResult<Option<Duration>> {
         let mut timeout = Duration::default();
 
         for (name, cost) in costs {
             let mut bucket = match buckets.get(name) {
-                Some(bucket) => bucket.lock().await,
+                Some(bucket) => lock(bucket),
                 None => Err(LibError::other(format!(
                     "RateLimiter: undefined bucket {}",
                     name
@@ -183,13 +274,13 @@ impl RateLimiter {
         Ok((!timeout.is_zero()).then_some(timeout))
     }
 
-    async fn set_costs(
-        buckets: Arc<HashMap<BucketName, Mutex<RateLimiterBucket>>>,
+    fn set_costs(
+        buckets: &HashMap<BucketName, Mutex<RateLimiterBucket>>,
         costs: &TaskCosts,
     ) -> BinanceResult<()> {
         for (name, cost) in costs {
             let mut bucket = match buckets.get(name) {
-                Some(bucket) => bucket.lock().await,
+                Some(bucket) => lock(bucket),
                 None => Err(LibError::other(format!(
                     "RateLimiter: undefined bucket {}",
                     name
@@ -211,6 +302,28 @@ impl RateLimiter {
     }
 }
 
+/// Queues `costs` and waits until the queue handler has charged them.
+async fn enqueue(
+    tasks_tx: &mpsc::UnboundedSender<TaskMessage>,
+    priority: u8,
+    costs: TaskCosts,
+) -> BinanceResult<()> {
+    let (tx, rx) = oneshot::channel::<TaskMessageResult>();
+    tasks_tx
+        .unbounded_send(TaskMessage {
+            priority,
+            costs,
+            tx,
+        })
+        .map_err(|_| LibError::other("RateLimiter: task channel was dropped"))?;
+    rx.await
+        .map_err(|_| LibError::other("RateLimiter: task channel was dropped"))?
+        .map_err(|e| {
+            log::error!("RateLimiter: task err. {:?}", e);
+            e
+        })
+}
+
 pub(crate) struct RateLimiterBucket {
     time_instant: Instant,
     delay: Instant,
@@ -259,6 +372,29 @@ impl RateLimiterBucket {
         let elapsed = Instant::now().duration_since(self.time_instant);
         self.interval - elapsed
     }
+
+    /// Time until a task with `cost` fits into the bucket, without updating it.
+    fn wait_time(&self, cost: u32, now: Instant) -> Duration {
+        let delay = self.delay.saturating_duration_since(now);
+        if !delay.is_zero() {
+            return delay;
+        }
+        let elapsed = now.saturating_duration_since(self.time_instant);
+        let amount = if elapsed > self.interval {
+            0
+        } else {
+            self.amount
+        };
+        if amount + cost > self.limit {
+            self.interval.saturating_sub(elapsed)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 struct Queue {
@@ -306,6 +442,7 @@ where
     costs: TaskCosts,
     req_builder: RequestBuilder<S>,
     tasks_tx: mpsc::UnboundedSender<TaskMessage>,
+    state: Arc<LimiterState>,
 }
 
 impl<S> TaskBuilder<S>
@@ -332,26 +469,20 @@ where
         let priority = self.priority;
         let costs = self.costs.clone();
         let req_builder = self.req_builder;
-        let mut tasks_tx = self.tasks_tx.clone();
+        let tasks_tx = self.tasks_tx.clone();
+        let closed = self.state.closed.load(Ordering::SeqCst);
+        // Counted from creation, so that `drain` also waits for tasks not polled yet.
+        let pending = (!closed).then(|| PendingGuard::new(self.state));
 
         let fut = async move {
-            let (tx, rx) = oneshot::channel::<TaskMessageResult>();
-            tasks_tx
-                .send(TaskMessage {
-                    priority,
-                    costs,
-                    tx,
-                })
-                .await
-                .map_err(|_| LibError::other("RateLimiter: task channel was dropped"))?;
-            rx.await
-                .map_err(|_| LibError::other("RateLimiter: task channel was dropped"))?
-                .map_err(|e| {
-                    log::error!("RateLimiter: task err. {:?}", e);
-                    e
-                })?;
-
-            req_builder.send::<V>().await
+            let Some(pending) = pending else {
+                Err(LibError::other("RateLimiter: closed"))?
+            };
+            enqueue(&tasks_tx, priority, costs).await?;
+
+            let res = req_builder.send_with_headers::<V>().await;
+            drop(pending);
+            res
         };
 
         Task {
@@ -365,10 +496,21 @@ pub struct Task<V>
 where
     V: serde::de::DeserializeOwned + Debug,
 {
-    fut: Pin<Box<dyn Future<Output = BinanceResult<V>>>>,
+    fut: Pin<Box<dyn Future<Output = BinanceResult<TaskResult<V>>>>>,
     costs: TaskCosts,
 }
 
+/// Response of a [`Task`] together with the rate limit usage reported by Binance, see
+/// [`Task::send_with_headers`].
+#[derive(Debug)]
+pub struct TaskResult<V> {
+    pub value: V,
+    /// Numeric `X-MBX-*` headers by lowercase name, e.g. `x-mbx-used-weight-1m`.
+    pub used_weights: HashMap<String, u32>,
+    /// `used_weights` parsed into intervals.
+    pub used_rate_limits: UsedRateLimits,
+}
+
 impl<V> Task<V>
 where
     V: serde::de::DeserializeOwned + Debug,
@@ -378,6 +520,12 @@ where
             costs: self.costs.clone(),
         }
     }
+
+    /// Resolves to the response along with its `X-MBX-*` headers instead of the bare
+    /// response.
+    pub async fn send_with_headers(self) -> BinanceResult<TaskResult<V>> {
+        self.fut.await
+    }
 }
 
 impl<V> Future for Task<V>
@@ -387,7 +535,9 @@ where
     type Output = BinanceResult<V>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.fut.poll_unpin(cx)
+        self.fut
+            .poll_unpin(cx)
+            .map(|result| result.map(|result| result.value))
     }
 }
 
@@ -449,6 +599,101 @@ mod tests {
         assert!(instant.elapsed() >= Duration::from_secs(30));
     }
 
+    #[actix_rt::test]
+    async fn test_rate_limiter_close() {
+        let spot_api = SpotApi::new(
+            ApiCred {
+                key: "key".to_string(),
+                secret: "secret".to_string(),
+            },
+            true,
+            None,
+        );
+
+        let rate_limiter = RateLimiterBuilder::default()
+            .bucket(
+                "interval_1__limit_1",
+                RateLimiterBucket::default()
+                    .interval(Duration::from_secs(1))
+                    .limit(1),
+            )
+            .start();
+        assert!(!rate_limiter.is_closed());
+
+        rate_limiter.clone().close();
+        assert!(rate_limiter.is_closed());
+
+        let task_res = rate_limiter
+            .task(spot_api.client.get("/api/v3/time").unwrap())
+            .cost("interval_1__limit_1", 1)
+            .send::<ServerTime>()
+            .await;
+        assert!(task_res.is_err());
+        assert_eq!(rate_limiter.pending(), 0);
+        rate_limiter.drain().await;
+    }
+
+    #[actix_rt::test]
+    async fn test_rate_limiter_drain_counts_unpolled_tasks() {
+        let spot_api = SpotApi::new(
+            ApiCred {
+                key: "key".to_string(),
+                secret: "secret".to_string(),
+            },
+            true,
+            None,
+        );
+        let rate_limiter = RateLimiterBuilder::default().start();
+
+        let task = rate_limiter
+            .task(spot_api.client.get("/api/v3/time").unwrap())
+            .send::<ServerTime>();
+        assert_eq!(rate_limiter.pending(), 1);
+
+        let drain = actix_rt::spawn({
+            let rate_limiter = rate_limiter.clone();
+            async move { rate_limiter.drain().await }
+        });
+        actix_rt::time::sleep(Duration::from_millis(20)).await;
+        assert!(!drain.is_finished());
+
+        drop(task);
+        assert_eq!(rate_limiter.pending(), 0);
+        actix_rt::time::timeout(Duration::from_secs(1), drain)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn test_rate_limiter_estimated_wait() {
+        let rate_limiter = RateLimiterBuilder::default()
+            .bucket(
+                "interval_60__limit_10",
+                RateLimiterBucket::default()
+                    .interval(Duration::from_secs(60))
+                    .limit(10),
+            )
+            .start();
+        let costs = |cost| TaskCosts::from([("interval_60__limit_10".into(), cost)]);
+
+        assert!(!rate_limiter.would_exceed(&costs(10)));
+        assert_eq!(rate_limiter.estimated_wait(&costs(10)), Duration::ZERO);
+
+        RateLimiter::set_costs(&rate_limiter.buckets, &costs(8)).unwrap();
+        assert!(!rate_limiter.would_exceed(&costs(2)));
+        assert!(rate_limiter.would_exceed(&costs(3)));
+        let wait = rate_limiter.estimated_wait(&costs(3));
+        assert!(wait > Duration::from_secs(59) && wait <= Duration::from_secs(60));
+
+        // Estimating does not consume the bucket.
+        assert!(!rate_limiter.would_exceed(&costs(2)));
+        assert_eq!(rate_limiter.pending(), 0);
+
+        let unknown = TaskCosts::from([("unknown".into(), 1)]);
+        assert_eq!(rate_limiter.estimated_wait(&unknown), Duration::ZERO);
+    }
+
     #[actix_rt::test]
     async fn test_rate_limiter_metadata() {
         let proxy = Proxy::from_env_with_prefix(CCX_BINANCE_API_PREFIX);
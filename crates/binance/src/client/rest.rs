@@ -1,4 +1,8 @@
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 use std::time::Instant;
 
 use actix_http::BoxedPayloadStream;
@@ -9,14 +13,21 @@ use actix_http::encoding::Decoder;
 use ccx_api_lib::Client;
 use ccx_api_lib::ClientRequest;
 use ccx_api_lib::ClientResponse;
+use ccx_api_lib::Decimal;
+use ccx_api_lib::format_decimal;
 use ccx_api_lib::make_client;
+use chrono::Utc;
+use serde::Deserialize;
 use serde::Serialize;
 
 use super::*;
 use crate::client::WebsocketStream;
 use crate::client::limits::UsedRateLimits;
+use crate::client::limits::used_weights;
 use crate::error::*;
+use crate::proto::RecvWindow;
 use crate::proto::TimeWindow;
+use crate::ws_stream::WsSubscription;
 
 /// API client.
 pub struct RestClient<S>
@@ -42,6 +53,12 @@ where
     S: BinanceSigner,
 {
     config: Config<S>,
+    /// Server time minus local time in milliseconds.
+    time_offset: AtomicI64,
+    /// When `time_offset` was last set, `None` before the first time sync.
+    time_synced_at: Mutex<Option<Instant>>,
+    /// Limiter charged with `costs` for the time sync request.
+    time_sync_limit: Option<(RateLimiter, TaskCosts)>,
 }
 
 pub struct RequestBuilder<S>
@@ -51,6 +68,7 @@ where
     api_client: RestClient<S>,
     request: ClientRequest,
     sign: Option<TimeWindow>,
+    recv_window: Option<RecvWindow>,
 }
 
 impl<S> RestClient<S>
@@ -58,7 +76,29 @@ where
     S: BinanceSigner,
 {
     pub fn new(config: Config<S>) -> Self {
-        let inner = Arc::new(ClientInner { config });
+        Self::with_time_sync_limit(config, None)
+    }
+
+    /// Client whose time sync requests wait for `costs` in `rate_limiter`, like the
+    /// requests sent as [`Task`]s.
+    pub(crate) fn with_rate_limiter(
+        config: Config<S>,
+        rate_limiter: RateLimiter,
+        costs: TaskCosts,
+    ) -> Self {
+        Self::with_time_sync_limit(config, Some((rate_limiter, costs)))
+    }
+
+    fn with_time_sync_limit(
+        config: Config<S>,
+        time_sync_limit: Option<(RateLimiter, TaskCosts)>,
+    ) -> Self {
+        let inner = Arc::new(ClientInner {
+            config,
+            time_offset: AtomicI64::new(0),
+            time_synced_at: Mutex::new(None),
+            time_sync_limit,
+        });
         RestClient { inner }
     }
 
@@ -74,6 +114,99 @@ where
         self.client_(true)
     }
 
+    pub(crate) fn config(&self) -> &Config<S> {
+        &self.inner.config
+    }
+
+    /// Server time minus local time in milliseconds, added to the timestamps of
+    /// signed requests.
+    pub fn time_offset(&self) -> i64 {
+        self.inner.time_offset.load(Ordering::Relaxed)
+    }
+
+    /// Sets the offset applied to the timestamps of signed requests made by this client
+    /// and its clones.
+    pub fn set_time_offset(&self, offset: i64) {
+        self.inner.time_offset.store(offset, Ordering::Relaxed);
+        *self.inner.time_synced_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Measures the offset between the server and the local clock with the server time
+    /// endpoint of the config and applies it to the signed requests made by this client
+    /// and its clones.
+    ///
+    /// Returns the offset in milliseconds. Called automatically when the time sync is
+    /// enabled with [`Config::with_time_sync`]. The request is charged to the rate
+    /// limiter of the api the client belongs to.
+    pub async fn resync(&self) -> BinanceResult<i64> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ServerTime {
+            server_time: i64,
+        }
+
+        if let Some((rate_limiter, costs)) = &self.inner.time_sync_limit {
+            rate_limiter.acquire(0, costs.clone()).await?;
+        }
+        let sent = Utc::now().timestamp_millis();
+        let server_time: ServerTime = self
+            .get(self.inner.config.time_endpoint)?
+            .send_unsynced()
+            .await?
+            .value;
+        let received = Utc::now().timestamp_millis();
+        let offset = measured_time_offset(sent, server_time.server_time, received);
+        log::debug!("Server time offset: {offset}ms");
+        self.set_time_offset(offset);
+        Ok(offset)
+    }
+
+    /// Resyncs the time offset if the time sync is enabled and the last sync is older
+    /// than its interval.
+    ///
+    /// Requests signed while a sync is in flight use the previous offset.
+    async fn sync_time_if_due(&self) -> BinanceResult<()> {
+        let Some(interval) = self.inner.config.time_sync_interval else {
+            return Ok(());
+        };
+        let previous = {
+            let mut synced_at = self.inner.time_synced_at.lock().unwrap();
+            let previous = *synced_at;
+            if previous.is_some_and(|synced_at| synced_at.elapsed() < interval) {
+                return Ok(());
+            }
+            // Claim the sync so that concurrent requests do not make it too.
+            *synced_at = Some(Instant::now());
+            previous
+        };
+        if let Err(e) = self.resync().await {
+            *self.inner.time_synced_at.lock().unwrap() = previous;
+            Err(e)?
+        }
+        Ok(())
+    }
+
+    /// `timestamp` and `recvWindow` of a signed request, for both the REST and the
+    /// websocket API.
+    ///
+    /// Resyncs the time offset first if it is due, then shifts the timestamp of
+    /// `time_window` by it. `recv_window` takes precedence over the window set on
+    /// `time_window`, then comes the one of the config.
+    pub(crate) async fn signed_time_args(
+        &self,
+        time_window: TimeWindow,
+        recv_window: Option<RecvWindow>,
+    ) -> BinanceResult<(u64, RecvWindow)> {
+        self.sync_time_if_due().await?;
+        let recv_window = recv_window
+            .or(time_window.explicit_recv_window())
+            .unwrap_or(self.inner.config.recv_window);
+        let timestamp = time_window
+            .timestamp()
+            .saturating_add_signed(self.time_offset());
+        Ok((timestamp, recv_window))
+    }
+
     pub fn request(&self, method: Method, endpoint: &str) -> BinanceResult<RequestBuilder<S>> {
         let url = self.inner.config.api_base.join(endpoint)?;
         log::debug!("Requesting: {}", url.as_str());
@@ -83,6 +216,7 @@ where
             api_client,
             request,
             sign: None,
+            recv_window: None,
         })
     }
 
@@ -102,10 +236,24 @@ where
         self.request(Method::DELETE, endpoint)
     }
 
-    pub async fn web_socket(&self) -> BinanceResult<WebsocketStream> {
+    pub async fn web_socket(&self) -> BinanceResult<WebsocketStream>
+    where
+        S: 'static,
+    {
         let url = self.inner.config.stream_base.clone();
         WebsocketStream::connect(self.clone(), url).await
     }
+
+    /// Market events of `subscriptions`, see [`MarketStream`].
+    pub fn market_stream<I>(&self, subscriptions: I) -> BinanceResult<MarketStream>
+    where
+        S: 'static,
+        I: IntoIterator,
+        I::Item: Into<WsSubscription>,
+    {
+        let url = self.inner.config.stream_base.clone();
+        WebsocketStream::market_stream(self.clone(), url, subscriptions)
+    }
 }
 
 impl<S> RequestBuilder<S>
@@ -121,11 +269,30 @@ where
         Ok(self)
     }
 
+    /// Adds an argument, decimals are added with [`Self::decimal_arg`].
     pub fn query_arg<Name: AsRef<str>, T: Serialize + ?Sized>(
-        mut self,
+        self,
         name: Name,
         query: &T,
     ) -> BinanceResult<Self> {
+        let encoded = encode_query_arg(name.as_ref(), query)?;
+        self.push_query(&encoded)
+    }
+
+    /// Adds an argument once per value, e.g. `asset=BTC&asset=USDT`.
+    pub fn query_arg_repeated<Name: AsRef<str>, T: Serialize>(
+        self,
+        name: Name,
+        values: &[T],
+    ) -> BinanceResult<Self> {
+        let encoded = encode_repeated_query_arg(name.as_ref(), values)?;
+        self.push_query(&encoded)
+    }
+
+    fn push_query(mut self, encoded: &str) -> BinanceResult<Self> {
+        if encoded.is_empty() {
+            return Ok(self);
+        }
         let mut parts = self.request.get_uri().clone().into_parts();
 
         if let Some(path_and_query) = parts.path_and_query {
@@ -138,7 +305,7 @@ where
                     buf.push('&');
                 }
             }
-            buf.push_str(&serde_urlencoded::to_string([(name.as_ref(), query)])?);
+            buf.push_str(encoded);
             parts.path_and_query = buf.parse().ok();
             let uri =
                 Uri::from_parts(parts).map_err(|e| BinanceError::other(format!("{:?}", e)))?;
@@ -148,6 +315,15 @@ where
         Ok(self)
     }
 
+    /// Adds a list argument encoded as a JSON array, e.g. `symbols=["BTCUSDT","ETHUSDT"]`.
+    pub fn query_arg_list<Name: AsRef<str>, V: AsRef<str>>(
+        self,
+        name: Name,
+        values: &[V],
+    ) -> BinanceResult<Self> {
+        self.query_arg(name, &json_array(values)?)
+    }
+
     pub fn try_query_arg<Name: AsRef<str>, T: Serialize>(
         self,
         name: Name,
@@ -159,6 +335,22 @@ where
         }
     }
 
+    /// Adds a decimal argument in plain notation, see [`format_decimal`].
+    pub fn decimal_arg<Name: AsRef<str>>(self, name: Name, value: &Decimal) -> BinanceResult<Self> {
+        self.query_arg(name, &format_decimal(value))
+    }
+
+    pub fn try_decimal_arg<Name: AsRef<str>>(
+        self,
+        name: Name,
+        value: &Option<Decimal>,
+    ) -> BinanceResult<Self> {
+        match value {
+            Some(value) => self.decimal_arg(name, value),
+            None => Ok(self),
+        }
+    }
+
     pub fn auth_header(mut self) -> BinanceResult<Self> {
         self.request = self
             .request
@@ -171,20 +363,39 @@ where
         self.auth_header()
     }
 
-    pub async fn send<V>(mut self) -> BinanceResult<V>
+    /// Overrides the `recvWindow` of a signed request, see [`Config::with_recv_window`].
+    pub fn recv_window(mut self, recv_window: RecvWindow) -> Self {
+        self.recv_window = Some(recv_window);
+        self
+    }
+
+    pub async fn send<V>(self) -> BinanceResult<V>
+    where
+        V: serde::de::DeserializeOwned,
+    {
+        Ok(self.send_with_headers().await?.value)
+    }
+
+    /// Like [`RequestBuilder::send`], also returning the rate limit usage reported in
+    /// the `X-MBX-*` response headers.
+    pub async fn send_with_headers<V>(mut self) -> BinanceResult<TaskResult<V>>
     where
         V: serde::de::DeserializeOwned,
     {
         self = if let Some(sign) = self.sign {
-            self = self.query_arg("timestamp", &sign.timestamp())?;
-            let recv_window = sign.recv_window();
-            if !recv_window.is_default() {
-                self = self.query_arg("recvWindow", &*recv_window)?;
-            }
-            self.sign().await?
+            self.time_window_args(sign).await?.sign().await?
         } else {
             self
         };
+        self.send_unsynced().await
+    }
+
+    /// Sends the request as is, without the time sync and signing of
+    /// [`RequestBuilder::send_with_headers`].
+    async fn send_unsynced<V>(self) -> BinanceResult<TaskResult<V>>
+    where
+        V: serde::de::DeserializeOwned,
+    {
         log::debug!("{}  {}", self.request.get_method(), self.request.get_uri(),);
 
         let tm = Instant::now();
@@ -202,12 +413,19 @@ where
             res.status(),
             String::from_utf8_lossy(&resp)
         );
-        if let Err(err) = check_response(res) {
+        let used_weights = used_weights(res.headers());
+        let used_rate_limits = UsedRateLimits::from_headers(res.headers());
+        log::debug!("  used_rate_limits:  {:?}", used_rate_limits);
+        if let Err(err) = check_response(res, &resp) {
             // log::debug!("Response: {}", String::from_utf8_lossy(&resp));
             Err(err)?
         };
         match serde_json::from_slice(&resp) {
-            Ok(json) => Ok(json),
+            Ok(value) => Ok(TaskResult {
+                value,
+                used_weights,
+                used_rate_limits,
+            }),
             Err(err) => {
                 // log::debug!("Response: {}", String::from_utf8_lossy(&resp));
                 Err(err)?
@@ -249,6 +467,16 @@ where
     //     Ok(())
     // }
 
+    /// Adds `recvWindow` and `timestamp`, see [`RestClient::signed_time_args`].
+    async fn time_window_args(mut self, time_window: TimeWindow) -> BinanceResult<Self> {
+        let (timestamp, recv_window) = self
+            .api_client
+            .signed_time_args(time_window, self.recv_window)
+            .await?;
+        self = self.query_arg("recvWindow", &*recv_window)?;
+        self.query_arg("timestamp", &timestamp)
+    }
+
     async fn sign(self) -> BinanceResult<Self> {
         let query = self.request.get_uri().query().unwrap_or("");
         let signature = self
@@ -262,27 +490,57 @@ where
     }
 }
 
-type AwcClientResponse = ClientResponse<Decoder<Payload<BoxedPayloadStream>>>;
-
-fn check_response(res: AwcClientResponse) -> BinanceResult<AwcClientResponse> {
-    let used_rate_limits = UsedRateLimits::from_headers(res.headers());
+/// Server time minus local time, assuming the server time was taken halfway through
+/// the round trip from `sent` to `received`.
+pub(crate) fn measured_time_offset(sent: i64, server_time: i64, received: i64) -> i64 {
+    server_time - (sent + received) / 2
+}
 
-    log::debug!("  used_rate_limits:  {:?}", used_rate_limits);
+type AwcClientResponse = ClientResponse<Decoder<Payload<BoxedPayloadStream>>>;
 
+fn check_response(res: AwcClientResponse, body: &[u8]) -> BinanceResult<AwcClientResponse> {
     match res.status() {
         StatusCode::OK => Ok(res),
         StatusCode::INTERNAL_SERVER_ERROR => Err(ApiServiceError::ServerError)?,
         StatusCode::SERVICE_UNAVAILABLE => Err(ApiServiceError::ServiceUnavailable)?,
         StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized)?,
-        // StatusCode::BAD_REQUEST => {
-        //     let error_json: BinanceContentError = response.json()?;
-        //
-        //     Err(ErrorKind::BinanceError(error_json.code, error_json.msg, response).into())
-        // }
+        StatusCode::TOO_MANY_REQUESTS => Err(ApiError::RateLimited {
+            retry_after: retry_after(&res),
+        })?,
+        StatusCode::IM_A_TEAPOT => Err(ApiError::IpBanned {
+            retry_after: retry_after(&res),
+        })?,
+        StatusCode::BAD_REQUEST => match serde_json::from_slice::<BinanceContentError>(body) {
+            Ok(error) if error.code == LISTEN_KEY_NOT_FOUND => Err(ApiError::ListenKeyExpired)?,
+            _ => Err(BinanceError::UnknownStatus(StatusCode::BAD_REQUEST))?,
+        },
         s => Err(BinanceError::UnknownStatus(s))?,
     }
 }
 
+/// `-1125 This listenKey does not exist.`
+const LISTEN_KEY_NOT_FOUND: i16 = -1125;
+
+/// `Retry-After` header, in seconds.
+fn retry_after(res: &AwcClientResponse) -> Option<Duration> {
+    let value = res.headers().get("Retry-After")?.to_str().ok()?;
+    value.trim().parse().ok().map(Duration::from_secs)
+}
+
+fn encode_query_arg<T: Serialize + ?Sized>(name: &str, value: &T) -> BinanceResult<String> {
+    Ok(serde_urlencoded::to_string([(name, value)])?)
+}
+
+fn encode_repeated_query_arg<T: Serialize>(name: &str, values: &[T]) -> BinanceResult<String> {
+    let pairs: Vec<(&str, &T)> = values.iter().map(|value| (name, value)).collect();
+    Ok(serde_urlencoded::to_string(pairs)?)
+}
+
+fn json_array<V: AsRef<str>>(values: &[V]) -> BinanceResult<String> {
+    let values: Vec<&str> = values.iter().map(AsRef::as_ref).collect();
+    Ok(serde_json::to_string(&values)?)
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
@@ -299,3 +557,188 @@ fn check_response(res: AwcClientResponse) -> BinanceResult<AwcClientResponse> {
 //         )
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::proto::RecvWindow;
+
+    fn rest_client(recv_window: RecvWindow) -> RestClient<ApiCred> {
+        let url = url::Url::parse("http://127.0.0.1:1/").unwrap();
+        RestClient::new(
+            Config::new(ApiCred::default(), url.clone(), url, None).with_recv_window(recv_window),
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_time_window_args() {
+        let client = rest_client(RecvWindow::DEFAULT);
+        let local = 1_700_000_000_000;
+        async fn args(client: &RestClient<ApiCred>, time_window: TimeWindow) -> String {
+            let request = client.get("/api/v3/account").unwrap();
+            request.time_window_args(time_window).await.unwrap().uri()
+        }
+
+        assert!(
+            args(&client, TimeWindow::new(local))
+                .await
+                .ends_with("?recvWindow=5000&timestamp=1700000000000")
+        );
+
+        client.set_time_offset(1_500);
+        assert_eq!(client.clone().time_offset(), 1_500);
+        assert!(
+            args(&client, TimeWindow::new(local))
+                .await
+                .ends_with("?recvWindow=5000&timestamp=1700000001500")
+        );
+
+        client.set_time_offset(-250);
+        assert!(
+            args(&client, TimeWindow::new(local))
+                .await
+                .ends_with("?recvWindow=5000&timestamp=1699999999750")
+        );
+
+        let client = rest_client(RecvWindow::new(10_000).unwrap());
+        assert!(
+            args(&client, TimeWindow::new(local))
+                .await
+                .ends_with("?recvWindow=10000&timestamp=1700000000000")
+        );
+        let time_window = TimeWindow::new(local).with_recv_window(RecvWindow::new(20_000).unwrap());
+        assert!(
+            args(&client, time_window)
+                .await
+                .ends_with("?recvWindow=20000&timestamp=1700000000000")
+        );
+        let explicit_default = TimeWindow::new(local).with_recv_window(RecvWindow::DEFAULT);
+        assert!(
+            args(&client, explicit_default)
+                .await
+                .ends_with("?recvWindow=5000&timestamp=1700000000000")
+        );
+        let request = client
+            .get("/api/v3/account")
+            .unwrap()
+            .recv_window(RecvWindow::new(30_000).unwrap());
+        assert!(
+            request
+                .time_window_args(time_window)
+                .await
+                .unwrap()
+                .uri()
+                .ends_with("?recvWindow=30000&timestamp=1700000000000")
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_sign_recv_window() {
+        // The example of the Binance docs.
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let url = url::Url::parse("http://127.0.0.1:1/").unwrap();
+        let config = Config::new(
+            ApiCred::new(None, Some(secret.to_string())),
+            url.clone(),
+            url,
+            None,
+        );
+        let client = RestClient::new(config);
+        let request = async |recv_window| {
+            client
+                .post("/api/v3/order")
+                .unwrap()
+                .query_arg("symbol", "LTCBTC")
+                .unwrap()
+                .query_arg("side", "BUY")
+                .unwrap()
+                .query_arg("type", "LIMIT")
+                .unwrap()
+                .query_arg("timeInForce", "GTC")
+                .unwrap()
+                .query_arg("quantity", "1")
+                .unwrap()
+                .query_arg("price", "0.1")
+                .unwrap()
+                .recv_window(recv_window)
+                .time_window_args(TimeWindow::new(1499827319559))
+                .await
+                .unwrap()
+        };
+
+        let signed = request(RecvWindow::DEFAULT)
+            .await
+            .sign()
+            .await
+            .unwrap()
+            .uri();
+        assert!(signed.ends_with(
+            "&price=0.1&recvWindow=5000&timestamp=1499827319559\
+             &signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        ));
+
+        let signed = request(RecvWindow::new(6000).unwrap())
+            .await
+            .sign()
+            .await
+            .unwrap()
+            .uri();
+        assert!(signed.contains("&recvWindow=6000&timestamp=1499827319559&signature="));
+        assert!(
+            !signed.ends_with("c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71")
+        );
+    }
+
+    #[test]
+    fn test_measured_time_offset() {
+        assert_eq!(measured_time_offset(1_000, 1_050, 1_100), 0);
+        assert_eq!(measured_time_offset(1_000, 4_600_050, 1_100), 4_599_000);
+        assert_eq!(measured_time_offset(1_000, 550, 1_100), -500);
+    }
+
+    #[test]
+    fn test_encode_list_query_arg() {
+        let symbols = json_array(&["BTCUSDT", "ETHUSDT"]).unwrap();
+        assert_eq!(symbols, r#"["BTCUSDT","ETHUSDT"]"#);
+        assert_eq!(
+            encode_query_arg("symbols", &symbols).unwrap(),
+            "symbols=%5B%22BTCUSDT%22%2C%22ETHUSDT%22%5D"
+        );
+        assert_eq!(
+            encode_query_arg("symbols", &json_array::<&str>(&[]).unwrap()).unwrap(),
+            "symbols=%5B%5D"
+        );
+    }
+
+    #[test]
+    fn test_encode_repeated_query_arg() {
+        assert_eq!(
+            encode_repeated_query_arg("asset", &["BTC", "USDT"]).unwrap(),
+            "asset=BTC&asset=USDT"
+        );
+        assert_eq!(encode_repeated_query_arg::<&str>("asset", &[]).unwrap(), "");
+    }
+
+    #[actix_rt::test]
+    async fn test_decimal_arg() {
+        use rust_decimal_macros::dec;
+
+        let client = rest_client(RecvWindow::DEFAULT);
+        let uri = |value: Option<Decimal>| {
+            let request = client.get("/api/v3/order").unwrap();
+            request.try_decimal_arg("quantity", &value).unwrap().uri()
+        };
+
+        for (value, arg) in [
+            (dec!(0.00000022), "0.00000022"),
+            (Decimal::new(1, 28), "0.0000000000000000000000000001"),
+            (dec!(1.500), "1.500"),
+            (Decimal::MAX, "79228162514264337593543950335"),
+            (-dec!(0.00), "0.00"),
+        ] {
+            assert!(uri(Some(value)).ends_with(&format!("/api/v3/order?quantity={arg}")));
+        }
+        assert!(uri(None).ends_with("/api/v3/order"));
+    }
+}
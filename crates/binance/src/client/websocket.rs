@@ -1,4 +1,8 @@
 use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context as TaskContext;
+use std::task::Poll;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -9,17 +13,23 @@ use actix_http::ws::Codec;
 use actix_web_actors::ws;
 use awc::BoxedSocket;
 use ccx_api_lib::Seq;
+use futures::Stream;
+use futures::StreamExt;
 use futures::channel::mpsc;
+use futures::future::LocalBoxFuture;
 use futures::stream::SplitSink;
 use serde::Deserialize;
 use serde::Serialize;
 use url::Url;
 
 use crate::client::RestClient;
+use crate::error::ApiError;
 use crate::error::BinanceError;
 use crate::error::BinanceResult;
+use crate::ws_stream::MarketEvent;
 use crate::ws_stream::UpstreamApiRequest;
 use crate::ws_stream::UpstreamWebsocketMessage;
+use crate::ws_stream::UpstreamWebsocketResult;
 use crate::ws_stream::WsCommand;
 use crate::ws_stream::WsEvent;
 use crate::ws_stream::WsSubscription;
@@ -28,14 +38,40 @@ use crate::ws_stream::WsSubscription;
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 /// How long before lack of client response causes a timeout.
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long [`MarketStream`] waits before reconnecting.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// How many streams a single connection can listen to.
+pub const MAX_STREAMS_PER_CONNECTION: usize = 1024;
 
 #[derive(actix::Message, Clone, Debug, Serialize, Deserialize)]
 #[rtype(result = "()")]
 struct M<T>(pub T);
 
+type Connection = (
+    WebsocketStreamTx,
+    mpsc::UnboundedReceiver<UpstreamWebsocketMessage<WsEvent>>,
+);
+/// Opens a connection to the combined stream endpoint, or to the raw endpoint of
+/// the given stream.
+type Connect =
+    Rc<dyn Fn(Option<WsSubscription>) -> LocalBoxFuture<'static, BinanceResult<Connection>>>;
+
 pub struct WebsocketStream {
     tx: WebsocketStreamTx,
     rx: mpsc::UnboundedReceiver<UpstreamWebsocketMessage<WsEvent>>,
+    connect: Connect,
+}
+
+/// Market events of a set of subscriptions.
+///
+/// A single stream is read from the raw stream endpoint (`/ws/<stream>`), several streams
+/// share a connection to the combined stream endpoint (`/stream`).
+///
+/// Reconnects and resubscribes when the connection drops. Errors, including rejected
+/// subscriptions and failed reconnects, are passed through without ending the stream.
+pub struct MarketStream {
+    rx: mpsc::UnboundedReceiver<BinanceResult<MarketEvent>>,
+    task: actix_rt::task::JoinHandle<()>,
 }
 
 pub struct WebsocketStreamTx {
@@ -47,6 +83,8 @@ pub struct Websocket {
     tx: mpsc::UnboundedSender<UpstreamWebsocketMessage<WsEvent>>,
     hb: Instant,
     id_seq: Seq<u64>,
+    /// Stream of a raw stream connection, its events come without the stream name.
+    raw_stream: Option<WsSubscription>,
 }
 
 impl Actor for Websocket {
@@ -84,7 +122,11 @@ impl StreamHandler<Result<ws::Frame, ws::ProtocolError>> for Websocket {
                 log::warn!("unexpected binary message (ignored)");
             }
             ws::Frame::Text(msg) => {
-                let res = serde_json::from_slice(&msg);
+                let res = serde_json::from_slice(&msg).or_else(|e| match &self.raw_stream {
+                    Some(stream) => MarketEvent::from_raw(stream.clone(), &msg)
+                        .map(UpstreamWebsocketMessage::Event),
+                    None => Err(e),
+                });
                 if res.is_err() {
                     log::error!(
                         "json message from server: {}",
@@ -137,10 +179,11 @@ impl Websocket {
     pub(crate) fn new(
         sink: SinkWrite<ws::Message, SplitSink<Framed<BoxedSocket, Codec>, ws::Message>>,
         tx: mpsc::UnboundedSender<UpstreamWebsocketMessage<WsEvent>>,
+        raw_stream: Option<WsSubscription>,
     ) -> Self {
         let hb = Instant::now();
         let id_seq = Seq::new();
-        Self { sink, tx, hb, id_seq }
+        Self { sink, tx, hb, id_seq, raw_stream }
     }
 
     /// helper method that sends ping to client every second.
@@ -162,12 +205,52 @@ impl Websocket {
 }
 
 impl WebsocketStream {
-    pub async fn connect<S: crate::client::BinanceSigner>(
+    /// Connects to the combined stream endpoint `url`.
+    pub async fn connect<S: crate::client::BinanceSigner + 'static>(
         api_client: RestClient<S>,
         url: Url,
     ) -> BinanceResult<Self> {
-        use futures::StreamExt;
+        let connect = Self::connector(api_client, url);
+        let (tx, rx) = connect(None).await?;
+        Ok(WebsocketStream { tx, rx, connect })
+    }
+
+    /// Market events of `subscriptions`, see [`MarketStream`].
+    ///
+    /// Unlike [`Self::subscribe`] does not need an open connection, so a single stream
+    /// only ever connects to the raw stream endpoint.
+    pub fn market_stream<S, I>(
+        api_client: RestClient<S>,
+        url: Url,
+        subscriptions: I,
+    ) -> BinanceResult<MarketStream>
+    where
+        S: crate::client::BinanceSigner + 'static,
+        I: IntoIterator,
+        I::Item: Into<WsSubscription>,
+    {
+        MarketStream::spawn(Self::connector(api_client, url), None, subscriptions)
+    }
+
+    fn connector<S: crate::client::BinanceSigner + 'static>(
+        api_client: RestClient<S>,
+        url: Url,
+    ) -> Connect {
+        Rc::new(move |raw_stream| {
+            Box::pin(Self::open(api_client.clone(), url.clone(), raw_stream))
+                as LocalBoxFuture<'static, _>
+        })
+    }
 
+    async fn open<S: crate::client::BinanceSigner>(
+        api_client: RestClient<S>,
+        url: Url,
+        raw_stream: Option<WsSubscription>,
+    ) -> BinanceResult<Connection> {
+        let url = match &raw_stream {
+            Some(stream) => raw_stream_url(url, stream)?,
+            None => url,
+        };
         log::debug!("Connecting WS: {}", url.as_str());
 
         let (response, connection) = api_client.client_h1().ws(url.as_str()).connect().await?;
@@ -177,11 +260,23 @@ impl WebsocketStream {
         let (tx, rx) = mpsc::unbounded();
         let addr = Websocket::create(move |ctx| {
             Websocket::add_stream(stream, ctx);
-            Websocket::new(SinkWrite::new(sink, ctx), tx)
+            Websocket::new(SinkWrite::new(sink, ctx), tx, raw_stream)
         });
 
-        let tx = WebsocketStreamTx { addr };
-        Ok(WebsocketStream { tx, rx })
+        Ok((WebsocketStreamTx { addr }, rx))
+    }
+
+    /// Subscribes to `subscriptions` and yields their events along with the stream names.
+    ///
+    /// Up to [`MAX_STREAMS_PER_CONNECTION`] streams share this connection. A single stream
+    /// is read from the raw stream endpoint instead, this connection is closed then.
+    /// The subscriptions are restored after a reconnect.
+    pub fn subscribe<I>(self, subscriptions: I) -> BinanceResult<MarketStream>
+    where
+        I: IntoIterator,
+        I::Item: Into<WsSubscription>,
+    {
+        MarketStream::spawn(self.connect.clone(), Some(self), subscriptions)
     }
 
     pub fn split(
@@ -221,4 +316,142 @@ impl WebsocketStreamTx {
             .await
             .map_err(|_e| BinanceError::IoError(io::ErrorKind::ConnectionAborted.into()))
     }
+
+    pub async fn unsubscribe_list(
+        &self,
+        subscriptions: Box<[WsSubscription]>,
+    ) -> BinanceResult<()> {
+        let cmd = WsCommand::Unsubscribe(subscriptions);
+        self.addr
+            .send(M(cmd))
+            .await
+            .map_err(|_e| BinanceError::IoError(io::ErrorKind::ConnectionAborted.into()))
+    }
+}
+
+/// Raw stream endpoint of `stream` next to the combined stream endpoint `url`,
+/// e.g. `wss://stream.binance.com/ws/btcusdt@trade` for `wss://stream.binance.com/stream`.
+fn raw_stream_url(mut url: Url, stream: &WsSubscription) -> BinanceResult<Url> {
+    url.path_segments_mut()
+        .map_err(|()| BinanceError::other("Invalid stream URL"))?
+        .pop_if_empty()
+        .pop()
+        .push("ws")
+        .push(&stream.to_string());
+    Ok(url)
+}
+
+impl MarketStream {
+    fn spawn<I>(
+        connect: Connect,
+        stream: Option<WebsocketStream>,
+        subscriptions: I,
+    ) -> BinanceResult<MarketStream>
+    where
+        I: IntoIterator,
+        I::Item: Into<WsSubscription>,
+    {
+        let subscriptions: Box<[WsSubscription]> =
+            subscriptions.into_iter().map(Into::into).collect();
+        if subscriptions.len() > MAX_STREAMS_PER_CONNECTION {
+            Err(ApiError::TooManyStreams {
+                requested: subscriptions.len(),
+                limit: MAX_STREAMS_PER_CONNECTION,
+            })?
+        }
+        let (tx, rx) = mpsc::unbounded();
+        let task = actix_rt::spawn(run_market_stream(connect, stream, subscriptions, tx));
+        Ok(MarketStream { rx, task })
+    }
+}
+
+async fn run_market_stream(
+    connect: Connect,
+    mut stream: Option<WebsocketStream>,
+    subscriptions: Box<[WsSubscription]>,
+    tx: mpsc::UnboundedSender<BinanceResult<MarketEvent>>,
+) {
+    // A single stream is read from its raw endpoint, which needs no subscription.
+    let raw_stream = match &*subscriptions {
+        [subscription] => Some(subscription.clone()),
+        _ => None,
+    };
+    if raw_stream.is_some() {
+        stream = None;
+    }
+    loop {
+        let connection = match stream.take() {
+            Some(stream) => Ok(stream.split()),
+            None => connect(raw_stream.clone()).await,
+        };
+        match connection {
+            Ok((sink, rx)) => {
+                let subscriptions = raw_stream.is_none().then(|| subscriptions.clone());
+                if !forward_market_events(sink, rx, subscriptions, &tx).await {
+                    return;
+                }
+            }
+            Err(e) => {
+                if tx.unbounded_send(Err(e)).is_err() {
+                    return;
+                }
+            }
+        }
+
+        log::warn!("Market stream disconnected, reconnecting");
+        actix_rt::time::sleep(RECONNECT_DELAY).await;
+        if tx.is_closed() {
+            return;
+        }
+    }
+}
+
+/// Subscribes and forwards events until the connection drops.
+///
+/// Returns `false` once the receiver is gone.
+async fn forward_market_events(
+    sink: WebsocketStreamTx,
+    mut rx: mpsc::UnboundedReceiver<UpstreamWebsocketMessage<WsEvent>>,
+    subscriptions: Option<Box<[WsSubscription]>>,
+    tx: &mpsc::UnboundedSender<BinanceResult<MarketEvent>>,
+) -> bool {
+    if let Some(subscriptions) = subscriptions {
+        if let Err(e) = sink.subscribe_list(subscriptions).await {
+            return tx.unbounded_send(Err(e)).is_ok();
+        }
+    }
+    while let Some(msg) = rx.next().await {
+        let item = match msg {
+            UpstreamWebsocketMessage::Event(event) => Ok(event),
+            UpstreamWebsocketMessage::Response(response) => match response.payload {
+                Some(UpstreamWebsocketResult::Err { code, msg }) => {
+                    Err(ApiError::WsRequestFailed {
+                        id: response.id,
+                        code,
+                        msg,
+                    }
+                    .into())
+                }
+                _ => continue,
+            },
+        };
+        if tx.unbounded_send(item).is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+impl Stream for MarketStream {
+    type Item = BinanceResult<MarketEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for MarketStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
 }
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+use std::time::Instant;
+
+use actix::io::SinkWrite;
+use actix::prelude::*;
+use actix_codec::Framed;
+use actix_http::ws::Codec;
+use actix_web_actors::ws;
+use awc::BoxedSocket;
+use ccx_api_lib::Decimal;
+use ccx_api_lib::Seq;
+use ccx_api_lib::format_decimal;
+use futures::StreamExt;
+use futures::channel::oneshot;
+use futures::stream::SplitSink;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Map;
+use serde_json::Value;
+use url::Url;
+
+use crate::api::spot::RateLimitInterval;
+use crate::api::spot::RateLimitType;
+use crate::client::BinanceSigner;
+use crate::client::RestClient;
+use crate::error::ApiError;
+use crate::error::BinanceError;
+use crate::error::BinanceResult;
+use crate::proto::TimeWindow;
+
+/// How often heartbeat pings are sent.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// How long before lack of server response causes a timeout.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Usage of a rate limit after a websocket API request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct WsApiRateLimit {
+    pub rate_limit_type: RateLimitType,
+    pub interval: RateLimitInterval,
+    pub interval_num: u32,
+    pub limit: u32,
+    pub count: u32,
+}
+
+/// Successful response of the websocket API.
+#[derive(Debug, Clone)]
+pub struct WsApiResponse<T> {
+    pub id: u64,
+    pub status: u16,
+    pub result: T,
+    /// Rate limits affected by the request.
+    pub rate_limits: Vec<WsApiRateLimit>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawResponse {
+    id: Option<u64>,
+    status: u16,
+    #[serde(default)]
+    result: Value,
+    error: Option<RawError>,
+    #[serde(default)]
+    rate_limits: Vec<WsApiRateLimit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawError {
+    code: i32,
+    msg: String,
+}
+
+#[derive(Serialize)]
+struct RawRequest<'a> {
+    id: u64,
+    method: &'a str,
+    params: &'a WsApiParams,
+}
+
+/// Params of a websocket API request.
+///
+/// Kept sorted by name, which is the order they are signed in.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WsApiParams(Map<String, Value>);
+
+impl WsApiParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn arg<T: Serialize + ?Sized>(mut self, name: &str, value: &T) -> BinanceResult<Self> {
+        self.0
+            .insert(name.to_string(), serde_json::to_value(value)?);
+        Ok(self)
+    }
+
+    pub fn try_arg<T: Serialize>(self, name: &str, value: &Option<T>) -> BinanceResult<Self> {
+        match value {
+            Some(value) => self.arg(name, value),
+            None => Ok(self),
+        }
+    }
+
+    /// Adds a decimal argument in plain notation, see [`format_decimal`].
+    pub fn decimal_arg(self, name: &str, value: &Decimal) -> BinanceResult<Self> {
+        self.arg(name, &format_decimal(value))
+    }
+
+    pub fn try_decimal_arg(self, name: &str, value: &Option<Decimal>) -> BinanceResult<Self> {
+        match value {
+            Some(value) => self.decimal_arg(name, value),
+            None => Ok(self),
+        }
+    }
+
+    /// `name=value` pairs joined with `&`, e.g. `apiKey=...&symbol=BTCUSDT&timestamp=...`.
+    fn signature_payload(&self) -> String {
+        let pairs: Vec<String> = self
+            .0
+            .iter()
+            .map(|(name, value)| match value {
+                // Strings are signed without the quotes.
+                Value::String(value) => format!("{name}={value}"),
+                value => format!("{name}={value}"),
+            })
+            .collect();
+        pairs.join("&")
+    }
+}
+
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+struct Call {
+    method: String,
+    params: WsApiParams,
+    reply: oneshot::Sender<RawResponse>,
+}
+
+/// Connection to the websocket API, e.g. `wss://ws-api.binance.com:443/ws-api/v3`.
+///
+/// Requests are sent as JSON-RPC over a single connection and their responses are matched
+/// by id, so any number of requests can be in flight at once. The connection is not
+/// restored when it drops; pending and later requests fail with `ConnectionAborted`.
+pub struct WsApiConnection<S>
+where
+    S: BinanceSigner,
+{
+    api_client: RestClient<S>,
+    addr: Addr<WsApiSocket>,
+}
+
+pub struct WsApiSocket {
+    sink: SinkWrite<ws::Message, SplitSink<Framed<BoxedSocket, Codec>, ws::Message>>,
+    hb: Instant,
+    id_seq: Seq<u64>,
+    pending: HashMap<u64, oneshot::Sender<RawResponse>>,
+}
+
+impl Actor for WsApiSocket {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.hb(ctx);
+    }
+}
+
+impl StreamHandler<Result<ws::Frame, ws::ProtocolError>> for WsApiSocket {
+    fn handle(&mut self, msg: Result<ws::Frame, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => {
+                log::warn!("WebSocket API broken: {:?}", e);
+                ctx.stop();
+                return;
+            }
+        };
+
+        match msg {
+            ws::Frame::Ping(msg) => {
+                self.hb = Instant::now();
+                if let Err(_msg) = self.sink.write(ws::Message::Pong(msg)) {
+                    log::warn!("Failed to send Pong. Disconnecting.");
+                    ctx.stop()
+                }
+            }
+            ws::Frame::Pong(_) => {
+                self.hb = Instant::now();
+            }
+            ws::Frame::Binary(_bin) => {
+                log::warn!("unexpected binary message (ignored)");
+            }
+            ws::Frame::Text(msg) => match serde_json::from_slice::<RawResponse>(&msg) {
+                Err(e) => {
+                    log::error!(
+                        "Failed to deserialize server message: {:?}: {}",
+                        e,
+                        String::from_utf8_lossy(&msg)
+                    );
+                }
+                Ok(response) => {
+                    let reply = response.id.and_then(|id| self.pending.remove(&id));
+                    match reply {
+                        Some(reply) => {
+                            let _ = reply.send(response);
+                        }
+                        None => log::warn!("Unexpected response: {:?}", response),
+                    }
+                }
+            },
+            ws::Frame::Close(_) => {
+                ctx.stop();
+            }
+            ws::Frame::Continuation(_) => {
+                ctx.stop();
+            }
+        }
+    }
+}
+
+impl actix::io::WriteHandler<ws::ProtocolError> for WsApiSocket {}
+
+impl Handler<Call> for WsApiSocket {
+    type Result = ();
+
+    fn handle(&mut self, call: Call, ctx: &mut Self::Context) {
+        let id = self.id_seq.next();
+        let msg = RawRequest {
+            id,
+            method: &call.method,
+            params: &call.params,
+        };
+        let msg = serde_json::to_string(&msg).expect("json encode");
+        log::debug!("Sending to server: `{}`", msg);
+        if let Err(_msg) = self.sink.write(ws::Message::Text(msg.into())) {
+            ctx.stop();
+            return;
+        }
+        self.pending.insert(id, call.reply);
+    }
+}
+
+impl WsApiSocket {
+    /// Sends a ping every [`HEARTBEAT_INTERVAL`] and disconnects when the server
+    /// stops answering.
+    fn hb(&mut self, ctx: &mut <Self as Actor>::Context) {
+        ctx.run_interval(HEARTBEAT_INTERVAL, move |act, ctx| {
+            if Instant::now().duration_since(act.hb) > CLIENT_TIMEOUT {
+                log::warn!("Websocket API heartbeat failed, disconnecting!");
+                ctx.stop();
+                return;
+            }
+            if let Err(_msg) = act.sink.write(ws::Message::Ping("".into())) {
+                log::warn!("Websocket API failed to send ping, stopping!");
+                ctx.stop()
+            };
+        });
+    }
+}
+
+impl<S> WsApiConnection<S>
+where
+    S: BinanceSigner,
+{
+    pub async fn connect(api_client: RestClient<S>, url: Url) -> BinanceResult<Self> {
+        log::debug!("Connecting WS API: {}", url.as_str());
+
+        let (response, connection) = api_client.client_h1().ws(url.as_str()).connect().await?;
+        log::debug!("{:?}", response);
+
+        let (sink, stream) = connection.split();
+        let addr = WsApiSocket::create(move |ctx| {
+            WsApiSocket::add_stream(stream, ctx);
+            WsApiSocket {
+                sink: SinkWrite::new(sink, ctx),
+                hb: Instant::now(),
+                id_seq: Seq::new(),
+                pending: HashMap::new(),
+            }
+        });
+
+        Ok(WsApiConnection { api_client, addr })
+    }
+
+    /// Sends an unsigned request, e.g. `time` or `depth`.
+    pub async fn request<V>(
+        &self,
+        method: &str,
+        params: WsApiParams,
+    ) -> BinanceResult<WsApiResponse<V>>
+    where
+        V: DeserializeOwned,
+    {
+        let (reply, rx) = oneshot::channel();
+        let call = Call {
+            method: method.to_string(),
+            params,
+            reply,
+        };
+        self.addr.send(call).await.map_err(aborted)?;
+        let response = rx.await.map_err(aborted)?;
+
+        let id = response.id.unwrap_or_default();
+        if let Some(RawError { code, msg }) = response.error {
+            Err(ApiError::WsRequestFailed { id, code, msg })?
+        }
+        Ok(WsApiResponse {
+            id,
+            status: response.status,
+            result: serde_json::from_value(response.result)?,
+            rate_limits: response.rate_limits,
+        })
+    }
+
+    /// Sends a request signed with the API key: `apiKey`, `timestamp`, `recvWindow`
+    /// and `signature` are added to the params.
+    ///
+    /// The timestamp and window are the ones of a signed REST request, see
+    /// [`RestClient::signed_time_args`].
+    pub async fn signed_request<V>(
+        &self,
+        method: &str,
+        params: WsApiParams,
+        time_window: impl Into<TimeWindow>,
+    ) -> BinanceResult<WsApiResponse<V>>
+    where
+        V: DeserializeOwned,
+    {
+        let (timestamp, recv_window) = self
+            .api_client
+            .signed_time_args(time_window.into(), None)
+            .await?;
+        let signer = self.api_client.config().signer();
+        let params = params
+            .arg("apiKey", signer.api_key())?
+            .arg("recvWindow", &*recv_window)?
+            .arg("timestamp", &timestamp)?;
+        let signature = signer.sign_data(&params.signature_payload()).await?;
+        let params = params.arg("signature", &signature)?;
+        self.request(method, params).await
+    }
+}
+
+fn aborted<E>(_e: E) -> BinanceError {
+    BinanceError::IoError(io::ErrorKind::ConnectionAborted.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_signature_payload() {
+        let params = WsApiParams::new()
+            .arg("symbol", "BTCUSDT")
+            .unwrap()
+            .arg("side", "SELL")
+            .unwrap()
+            .arg("type", "LIMIT")
+            .unwrap()
+            .arg("timeInForce", "GTC")
+            .unwrap()
+            .arg("price", &dec!(52000.00))
+            .unwrap()
+            .arg("quantity", &dec!(0.01000000))
+            .unwrap()
+            .try_arg::<&str>("newClientOrderId", &None)
+            .unwrap()
+            .arg("newOrderRespType", "ACK")
+            .unwrap()
+            .arg("recvWindow", &100)
+            .unwrap()
+            .arg("timestamp", &1645423376532_u64)
+            .unwrap()
+            .arg(
+                "apiKey",
+                "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A",
+            )
+            .unwrap();
+        assert_eq!(
+            params.signature_payload(),
+            "apiKey=vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A&\
+             newOrderRespType=ACK&price=52000.00&quantity=0.01000000&recvWindow=100&\
+             side=SELL&symbol=BTCUSDT&timeInForce=GTC&timestamp=1645423376532&type=LIMIT"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let json = r#"{
+            "id": 7,
+            "status": 400,
+            "error": {"code": -2011, "msg": "Unknown order sent."},
+            "rateLimits": [
+                {"rateLimitType": "REQUEST_WEIGHT", "interval": "MINUTE", "intervalNum": 1,
+                 "limit": 6000, "count": 3}
+            ]
+        }"#;
+        let response: RawResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.id, Some(7));
+        assert_eq!(response.status, 400);
+        assert_eq!(response.error.unwrap().code, -2011);
+        assert_eq!(
+            response.rate_limits,
+            [WsApiRateLimit {
+                rate_limit_type: RateLimitType::RequestWeight,
+                interval: RateLimitInterval::Minute,
+                interval_num: 1,
+                limit: 6000,
+                count: 3,
+            }]
+        );
+    }
+}
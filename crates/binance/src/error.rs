@@ -1,25 +1,81 @@
 use std::borrow::Cow;
+use std::time::Duration;
 
 pub use ccx_api_lib::*;
 use thiserror::Error;
 
+use crate::api::spot::SymbolCapability;
+use crate::api::spot::util::GuardRule;
+use crate::api::spot::util::StaleSnapshot;
+
 #[derive(Clone, Debug, Error)]
 pub enum ApiError {
     #[error("Unauthorized")]
     Unauthorized,
+    /// HTTP 429: a request rate limit is exceeded.
+    #[error("Rate limit exceeded, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
+    /// HTTP 418: the IP is banned for repeatedly exceeding the rate limits.
+    #[error("IP banned, retry after {retry_after:?}")]
+    IpBanned { retry_after: Option<Duration> },
     #[error("Mandatory field(s) omitted: {0}")]
     MandatoryFieldOmitted(Cow<'static, str>),
     #[error("Argument is out of bounds")]
     OutOfBounds,
+    #[error("Order rejected by guard ({rule}): {details}")]
+    GuardRejected { rule: GuardRule, details: String },
+    #[error("Stale order book snapshot: {0}")]
+    StaleSnapshot(StaleSnapshot),
+    #[error("{symbol} does not allow {capability}")]
+    CapabilityNotSupported {
+        symbol: Atom,
+        capability: SymbolCapability,
+    },
+    /// The listen key of a user data stream expired or was closed.
+    #[error("Listen key does not exist")]
+    ListenKeyExpired,
+    /// A websocket request, e.g. a subscription, was rejected.
+    #[error("Websocket request {id} failed with {code}: {msg}")]
+    WsRequestFailed { id: u64, code: i32, msg: String },
+    #[error("{requested} streams requested, a connection is limited to {limit}")]
+    TooManyStreams { requested: usize, limit: usize },
+    /// A depth diff does not continue the local order book, a new snapshot is needed.
+    #[error(transparent)]
+    OrderBookDesynced(#[from] OrderBookDesynced),
 }
 
 impl ApiError {
     pub fn mandatory_field_omitted(field: impl Into<Cow<'static, str>>) -> Self {
         ApiError::MandatoryFieldOmitted(field.into())
     }
+
+    pub fn guard_rejected(rule: GuardRule, details: impl Into<String>) -> Self {
+        ApiError::GuardRejected {
+            rule,
+            details: details.into(),
+        }
+    }
 }
 
-impl CcxApiError for ApiError {}
+impl CcxApiError for ApiError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::RateLimited { .. } => true,
+            // A ban can last for days, only wait for it if its end is known.
+            ApiError::IpBanned { retry_after } => retry_after.is_some(),
+            _ => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiError::RateLimited { retry_after } | ApiError::IpBanned { retry_after } => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+}
 
 pub type BinanceResult<T> = ccx_api_lib::LibResult<T, ApiError>;
 pub type BinanceError = ccx_api_lib::LibError<ApiError>;
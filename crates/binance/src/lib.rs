@@ -5,10 +5,10 @@ pub mod api;
 pub mod client;
 pub mod error;
 pub mod proto;
+#[cfg(feature = "with_network")]
+pub mod retry;
 pub mod util;
 
-pub use self::client::BinanceSigner;
-pub use self::client::SignResult;
 pub use self::error::*;
 pub use self::proto::*;
 #[cfg(feature = "with_network")]
@@ -16,8 +16,11 @@ pub use self::with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    pub use super::api::margin::MarginApi;
     pub use super::api::spot::SpotApi;
     pub use super::api::um::UmApi;
+    pub use super::client::BinanceSigner;
+    pub use super::client::SignResult;
 }
 
 pub use ccx_api_lib;
@@ -3,6 +3,9 @@ use serde::Serialize;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum ChartInterval {
+    /// Spot only.
+    #[serde(rename = "1s")]
+    Second1,
     #[serde(rename = "1m")]
     Minute1,
     #[serde(rename = "3m")]
@@ -36,9 +39,30 @@ pub enum ChartInterval {
 }
 
 impl ChartInterval {
+    /// All intervals, from the shortest to the longest.
+    pub const ALL: [ChartInterval; 16] = [
+        ChartInterval::Second1,
+        ChartInterval::Minute1,
+        ChartInterval::Minute3,
+        ChartInterval::Minute5,
+        ChartInterval::Minute15,
+        ChartInterval::Minute30,
+        ChartInterval::Hour1,
+        ChartInterval::Hour2,
+        ChartInterval::Hour4,
+        ChartInterval::Hour6,
+        ChartInterval::Hour8,
+        ChartInterval::Hour12,
+        ChartInterval::Day1,
+        ChartInterval::Day3,
+        ChartInterval::Week1,
+        ChartInterval::Month1,
+    ];
+
     pub fn as_str(self) -> &'static str {
         use ChartInterval::*;
         match self {
+            Second1 => "1s",
             Minute1 => "1m",
             Minute3 => "3m",
             Minute5 => "5m",
@@ -56,6 +80,39 @@ impl ChartInterval {
             Month1 => "1M",
         }
     }
+
+    /// Length of the interval in milliseconds, `None` for [`ChartInterval::Month1`]
+    /// since months differ in length.
+    pub fn millis(self) -> Option<u64> {
+        use ChartInterval::*;
+        const MINUTE: u64 = 60_000;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+        Some(match self {
+            Second1 => 1_000,
+            Minute1 => MINUTE,
+            Minute3 => 3 * MINUTE,
+            Minute5 => 5 * MINUTE,
+            Minute15 => 15 * MINUTE,
+            Minute30 => 30 * MINUTE,
+            Hour1 => HOUR,
+            Hour2 => 2 * HOUR,
+            Hour4 => 4 * HOUR,
+            Hour6 => 6 * HOUR,
+            Hour8 => 8 * HOUR,
+            Hour12 => 12 * HOUR,
+            Day1 => DAY,
+            Day3 => 3 * DAY,
+            Week1 => 7 * DAY,
+            Month1 => None?,
+        })
+    }
+
+    /// Spacing of the klines of this interval, `None` for [`ChartInterval::Month1`]
+    /// since months differ in length.
+    pub fn duration(self) -> Option<chrono::Duration> {
+        Some(chrono::Duration::milliseconds(self.millis()? as i64))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
@@ -122,6 +179,11 @@ pub enum TransferKind {
     #[serde(rename = "MARGIN_UMFUTURE")]
     MarginUmFuture, // Margin（cross）account transfer to USDⓈ-M Futures
 
+    #[serde(rename = "ISOLATEDMARGIN_MAIN")]
+    IsolatedMarginMain, // Isolated margin account transfer to Spot account, needs `fromSymbol`
+    #[serde(rename = "MAIN_ISOLATEDMARGIN")]
+    MainIsolatedMargin, // Spot account transfer to Isolated margin account, needs `toSymbol`
+
     #[serde(rename = "MINING_MAIN")]
     MiningMain, // Mining account transfer to Spot account
     #[serde(rename = "MINING_UMFUTURE")]
@@ -136,3 +198,54 @@ pub enum TransferKind {
     #[serde(rename = "UMFUTURE_MARGIN")]
     UmFutureMargin, // USDⓈ-M Futures account transfer to Margin（cross）account
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_interval_as_str() {
+        let expected = [
+            "1s", "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d",
+            "1w", "1M",
+        ];
+        for (interval, expected) in ChartInterval::ALL.into_iter().zip(expected) {
+            assert_eq!(interval.as_str(), expected);
+            assert_eq!(
+                serde_json::to_string(&interval).unwrap(),
+                format!("\"{expected}\"")
+            );
+            let query = serde_urlencoded::to_string([
+                ("symbol", "BTCUSDT"),
+                ("interval", interval.as_str()),
+            ])
+            .unwrap();
+            assert_eq!(query, format!("symbol=BTCUSDT&interval={expected}"));
+            let parsed: ChartInterval = serde_json::from_str(&format!("\"{expected}\"")).unwrap();
+            assert_eq!(parsed, interval);
+        }
+        assert!(ChartInterval::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_chart_interval_millis() {
+        assert_eq!(ChartInterval::Second1.millis(), Some(1_000));
+        assert_eq!(ChartInterval::Minute15.millis(), Some(900_000));
+        assert_eq!(ChartInterval::Week1.millis(), Some(604_800_000));
+        assert_eq!(ChartInterval::Month1.millis(), None);
+    }
+
+    #[test]
+    fn test_chart_interval_duration() {
+        use chrono::Duration;
+
+        assert_eq!(
+            ChartInterval::Minute1.duration(),
+            Some(Duration::minutes(1))
+        );
+        assert_eq!(ChartInterval::Hour1.duration(), Some(Duration::hours(1)));
+        assert_eq!(ChartInterval::Day1.duration(), Some(Duration::days(1)));
+        assert_eq!(ChartInterval::Week1.duration(), Some(Duration::weeks(1)));
+        assert_eq!(ChartInterval::Month1.duration(), None);
+    }
+}
@@ -1,22 +1,26 @@
 use std::ops;
+use std::time::Duration;
 
 use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::ApiError;
+use crate::BinanceError;
 use crate::BinanceResult;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct TimeWindow {
     timestamp: u64,
-    recv_window: RecvWindow,
+    /// `None` unless set with [`TimeWindow::with_recv_window`].
+    recv_window: Option<RecvWindow>,
 }
 
 impl TimeWindow {
     pub fn new(timestamp: u64) -> Self {
-        let recv_window = RecvWindow::default();
         TimeWindow {
             timestamp,
-            recv_window,
+            recv_window: None,
         }
     }
 
@@ -29,8 +33,22 @@ impl TimeWindow {
     }
 
     pub fn recv_window(&self) -> RecvWindow {
+        self.recv_window.unwrap_or_default()
+    }
+
+    /// The window set with [`TimeWindow::with_recv_window`], if any.
+    pub fn explicit_recv_window(&self) -> Option<RecvWindow> {
         self.recv_window
     }
+
+    /// Sets the `recvWindow` of the request.
+    ///
+    /// Takes precedence over the one of the client config, even when equal to
+    /// [`RecvWindow::DEFAULT`].
+    pub fn with_recv_window(mut self, recv_window: RecvWindow) -> Self {
+        self.recv_window = Some(recv_window);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -52,15 +70,69 @@ impl ops::Deref for RecvWindow {
 
 impl RecvWindow {
     pub const DEFAULT: RecvWindow = RecvWindow(5000);
+    /// Max window accepted by Binance, in milliseconds.
+    pub const MAX: u32 = 60000;
 
     pub fn new(window: u32) -> BinanceResult<Self> {
         match () {
-            () if window > 60000 => Err(ApiError::OutOfBounds)?,
+            () if window > Self::MAX => Err(ApiError::OutOfBounds)?,
             () => Ok(RecvWindow(window)),
         }
     }
 
+    /// Window of `duration` truncated to milliseconds, at most 60 seconds.
+    pub fn from_duration(duration: Duration) -> BinanceResult<Self> {
+        let window = u32::try_from(duration.as_millis()).map_err(|_| ApiError::OutOfBounds)?;
+        RecvWindow::new(window)
+    }
+
     pub fn is_default(self) -> bool {
         self == Self::DEFAULT
     }
 }
+
+impl TryFrom<Duration> for RecvWindow {
+    type Error = BinanceError;
+
+    fn try_from(duration: Duration) -> BinanceResult<Self> {
+        RecvWindow::from_duration(duration)
+    }
+}
+
+/// A page of a list endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Rows<T> {
+    /// Omitted when the page is empty. The fiat history sends it as `data`.
+    #[serde(default = "Vec::new", alias = "data")]
+    pub rows: Vec<T>,
+    /// Number of rows on all pages.
+    pub total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv_window_from_duration() {
+        let window = RecvWindow::from_duration(Duration::from_millis(10_500)).unwrap();
+        assert_eq!(*window, 10_500);
+        assert_eq!(
+            *RecvWindow::try_from(Duration::from_secs(60)).unwrap(),
+            60_000
+        );
+        assert!(RecvWindow::from_duration(Duration::from_millis(60_001)).is_err());
+        assert!(RecvWindow::from_duration(Duration::from_secs(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_empty_rows() {
+        /// Row type without a `Default` impl.
+        #[derive(Debug, Deserialize)]
+        struct Row {}
+
+        let rows: Rows<Row> = serde_json::from_str(r#"{"total": 0}"#).unwrap();
+        assert!(rows.rows.is_empty());
+        assert_eq!(rows.total, 0);
+    }
+}
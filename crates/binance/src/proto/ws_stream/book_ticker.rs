@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Atom;
+use crate::Decimal;
+
+/// Best bid and ask of `<symbol>@bookTicker`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct BookTickerEvent {
+    /// Order book update id.
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "s")]
+    pub symbol: Atom,
+    #[serde(rename = "b")]
+    pub best_bid_price: Decimal,
+    #[serde(rename = "B")]
+    pub best_bid_qty: Decimal,
+    #[serde(rename = "a")]
+    pub best_ask_price: Decimal,
+    #[serde(rename = "A")]
+    pub best_ask_qty: Decimal,
+}
@@ -2,9 +2,12 @@ use serde::Deserialize;
 use serde::Serialize;
 use string_cache::DefaultAtom as Atom;
 
+use crate::ChartInterval;
+
 mod account_update;
 mod agg_trade;
 mod balance;
+mod book_ticker;
 mod day_ticker;
 mod kline;
 mod mini_ticker;
@@ -17,6 +20,7 @@ mod ws_kline;
 pub use account_update::*;
 pub use agg_trade::*;
 pub use balance::*;
+pub use book_ticker::*;
 pub use day_ticker::*;
 pub use kline::*;
 pub use mini_ticker::*;
@@ -32,6 +36,40 @@ pub enum WsEvent {
     OrderBookDiff(OrderBookDiffEvent),
     Kline(KlineEvent),
     Trade(TradeEvent),
+    MiniTicker(MiniTickerEvent),
+    Ticker(TickerEvent),
+    BookTicker(BookTickerEvent),
+}
+
+/// A market stream event along with the name of its stream, e.g. `bnbusdt@bookTicker`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MarketEvent {
+    pub stream: WsSubscription,
+    pub event: WsEvent,
+}
+
+impl MarketEvent {
+    /// Decodes an event of the raw stream endpoint, which comes without the stream name.
+    pub fn from_raw(stream: WsSubscription, data: &[u8]) -> serde_json::Result<Self> {
+        let event = match stream.stream {
+            WsStream::Depth | WsStream::Depth100ms => {
+                WsEvent::OrderBookDiff(serde_json::from_slice(data)?)
+            }
+            WsStream::Trade => WsEvent::Trade(serde_json::from_slice(data)?),
+            WsStream::AggTrade => WsEvent::AggTrade(serde_json::from_slice(data)?),
+            WsStream::Kline(_) => WsEvent::Kline(serde_json::from_slice(data)?),
+            WsStream::MiniTicker => WsEvent::MiniTicker(serde_json::from_slice(data)?),
+            WsStream::Ticker => WsEvent::Ticker(serde_json::from_slice(data)?),
+            WsStream::BookTicker => WsEvent::BookTicker(serde_json::from_slice(data)?),
+        };
+        Ok(MarketEvent { stream, event })
+    }
+}
+
+impl From<MarketEvent> for WsEvent {
+    fn from(event: MarketEvent) -> Self {
+        event.event
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -56,6 +94,20 @@ impl WsSubscription {
         let market = market.into();
         WsSubscription { market, stream }
     }
+
+    pub fn market(&self) -> &Atom {
+        &self.market
+    }
+
+    pub fn stream(&self) -> WsStream {
+        self.stream
+    }
+}
+
+impl std::fmt::Display for WsSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.market, self.stream.as_str())
+    }
 }
 
 impl<A> From<(A, WsStream)> for WsSubscription
@@ -72,18 +124,57 @@ pub enum WsStream {
     Depth,
     Depth100ms,
     Trade,
+    AggTrade,
+    Kline(ChartInterval),
+    /// Rolling 24hr mini ticker.
+    MiniTicker,
+    /// Rolling 24hr ticker.
+    Ticker,
+    BookTicker,
 }
 
 impl WsStream {
     const DEPTH: &'static str = "depth";
     const DEPTH_100MS: &'static str = "depth@100ms";
     const TRADE: &'static str = "trade";
+    const AGG_TRADE: &'static str = "aggTrade";
+    const KLINE: &'static str = "kline_";
+    const MINI_TICKER: &'static str = "miniTicker";
+    const TICKER: &'static str = "ticker";
+    const BOOK_TICKER: &'static str = "bookTicker";
 
     pub fn as_str(self) -> &'static str {
         match self {
             WsStream::Depth => Self::DEPTH,
             WsStream::Depth100ms => Self::DEPTH_100MS,
             WsStream::Trade => Self::TRADE,
+            WsStream::AggTrade => Self::AGG_TRADE,
+            WsStream::Kline(interval) => Self::kline_as_str(interval),
+            WsStream::MiniTicker => Self::MINI_TICKER,
+            WsStream::Ticker => Self::TICKER,
+            WsStream::BookTicker => Self::BOOK_TICKER,
+        }
+    }
+
+    fn kline_as_str(interval: ChartInterval) -> &'static str {
+        use ChartInterval::*;
+        match interval {
+            Second1 => "kline_1s",
+            Minute1 => "kline_1m",
+            Minute3 => "kline_3m",
+            Minute5 => "kline_5m",
+            Minute15 => "kline_15m",
+            Minute30 => "kline_30m",
+            Hour1 => "kline_1h",
+            Hour2 => "kline_2h",
+            Hour4 => "kline_4h",
+            Hour6 => "kline_6h",
+            Hour8 => "kline_8h",
+            Hour12 => "kline_12h",
+            Day1 => "kline_1d",
+            Day3 => "kline_3d",
+            Week1 => "kline_1w",
+            Month1 => "kline_1M",
         }
     }
 
@@ -93,7 +184,17 @@ impl WsStream {
             Self::DEPTH => Self::Depth,
             Self::DEPTH_100MS => Self::Depth100ms,
             Self::TRADE => Self::Trade,
-            _ => None?,
+            Self::AGG_TRADE => Self::AggTrade,
+            Self::MINI_TICKER => Self::MiniTicker,
+            Self::TICKER => Self::Ticker,
+            Self::BOOK_TICKER => Self::BookTicker,
+            _ => {
+                let interval = s.strip_prefix(Self::KLINE)?;
+                let interval = ChartInterval::ALL
+                    .into_iter()
+                    .find(|i| i.as_str() == interval)?;
+                Self::Kline(interval)
+            }
         })
     }
 }
@@ -117,7 +218,7 @@ pub enum UpstreamApiResult<T> {
 #[serde(untagged)]
 pub enum UpstreamWebsocketMessage<T> {
     Response(UpstreamWebsocketResponse<T>),
-    Event(WsEvent),
+    Event(MarketEvent),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -155,6 +256,7 @@ mod deser {
     use serde::de::Visitor;
     use serde::de::{self};
 
+    use super::MarketEvent;
     use super::WsEvent;
     use super::WsStream;
     use super::WsSubscription;
@@ -164,11 +266,7 @@ mod deser {
         where
             S: Serializer,
         {
-            let mut buffer = String::with_capacity(32);
-            buffer.push_str(&self.market);
-            buffer.push('@');
-            buffer.push_str(self.stream.as_str());
-            serializer.serialize_str(&buffer)
+            serializer.collect_str(self)
         }
     }
 
@@ -253,13 +351,13 @@ mod deser {
         }
     }
 
-    struct WsEventVisitor;
+    struct MarketEventVisitor;
 
-    impl<'de> Visitor<'de> for WsEventVisitor {
-        type Value = WsEvent;
+    impl<'de> Visitor<'de> for MarketEventVisitor {
+        type Value = MarketEvent;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("enum WsEvent")
+            formatter.write_str("struct MarketEvent")
         }
 
         fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
@@ -274,8 +372,7 @@ mod deser {
                         if stream.is_some() {
                             return Err(de::Error::duplicate_field(WsEventField::STREAM));
                         }
-                        let x: WsSubscription = map.next_value()?;
-                        stream = Some(x.stream);
+                        stream = Some(map.next_value::<WsSubscription>()?);
                     }
                     WsEventField::Data => {
                         if result.is_some() {
@@ -284,17 +381,32 @@ mod deser {
                         let stream = stream
                             .as_ref()
                             .ok_or_else(|| de::Error::missing_field(WsEventField::STREAM))?;
-                        result = Some(match stream {
+                        result = Some(match stream.stream {
                             WsStream::Depth | WsStream::Depth100ms => {
                                 WsEvent::OrderBookDiff(map.next_value()?)
                             }
                             WsStream::Trade => WsEvent::Trade(map.next_value()?),
+                            WsStream::AggTrade => WsEvent::AggTrade(map.next_value()?),
+                            WsStream::Kline(_) => WsEvent::Kline(map.next_value()?),
+                            WsStream::MiniTicker => WsEvent::MiniTicker(map.next_value()?),
+                            WsStream::Ticker => WsEvent::Ticker(map.next_value()?),
+                            WsStream::BookTicker => WsEvent::BookTicker(map.next_value()?),
                         });
                     }
                 }
             }
-            let result = result.ok_or_else(|| de::Error::missing_field(WsEventField::STREAM))?;
-            Ok(result)
+            let event = result.ok_or_else(|| de::Error::missing_field(WsEventField::DATA))?;
+            let stream = stream.ok_or_else(|| de::Error::missing_field(WsEventField::STREAM))?;
+            Ok(MarketEvent { stream, event })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for MarketEvent {
+        fn deserialize<D>(deserializer: D) -> Result<MarketEvent, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(MarketEventVisitor)
         }
     }
 
@@ -303,13 +415,15 @@ mod deser {
         where
             D: Deserializer<'de>,
         {
-            deserializer.deserialize_map(WsEventVisitor)
+            MarketEvent::deserialize(deserializer).map(WsEvent::from)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rust_decimal_macros::dec;
+
     use super::*;
 
     #[test]
@@ -362,4 +476,196 @@ mod tests {
 
         let _res = serde_json::from_str::<UpstreamWebsocketMessage<WsEvent>>(input).unwrap();
     }
+
+    fn decode_market_event(stream: &str, data: &str) -> WsEvent {
+        let input = format!(r#"{{"stream":"{stream}","data":{data}}}"#);
+        serde_json::from_str(&input).unwrap()
+    }
+
+    #[test]
+    fn test_stream_names() {
+        let streams = [
+            (WsStream::Depth100ms, "btcusdt@depth@100ms"),
+            (WsStream::Trade, "btcusdt@trade"),
+            (WsStream::AggTrade, "btcusdt@aggTrade"),
+            (WsStream::Kline(ChartInterval::Minute1), "btcusdt@kline_1m"),
+            (WsStream::Kline(ChartInterval::Month1), "btcusdt@kline_1M"),
+            (WsStream::MiniTicker, "btcusdt@miniTicker"),
+            (WsStream::Ticker, "btcusdt@ticker"),
+            (WsStream::BookTicker, "btcusdt@bookTicker"),
+        ];
+        for (stream, name) in streams {
+            let subscription = WsSubscription::new("btcusdt", stream);
+            assert_eq!(
+                serde_json::to_string(&subscription).unwrap(),
+                format!("\"{name}\"")
+            );
+            let decoded: WsSubscription = serde_json::from_str(&format!("\"{name}\"")).unwrap();
+            assert_eq!(decoded, subscription);
+        }
+        assert!(serde_json::from_str::<WsSubscription>("\"btcusdt@kline_2m\"").is_err());
+
+        let request = UpstreamApiRequest {
+            id: 7,
+            payload: WsCommand::Unsubscribe(Box::new([WsSubscription::new(
+                "btcusdt",
+                WsStream::BookTicker,
+            )])),
+        };
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"id":7,"method":"UNSUBSCRIBE","params":["btcusdt@bookTicker"]}"#
+        );
+    }
+
+    #[test]
+    fn decode_agg_trade_event() {
+        let event = decode_market_event(
+            "bnbbtc@aggTrade",
+            r#"{"e":"aggTrade","E":1672515782136,"s":"BNBBTC","a":12345,"p":"0.001","q":"100",
+                "f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#,
+        );
+        let WsEvent::AggTrade(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.id, 12345);
+        assert_eq!(event.first_trade_id, 100);
+        assert_eq!(event.last_trade_id, 105);
+        assert_eq!(event.price, dec!(0.001));
+    }
+
+    #[test]
+    fn decode_trade_event() {
+        let event = decode_market_event(
+            "btcusdt@trade",
+            r#"{"e":"trade","E":1722723254022,"s":"BTCUSDT","t":3717726327,"p":"60668.01000000",
+                "q":"0.00009000","T":1722723254021,"m":true,"M":true}"#,
+        );
+        let WsEvent::Trade(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.id, 3717726327);
+        assert_eq!(event.qty, dec!(0.00009));
+    }
+
+    #[test]
+    fn decode_kline_event() {
+        let event = decode_market_event(
+            "bnbbtc@kline_1m",
+            r#"{"e":"kline","E":1672515782136,"s":"BNBBTC","k":{"t":1672515780000,
+                "T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,"o":"0.0010",
+                "c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,"x":false,
+                "q":"1.0000","V":"500","Q":"0.500","B":"123456"}}"#,
+        );
+        let WsEvent::Kline(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.kline.interval, ChartInterval::Minute1);
+        assert_eq!(event.kline.close, dec!(0.0020));
+        assert!(!event.kline.is_final_bar);
+    }
+
+    #[test]
+    fn decode_mini_ticker_event() {
+        let event = decode_market_event(
+            "bnbbtc@miniTicker",
+            r#"{"e":"24hrMiniTicker","E":1672515782136,"s":"BNBBTC","c":"0.0025","o":"0.0010",
+                "h":"0.0025","l":"0.0010","v":"10000","q":"18"}"#,
+        );
+        let WsEvent::MiniTicker(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.close, dec!(0.0025));
+        assert_eq!(event.quote_volume, dec!(18));
+    }
+
+    #[test]
+    fn decode_ticker_event() {
+        let event = decode_market_event(
+            "bnbbtc@ticker",
+            r#"{"e":"24hrTicker","E":1672515782136,"s":"BNBBTC","p":"0.0015","P":"250.00",
+                "w":"0.0018","x":"0.0009","c":"0.0025","Q":"10","b":"0.0024","B":"10",
+                "a":"0.0026","A":"100","o":"0.0010","h":"0.0025","l":"0.0010","v":"10000",
+                "q":"18","O":0,"C":86400000,"F":0,"L":18150,"n":18151}"#,
+        );
+        let WsEvent::Ticker(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.best_bid_price, dec!(0.0024));
+        assert_eq!(event.number_of_trades, 18151);
+    }
+
+    #[test]
+    fn decode_book_ticker_event() {
+        let event = decode_market_event(
+            "bnbusdt@bookTicker",
+            r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000",
+                "a":"25.36520000","A":"40.66000000"}"#,
+        );
+        assert_eq!(
+            event,
+            WsEvent::BookTicker(BookTickerEvent {
+                update_id: 400900217,
+                symbol: "BNBUSDT".into(),
+                best_bid_price: dec!(25.3519),
+                best_bid_qty: dec!(31.21),
+                best_ask_price: dec!(25.3652),
+                best_ask_qty: dec!(40.66),
+            })
+        );
+    }
+
+    #[test]
+    fn decode_depth_100ms_event() {
+        let event = decode_market_event(
+            "bnbbtc@depth@100ms",
+            r#"{"e":"depthUpdate","E":1672515782136,"s":"BNBBTC","U":157,"u":160,
+                "b":[["0.0024","10"]],"a":[["0.0026","100"]]}"#,
+        );
+        let WsEvent::OrderBookDiff(event) = event else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(event.first_update_id, 157);
+        assert_eq!(event.final_update_id, 160);
+        assert_eq!(event.bids[0].price, dec!(0.0024));
+    }
+
+    #[test]
+    fn decode_market_event_stream() {
+        let input = r#"{"stream":"bnbusdt@bookTicker","data":{"u":400900217,"s":"BNBUSDT",
+            "b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}}"#;
+        let event: MarketEvent = serde_json::from_str(input).unwrap();
+        assert_eq!(event.stream.market(), "bnbusdt");
+        assert_eq!(event.stream.stream(), WsStream::BookTicker);
+        assert!(matches!(event.event, WsEvent::BookTicker(_)));
+
+        let input = r#"{"stream":"btcusdt@depth@100ms","data":{}}"#;
+        assert!(serde_json::from_str::<MarketEvent>(input).is_err());
+    }
+
+    #[test]
+    fn decode_subscription_responses() {
+        let input = r#"{"result":null,"id":1}"#;
+        let res = serde_json::from_str::<UpstreamWebsocketMessage<()>>(input).unwrap();
+        assert_eq!(
+            res,
+            UpstreamWebsocketMessage::Response(UpstreamWebsocketResponse {
+                id: 1,
+                payload: Some(UpstreamWebsocketResult::Ok(())),
+            })
+        );
+
+        let input = r#"{"error":{"code":2,"msg":"Invalid request"},"id":3}"#;
+        let res = serde_json::from_str::<UpstreamWebsocketMessage<()>>(input).unwrap();
+        assert_eq!(
+            res,
+            UpstreamWebsocketMessage::Response(UpstreamWebsocketResponse {
+                id: 3,
+                payload: Some(UpstreamWebsocketResult::Err {
+                    code: 2,
+                    msg: "Invalid request".into(),
+                }),
+            })
+        );
+    }
 }
@@ -0,0 +1,302 @@
+//! Retrying of requests that failed with a transient error.
+//!
+//! A request that failed with a server error, a timeout or a broken connection may still
+//! have been executed, e.g. placed an order. [`with_backoff`] only retries such errors for
+//! policies marked [`idempotent`](RetryPolicy::idempotent), so that wrapping a new order does
+//! not place it twice. Rate limit errors are retried either way, the exchange rejects those
+//! requests without executing them.
+//!
+//! ```no_run
+//! # async fn run(api: ccx_binance::SpotApi<ccx_binance::ApiCred>) -> ccx_binance::BinanceResult<()> {
+//! use ccx_binance::retry::RetryPolicy;
+//! use ccx_binance::retry::with_backoff;
+//!
+//! let ticker = with_backoff(RetryPolicy::default().idempotent(), || async {
+//!     api.ticker_price("BTCUSDT")?.await
+//! })
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::ApiError;
+use crate::error::BinanceError;
+use crate::error::BinanceResult;
+
+/// Exponential backoff with jitter.
+///
+/// The n-th retry waits `initial_delay * multiplier^n`, capped at `max_delay` and reduced
+/// by up to `jitter` (a fraction from 0 to 1) at random so that clients do not retry
+/// in lockstep. A `Retry-After` reported by the exchange is used as is instead.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    jitter: f64,
+    idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2,
+            jitter: 0.2,
+            idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Also retries server errors, timeouts and connection failures.
+    ///
+    /// Only for requests that are safe to repeat, e.g. market data or order queries.
+    /// Never for new orders or cancellations.
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Whether to retry after `error`.
+    fn should_retry(&self, error: &BinanceError) -> bool {
+        if !error.is_retryable() {
+            return false;
+        }
+        // Rate limited requests are rejected before they are executed.
+        let rejected = matches!(
+            error,
+            BinanceError::ApiError(ApiError::RateLimited { .. } | ApiError::IpBanned { .. })
+        );
+        rejected || self.idempotent
+    }
+
+    /// Delay before the `retry`-th retry (starting with 0), without jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(retry);
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+
+    /// Delay before retrying after `error`, `random` is a number in `[0, 1)`.
+    fn delay(&self, retry: u32, error: &BinanceError, random: f64) -> Duration {
+        error
+            .retry_after()
+            .unwrap_or_else(|| self.backoff(retry).mul_f64(1.0 - self.jitter * random))
+    }
+}
+
+/// Runs `f` until it succeeds, fails with an error that is not
+/// [retryable](ccx_api_lib::LibError::is_retryable), or the retries of `policy` run out.
+///
+/// Unless `policy` is [`idempotent`](RetryPolicy::idempotent), only rate limit errors are
+/// retried, see the [module docs](self).
+///
+/// `f` has to send a new request on every call, e.g. create a new `Task`.
+pub async fn with_backoff<F, Fut, T>(policy: RetryPolicy, mut f: F) -> BinanceResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = BinanceResult<T>>,
+{
+    let mut retry = 0;
+    loop {
+        match f().await {
+            Err(e) if policy.should_retry(&e) && retry < policy.max_retries => {
+                let delay = policy.delay(retry, &e, random());
+                log::warn!("Retrying in {delay:?} after: {e}");
+                actix_rt::time::sleep(delay).await;
+                retry += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Number in `[0, 1)` for the jitter, randomly seeded on every call.
+fn random() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    let hash = RandomState::new().hash_one(std::time::SystemTime::now());
+    (hash >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::error::ApiServiceError;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(100), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new()
+            .initial_delay(Duration::from_millis(100))
+            .jitter(0.5);
+        let server_error = BinanceError::from(ApiServiceError::ServerError);
+        assert_eq!(
+            policy.delay(1, &server_error, 0.0),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            policy.delay(1, &server_error, 0.5),
+            Duration::from_millis(150)
+        );
+
+        // The delay requested by the exchange takes precedence.
+        let rate_limited = BinanceError::from(ApiError::RateLimited {
+            retry_after: Some(Duration::from_secs(7)),
+        });
+        assert_eq!(policy.delay(1, &rate_limited, 0.5), Duration::from_secs(7));
+        let rate_limited = BinanceError::from(ApiError::RateLimited { retry_after: None });
+        assert_eq!(
+            policy.delay(0, &rate_limited, 0.0),
+            Duration::from_millis(100)
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(BinanceError::from(ApiServiceError::ServiceUnavailable).is_retryable());
+        assert!(BinanceError::from(ApiError::RateLimited { retry_after: None }).is_retryable());
+        assert!(
+            BinanceError::from(ApiError::IpBanned {
+                retry_after: Some(Duration::from_secs(120)),
+            })
+            .is_retryable()
+        );
+        assert!(!BinanceError::from(ApiError::IpBanned { retry_after: None }).is_retryable());
+        assert!(!BinanceError::from(ApiError::Unauthorized).is_retryable());
+        assert!(!BinanceError::from(ApiError::OutOfBounds).is_retryable());
+    }
+
+    #[actix_rt::test]
+    async fn test_with_backoff_retries_transient_errors() {
+        let policy = RetryPolicy::new()
+            .initial_delay(Duration::from_millis(1))
+            .idempotent();
+        let calls = Cell::new(0);
+        let res = with_backoff(policy, || {
+            calls.set(calls.get() + 1);
+            futures::future::ready(match calls.get() {
+                1 => Err(ApiServiceError::ServiceUnavailable.into()),
+                2 => Err(ApiError::RateLimited {
+                    retry_after: Some(Duration::from_millis(1)),
+                }
+                .into()),
+                n => Ok(n),
+            })
+        })
+        .await;
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_with_backoff_gives_up() {
+        let policy = RetryPolicy::new()
+            .max_retries(2)
+            .initial_delay(Duration::from_millis(1))
+            .idempotent();
+        let calls = Cell::new(0);
+        let res: BinanceResult<()> = with_backoff(policy.clone(), || {
+            calls.set(calls.get() + 1);
+            futures::future::err(ApiServiceError::ServerError.into())
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(calls.get(), 3);
+
+        // Errors that are not retryable are returned right away.
+        calls.set(0);
+        let res: BinanceResult<()> = with_backoff(policy, || {
+            calls.set(calls.get() + 1);
+            futures::future::err(ApiError::Unauthorized.into())
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_with_backoff_does_not_repeat_executed_requests() {
+        let policy = RetryPolicy::new().initial_delay(Duration::from_millis(1));
+        let calls = Cell::new(0);
+        let res: BinanceResult<()> = with_backoff(policy.clone(), || {
+            calls.set(calls.get() + 1);
+            futures::future::err(ApiServiceError::ServiceUnavailable.into())
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(calls.get(), 1);
+
+        // A rate limited request was not executed and is retried.
+        calls.set(0);
+        let res = with_backoff(policy, || {
+            calls.set(calls.get() + 1);
+            futures::future::ready(match calls.get() {
+                1 => Err(ApiError::RateLimited {
+                    retry_after: Some(Duration::from_millis(1)),
+                }
+                .into()),
+                n => Ok(n),
+            })
+        })
+        .await;
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_random() {
+        for _ in 0..100 {
+            assert!((0.0..1.0).contains(&random()));
+        }
+    }
+}
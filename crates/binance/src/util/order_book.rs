@@ -1,22 +1,29 @@
 use std::collections::BTreeMap;
 
+use ccx_api_lib::DiffSequence;
+use ccx_api_lib::OrderBookDesynced;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::Zero;
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::BinanceError;
+use crate::ApiError;
 use crate::BinanceResult;
 use crate::ws_stream::OrderBookDiffEvent;
 
+/// Local order book maintained from a depth snapshot and the diff stream.
+///
+/// Diffs are buffered until [`init`](Self::init) gets a snapshot, e.g. `SpotApi::depth` with
+/// [`OrderBookLimit::N5000`](crate::api::spot::OrderBookLimit::N5000). When the diffs do not
+/// continue the book, [`ApiError::OrderBookDesynced`] is returned and the updater goes back to
+/// buffering, so a new snapshot has to be passed to `init`.
 pub enum OrderBookUpdater {
     Preparing { buffer: Vec<OrderBookDiffEvent> },
     Ready { state: OrderBookState },
 }
 
 pub struct OrderBookState {
-    last_update_id: u64,
-    dirty: bool,
+    sequence: DiffSequence,
     asks: BTreeMap<Decimal, Decimal>,
     bids: BTreeMap<Decimal, Decimal>,
 }
@@ -61,17 +68,38 @@ impl OrderBookUpdater {
     pub fn push_diff(&mut self, update: OrderBookDiffEvent) -> BinanceResult<()> {
         match self {
             OrderBookUpdater::Preparing { buffer } => buffer.push(update),
-            OrderBookUpdater::Ready { state } => state.update(update)?,
+            OrderBookUpdater::Ready { state } => match state.check(&update) {
+                Ok(true) => state.apply(update),
+                Ok(false) => {}
+                Err(e) => {
+                    *self = OrderBookUpdater::Preparing {
+                        buffer: vec![update],
+                    };
+                    Err(ApiError::from(e))?
+                }
+            },
         }
         Ok(())
     }
 
+    /// Applies the buffered diffs on top of `snapshot`.
+    ///
+    /// Fails with [`ApiError::OrderBookDesynced`] when the snapshot is older than the buffered
+    /// diffs, which are kept for the next snapshot.
     pub fn init(&mut self, snapshot: OrderBook) -> BinanceResult<()> {
         match self {
             OrderBookUpdater::Preparing { buffer } => {
                 let mut state = OrderBookState::new(snapshot);
-                for diff in buffer.drain(..) {
-                    state.update(diff)?;
+                let mut diffs = std::mem::take(buffer).into_iter();
+                while let Some(diff) = diffs.next() {
+                    match state.check(&diff) {
+                        Ok(true) => state.apply(diff),
+                        Ok(false) => {}
+                        Err(e) => {
+                            *buffer = std::iter::once(diff).chain(diffs).collect();
+                            return Err(ApiError::from(e).into());
+                        }
+                    }
                 }
                 *self = OrderBookUpdater::Ready { state };
                 Ok(())
@@ -93,13 +121,29 @@ impl Default for OrderBookUpdater {
 impl OrderBookState {
     pub fn new(snapshot: OrderBook) -> Self {
         OrderBookState {
-            last_update_id: snapshot.last_update_id,
-            dirty: true,
+            sequence: DiffSequence::new(snapshot.last_update_id),
             asks: snapshot.asks.iter().map(|v| (v.price, v.qty)).collect(),
             bids: snapshot.bids.iter().map(|v| (v.price, v.qty)).collect(),
         }
     }
 
+    pub fn last_update_id(&self) -> u64 {
+        self.sequence.last_update_id()
+    }
+
+    /// Copy of the book with bids from the highest and asks from the lowest price.
+    pub fn to_order_book(&self) -> OrderBook {
+        OrderBook {
+            last_update_id: self.last_update_id(),
+            bids: (self.bids.iter().rev())
+                .map(|(&price, &qty)| Bid { price, qty })
+                .collect(),
+            asks: (self.asks.iter())
+                .map(|(&price, &qty)| Ask { price, qty })
+                .collect(),
+        }
+    }
+
     pub fn asks(&self) -> &BTreeMap<Decimal, Decimal> {
         &self.asks
     }
@@ -209,38 +253,19 @@ impl OrderBookState {
     }
 
     pub fn update(&mut self, diff: OrderBookDiffEvent) -> BinanceResult<()> {
-        /*
-           Drop any event where final_update_id is <= lastUpdateId in the snapshot.
-
-           The first processed event should have
-               first_update_id <= lastUpdateId+1 AND final_update_id >= lastUpdateId+1.
-
-           While listening to the stream, each new event's first_update_id should be equal
-               to the previous event's final_update_id + 1.
-        */
-        let next_id = self.last_update_id + 1;
-        if self.dirty {
-            if diff.final_update_id < next_id {
-                // Ignore an old update.
-                return Ok(());
-            }
-            if diff.first_update_id > next_id {
-                Err(BinanceError::other(format!(
-                    "first_update_id > next_id:   {};   {}",
-                    diff.first_update_id, next_id
-                )))?
-            }
-            // ^^ ensures diff.first_update_id <= next_id && diff.final_update_id > next_id
-            self.dirty = false;
-        } else if diff.first_update_id != next_id {
-            Err(BinanceError::other(format!(
-                "first_update_id != next_id:   {};   {}",
-                diff.first_update_id, next_id
-            )))?
+        if self.check(&diff).map_err(ApiError::from)? {
+            self.apply(diff);
         }
+        Ok(())
+    }
 
-        self.last_update_id = diff.final_update_id;
+    /// Whether `diff` continues the book, `false` for an outdated diff.
+    fn check(&mut self, diff: &OrderBookDiffEvent) -> Result<bool, OrderBookDesynced> {
+        self.sequence
+            .accept(diff.first_update_id, diff.final_update_id)
+    }
 
+    fn apply(&mut self, diff: OrderBookDiffEvent) {
         for e in diff.asks {
             if e.qty.is_zero() {
                 self.asks.remove(&e.price);
@@ -255,6 +280,73 @@ impl OrderBookState {
                 self.bids.insert(e.price, e.qty);
             }
         }
-        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::BinanceError;
+    use crate::api::spot::SpotOrderBook;
+
+    #[derive(Deserialize)]
+    struct Recording {
+        snapshot: SpotOrderBook,
+        diffs: Vec<OrderBookDiffEvent>,
+    }
+
+    fn recording() -> Recording {
+        serde_json::from_str(include_str!("../../examples/data/depth_sync.json")).unwrap()
+    }
+
+    #[test]
+    fn test_sync_and_gap() {
+        let Recording { snapshot, diffs } = recording();
+        let mut diffs = diffs.into_iter();
+        let mut updater = OrderBookUpdater::new();
+
+        // 157..=160 is older than the snapshot, 161..=163 brackets its last update id.
+        for diff in diffs.by_ref().take(2) {
+            updater.push_diff(diff).unwrap();
+        }
+        updater.init(snapshot.clone().into()).unwrap();
+        let state = updater.state().unwrap();
+        assert_eq!(state.last_update_id(), 163);
+        assert_eq!(state.next_bid(), Some((&dec!(0.0024), &dec!(12))));
+        assert_eq!(state.next_ask(), Some((&dec!(0.0026), &dec!(90))));
+
+        updater.push_diff(diffs.next().unwrap()).unwrap();
+        let state = updater.state().unwrap();
+        assert_eq!(state.last_update_id(), 165);
+        let book = state.to_order_book();
+        assert_eq!(book.last_update_id, 165);
+        assert_eq!(book.bids.first().unwrap().price, dec!(0.0023));
+        assert_eq!(book.asks.first().unwrap().price, dec!(0.0026));
+        assert!(book.bids.windows(2).all(|w| w[0].price > w[1].price));
+        assert!(book.asks.windows(2).all(|w| w[0].price < w[1].price));
+        assert_eq!(state.bids().get(&dec!(0.0024)), None);
+        assert_eq!(state.next_bid(), Some((&dec!(0.0023), &dec!(100))));
+
+        // 166 is missing.
+        let err = updater.push_diff(diffs.next().unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            BinanceError::ApiError(ApiError::OrderBookDesynced(OrderBookDesynced {
+                expected: 166,
+                first_update_id: 167,
+            }))
+        ));
+        assert!(updater.state().is_none());
+
+        // The old snapshot can not continue the buffered diffs either.
+        updater.init(snapshot.into()).unwrap_err();
+        assert!(updater.state().is_none());
+
+        let mut snapshot = recording().snapshot;
+        snapshot.last_update_id = 166;
+        updater.init(snapshot.into()).unwrap();
+        assert_eq!(updater.state().unwrap().last_update_id(), 168);
     }
 }
@@ -0,0 +1,76 @@
+//! Minimal HTTP server mocking the Binance REST API, shared by the integration tests.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+/// Response of the mock.
+pub struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    /// `200 OK` with a JSON body.
+    pub fn ok(body: impl Into<String>) -> Self {
+        Response {
+            status: "200 OK",
+            body: body.into(),
+        }
+    }
+
+    /// Error with the given status line, e.g. `400 Bad Request`, and a JSON body.
+    pub fn error(status: &'static str, body: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: body.into(),
+        }
+    }
+}
+
+/// Serves one request per connection, answering with what `respond` returns for the
+/// request line. The request lines are recorded in `request_lines`.
+pub async fn serve(
+    listener: TcpListener,
+    mut respond: impl FnMut(&str) -> Response,
+    request_lines: Arc<Mutex<Vec<String>>>,
+) {
+    loop {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+            let mut chunk = [0; 1024];
+            let n = stream.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection closed before the request was read");
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let request = String::from_utf8_lossy(&buf);
+        let request_line = request.lines().next().unwrap_or_default().to_string();
+        let Response { status, body } = respond(&request_line);
+        request_lines.lock().unwrap().push(request_line);
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.unwrap();
+    }
+}
+
+/// Starts [`serve`] on a local port, returning its address and the recorded request lines.
+pub async fn start(
+    respond: impl FnMut(&str) -> Response + 'static,
+) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let request_lines = Arc::new(Mutex::new(Vec::new()));
+    actix_rt::spawn(serve(listener, respond, request_lines.clone()));
+    (addr, request_lines)
+}
@@ -0,0 +1,229 @@
+//! Checks that a market stream picks the endpoint by the number of streams, reconnects and
+//! restores its subscriptions.
+#![cfg(feature = "with_network")]
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use actix::Actor;
+use actix::ActorContext;
+use actix::StreamHandler;
+use actix_web::App;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::HttpServer;
+use actix_web::web;
+use actix_web_actors::ws;
+use ccx_binance::ApiCred;
+use ccx_binance::ApiError;
+use ccx_binance::BinanceError;
+use ccx_binance::SpotApi;
+use ccx_binance::client::MAX_STREAMS_PER_CONNECTION;
+use ccx_binance::ws_stream::WsEvent;
+use ccx_binance::ws_stream::WsStream;
+use futures::StreamExt;
+
+#[derive(Clone, Default)]
+struct Server {
+    connections: Arc<AtomicU64>,
+    requests: Arc<Mutex<Vec<String>>>,
+    raw_streams: Arc<Mutex<Vec<String>>>,
+}
+
+/// Answers every request and sends a trade with the connection number as its id.
+/// The first connection is closed right after.
+struct MockStream {
+    connection: u64,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Actor for MockStream {
+    type Context = ws::WebsocketContext<Self>;
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MockStream {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => {
+                self.requests.lock().unwrap().push(text.to_string());
+                let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                ctx.text(format!(r#"{{"result":null,"id":{}}}"#, request["id"]));
+                ctx.text(trade_event(self.connection));
+                if self.connection == 1 {
+                    ctx.close(None);
+                    ctx.stop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Sends a trade with the connection number as its id right away, without the stream name.
+/// The first connection is closed right after.
+struct MockRawStream {
+    connection: u64,
+}
+
+impl Actor for MockRawStream {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.text(raw_trade_event(self.connection));
+        if self.connection == 1 {
+            ctx.close(None);
+            ctx.stop();
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MockRawStream {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        if let Ok(ws::Message::Ping(msg)) = msg {
+            ctx.pong(&msg);
+        }
+    }
+}
+
+fn raw_trade_event(id: u64) -> String {
+    format!(
+        r#"{{"e":"trade","E":1722723254022,"s":"BTCUSDT","t":{id},"p":"60668.01",
+            "q":"0.00009","T":1722723254021,"m":true,"M":true}}"#
+    )
+}
+
+fn trade_event(id: u64) -> String {
+    format!(
+        r#"{{"stream":"btcusdt@trade","data":{}}}"#,
+        raw_trade_event(id)
+    )
+}
+
+async fn stream(
+    req: HttpRequest,
+    payload: web::Payload,
+    server: web::Data<Server>,
+) -> actix_web::Result<HttpResponse> {
+    let connection = server.connections.fetch_add(1, Ordering::SeqCst) + 1;
+    let requests = server.requests.clone();
+    ws::start(
+        MockStream {
+            connection,
+            requests,
+        },
+        &req,
+        payload,
+    )
+}
+
+async fn raw_stream(
+    req: HttpRequest,
+    payload: web::Payload,
+    path: web::Path<String>,
+    server: web::Data<Server>,
+) -> actix_web::Result<HttpResponse> {
+    server.raw_streams.lock().unwrap().push(path.into_inner());
+    let connection = server.connections.fetch_add(1, Ordering::SeqCst) + 1;
+    ws::start(MockRawStream { connection }, &req, payload)
+}
+
+fn start_server(server: Server) -> SpotApi<ApiCred> {
+    let http = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(server.clone()))
+            .route("/stream", web::get().to(stream))
+            .route("/ws/{stream}", web::get().to(raw_stream))
+    })
+    .workers(1)
+    .disable_signals()
+    .bind("127.0.0.1:0")
+    .unwrap();
+    let addr = http.addrs()[0];
+    actix_rt::spawn(http.run());
+
+    SpotApi::with_base_urls(
+        ApiCred::new(None, None),
+        format!("http://{addr}/").parse().unwrap(),
+        format!("ws://{addr}/stream").parse().unwrap(),
+        None,
+    )
+}
+
+#[actix_rt::test]
+async fn test_market_stream_resubscribes() {
+    let server = Server::default();
+    let api = start_server(server.clone());
+    let mut events = api
+        .ws()
+        .await
+        .unwrap()
+        .subscribe([("btcusdt", WsStream::Trade), ("ethusdt", WsStream::Trade)])
+        .unwrap();
+
+    for connection in 1..=2 {
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.stream.market(), "btcusdt");
+        assert_eq!(event.stream.stream(), WsStream::Trade);
+        match event.event {
+            WsEvent::Trade(trade) => assert_eq!(trade.id, connection),
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    assert_eq!(server.connections.load(Ordering::SeqCst), 2);
+    let requests = server.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0], requests[1]);
+    assert!(
+        requests[0]
+            .ends_with(r#""method":"SUBSCRIBE","params":["btcusdt@trade","ethusdt@trade"]}"#),
+        "{}",
+        requests[0]
+    );
+
+    let too_many = (0..=MAX_STREAMS_PER_CONNECTION).map(|i| (format!("s{i}"), WsStream::Trade));
+    let res = api.ws().await.unwrap().subscribe(too_many);
+    assert!(matches!(
+        res,
+        Err(BinanceError::ApiError(ApiError::TooManyStreams {
+            requested: 1025,
+            limit: 1024
+        }))
+    ));
+}
+
+#[actix_rt::test]
+async fn test_single_stream_uses_raw_endpoint() {
+    let server = Server::default();
+    let api = start_server(server.clone());
+    let mut events = api.market_stream([("btcusdt", WsStream::Trade)]).unwrap();
+
+    for connection in 1..=2 {
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.stream.market(), "btcusdt");
+        assert_eq!(event.stream.stream(), WsStream::Trade);
+        match event.event {
+            WsEvent::Trade(trade) => assert_eq!(trade.id, connection),
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    assert_eq!(server.connections.load(Ordering::SeqCst), 2);
+    assert!(server.requests.lock().unwrap().is_empty());
+    let raw_streams = server.raw_streams.lock().unwrap().clone();
+    assert_eq!(raw_streams, ["btcusdt@trade", "btcusdt@trade"]);
+
+    // An open connection to the combined endpoint is replaced as well.
+    let mut events = api
+        .ws()
+        .await
+        .unwrap()
+        .subscribe([("btcusdt", WsStream::Trade)])
+        .unwrap();
+    events.next().await.unwrap().unwrap();
+    assert_eq!(server.raw_streams.lock().unwrap().len(), 3);
+    assert!(server.requests.lock().unwrap().is_empty());
+}
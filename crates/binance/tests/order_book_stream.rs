@@ -0,0 +1,117 @@
+//! Checks that the order book stream bootstraps from a snapshot and resyncs after a gap.
+#![cfg(feature = "with_network")]
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use actix::Actor;
+use actix::StreamHandler;
+use actix_web::App;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::HttpServer;
+use actix_web::web;
+use actix_web_actors::ws;
+use ccx_binance::ApiCred;
+use ccx_binance::SpotApi;
+use ccx_binance::api::spot::DepthUpdateSpeed;
+use ccx_binance::api::spot::OrderBookEvent;
+use ccx_binance::util::OrderBookState;
+use futures::StreamExt;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+use serde_json::json;
+
+fn recording() -> Value {
+    serde_json::from_str(include_str!("../examples/data/depth_sync.json")).unwrap()
+}
+
+/// Sends the recorded diffs, the last of which skips an update, once connected.
+struct MockStream;
+
+impl Actor for MockStream {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        for diff in recording()["diffs"].as_array().unwrap() {
+            ctx.text(diff.to_string());
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MockStream {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        if let Ok(ws::Message::Ping(msg)) = msg {
+            ctx.pong(&msg);
+        }
+    }
+}
+
+/// Raw stream endpoint, a single stream does not use the combined one.
+async fn stream(
+    req: HttpRequest,
+    payload: web::Payload,
+    path: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    assert_eq!(path.as_str(), "bnbbtc@depth@100ms");
+    ws::start(MockStream, &req, payload)
+}
+
+/// The recorded snapshot, then one that continues the diffs after the gap.
+async fn depth(snapshots: web::Data<Arc<AtomicU64>>) -> HttpResponse {
+    let mut snapshot = recording()["snapshot"].clone();
+    if snapshots.fetch_add(1, Ordering::SeqCst) > 0 {
+        snapshot["lastUpdateId"] = json!(166);
+    }
+    HttpResponse::Ok().json(snapshot)
+}
+
+#[actix_rt::test]
+async fn test_order_book_stream_resyncs() {
+    let snapshots = Arc::new(AtomicU64::new(0));
+    let state = snapshots.clone();
+    let http = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .route("/ws/{stream}", web::get().to(stream))
+            .route("/api/v3/depth", web::get().to(depth))
+    })
+    .workers(1)
+    .disable_signals()
+    .bind("127.0.0.1:0")
+    .unwrap();
+    let addr = http.addrs()[0];
+    actix_rt::spawn(http.run());
+
+    let api = SpotApi::with_base_urls(
+        ApiCred::new(None, None),
+        format!("http://{addr}/").parse().unwrap(),
+        format!("ws://{addr}/stream").parse().unwrap(),
+        None,
+    );
+    let mut books = api
+        .order_book_stream("BNBBTC", DepthUpdateSpeed::Ms100)
+        .await
+        .unwrap();
+
+    // The diffs may arrive before the first snapshot, so the events depend on timing but
+    // the book ends up the same.
+    let mut state = None;
+    while state
+        .as_ref()
+        .is_none_or(|state: &OrderBookState| state.last_update_id() < 168)
+    {
+        match books.next().await.unwrap().unwrap() {
+            OrderBookEvent::Snapshot(book) => state = Some(OrderBookState::new(book)),
+            OrderBookEvent::Diff(diff) => state.as_mut().unwrap().update(diff).unwrap(),
+        }
+    }
+    let state = state.unwrap();
+    assert_eq!(state.last_update_id(), 168);
+    assert_eq!(state.bids().get(&dec!(0.0024)), Some(&dec!(10)));
+    assert_eq!(state.bids().get(&dec!(0.0023)), Some(&dec!(90)));
+    assert_eq!(state.next_ask(), Some((&dec!(0.0026), &dec!(100))));
+    assert_eq!(state.asks().len(), 1);
+    assert_eq!(snapshots.load(Ordering::SeqCst), 2);
+}
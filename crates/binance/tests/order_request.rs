@@ -0,0 +1,52 @@
+//! Checks the query string of a new order sent to the REST API.
+#![cfg(feature = "with_network")]
+
+use ccx_binance::SpotApi;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::spot::NewOrderRequest;
+use ccx_binance::api::spot::OrderSide;
+use ccx_binance::api::spot::OrderType;
+use ccx_binance::api::spot::TimeInForce;
+use ccx_binance::client::ApiCred;
+use ccx_binance::client::Config;
+use rust_decimal_macros::dec;
+
+mod common;
+
+use common::Response;
+
+#[actix_rt::test]
+async fn test_order_decimals_in_plain_notation() {
+    let (addr, request_lines) = common::start(|_| Response::ok("{}")).await;
+
+    let url: url::Url = format!("http://{addr}/").parse().unwrap();
+    let config = Config::new(
+        ApiCred::new(Some("key".into()), Some("secret".into())),
+        url.clone(),
+        url,
+        None,
+    );
+    let api = SpotApi::with_config(config);
+
+    let order = NewOrderRequest::new("SHIBUSDT", OrderSide::Buy, OrderType::Limit)
+        .time_in_force(TimeInForce::Gtc)
+        .quantity(dec!(1500000.00))
+        .price(dec!(0.00000022))
+        // Negative zero, e.g. left by a rounding, is sent without the sign.
+        .iceberg_qty(-dec!(0.00));
+    api.test_order(&order, false, TimeWindow::new(1499827319559))
+        .unwrap()
+        .await
+        .unwrap();
+
+    let request_lines = request_lines.lock().unwrap();
+    let query = request_lines[0].split(['?', ' ']).nth(2).unwrap();
+    assert!(
+        query.starts_with(
+            "symbol=SHIBUSDT&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1500000.00&\
+             icebergQty=0.00&price=0.00000022&recvWindow=5000&timestamp=1499827319559&\
+             signature="
+        ),
+        "{query}"
+    );
+}
@@ -0,0 +1,56 @@
+//! Checks that shutting down waits for the requests in flight.
+#![cfg(feature = "with_network")]
+
+use std::time::Duration;
+
+use ccx_binance::SpotApi;
+use ccx_binance::client::ApiCred;
+use ccx_binance::client::Config;
+use futures::channel::oneshot;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+#[actix_rt::test]
+async fn test_shutdown_waits_for_requests_in_flight() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (received_tx, received_rx) = oneshot::channel();
+    let (respond_tx, respond_rx) = oneshot::channel::<()>();
+    actix_rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut chunk = [0; 1024];
+        let _ = stream.read(&mut chunk).await.unwrap();
+        received_tx.send(()).unwrap();
+        respond_rx.await.unwrap();
+        let body = r#"{"serverTime":1499827319559}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.unwrap();
+    });
+
+    let url: url::Url = format!("http://{addr}/").parse().unwrap();
+    let config = Config::new(ApiCred::new(None, None), url.clone(), url, None);
+    let api = SpotApi::with_config(config);
+
+    let request = actix_rt::spawn(api.time().unwrap());
+    received_rx.await.unwrap();
+    // Released by the limiter, but the response has not arrived yet.
+    assert_eq!(api.rate_limiter().pending(), 1);
+
+    let shutdown = actix_rt::spawn({
+        let api = api.clone();
+        async move { api.shutdown().await }
+    });
+    actix_rt::time::sleep(Duration::from_millis(50)).await;
+    assert!(!shutdown.is_finished());
+
+    respond_tx.send(()).unwrap();
+    shutdown.await.unwrap();
+    assert_eq!(api.rate_limiter().pending(), 0);
+    assert!(request.await.unwrap().is_ok());
+}
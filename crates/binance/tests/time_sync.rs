@@ -0,0 +1,95 @@
+//! Checks that signed requests are shifted by the automatically synced server time.
+#![cfg(feature = "with_network")]
+
+use std::time::Duration;
+
+use ccx_binance::SpotApi;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::spot::RL_WEIGHT_PER_MINUTE;
+use ccx_binance::client::ApiCred;
+use ccx_binance::client::Config;
+use ccx_binance::client::TaskCosts;
+use chrono::Utc;
+
+mod common;
+
+use common::Response;
+
+/// How far the mocked server clock is ahead of the local one.
+const SERVER_AHEAD_MS: i64 = 3_600_000;
+
+/// `timestamp` argument of a request line.
+fn timestamp(request_line: &str) -> i64 {
+    let query = request_line.split(['?', ' ']).nth(2).unwrap();
+    let arg = query
+        .split('&')
+        .find_map(|arg| arg.strip_prefix("timestamp="));
+    arg.unwrap().parse().unwrap()
+}
+
+#[actix_rt::test]
+async fn test_signed_requests_use_server_time() {
+    let (addr, request_lines) = common::start(|request_line| {
+        if request_line.starts_with("GET /api/v3/time ") {
+            let server_time = Utc::now().timestamp_millis() + SERVER_AHEAD_MS;
+            Response::ok(format!(r#"{{"serverTime":{server_time}}}"#))
+        } else {
+            Response::ok("{}")
+        }
+    })
+    .await;
+
+    let url: url::Url = format!("http://{addr}/").parse().unwrap();
+    let config = Config::new(
+        ApiCred::new(Some("key".into()), Some("secret".into())),
+        url.clone(),
+        url,
+        None,
+    )
+    .with_time_sync(Duration::from_secs(3600));
+    let api = SpotApi::with_config(config);
+    assert_eq!(api.time_offset(), 0);
+
+    for _ in 0..2 {
+        let local = Utc::now().timestamp_millis();
+        let _: serde_json::Value = api
+            .client
+            .get("/api/v3/account")
+            .unwrap()
+            .signed(TimeWindow::now())
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        let request_lines = request_lines.lock().unwrap();
+        let shift = timestamp(request_lines.last().unwrap()) - local;
+        assert!((shift - SERVER_AHEAD_MS).abs() < 1_000, "shift: {shift}");
+    }
+    let offset = api.time_offset();
+    assert!((offset - SERVER_AHEAD_MS).abs() < 1_000, "offset: {offset}");
+
+    // The time is synced before the first signed request only, until the interval passes.
+    let methods: Vec<String> = request_lines
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|line| line.split('?').next().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            "GET /api/v3/time HTTP/1.1",
+            "GET /api/v3/account",
+            "GET /api/v3/account",
+        ]
+    );
+
+    let offset = api.sync_time().await.unwrap();
+    assert!((offset - SERVER_AHEAD_MS).abs() < 1_000, "offset: {offset}");
+    assert_eq!(request_lines.lock().unwrap().len(), 4);
+
+    // Both time requests were charged to the weight bucket, limited to 1200.
+    let weight = |cost| TaskCosts::from([(RL_WEIGHT_PER_MINUTE.into(), cost)]);
+    assert!(!api.rate_limiter().would_exceed(&weight(1_198)));
+    assert!(api.rate_limiter().would_exceed(&weight(1_199)));
+}
@@ -0,0 +1,63 @@
+//! Checks that an expired listen key is replaced by the keepalive task.
+#![cfg(feature = "with_network")]
+
+use std::time::Duration;
+
+use ccx_binance::SpotApi;
+use ccx_binance::api::spot::UserDataStreamHandle;
+use ccx_binance::client::ApiCred;
+use futures::StreamExt;
+
+mod common;
+
+use common::Response;
+
+const LISTEN_KEY_NOT_FOUND: &str = r#"{"code":-1125,"msg":"This listenKey does not exist."}"#;
+
+#[actix_rt::test]
+async fn test_expired_listen_key_is_recreated() {
+    // The first key expires on its first keepalive.
+    let mut created = 0;
+    let (addr, request_lines) = common::start(move |request_line| {
+        if request_line.starts_with("POST ") {
+            created += 1;
+            Response::ok(format!(r#"{{"listenKey":"key{created}"}}"#))
+        } else if request_line.starts_with("PUT ") && request_line.contains("listenKey=key1 ") {
+            Response::error("400 Bad Request", LISTEN_KEY_NOT_FOUND)
+        } else {
+            Response::ok("{}")
+        }
+    })
+    .await;
+
+    let api = SpotApi::with_base_urls(
+        ApiCred::new(Some("key".into()), Some("secret".into())),
+        format!("http://{addr}/").parse().unwrap(),
+        format!("ws://{addr}/").parse().unwrap(),
+        None,
+    );
+    let handle = UserDataStreamHandle::start(api, Duration::from_millis(50))
+        .await
+        .unwrap();
+    assert_eq!(handle.listen_key(), "key1");
+
+    let mut key_changed = handle.on_key_changed();
+    assert_eq!(key_changed.next().await.unwrap(), "key2");
+    assert_eq!(handle.listen_key(), "key2");
+
+    handle.close().await.unwrap();
+
+    let request_lines = request_lines.lock().unwrap().clone();
+    assert_eq!(
+        request_lines[..3],
+        [
+            "POST /api/v3/userDataStream HTTP/1.1",
+            "PUT /api/v3/userDataStream?listenKey=key1 HTTP/1.1",
+            "POST /api/v3/userDataStream HTTP/1.1",
+        ]
+    );
+    assert_eq!(
+        request_lines.last().unwrap(),
+        "DELETE /api/v3/userDataStream?listenKey=key2 HTTP/1.1"
+    );
+}
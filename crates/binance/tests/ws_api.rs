@@ -0,0 +1,216 @@
+//! Checks that websocket API requests are signed and their responses matched by id.
+#![cfg(feature = "with_network")]
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use actix::Actor;
+use actix::StreamHandler;
+use actix_web::App;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::HttpServer;
+use actix_web::web;
+use actix_web_actors::ws;
+use ccx_binance::ApiCred;
+use ccx_binance::ApiError;
+use ccx_binance::BinanceError;
+use ccx_binance::BinanceSigner;
+use ccx_binance::RecvWindow;
+use ccx_binance::SpotApi;
+use ccx_binance::TimeWindow;
+use ccx_binance::api::spot::NewOrderAck;
+use ccx_binance::api::spot::NewOrderRequest;
+use ccx_binance::api::spot::OrderResponseType;
+use ccx_binance::api::spot::OrderSide;
+use ccx_binance::api::spot::OrderType;
+use ccx_binance::api::spot::RateLimitType;
+use ccx_binance::api::spot::TimeInForce;
+use ccx_binance::client::Config;
+use rust_decimal_macros::dec;
+use serde_json::Value;
+use serde_json::json;
+
+const RATE_LIMITS: &str = r#"[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE",
+    "intervalNum":1,"limit":6000,"count":5}]"#;
+
+/// Holds the first request until the second one arrives, then answers both
+/// in reverse order. A cancel is always rejected.
+struct MockApi {
+    requests: Arc<Mutex<Vec<Value>>>,
+    held: Option<Value>,
+}
+
+impl Actor for MockApi {
+    type Context = ws::WebsocketContext<Self>;
+}
+
+impl MockApi {
+    fn respond(request: &Value) -> String {
+        let id = &request["id"];
+        let result = match request["method"].as_str().unwrap() {
+            "order.place" => json!({
+                "symbol": "BTCUSDT",
+                "orderId": 12569099453_u64,
+                "orderListId": -1,
+                "clientOrderId": "4d96324ff9d44481926157ec08158a40",
+                "transactTime": 1660801715639_u64,
+            }),
+            "order.status" => json!({
+                "symbol": "BTCUSDT",
+                "orderId": 12569099453_u64,
+                "orderListId": -1,
+                "clientOrderId": "4d96324ff9d44481926157ec08158a40",
+                "price": "23416.10000000",
+                "origQty": "0.00847000",
+                "executedQty": "0.00847000",
+                "cummulativeQuoteQty": "198.33521500",
+                "status": "FILLED",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "SELL",
+                "stopPrice": "0.00000000",
+                "icebergQty": "0.00000000",
+                "time": 1660801715639_u64,
+                "updateTime": 1660801717945_u64,
+                "isWorking": true,
+                "origQuoteOrderQty": "0.00000000",
+            }),
+            _ => {
+                return format!(
+                    r#"{{"id":{id},"status":400,"error":{{"code":-2011,
+                        "msg":"Unknown order sent."}},"rateLimits":{RATE_LIMITS}}}"#
+                );
+            }
+        };
+        format!(r#"{{"id":{id},"status":200,"result":{result},"rateLimits":{RATE_LIMITS}}}"#)
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MockApi {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => {
+                let request: Value = serde_json::from_str(&text).unwrap();
+                self.requests.lock().unwrap().push(request.clone());
+                if request["method"] == "order.cancel" {
+                    ctx.text(Self::respond(&request));
+                } else if let Some(held) = self.held.take() {
+                    ctx.text(Self::respond(&request));
+                    ctx.text(Self::respond(&held));
+                } else {
+                    self.held = Some(request);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn ws_api(
+    req: HttpRequest,
+    payload: web::Payload,
+    requests: web::Data<Arc<Mutex<Vec<Value>>>>,
+) -> actix_web::Result<HttpResponse> {
+    let requests = requests.get_ref().clone();
+    ws::start(
+        MockApi {
+            requests,
+            held: None,
+        },
+        &req,
+        payload,
+    )
+}
+
+#[actix_rt::test]
+async fn test_ws_api_correlates_responses() {
+    let requests: Arc<Mutex<Vec<Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let state = requests.clone();
+    let http = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(state.clone()))
+            .route("/ws-api/v3", web::get().to(ws_api))
+    })
+    .workers(1)
+    .disable_signals()
+    .bind("127.0.0.1:0")
+    .unwrap();
+    let addr = http.addrs()[0];
+    actix_rt::spawn(http.run());
+
+    let cred = ApiCred::new(Some("key".into()), Some("secret".into()));
+    let config = Config::new(
+        cred.clone(),
+        format!("http://{addr}/").parse().unwrap(),
+        format!("ws://{addr}/stream").parse().unwrap(),
+        None,
+    )
+    .with_recv_window(RecvWindow::new(10_000).unwrap());
+    let api = SpotApi::with_config(
+        config.with_ws_api_base(format!("ws://{addr}/ws-api/v3").parse().unwrap()),
+    );
+    // Applied to the websocket API like to the REST one.
+    api.client.set_time_offset(1_500);
+    let ws_api = api.ws_api().await.unwrap();
+
+    let order = NewOrderRequest::new("BTCUSDT", OrderSide::Sell, OrderType::Limit)
+        .time_in_force(TimeInForce::Gtc)
+        .price(dec!(23416.10))
+        .quantity(dec!(0.00847))
+        .new_order_resp_type(OrderResponseType::Ack);
+    let time_window = TimeWindow::new(1660801715000);
+    let (placed, status) = futures::join!(
+        ws_api.order_place::<NewOrderAck>(&order, time_window),
+        ws_api.order_status("BTCUSDT", Some(12569099453), None, time_window),
+    );
+
+    let placed = placed.unwrap();
+    assert_eq!(placed.id, 1);
+    assert_eq!(placed.result.order_id, 12569099453);
+    let status = status.unwrap();
+    assert_eq!(status.id, 2);
+    assert_eq!(status.result.executed_qty, dec!(0.00847));
+    assert_eq!(
+        status.rate_limits[0].rate_limit_type,
+        RateLimitType::RequestWeight
+    );
+    assert_eq!(status.rate_limits[0].count, 5);
+
+    let res = ws_api
+        .order_cancel("BTCUSDT", Some(1), None, time_window)
+        .await;
+    assert!(matches!(
+        res,
+        Err(BinanceError::ApiError(ApiError::WsRequestFailed {
+            id: 3,
+            code: -2011,
+            ..
+        }))
+    ));
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0]["method"], "order.place");
+    let params = requests[0]["params"].as_object().unwrap();
+    assert_eq!(params["apiKey"], "key");
+    assert_eq!(params["price"], "23416.10");
+    assert_eq!(params["recvWindow"], 10_000);
+    assert_eq!(params["timestamp"], 1660801716500_u64);
+    let payload = params
+        .iter()
+        .filter(|(name, _)| name.as_str() != "signature")
+        .map(|(name, value)| match value {
+            Value::String(value) => format!("{name}={value}"),
+            value => format!("{name}={value}"),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    assert_eq!(
+        payload,
+        "apiKey=key&newOrderRespType=ACK&price=23416.10&quantity=0.00847&recvWindow=10000&\
+         side=SELL&symbol=BTCUSDT&timeInForce=GTC&timestamp=1660801716500&type=LIMIT"
+    );
+    assert_eq!(params["signature"], cred.sign_data(&payload).await.unwrap());
+}
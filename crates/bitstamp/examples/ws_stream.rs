@@ -0,0 +1,44 @@
+use ccx_bitstamp::ApiCred;
+use ccx_bitstamp::BitstampResult;
+use ccx_bitstamp::api::Api as BitstampApi;
+use ccx_bitstamp::ws_stream::WsEvent;
+use ccx_bitstamp::ws_stream::WsStream;
+use futures::StreamExt;
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    if let Err(e) = main_bitstamp().await {
+        log::error!("bitstamp err: {:?}", e);
+    }
+}
+
+async fn main_bitstamp() -> BitstampResult<()> {
+    let bitstamp = BitstampApi::<ApiCred>::from_env();
+
+    let mut stream = bitstamp.ws().await?;
+    stream
+        .subscribe_one((WsStream::LiveTrades, "btcusd"))
+        .await?;
+    stream
+        .subscribe_one((WsStream::OrderBook, "btcusd"))
+        .await?;
+
+    while let Some(event) = stream.next().await {
+        match event {
+            WsEvent::LiveTrade { pair, data } => {
+                println!("{pair} trade: {} @ {}", data.amount, data.price);
+            }
+            WsEvent::OrderBook { pair, data } => {
+                let bid = data.bids.first().map(|e| e.price);
+                let ask = data.asks.first().map(|e| e.price);
+                println!("{pair} book: {bid:?} / {ask:?}");
+            }
+            event => log::debug!("{:?}", event),
+        }
+    }
+
+    Ok(())
+}
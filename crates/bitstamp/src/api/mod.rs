@@ -65,6 +65,10 @@ mod with_network {
         /// Reads config from env vars with names like:
         /// "CCX_BITSTAMP_API_KEY", "CCX_BITSTAMP_API_SECRET", "CCX_BITSTAMP_API_PASSPHRASE",
         /// "CCX_BITSTAMP_API_TESTNET"
+        ///
+        /// Requests are routed through a SOCKS5 proxy when "CCX_BITSTAMP_API_PROXY_HOST" and
+        /// "CCX_BITSTAMP_API_PROXY_PORT" are set ("CCX_BITSTAMP_API_PROXY_USERNAME" and
+        /// "CCX_BITSTAMP_API_PROXY_PASSWORD" are optional).
         pub fn from_env() -> Api<ApiCred> {
             let testnet = Config::<ApiCred>::env_var("TESTNET").as_deref() == Some("1");
             let proxy = Proxy::from_env_with_prefix(CCX_BITSTAMP_API_PREFIX);
@@ -76,7 +80,8 @@ mod with_network {
         }
 
         /// Reads config from env vars with names like:
-        /// "${prefix}_KEY", "${prefix}_SECRET", "${prefix}_PASSPHRASE", "${prefix}_TESTNET"
+        /// "${prefix}_KEY", "${prefix}_SECRET", "${prefix}_PASSPHRASE", "${prefix}_TESTNET",
+        /// and "${prefix}_PROXY_*"
         pub fn from_env_with_prefix(prefix: &str) -> Api<ApiCred> {
             let testnet = env_var_with_prefix(prefix, "TESTNET").as_deref() == Some("1");
             let proxy = Proxy::from_env_with_prefix(prefix);
@@ -0,0 +1,214 @@
+use crate::api::RL_GENERAL_KEY;
+use crate::api::order::MarketOrder;
+use crate::api::order::MarketOrderType;
+use crate::api::prelude::*;
+
+/// Amount of a market order.
+///
+/// Bitstamp only accepts base currency amounts for market orders. Amounts in the counter
+/// currency are placed as instant orders: an instant buy always spends the counter
+/// currency, an instant sell needs `amount_in_counter` to sell the base currency worth
+/// the given amount.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketAmount {
+    Base(Decimal),
+    Counter(Decimal),
+}
+
+/// Body of `buy/market/{pair}/` and `sell/market/{pair}/`.
+#[derive(Clone, Debug, Serialize)]
+pub struct MarketOrderRequest<'a> {
+    /// Amount in the base currency.
+    pub amount: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<&'a str>,
+}
+
+/// Body of `buy/instant/{pair}/` and `sell/instant/{pair}/`.
+#[derive(Clone, Debug, Serialize)]
+pub struct InstantOrderRequest<'a> {
+    /// Amount in the counter currency for buys, in the base currency for sells
+    /// unless `amount_in_counter` is set.
+    pub amount: Decimal,
+    /// Only supported by instant sell orders.
+    #[serde(
+        skip_serializing_if = "std::ops::Not::not",
+        serialize_with = "serialize_true"
+    )]
+    pub amount_in_counter: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<&'a str>,
+}
+
+fn serialize_true<S: serde::Serializer>(_: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str("True")
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum MarketRequest<'a> {
+    Market(MarketOrderRequest<'a>),
+    Instant(InstantOrderRequest<'a>),
+}
+
+impl<'a> MarketRequest<'a> {
+    fn new(side: MarketOrderType, amount: MarketAmount, client_order_id: Option<&'a str>) -> Self {
+        match amount {
+            MarketAmount::Base(amount) => MarketRequest::Market(MarketOrderRequest {
+                amount,
+                client_order_id,
+            }),
+            MarketAmount::Counter(amount) => MarketRequest::Instant(InstantOrderRequest {
+                amount,
+                amount_in_counter: matches!(side, MarketOrderType::Sell),
+                client_order_id,
+            }),
+        }
+    }
+
+    fn endpoint(&self, side: MarketOrderType, currency_pair: &str) -> String {
+        let side = match side {
+            MarketOrderType::Buy => "buy",
+            MarketOrderType::Sell => "sell",
+        };
+        let kind = match self {
+            MarketRequest::Market(_) => "market",
+            MarketRequest::Instant(_) => "instant",
+        };
+        format!("{side}/{kind}/{currency_pair}/")
+    }
+}
+
+#[cfg(feature = "with_network")]
+impl<S> Api<S>
+where
+    S: crate::client::BitstampSigner,
+    S: Unpin + 'static,
+{
+    /// Buy at market price
+    ///
+    /// A base currency amount is placed as a market order, a counter currency amount
+    /// as an instant order spending that amount.
+    ///
+    /// [https://www.bitstamp.net/api/#buy-market-order]
+    /// [https://www.bitstamp.net/api/#buy-instant-order]
+    pub fn buy_market(
+        &self,
+        currency_pair: impl AsRef<str>,
+        amount: MarketAmount,
+        client_order_id: Option<&str>,
+    ) -> BitstampResult<Task<MarketOrder>> {
+        self.market(
+            MarketOrderType::Buy,
+            currency_pair.as_ref(),
+            amount,
+            client_order_id,
+        )
+    }
+
+    /// Sell at market price
+    ///
+    /// A base currency amount is placed as a market order, a counter currency amount
+    /// as an instant order with `amount_in_counter`.
+    ///
+    /// [https://www.bitstamp.net/api/#sell-market-order]
+    /// [https://www.bitstamp.net/api/#sell-instant-order]
+    pub fn sell_market(
+        &self,
+        currency_pair: impl AsRef<str>,
+        amount: MarketAmount,
+        client_order_id: Option<&str>,
+    ) -> BitstampResult<Task<MarketOrder>> {
+        self.market(
+            MarketOrderType::Sell,
+            currency_pair.as_ref(),
+            amount,
+            client_order_id,
+        )
+    }
+
+    fn market(
+        &self,
+        side: MarketOrderType,
+        currency_pair: &str,
+        amount: MarketAmount,
+        client_order_id: Option<&str>,
+    ) -> BitstampResult<Task<MarketOrder>> {
+        let request = MarketRequest::new(side, amount, client_order_id);
+
+        Ok(self
+            .rate_limiter
+            .task(
+                self.client
+                    .post(&request.endpoint(side, currency_pair))?
+                    .signed_now()?
+                    .request_body(request)?,
+            )
+            .cost(RL_GENERAL_KEY, 1)
+            .send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(
+        side: MarketOrderType,
+        amount: MarketAmount,
+        client_order_id: Option<&str>,
+    ) -> (String, String) {
+        let request = MarketRequest::new(side, amount, client_order_id);
+        (
+            request.endpoint(side, "btcusd"),
+            serde_urlencoded::to_string(&request).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_base_amount_is_market_order() {
+        assert_eq!(
+            encode(
+                MarketOrderType::Buy,
+                MarketAmount::Base(Decimal::new(5, 1)),
+                None
+            ),
+            ("buy/market/btcusd/".to_string(), "amount=0.5".to_string())
+        );
+        assert_eq!(
+            encode(
+                MarketOrderType::Sell,
+                MarketAmount::Base(Decimal::new(5, 1)),
+                Some("abc")
+            ),
+            (
+                "sell/market/btcusd/".to_string(),
+                "amount=0.5&client_order_id=abc".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_counter_amount_is_instant_order() {
+        // Instant buys are always in the counter currency.
+        assert_eq!(
+            encode(
+                MarketOrderType::Buy,
+                MarketAmount::Counter(Decimal::from(100)),
+                None
+            ),
+            ("buy/instant/btcusd/".to_string(), "amount=100".to_string())
+        );
+        assert_eq!(
+            encode(
+                MarketOrderType::Sell,
+                MarketAmount::Counter(Decimal::from(100)),
+                Some("abc")
+            ),
+            (
+                "sell/instant/btcusd/".to_string(),
+                "amount=100&amount_in_counter=True&client_order_id=abc".to_string()
+            )
+        );
+    }
+}
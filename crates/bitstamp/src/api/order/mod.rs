@@ -1,11 +1,13 @@
 mod buy_market;
 mod list_open;
+mod market;
 mod sell_market;
 mod status;
 mod types;
 
 pub use buy_market::*;
 pub use list_open::*;
+pub use market::*;
 pub use sell_market::*;
 pub use status::*;
 pub use types::*;
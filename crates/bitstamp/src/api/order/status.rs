@@ -59,4 +59,32 @@ where
             .cost(RL_GENERAL_KEY, 1)
             .send())
     }
+
+    /// Polls the order status every `poll` until the order is finished, expired or
+    /// canceled, and returns the last status.
+    ///
+    /// Fails with [`std::io::ErrorKind::TimedOut`] if the order is still open after `timeout`.
+    pub async fn wait_for_terminal(
+        &self,
+        id: impl Into<EitherOrderId>,
+        poll: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> BitstampResult<OrderStatusResponse> {
+        use std::io;
+        use std::time::Instant;
+
+        let id = id.into();
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = self.order_status(id, None)?.await?;
+            if status.status.is_terminal() {
+                return Ok(status);
+            }
+            if Instant::now() + poll > deadline {
+                log::debug!("Order {id} is still open after {timeout:?}");
+                Err(io::Error::from(io::ErrorKind::TimedOut))?
+            }
+            actix::clock::sleep(poll).await;
+        }
+    }
 }
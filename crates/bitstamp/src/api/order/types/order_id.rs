@@ -22,6 +22,12 @@ impl std::fmt::Display for EitherOrderId {
 #[derive(Clone, Copy, Debug, Deref, PartialEq, Eq)]
 pub struct OrderId(i64);
 
+impl From<OrderId> for EitherOrderId {
+    fn from(id: OrderId) -> Self {
+        EitherOrderId::Bitstamp(id.0)
+    }
+}
+
 impl<'de> Deserialize<'de> for OrderId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -6,7 +6,7 @@ use super::OrderId;
 use crate::Decimal;
 use crate::DtBitstamp;
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 pub enum OrderStatusType {
     Open,
     Finished,
@@ -14,6 +14,13 @@ pub enum OrderStatusType {
     Canceled,
 }
 
+impl OrderStatusType {
+    /// Whether the order can no longer change.
+    pub fn is_terminal(self) -> bool {
+        !matches!(self, OrderStatusType::Open)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub enum OrderStatusTransactionType {
     Deposit,
@@ -73,3 +80,29 @@ mod order_status_transaction_type {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_terminal() {
+        let status: OrderStatus = serde_json::from_str(
+            r#"{
+                "id": 1458532827766784,
+                "datetime": "2022-01-31 14:43:15",
+                "type": "0",
+                "status": "Open",
+                "transactions": [],
+                "amount_remaining": "0.50000000",
+                "client_order_id": null
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(status.status, OrderStatusType::Open);
+        assert!(!status.status.is_terminal());
+        assert!(OrderStatusType::Finished.is_terminal());
+        assert!(OrderStatusType::Expired.is_terminal());
+        assert!(OrderStatusType::Canceled.is_terminal());
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 use std::io;
+use std::pin::Pin;
+use std::task::Context as TaskContext;
+use std::task::Poll;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -10,6 +13,8 @@ use actix_http::ws::Codec;
 use actix_http::ws::Item as FrameItem;
 use actix_web_actors::ws;
 use awc::BoxedSocket;
+use futures::Stream;
+use futures::StreamExt;
 use futures::channel::mpsc;
 use futures::stream::SplitSink;
 use serde::Deserialize;
@@ -43,6 +48,15 @@ struct M<T>(pub T);
 #[rtype(result = "()")]
 struct ReconnectSocket;
 
+/// Frame received over the connection with the given id.
+///
+/// The end of the connection is reported as a frame without data, so that a connection
+/// replaced by a reconnect can end without stopping the actor.
+struct ConnectionFrame {
+    connection_id: u64,
+    frame: Option<Result<ws::Frame, ws::ProtocolError>>,
+}
+
 pub struct WebsocketStream {
     tx: WebsocketStreamTx,
     rx: mpsc::UnboundedReceiver<WsEvent>,
@@ -60,6 +74,8 @@ pub struct Websocket {
     channels: HashMap<WsSubscription, bool>,
     buffer: Option<Vec<u8>>,
 
+    /// Id of the current connection, incremented on every reconnect.
+    connection_id: u64,
     inner: Option<InnerSocket>,
 }
 
@@ -77,15 +93,25 @@ impl Actor for Websocket {
 }
 
 /// Handler for `ws::Message`.
-impl StreamHandler<Result<ws::Frame, ws::ProtocolError>> for Websocket {
-    fn handle(&mut self, msg: Result<ws::Frame, ws::ProtocolError>, ctx: &mut Self::Context) {
-        let msg = match msg {
-            Ok(msg) => msg,
-            Err(e) => {
+impl StreamHandler<ConnectionFrame> for Websocket {
+    fn handle(&mut self, msg: ConnectionFrame, ctx: &mut Self::Context) {
+        if msg.connection_id != self.connection_id {
+            // Leftovers of a connection that was replaced by a reconnect.
+            return;
+        }
+
+        let msg = match msg.frame {
+            Some(Ok(msg)) => msg,
+            Some(Err(e)) => {
                 log::warn!("WebSocket broken: {:?}", e);
                 ctx.stop();
                 return;
             }
+            None => {
+                log::warn!("WebSocket closed by server");
+                ctx.stop();
+                return;
+            }
         };
 
         match msg {
@@ -113,9 +139,17 @@ impl StreamHandler<Result<ws::Frame, ws::ProtocolError>> for Websocket {
             }
         }
     }
+
+    fn finished(&mut self, _ctx: &mut Self::Context) {
+        // The end of the current connection is handled in `handle`.
+    }
 }
 
-impl actix::io::WriteHandler<ws::ProtocolError> for Websocket {}
+impl actix::io::WriteHandler<ws::ProtocolError> for Websocket {
+    fn finished(&mut self, _ctx: &mut Self::Context) {
+        // Only the sink of a replaced connection is closed on purpose.
+    }
+}
 
 impl Handler<M<WsCommand>> for Websocket {
     type Result = ();
@@ -142,8 +176,6 @@ impl Handler<ReconnectSocket> for Websocket {
     type Result = ResponseActFuture<Self, ()>;
 
     fn handle(&mut self, _: ReconnectSocket, _: &mut Self::Context) -> Self::Result {
-        use futures::StreamExt as _;
-
         let api_client = self.api_client.clone();
         let ws_url = self.ws_url.clone();
 
@@ -160,11 +192,29 @@ impl Handler<ReconnectSocket> for Websocket {
             log::debug!("Websocket response: {:?}", resp);
             let (sink, stream) = connection.split();
 
+            act.connection_id += 1;
+            act.buffer = None;
+            let connection_id = act.connection_id;
+            let stream = stream
+                .map(move |frame| ConnectionFrame {
+                    connection_id,
+                    frame: Some(frame),
+                })
+                .chain(futures::stream::once(async move {
+                    ConnectionFrame {
+                        connection_id,
+                        frame: None,
+                    }
+                }));
+
             ctx.add_stream(stream);
-            act.inner = Some(InnerSocket {
+            let old = act.inner.replace(InnerSocket {
                 sink: SinkWrite::new(sink, ctx),
                 hb: Instant::now(),
             });
+            if let Some(mut old) = old {
+                old.sink.close();
+            }
 
             // Resubscribe to previous subscriptions.
             let old_subscriptions = std::mem::take(&mut act.channels);
@@ -186,6 +236,7 @@ impl Websocket {
             tx,
             channels: HashMap::new(),
             buffer: None,
+            connection_id: 0,
             inner: None,
         }
     }
@@ -335,6 +386,14 @@ impl WebsocketStream {
     }
 }
 
+impl Stream for WebsocketStream {
+    type Item = WsEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<WsEvent>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
 impl std::ops::Deref for WebsocketStream {
     type Target = WebsocketStreamTx;
 
@@ -354,4 +413,15 @@ impl WebsocketStreamTx {
             .await
             .map_err(|_e| BitstampError::IoError(io::ErrorKind::ConnectionAborted.into()))
     }
+
+    pub async fn unsubscribe_one(
+        &self,
+        subscription: impl Into<WsSubscription>,
+    ) -> BitstampResult<()> {
+        let cmd = WsCommand::Unsubscribe(subscription.into());
+        self.addr
+            .send(M(cmd))
+            .await
+            .map_err(|_e| BitstampError::IoError(io::ErrorKind::ConnectionAborted.into()))
+    }
 }
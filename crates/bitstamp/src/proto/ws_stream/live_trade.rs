@@ -29,6 +29,7 @@ pub struct LiveTradeEvent {
 #[cfg(test)]
 mod tests {
     use crate::ws_stream::Event;
+    use crate::ws_stream::WsEvent;
 
     #[test]
     fn test_deserialize() {
@@ -51,6 +52,13 @@ mod tests {
         }"#;
 
         let res = serde_json::from_str::<Event>(json);
-        assert!(res.is_ok(), "Failed to deserialize: {:?}", res);
+        match res {
+            Ok(Event::Client(WsEvent::LiveTrade { pair, data })) => {
+                assert_eq!(&pair, "btcusd");
+                assert_eq!(data.id, 296045814);
+                assert_eq!(data.sell_order_id, 1651468496011265);
+            }
+            res => panic!("Failed to deserialize: {:?}", res),
+        }
     }
 }
@@ -101,6 +101,10 @@ impl WsEvent {
         let (stream, pair) = channel_from_raw(&channel)
             .ok_or_else(|| DeserializeError::InvalidChannelName(channel))?;
         let event = match (ev, stream) {
+            (ClientEventType::Trade, WsStream::LiveTrades) => WsEvent::LiveTrade {
+                pair,
+                data: serde_json::from_value(data)?,
+            },
             (ClientEventType::Data, WsStream::OrderBook) => WsEvent::OrderBook {
                 pair,
                 data: serde_json::from_value(data)?,
@@ -200,3 +204,34 @@ pub(crate) enum DeserializeError {
     #[display("Invalid combination of `event`: {:?} and `stream`:{:?}", _0, _1)]
     InvalidEventAndChannel(ClientEventType, WsStream),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_reconnect_request() {
+        let json = r#"{"event":"bts:request_reconnect","channel":"","data":""}"#;
+
+        let res = serde_json::from_str::<Event>(json);
+        assert!(
+            matches!(res, Ok(Event::System(SystemEvent::ReconnectRequest))),
+            "Failed to deserialize reconnect request: {:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_deserialize_subscription_succeeded() {
+        let json =
+            r#"{"event":"bts:subscription_succeeded","channel":"live_trades_btcusd","data":{}}"#;
+
+        let res = serde_json::from_str::<Event>(json);
+        match res {
+            Ok(Event::System(SystemEvent::SubscriptionSucceeded { channel })) => {
+                assert_eq!(channel, (WsStream::LiveTrades, Atom::from("btcusd")));
+            }
+            res => panic!("Failed to deserialize subscription: {:?}", res),
+        }
+    }
+}
@@ -52,7 +52,7 @@ pub fn client_without_proxy(cfg: Arc<ClientConfig>) -> Client {
 pub fn client_with_proxy(cfg: Arc<ClientConfig>, proxy: &Proxy) -> Client {
     let connector = Connector::new()
         .rustls_0_23(cfg)
-        .connector(SocksConnector::new(proxy.addr()))
+        .connector(SocksConnector::from_proxy(proxy))
         .timeout(CONNECT_TIMEOUT);
     Client::builder()
         .connector(connector)
@@ -128,8 +128,7 @@ pub fn client_without_proxy() -> Client {
 }
 
 pub fn client_with_proxy(proxy: &Proxy) -> Client {
-    let proxy_url = format!("socks5://{}", proxy.addr());
-    let reqwest_proxy = reqwest::Proxy::all(&proxy_url).expect("Failed to create proxy");
+    let reqwest_proxy = reqwest::Proxy::all(proxy.url()).expect("Failed to create proxy");
 
     let inner = reqwest::Client::builder()
         .proxy(reqwest_proxy)
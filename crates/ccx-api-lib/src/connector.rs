@@ -23,26 +23,48 @@ use tokio_socks::Error as SocksConnectError;
 use tokio_socks::TargetAddr;
 use tokio_socks::tcp::Socks5Stream;
 
+use crate::Proxy;
+use crate::ProxyAuth;
+
 fn to_connect_error(e: SocksConnectError) -> ConnectError {
     ConnectError::Io(Error::new(ErrorKind::Other, e))
 }
 
 #[derive(Clone, Debug)]
-pub struct SocksConnector(pub Arc<str>);
+pub struct SocksConnector {
+    proxy: Arc<str>,
+    auth: Option<Arc<ProxyAuth>>,
+}
 
 impl SocksConnector {
     pub fn new(proxy: impl Into<Arc<str>>) -> Self {
-        SocksConnector(proxy.into())
+        SocksConnector {
+            proxy: proxy.into(),
+            auth: None,
+        }
+    }
+
+    pub fn from_proxy(proxy: &Proxy) -> Self {
+        SocksConnector {
+            proxy: proxy.addr().into(),
+            auth: proxy.auth.clone().map(Arc::new),
+        }
     }
 
     async fn connect(
         proxy: Arc<str>,
+        auth: Option<Arc<ProxyAuth>>,
         req: ConnectInfo<Uri>,
     ) -> Result<Connection<Uri, W>, ConnectError> {
         let target = TargetAddr::Domain(req.hostname().into(), req.port());
-        let res = Socks5Stream::connect(&*proxy, target)
-            .await
-            .map_err(to_connect_error)?;
+        let res = match auth {
+            Some(auth) => {
+                Socks5Stream::connect_with_password(&*proxy, target, &auth.username, &auth.password)
+                    .await
+            }
+            None => Socks5Stream::connect(&*proxy, target).await,
+        }
+        .map_err(to_connect_error)?;
         let uri = format!("{}:{}", req.hostname(), req.port());
         let uri = Uri::try_from(&uri)
             // .map_err(|e| LibError::other(format!("Failed to parse {:?}: {:?}", uri, e)))?
@@ -63,7 +85,7 @@ impl Service<ConnectInfo<Uri>> for SocksConnector {
     }
 
     fn call(&self, req: ConnectInfo<Uri>) -> Self::Future {
-        Box::pin(Self::connect(self.0.clone(), req))
+        Box::pin(Self::connect(self.proxy.clone(), self.auth.clone(), req))
     }
 }
 
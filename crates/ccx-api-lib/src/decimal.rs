@@ -0,0 +1,46 @@
+use rust_decimal::Decimal;
+
+/// Formats `d` in plain notation for a request, e.g. `0.00000022`.
+///
+/// Never uses an exponent and keeps the scale, so `1.500` stays `1.500`. Negative zero,
+/// which some exchanges reject, is written without the sign.
+pub fn format_decimal(d: &Decimal) -> String {
+    if d.is_zero() && d.is_sign_negative() {
+        return d.abs().to_string();
+    }
+    d.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_format_decimal() {
+        assert_eq!(format_decimal(&dec!(0.00000022)), "0.00000022");
+        assert_eq!(
+            format_decimal(&Decimal::new(1, 28)),
+            "0.0000000000000000000000000001"
+        );
+        assert_eq!(format_decimal(&dec!(1.500)), "1.500");
+        assert_eq!(format_decimal(&dec!(-42.10)), "-42.10");
+        assert_eq!(
+            format_decimal(&Decimal::MAX),
+            "79228162514264337593543950335"
+        );
+        assert_eq!(
+            format_decimal(&Decimal::from_i128_with_scale(10_i128.pow(20), 0)),
+            "100000000000000000000"
+        );
+        assert_eq!(
+            format_decimal(&Decimal::from_scientific("2.2e-7").unwrap()),
+            "0.00000022"
+        );
+
+        let mut zero = dec!(0.00);
+        zero.set_sign_negative(true);
+        assert_eq!(format_decimal(&zero), "0.00");
+    }
+}
@@ -1,9 +1,12 @@
 use std::fmt;
 use std::io;
 use std::time;
+use std::time::Duration;
 
 use thiserror::Error;
 
+use crate::OrderBookDesynced;
+
 #[cfg(any(feature = "with_awc", feature = "with_reqwest"))]
 use self::with_network::*;
 
@@ -108,6 +111,8 @@ where
     WsProtocolError(#[from] ProtocolError),
     #[error("Sign Error: {0}")]
     SignError(#[from] SignError),
+    #[error(transparent)]
+    OrderBookDesynced(#[from] OrderBookDesynced),
     #[error("Other Error: {0}")]
     Other(String),
 }
@@ -149,6 +154,41 @@ where
     pub fn other(s: impl Into<String>) -> Self {
         Self::Other(s.into())
     }
+
+    /// Whether the request may succeed if it is sent again.
+    ///
+    /// Transport errors and server-side failures are retryable, client errors are retryable
+    /// only if the exchange-specific error says so.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ApiError(e) => e.is_retryable(),
+            Self::ServiceError(_) => true,
+            #[cfg(any(feature = "with_awc", feature = "with_reqwest"))]
+            Self::RequestError(_) => true,
+            Self::IoError(_) => true,
+            #[cfg(feature = "with_awc")]
+            Self::Payload(_) => true,
+            _ => false,
+        }
+    }
+
+    /// How long the exchange asked to wait before the next request, if it did.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::ApiError(e) => e.retry_after(),
+            _ => None,
+        }
+    }
 }
 
-pub trait CcxApiError: std::error::Error + Send + Sync {}
+pub trait CcxApiError: std::error::Error + Send + Sync {
+    /// See [`LibError::is_retryable`].
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    /// See [`LibError::retry_after`].
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -9,10 +9,12 @@ mod client;
 #[cfg(feature = "with_awc")]
 mod connector;
 mod cred;
+mod decimal;
 mod env;
 #[cfg(feature = "with_env_logger")]
 pub mod env_logger_util;
 mod error;
+mod order_book;
 mod proxy;
 mod rate_limiter;
 mod seq;
@@ -29,7 +31,9 @@ pub use self::client::*;
 #[cfg(feature = "with_awc")]
 pub use self::connector::*;
 pub use self::cred::*;
+pub use self::decimal::*;
 pub use self::env::*;
 pub use self::error::*;
+pub use self::order_book::*;
 pub use self::proxy::*;
 pub use self::seq::*;
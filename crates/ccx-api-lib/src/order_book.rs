@@ -0,0 +1,164 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use thiserror::Error;
+
+/// Delay of the first repeated snapshot request, see [`SnapshotBackoff`].
+pub const SNAPSHOT_BACKOFF_MIN: Duration = Duration::from_secs(1);
+/// Longest delay between snapshot requests, requests further apart are not delayed.
+pub const SNAPSHOT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// A depth diff does not continue the local order book, a new snapshot is needed.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("Order book desynced: expected update {expected}, got {first_update_id}")]
+pub struct OrderBookDesynced {
+    pub expected: u64,
+    pub first_update_id: u64,
+}
+
+/// Update ids of a local order book built from a depth snapshot and the diffs following it.
+#[derive(Clone, Copy, Debug)]
+pub struct DiffSequence {
+    last_update_id: u64,
+    /// No diff continuing the snapshot has been accepted yet.
+    dirty: bool,
+}
+
+impl DiffSequence {
+    pub fn new(snapshot_update_id: u64) -> Self {
+        DiffSequence {
+            last_update_id: snapshot_update_id,
+            dirty: true,
+        }
+    }
+
+    /// Update id of the snapshot or of the latest accepted diff.
+    pub fn last_update_id(&self) -> u64 {
+        self.last_update_id
+    }
+
+    /// Whether a diff following the snapshot was accepted.
+    pub fn is_synced(&self) -> bool {
+        !self.dirty
+    }
+
+    /// Whether the diff with ids `first_update_id..=last_update_id` continues the book,
+    /// `false` for an outdated diff which is to be dropped.
+    ///
+    /// An accepted diff becomes the latest one, the sequence is left as is on error.
+    pub fn accept(
+        &mut self,
+        first_update_id: u64,
+        last_update_id: u64,
+    ) -> Result<bool, OrderBookDesynced> {
+        /*
+           Drop any diff where last_update_id is <= lastUpdateId in the snapshot.
+
+           The first processed diff should have
+               first_update_id <= lastUpdateId+1 AND last_update_id >= lastUpdateId+1.
+
+           While listening to the stream, each new diff's first_update_id should be equal
+               to the previous diff's last_update_id + 1.
+        */
+        let next_id = self.last_update_id + 1;
+        if self.dirty {
+            if last_update_id < next_id {
+                // Ignore an old diff.
+                return Ok(false);
+            }
+            if first_update_id > next_id {
+                Err(OrderBookDesynced {
+                    expected: next_id,
+                    first_update_id,
+                })?
+            }
+            // ^^ ensures first_update_id <= next_id && last_update_id >= next_id
+            self.dirty = false;
+        } else if first_update_id != next_id {
+            Err(OrderBookDesynced {
+                expected: next_id,
+                first_update_id,
+            })?
+        }
+        self.last_update_id = last_update_id;
+        Ok(true)
+    }
+}
+
+/// Delays order book snapshot requests which follow each other within
+/// [`SNAPSHOT_BACKOFF_MAX`], doubling the delay from [`SNAPSHOT_BACKOFF_MIN`].
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotBackoff {
+    last_request: Option<Instant>,
+    retries: u32,
+}
+
+impl SnapshotBackoff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay of the next snapshot request.
+    pub fn next_delay(&mut self) -> Duration {
+        let now = Instant::now();
+        let repeated = (self.last_request)
+            .is_some_and(|last| now.saturating_duration_since(last) < SNAPSHOT_BACKOFF_MAX);
+        let delay = if repeated {
+            self.retries = self.retries.saturating_add(1);
+            SNAPSHOT_BACKOFF_MIN
+                .saturating_mul(1 << (self.retries - 1).min(16))
+                .min(SNAPSHOT_BACKOFF_MAX)
+        } else {
+            self.retries = 0;
+            Duration::ZERO
+        };
+        self.last_request = Some(now + delay);
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_sequence() {
+        let mut seq = DiffSequence::new(10);
+        assert_eq!(seq.accept(8, 10), Ok(false));
+        assert!(!seq.is_synced());
+        assert_eq!(
+            seq.accept(12, 14),
+            Err(OrderBookDesynced {
+                expected: 11,
+                first_update_id: 12
+            })
+        );
+        assert_eq!(seq.accept(9, 12), Ok(true));
+        assert!(seq.is_synced());
+        assert_eq!(seq.accept(13, 13), Ok(true));
+        assert_eq!(
+            seq.accept(15, 16),
+            Err(OrderBookDesynced {
+                expected: 14,
+                first_update_id: 15
+            })
+        );
+        assert_eq!(seq.last_update_id(), 13);
+    }
+
+    #[test]
+    fn test_snapshot_backoff() {
+        let mut backoff = SnapshotBackoff::new();
+        assert_eq!(backoff.next_delay(), Duration::ZERO);
+        assert_eq!(backoff.next_delay(), SNAPSHOT_BACKOFF_MIN);
+        assert_eq!(backoff.next_delay(), SNAPSHOT_BACKOFF_MIN * 2);
+        for _ in 0..10 {
+            backoff.next_delay();
+        }
+        assert_eq!(backoff.next_delay(), SNAPSHOT_BACKOFF_MAX);
+
+        // A request long after the previous one is not delayed.
+        backoff.last_request = Some(Instant::now() - SNAPSHOT_BACKOFF_MAX);
+        assert_eq!(backoff.next_delay(), Duration::ZERO);
+    }
+}
@@ -3,6 +3,18 @@
 /// Unlike `serde_with::NoneAsEmptyString`, it uses `Serialize` & `Deserialize`
 /// instead of [FromStr](std::str::FromStr) & [Display](core::fmt::Display)
 pub mod none_as_empty_str;
+/// Deserialize a field sent either as a number or as a string, e.g. an id, with
+/// [FromStr](std::str::FromStr)
+///
+/// Use with `#[serde(deserialize_with = "number_or_string::deserialize")]`, or
+/// `number_or_string::option::deserialize` for an optional field.
+pub mod number_or_string;
+/// Serialize [Decimal](rust_decimal::Decimal) request fields with
+/// [format_decimal](crate::format_decimal)
+///
+/// Use with `#[serde(serialize_with = "plain_decimal::serialize")]`, or
+/// `plain_decimal::option::serialize` for an optional field.
+pub mod plain_decimal;
 
 use serde::Deserialize;
 use serde::Deserializer;
@@ -0,0 +1,83 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::de;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Number(u64),
+    String(String),
+}
+
+impl Value {
+    fn parse<T, E>(self) -> Result<T, E>
+    where
+        T: FromStr,
+        T::Err: Display,
+        E: de::Error,
+    {
+        match self {
+            Value::Number(v) => v.to_string().parse(),
+            Value::String(v) => v.parse(),
+        }
+        .map_err(E::custom)
+    }
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Value::deserialize(deserializer)?.parse()
+}
+
+/// [`deserialize`] for an optional field.
+pub mod option {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        Option::<Value>::deserialize(deserializer)?
+            .map(Value::parse)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Test {
+        #[serde(deserialize_with = "super::deserialize")]
+        id: u64,
+        #[serde(default, deserialize_with = "super::option::deserialize")]
+        account: Option<String>,
+    }
+
+    #[test]
+    fn test_deserialize_number_or_string() {
+        let case: Test = serde_json::from_str(r#"{"id":"42","account":1234}"#).unwrap();
+        assert_eq!(
+            case,
+            Test {
+                id: 42,
+                account: Some("1234".to_string()),
+            }
+        );
+        let case: Test = serde_json::from_str(r#"{"id":42,"account":"a-1"}"#).unwrap();
+        assert_eq!(case.account.as_deref(), Some("a-1"));
+        let case: Test = serde_json::from_str(r#"{"id":42}"#).unwrap();
+        assert_eq!(case.account, None);
+        assert!(serde_json::from_str::<Test>(r#"{"id":"x"}"#).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use rust_decimal::Decimal;
+use serde::Serializer;
+
+use crate::format_decimal;
+
+pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&format_decimal(value))
+}
+
+/// [`serialize`] for an optional field.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Test {
+        #[serde(serialize_with = "super::serialize")]
+        amount: Decimal,
+        #[serde(serialize_with = "super::option::serialize")]
+        price: Option<Decimal>,
+    }
+
+    #[test]
+    fn test_serialize_plain_decimal() {
+        let mut zero = dec!(0.00);
+        zero.set_sign_negative(true);
+        let case = Test {
+            amount: zero,
+            price: Some(dec!(0.00000022)),
+        };
+        assert_eq!(
+            serde_json::to_string(&case).unwrap(),
+            r#"{"amount":"0.00","price":"0.00000022"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Test {
+                amount: dec!(1.500),
+                price: None,
+            })
+            .unwrap(),
+            r#"{"amount":"1.500","price":null}"#
+        );
+    }
+}
@@ -11,7 +11,7 @@ use crate::client::RestTradeClient;
 use crate::client::TradeConfig;
 use crate::client::TradeRateLimiter;
 
-pub const API_BASE: &str = "https://coinbase.com/";
+pub const API_BASE: &str = "https://api.coinbase.com/";
 pub const STREAM_BASE: &str = "wss://coinbase.com/";
 
 pub const RL_IP_KEY: &str = "portfolio";
@@ -19,22 +19,34 @@ pub const RL_IP_INTERVAL: Duration = Duration::from_secs(1);
 pub const RL_IP_LIMIT: u32 = 10;
 
 // TODO mod error;
+mod order;
+mod product;
 pub mod types;
 
+pub use order::*;
+pub use product::*;
+
+mod prelude {
+    #[cfg(feature = "with_network")]
+    pub use super::TradeApi;
+    pub use crate::DtCoinbasePrime;
+    pub use crate::api::prelude::*;
+}
+
 #[cfg(feature = "with_network")]
 pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
     use super::*;
+    use crate::client::CoinbaseJwtSigner;
     use crate::client::CoinbaseTradeSigner;
     use crate::client::TradeRateLimiterBuilder;
+    use crate::error::CoinbaseResult;
 
     #[derive(Clone)]
     pub struct TradeApi<S: CoinbaseTradeSigner = ApiCred> {
-        #[allow(dead_code)]
         pub(crate) client: RestTradeClient<S>,
-        #[allow(dead_code)]
         pub(crate) rate_limiter: TradeRateLimiter,
     }
 
@@ -57,10 +69,24 @@ mod with_network {
         }
     }
 
+    impl TradeApi<CoinbaseJwtSigner> {
+        /// Uses Cloud API key JWT signing and reads config from env vars with names like:
+        /// "${prefix}_KEY" (the key name), "${prefix}_SECRET" (the EC private key in PEM format)
+        pub fn from_env_jwt_with_prefix(
+            prefix: &str,
+        ) -> CoinbaseResult<TradeApi<CoinbaseJwtSigner>> {
+            let proxy = Proxy::from_env_with_prefix(prefix);
+            let signer = CoinbaseJwtSigner::from_env_with_prefix(prefix)?;
+            Ok(TradeApi::new(signer, proxy))
+        }
+    }
+
     impl<S> TradeApi<S>
     where
         S: CoinbaseTradeSigner,
     {
+        /// Public market data endpoints are not signed, so `ApiCred::new(None, None)`
+        /// is enough for them.
         pub fn new(signer: S, proxy: Option<Proxy>) -> Self {
             let api_base = Url::parse(API_BASE).unwrap();
             let stream_base = Url::parse(STREAM_BASE).unwrap();
@@ -0,0 +1,45 @@
+use crate::api::trade::CancelOrdersResult;
+use crate::api::trade::RL_IP_KEY;
+use crate::api::trade::prelude::*;
+
+pub type CancelOrdersResponse = CancelOrdersResult;
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+struct CancelOrdersRequest<'a> {
+    order_ids: &'a [String],
+}
+
+#[cfg(feature = "with_network")]
+impl<S> TradeApi<S>
+where
+    S: crate::client::CoinbaseTradeSigner,
+    S: Unpin + 'static,
+{
+    /// # Cancel Orders.
+    ///
+    /// Initiate cancel requests for one or more orders.
+    /// Each order gets its own result, the request itself succeeds anyway.
+    ///
+    /// ## Parameters
+    ///
+    /// * `order_ids` - The ids of the orders to cancel, at most 100.
+    ///
+    /// [https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_cancelorders]
+    pub fn cancel_orders(
+        &self,
+        order_ids: &[String],
+    ) -> CoinbaseResult<Task<CancelOrdersResponse>> {
+        let timestamp = Utc::now().timestamp() as u64;
+        let endpoint = "/api/v3/brokerage/orders/batch_cancel";
+        Ok(self
+            .rate_limiter
+            .task(
+                self.client
+                    .post(endpoint)?
+                    .signed(timestamp)?
+                    .request_body(CancelOrdersRequest { order_ids })?,
+            )
+            .cost(RL_IP_KEY, 1)
+            .send())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::api::trade::CreateOrderRequest;
+use crate::api::trade::CreateOrderResult;
+use crate::api::trade::RL_IP_KEY;
+use crate::api::trade::prelude::*;
+
+pub type CreateOrderResponse = CreateOrderResult;
+
+#[cfg(feature = "with_network")]
+impl<S> TradeApi<S>
+where
+    S: crate::client::CoinbaseTradeSigner,
+    S: Unpin + 'static,
+{
+    /// # Create Order.
+    ///
+    /// Create an order with a specified `product_id` (asset-pair), `side` (buy/sell), etc.
+    ///
+    /// A rejected order still gets a successful response, check
+    /// [`CreateOrderResult::success`] and `error_response`.
+    ///
+    /// ## Parameters
+    ///
+    /// * `request` - see [`CreateOrderRequest`] and [`OrderConfiguration`](crate::api::trade::OrderConfiguration).
+    ///
+    /// [https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_postorder]
+    pub fn create_order(
+        &self,
+        request: &CreateOrderRequest,
+    ) -> CoinbaseResult<Task<CreateOrderResponse>> {
+        let timestamp = Utc::now().timestamp() as u64;
+        let endpoint = "/api/v3/brokerage/orders";
+        Ok(self
+            .rate_limiter
+            .task(
+                self.client
+                    .post(endpoint)?
+                    .signed(timestamp)?
+                    .request_body(request)?,
+            )
+            .cost(RL_IP_KEY, 1)
+            .send())
+    }
+}
@@ -0,0 +1,5 @@
+mod cancel;
+mod create;
+mod types;
+
+pub use self::types::*;
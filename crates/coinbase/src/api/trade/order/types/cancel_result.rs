@@ -0,0 +1,42 @@
+use crate::api::trade::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CancelOrdersResult {
+    pub results: Vec<CancelOrderResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CancelOrderResult {
+    pub success: bool,
+    /// `UNKNOWN_CANCEL_FAILURE_REASON` when the order was canceled.
+    #[serde(default)]
+    pub failure_reason: Option<String>,
+    pub order_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_doc() {
+        let json = r#"{
+          "results": [
+            {
+              "success": true,
+              "failure_reason": "UNKNOWN_CANCEL_FAILURE_REASON",
+              "order_id": "0000-00000"
+            },
+            {
+              "success": false,
+              "failure_reason": "UNKNOWN_CANCEL_ORDER",
+              "order_id": "0000-00001"
+            }
+          ]
+        }"#;
+        let result: CancelOrdersResult = serde_json::from_str(json).unwrap();
+        assert!(result.results[0].success);
+        assert!(!result.results[1].success);
+        assert_eq!(result.results[1].order_id, "0000-00001");
+    }
+}
@@ -0,0 +1,29 @@
+use crate::api::trade::OrderConfiguration;
+use crate::api::trade::OrderSide;
+use crate::api::trade::prelude::*;
+
+/// New order parameters, see `TradeApi::create_order`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CreateOrderRequest {
+    /// A unique id generated by the client. A repeated id returns the existing order.
+    pub client_order_id: String,
+    pub product_id: String,
+    pub side: OrderSide,
+    pub order_configuration: OrderConfiguration,
+}
+
+impl CreateOrderRequest {
+    pub fn new(
+        client_order_id: impl Into<String>,
+        product_id: impl Into<String>,
+        side: OrderSide,
+        order_configuration: OrderConfiguration,
+    ) -> Self {
+        CreateOrderRequest {
+            client_order_id: client_order_id.into(),
+            product_id: product_id.into(),
+            side,
+            order_configuration,
+        }
+    }
+}
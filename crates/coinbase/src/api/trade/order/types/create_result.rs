@@ -0,0 +1,93 @@
+use crate::api::trade::OrderConfiguration;
+use crate::api::trade::OrderSide;
+use crate::api::trade::prelude::*;
+
+/// A rejected order is reported in `error_response` with a successful HTTP status.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CreateOrderResult {
+    pub success: bool,
+    #[serde(default)]
+    pub success_response: Option<CreateOrderSuccess>,
+    #[serde(default)]
+    pub error_response: Option<CreateOrderError>,
+    #[serde(default)]
+    pub order_configuration: Option<OrderConfiguration>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CreateOrderSuccess {
+    pub order_id: String,
+    pub product_id: Atom,
+    pub side: OrderSide,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct CreateOrderError {
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub error_details: String,
+    #[serde(default)]
+    pub preview_failure_reason: Option<String>,
+    #[serde(default)]
+    pub new_order_failure_reason: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_success() {
+        let json = r#"{
+          "success": true,
+          "success_response": {
+            "order_id": "11111-00000-000000",
+            "product_id": "BTC-USD",
+            "side": "BUY",
+            "client_order_id": "0000-00000-000000"
+          },
+          "order_configuration": {
+            "limit_limit_gtc": {
+              "base_size": "0.001",
+              "limit_price": "10000.00",
+              "post_only": false
+            }
+          }
+        }"#;
+        let result: CreateOrderResult = serde_json::from_str(json).unwrap();
+        assert!(result.success);
+        let success = result.success_response.unwrap();
+        assert_eq!(success.order_id, "11111-00000-000000");
+        assert_eq!(success.side, OrderSide::Buy);
+        assert_eq!(
+            result.order_configuration,
+            Some(OrderConfiguration::limit_gtc(
+                dec!(0.001),
+                dec!(10000),
+                false
+            ))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_error() {
+        let json = r#"{
+          "success": false,
+          "error_response": {
+            "error": "INSUFFICIENT_FUND",
+            "message": "Insufficient balance in source account",
+            "error_details": "",
+            "preview_failure_reason": "PREVIEW_INSUFFICIENT_FUND"
+          }
+        }"#;
+        let result: CreateOrderResult = serde_json::from_str(json).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error_response.unwrap().error, "INSUFFICIENT_FUND");
+    }
+}
@@ -0,0 +1,11 @@
+mod cancel_result;
+mod create_request;
+mod create_result;
+mod order_configuration;
+mod order_side;
+
+pub use self::cancel_result::*;
+pub use self::create_request::*;
+pub use self::create_result::*;
+pub use self::order_configuration::*;
+pub use self::order_side::*;
@@ -0,0 +1,95 @@
+use crate::api::trade::prelude::*;
+
+/// The kind of the order and its size and price, keyed by the configuration name.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub enum OrderConfiguration {
+    /// Market order, filled immediately or canceled.
+    #[serde(rename = "market_market_ioc")]
+    MarketIoc(MarketIoc),
+    /// Limit order, good until canceled.
+    #[serde(rename = "limit_limit_gtc")]
+    LimitGtc(LimitGtc),
+    /// Limit order, filled completely and immediately or canceled.
+    #[serde(rename = "limit_limit_fok")]
+    LimitFok(LimitFok),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct MarketIoc {
+    /// Amount of the quote currency to spend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_size: Option<Decimal>,
+    /// Amount of the base currency to buy or sell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_size: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct LimitGtc {
+    pub base_size: Decimal,
+    pub limit_price: Decimal,
+    /// The order is rejected if it would take liquidity.
+    #[serde(default)]
+    pub post_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct LimitFok {
+    pub base_size: Decimal,
+    pub limit_price: Decimal,
+}
+
+impl OrderConfiguration {
+    /// Market order for `base_size` of the base currency.
+    pub fn market_base(base_size: Decimal) -> Self {
+        OrderConfiguration::MarketIoc(MarketIoc {
+            quote_size: None,
+            base_size: Some(base_size),
+        })
+    }
+
+    /// Market order spending `quote_size` of the quote currency.
+    pub fn market_quote(quote_size: Decimal) -> Self {
+        OrderConfiguration::MarketIoc(MarketIoc {
+            quote_size: Some(quote_size),
+            base_size: None,
+        })
+    }
+
+    pub fn limit_gtc(base_size: Decimal, limit_price: Decimal, post_only: bool) -> Self {
+        OrderConfiguration::LimitGtc(LimitGtc {
+            base_size,
+            limit_price,
+            post_only,
+        })
+    }
+
+    pub fn limit_fok(base_size: Decimal, limit_price: Decimal) -> Self {
+        OrderConfiguration::LimitFok(LimitFok {
+            base_size,
+            limit_price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let config = OrderConfiguration::market_quote(dec!(10));
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"market_market_ioc":{"quote_size":"10"}}"#
+        );
+
+        let config = OrderConfiguration::limit_gtc(dec!(0.001), dec!(10000.5), true);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"limit_limit_gtc":{"base_size":"0.001","limit_price":"10000.5","post_only":true}}"#
+        );
+    }
+}
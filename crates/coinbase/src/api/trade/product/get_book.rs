@@ -0,0 +1,43 @@
+use crate::api::trade::ProductBook;
+use crate::api::trade::RL_IP_KEY;
+use crate::api::trade::prelude::*;
+
+pub type GetProductBookResponse = ProductBook;
+
+#[cfg(feature = "with_network")]
+impl<S> TradeApi<S>
+where
+    S: crate::client::CoinbaseTradeSigner,
+    S: Unpin + 'static,
+{
+    /// # Get Public Product Book.
+    ///
+    /// Get a list of bids/asks for a single product.
+    /// The request is not signed, so it works without credentials.
+    ///
+    /// Unlike the Exchange API book, price levels are always aggregated,
+    /// so there is no `level` parameter and no order count per level.
+    ///
+    /// ## Parameters
+    ///
+    /// * `product_id` - BTC-USD, ETH-EUR, etc.
+    /// * `limit` - Number of price levels per side. The full book if omitted.
+    ///
+    /// [https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_getpublicproductbook]
+    pub fn product_book(
+        &self,
+        product_id: &str,
+        limit: Option<u32>,
+    ) -> CoinbaseResult<Task<GetProductBookResponse>> {
+        Ok(self
+            .rate_limiter
+            .task(
+                self.client
+                    .get("/api/v3/brokerage/market/product_book")?
+                    .query_arg("product_id", product_id)?
+                    .try_query_arg("limit", &limit)?,
+            )
+            .cost(RL_IP_KEY, 1)
+            .send())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::api::trade::ProductTicker;
+use crate::api::trade::RL_IP_KEY;
+use crate::api::trade::prelude::*;
+
+pub type GetProductTickerResponse = ProductTicker;
+
+#[cfg(feature = "with_network")]
+impl<S> TradeApi<S>
+where
+    S: crate::client::CoinbaseTradeSigner,
+    S: Unpin + 'static,
+{
+    /// # Get Public Market Trades.
+    ///
+    /// Get snapshot information by product ID about the last trades (ticks)
+    /// and the best bid/ask. The request is not signed, so it works without credentials.
+    ///
+    /// ## Parameters
+    ///
+    /// * `product_id` - BTC-USD, ETH-EUR, etc.
+    /// * `limit` - Number of trades to return.
+    ///
+    /// [https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_getpublicmarkettrades]
+    pub fn product_ticker(
+        &self,
+        product_id: &str,
+        limit: u32,
+    ) -> CoinbaseResult<Task<GetProductTickerResponse>> {
+        let endpoint = format!("/api/v3/brokerage/market/products/{product_id}/ticker");
+        Ok(self
+            .rate_limiter
+            .task(self.client.get(&endpoint)?.query_arg("limit", &limit)?)
+            .cost(RL_IP_KEY, 1)
+            .send())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::api::trade::ProductList;
+use crate::api::trade::RL_IP_KEY;
+use crate::api::trade::prelude::*;
+
+pub type ListProductsResponse = ProductList;
+
+#[cfg(feature = "with_network")]
+impl<S> TradeApi<S>
+where
+    S: crate::client::CoinbaseTradeSigner,
+    S: Unpin + 'static,
+{
+    /// # List Public Products.
+    ///
+    /// Get a list of the available currency pairs for trading.
+    /// The request is not signed, so it works without credentials.
+    ///
+    /// [https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_getpublicproducts]
+    pub fn products(&self) -> CoinbaseResult<Task<ListProductsResponse>> {
+        let endpoint = "/api/v3/brokerage/market/products";
+        Ok(self
+            .rate_limiter
+            .task(self.client.get(endpoint)?)
+            .cost(RL_IP_KEY, 1)
+            .send())
+    }
+}
@@ -0,0 +1,6 @@
+mod get_book;
+mod get_ticker;
+mod list;
+mod types;
+
+pub use self::types::*;
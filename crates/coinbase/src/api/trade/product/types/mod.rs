@@ -0,0 +1,7 @@
+mod product;
+mod product_book;
+mod product_ticker;
+
+pub use self::product::*;
+pub use self::product_book::*;
+pub use self::product_ticker::*;
@@ -0,0 +1,123 @@
+use crate::api::trade::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ProductList {
+    pub products: Vec<Product>,
+    pub num_products: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Product {
+    /// The trading pair, e.g. BTC-USD.
+    pub product_id: Atom,
+    /// The current price. Empty for products that have not traded.
+    #[serde(default, with = "maybe_str")]
+    pub price: Option<Decimal>,
+    /// The price change over the last 24 hours, in percent.
+    #[serde(default, with = "maybe_str")]
+    pub price_percentage_change_24h: Option<Decimal>,
+    /// The trading volume over the last 24 hours in the base currency.
+    #[serde(default, with = "maybe_str")]
+    pub volume_24h: Option<Decimal>,
+    /// Minimum amount base value can be increased or decreased at once.
+    pub base_increment: Decimal,
+    /// Minimum amount quote value can be increased or decreased at once.
+    pub quote_increment: Decimal,
+    /// Minimum size that can be represented of base currency.
+    pub base_min_size: Decimal,
+    /// Maximum size that can be represented of base currency.
+    pub base_max_size: Decimal,
+    /// Minimum size that can be represented of quote currency.
+    pub quote_min_size: Decimal,
+    /// Maximum size that can be represented of quote currency.
+    pub quote_max_size: Decimal,
+    pub base_currency_id: Atom,
+    pub quote_currency_id: Atom,
+    /// The status of the product, e.g. online.
+    pub status: Atom,
+    /// The type of the product, e.g. SPOT or FUTURE.
+    pub product_type: Atom,
+    pub is_disabled: bool,
+    pub trading_disabled: bool,
+    pub cancel_only: bool,
+    pub limit_only: bool,
+    pub post_only: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_doc() {
+        let json = r#"{
+          "products": [
+            {
+              "product_id": "BTC-USD",
+              "price": "67123.45",
+              "price_percentage_change_24h": "-1.2345",
+              "volume_24h": "12345.678",
+              "volume_percentage_change_24h": "10.1",
+              "base_increment": "0.00000001",
+              "quote_increment": "0.01",
+              "quote_min_size": "1",
+              "quote_max_size": "150000000",
+              "base_min_size": "0.00000001",
+              "base_max_size": "3400",
+              "base_name": "Bitcoin",
+              "quote_name": "US Dollar",
+              "watched": false,
+              "is_disabled": false,
+              "new": false,
+              "status": "online",
+              "cancel_only": false,
+              "limit_only": false,
+              "post_only": false,
+              "trading_disabled": false,
+              "auction_mode": false,
+              "product_type": "SPOT",
+              "quote_currency_id": "USD",
+              "base_currency_id": "BTC",
+              "mid_market_price": "",
+              "alias": "",
+              "alias_to": []
+            },
+            {
+              "product_id": "NEW-USD",
+              "price": "",
+              "price_percentage_change_24h": "",
+              "volume_24h": "",
+              "base_increment": "0.1",
+              "quote_increment": "0.0001",
+              "quote_min_size": "1",
+              "quote_max_size": "10000000",
+              "base_min_size": "0.1",
+              "base_max_size": "1000000",
+              "is_disabled": false,
+              "status": "online",
+              "cancel_only": false,
+              "limit_only": true,
+              "post_only": false,
+              "trading_disabled": false,
+              "product_type": "SPOT",
+              "quote_currency_id": "USD",
+              "base_currency_id": "NEW"
+            }
+          ],
+          "num_products": 2
+        }"#;
+        let list: ProductList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.num_products, 2);
+        let btc = &list.products[0];
+        assert_eq!(btc.product_id, Atom::from("BTC-USD"));
+        assert_eq!(btc.price, Some(dec!(67123.45)));
+        assert_eq!(btc.price_percentage_change_24h, Some(dec!(-1.2345)));
+        assert_eq!(btc.quote_increment, dec!(0.01));
+        let new = &list.products[1];
+        assert_eq!(new.price, None);
+        assert_eq!(new.volume_24h, None);
+        assert!(new.limit_only);
+    }
+}
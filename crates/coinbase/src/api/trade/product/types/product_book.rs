@@ -0,0 +1,74 @@
+use crate::api::trade::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ProductBook {
+    pub pricebook: PriceBook,
+    /// The last trade price.
+    #[serde(default, with = "maybe_str")]
+    pub last: Option<Decimal>,
+    /// The mid-market price.
+    #[serde(default, with = "maybe_str")]
+    pub mid_market: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct PriceBook {
+    pub product_id: Atom,
+    /// Best bid first.
+    pub bids: Vec<PriceBookLevel>,
+    /// Best ask first.
+    pub asks: Vec<PriceBookLevel>,
+    /// The time of the snapshot.
+    pub time: DtCoinbasePrime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct PriceBookLevel {
+    /// The price of the level in the quote currency.
+    pub price: Decimal,
+    /// The total size of the orders at this price in the base currency.
+    pub size: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_doc() {
+        let json = r#"{
+          "pricebook": {
+            "product_id": "BTC-USD",
+            "bids": [
+              {"price": "67100.01", "size": "0.25"},
+              {"price": "67100", "size": "1.5"}
+            ],
+            "asks": [
+              {"price": "67100.02", "size": "0.1"}
+            ],
+            "time": "2024-06-05T10:07:08.123456Z"
+          },
+          "last": "67100.02",
+          "mid_market": "67100.015",
+          "spread_bps": "0.0014",
+          "spread_absolute": "0.01"
+        }"#;
+        let book: ProductBook = serde_json::from_str(json).unwrap();
+        assert_eq!(book.pricebook.product_id, Atom::from("BTC-USD"));
+        assert_eq!(
+            book.pricebook.bids[1],
+            PriceBookLevel {
+                price: dec!(67100),
+                size: dec!(1.5),
+            }
+        );
+        assert_eq!(book.pricebook.asks[0].price, dec!(67100.02));
+        assert_eq!(book.mid_market, Some(dec!(67100.015)));
+        assert_eq!(
+            book.pricebook.time,
+            DtCoinbasePrime::parse_from_str("2024-06-05T10:07:08.123456Z").unwrap()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::api::trade::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct ProductTicker {
+    /// The latest trades, most recent first.
+    pub trades: Vec<MarketTrade>,
+    /// The best bid price in the quote currency.
+    #[serde(default, with = "maybe_str")]
+    pub best_bid: Option<Decimal>,
+    /// The best ask price in the quote currency.
+    #[serde(default, with = "maybe_str")]
+    pub best_ask: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct MarketTrade {
+    /// The unique identifier for the trade.
+    pub trade_id: String,
+    pub product_id: Atom,
+    /// The price of the trade in the quote currency.
+    pub price: Decimal,
+    /// The size of the trade in the base currency.
+    pub size: Decimal,
+    /// The time of the trade.
+    pub time: DtCoinbasePrime,
+    /// The side of the taker.
+    pub side: MarketTradeSide,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MarketTradeSide {
+    Buy,
+    Sell,
+    #[serde(other)]
+    UnknownOrderSide,
+}
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_doc() {
+        let json = r#"{
+          "trades": [
+            {
+              "trade_id": "34b080bf-fcfd-445a-832b-46b5ddc65601",
+              "product_id": "BTC-USD",
+              "price": "140.91",
+              "size": "4",
+              "time": "2021-05-31T09:59:59.000Z",
+              "side": "BUY",
+              "bid": "",
+              "ask": "",
+              "exchange": "coinbase"
+            }
+          ],
+          "best_bid": "291.13",
+          "best_ask": "292.40"
+        }"#;
+        let sample = ProductTicker {
+            trades: vec![MarketTrade {
+                trade_id: "34b080bf-fcfd-445a-832b-46b5ddc65601".to_string(),
+                product_id: "BTC-USD".into(),
+                price: dec!(140.91),
+                size: dec!(4),
+                time: DtCoinbasePrime::parse_from_str("2021-05-31T09:59:59.000Z").unwrap(),
+                side: MarketTradeSide::Buy,
+            }],
+            best_bid: Some(dec!(291.13)),
+            best_ask: Some(dec!(292.40)),
+        };
+        let ticker: ProductTicker = serde_json::from_str(json).unwrap();
+        assert_eq!(ticker, sample);
+    }
+}
@@ -77,7 +77,7 @@ where
             .timeout(std::time::Duration::from_secs(60));
 
         if let Some(proxy) = proxy {
-            let proxy_url = format!("socks5://{}", proxy.addr());
+            let proxy_url = proxy.url();
             if let Ok(reqwest_proxy) = ::reqwest::Proxy::all(&proxy_url) {
                 builder = builder.proxy(reqwest_proxy);
             }
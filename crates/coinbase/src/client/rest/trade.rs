@@ -50,7 +50,7 @@ where
     method: Method,
     query_params: Vec<(String, String)>,
     headers: HashMap<String, String>,
-    sign: Option<(u32,)>,
+    sign: Option<(u64,)>,
     body: String,
 }
 
@@ -76,7 +76,7 @@ where
             .timeout(std::time::Duration::from_secs(60));
 
         if let Some(proxy) = proxy {
-            let proxy_url = format!("socks5://{}", proxy.addr());
+            let proxy_url = proxy.url();
             if let Ok(reqwest_proxy) = ::reqwest::Proxy::all(&proxy_url) {
                 builder = builder.proxy(reqwest_proxy);
             }
@@ -138,7 +138,11 @@ where
         name: Name,
         query: &T,
     ) -> CoinbaseResult<Self> {
-        let serialized = serde_json::to_string(query)?;
+        // Strings go as is, `url` encodes them with the rest of the query.
+        let serialized = match serde_json::to_value(query)? {
+            serde_json::Value::String(s) => s,
+            value => value.to_string(),
+        };
         self.query_params
             .push((name.as_ref().to_string(), serialized));
         Ok(self)
@@ -168,7 +172,7 @@ where
         Ok(self)
     }
 
-    pub fn signed(mut self, timestamp: u32) -> CoinbaseResult<Self> {
+    pub fn signed(mut self, timestamp: u64) -> CoinbaseResult<Self> {
         self.sign = Some((timestamp,));
         Ok(self)
     }
@@ -187,11 +191,6 @@ where
             .client
             .request(self.method.clone(), self.url.clone());
 
-        // Add query parameters
-        if !self.query_params.is_empty() {
-            request = request.query(&self.query_params);
-        }
-
         // Add headers
         for (key, value) in &self.headers {
             request = request.header(key, value);
@@ -246,30 +245,30 @@ where
     }
 
     async fn sign(mut self) -> CoinbaseResult<Self> {
+        // The query is a part of the signed request target.
+        let query_params = std::mem::take(&mut self.query_params);
+        if !query_params.is_empty() {
+            self.url.query_pairs_mut().extend_pairs(query_params);
+        }
+
         if let Some((timestamp,)) = self.sign {
-            let path_and_query = format!(
-                "{}{}",
-                self.url.path(),
-                self.url
-                    .query()
-                    .map(|q| format!("?{}", q))
-                    .unwrap_or_default()
-            );
+            let request_target = request_target(&self.url);
 
             if self.method == Method::GET {
                 self.body = String::new();
             }
 
-            let signature = self
-                .api_client
-                .inner
-                .config
-                .signer()
-                .sign_data(timestamp, self.method.as_str(), &path_and_query, &self.body)
+            let signer = self.api_client.inner.config.signer();
+            let signature = signer
+                .sign_data(timestamp, self.method.as_str(), &request_target, &self.body)
                 .await?;
 
-            self.headers
-                .insert("Authorization".to_string(), format!("Bearer {}", signature));
+            if signer.is_bearer() {
+                self.headers
+                    .insert("Authorization".to_string(), format!("Bearer {}", signature));
+            } else {
+                self.headers.insert("CB-ACCESS-SIGN".to_string(), signature);
+            }
             self.headers.insert(
                 "CB-ACCESS-KEY".to_string(),
                 self.api_client.inner.config.api_key().to_string(),
@@ -294,6 +293,14 @@ struct ApiErrorMessage {
     message: String,
 }
 
+/// Path and `?query` exactly as sent on the wire.
+fn request_target(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
 fn check_response(status: StatusCode) -> CoinbaseApiResult<()> {
     match status {
         StatusCode::OK => Ok(()),
@@ -326,3 +333,125 @@ fn from_response<V: DeserializeOwned>(code: StatusCode, body: &[u8]) -> Coinbase
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ccx_api_lib::dec;
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::api::trade::CreateOrderRequest;
+    use crate::api::trade::OrderConfiguration;
+    use crate::api::trade::OrderSide;
+
+    #[test]
+    fn test_request_target() {
+        let url = Url::parse("https://api.coinbase.com/api/v3/brokerage/orders").unwrap();
+        assert_eq!(request_target(&url), "/api/v3/brokerage/orders");
+
+        let mut url = Url::parse("https://api.coinbase.com/orders").unwrap();
+        url.query_pairs_mut()
+            .append_pair("status", "open")
+            .append_pair("product_id", "BTC USD");
+        assert_eq!(
+            request_target(&url),
+            "/orders?status=open&product_id=BTC+USD"
+        );
+    }
+
+    #[test]
+    fn test_sign_create_order() {
+        let cred = ApiCred::new(Some("key".to_string()), Some("secret".to_string()));
+        let client = RestTradeClient::new(TradeConfig::new(
+            cred.clone(),
+            Url::parse("https://api.coinbase.com/").unwrap(),
+            Url::parse("wss://coinbase.com/").unwrap(),
+            None,
+        ));
+        let request = CreateOrderRequest::new(
+            "0000-00000-000000",
+            "BTC-USD",
+            OrderSide::Buy,
+            OrderConfiguration::limit_gtc(dec!(0.001), dec!(10000), false),
+        );
+        let builder = client
+            .post("/api/v3/brokerage/orders")
+            .unwrap()
+            .signed(1700000000)
+            .unwrap()
+            .request_body(&request)
+            .unwrap();
+        assert_eq!(
+            builder.body,
+            r#"{"client_order_id":"0000-00000-000000","product_id":"BTC-USD","side":"BUY","order_configuration":{"limit_limit_gtc":{"base_size":"0.001","limit_price":"10000","post_only":false}}}"#
+        );
+
+        let signature = block_on(cred.sign_data(
+            1700000000,
+            "POST",
+            "/api/v3/brokerage/orders",
+            &builder.body,
+        ))
+        .unwrap();
+        let builder = block_on(builder.sign()).unwrap();
+        assert_eq!(builder.headers["CB-ACCESS-SIGN"], signature);
+        assert_eq!(builder.headers["CB-ACCESS-KEY"], "key");
+        assert_eq!(builder.headers["CB-ACCESS-TIMESTAMP"], "1700000000");
+        assert!(!builder.headers.contains_key("Authorization"));
+    }
+
+    #[test]
+    fn test_sign_query_args() {
+        let cred = ApiCred::new(Some("key".to_string()), Some("secret".to_string()));
+        let client = RestTradeClient::new(TradeConfig::new(
+            cred.clone(),
+            Url::parse("https://api.coinbase.com/").unwrap(),
+            Url::parse("wss://coinbase.com/").unwrap(),
+            None,
+        ));
+        let builder = client
+            .get("/api/v3/brokerage/orders/historical/fills")
+            .unwrap()
+            .query_arg("limit", &100)
+            .unwrap()
+            .signed(1700000000)
+            .unwrap();
+
+        let signature = block_on(cred.sign_data(
+            1700000000,
+            "GET",
+            "/api/v3/brokerage/orders/historical/fills?limit=100",
+            "",
+        ))
+        .unwrap();
+        let builder = block_on(builder.sign()).unwrap();
+        assert_eq!(builder.headers["CB-ACCESS-SIGN"], signature);
+        assert_eq!(
+            builder.uri(),
+            "https://api.coinbase.com/api/v3/brokerage/orders/historical/fills?limit=100"
+        );
+    }
+
+    #[test]
+    fn test_query_args_unsigned() {
+        let client = RestTradeClient::new(TradeConfig::new(
+            ApiCred::new(None, None),
+            Url::parse("https://api.coinbase.com/").unwrap(),
+            Url::parse("wss://coinbase.com/").unwrap(),
+            None,
+        ));
+        let builder = client
+            .get("/api/v3/brokerage/market/product_book")
+            .unwrap()
+            .query_arg("product_id", "BTC-USD&x=1")
+            .unwrap()
+            .try_query_arg("limit", &Some(10))
+            .unwrap();
+        let builder = block_on(builder.sign()).unwrap();
+        assert_eq!(
+            builder.uri(),
+            "https://api.coinbase.com/api/v3/brokerage/market/product_book?product_id=BTC-USD%26x%3D1&limit=10"
+        );
+        assert!(!builder.headers.contains_key("CB-ACCESS-SIGN"));
+    }
+}
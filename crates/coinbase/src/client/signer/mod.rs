@@ -1,7 +1,9 @@
 mod exchange;
+mod jwt;
 mod prime;
 mod trade;
 
 pub use self::exchange::*;
+pub use self::jwt::*;
 pub use self::prime::*;
 pub use self::trade::*;
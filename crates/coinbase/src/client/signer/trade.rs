@@ -10,23 +10,34 @@ use crate::CoinbaseResult;
 pub type TradeSignResult<'a> = Pin<Box<dyn Future<Output = CoinbaseResult<String>> + Send + 'a>>;
 
 pub trait CoinbaseTradeSigner: Sync + Send {
+    /// Signs `timestamp + method + request_target + json_payload`.
+    ///
+    /// `request_target` is the path followed by `?query` if the request has one. It must be
+    /// exactly the string that goes on the wire (same encoding and parameter order),
+    /// otherwise the signature is rejected.
     fn sign_data<'a, 'b: 'a, 'c: 'b>(
         &'c self,
-        timestamp: u32,
+        timestamp: u64,
         method: &'b str,
-        url_path: &'b str,
+        request_target: &'b str,
         json_payload: &'b str,
     ) -> TradeSignResult<'a>;
 
     fn api_key(&self) -> &str;
+
+    /// Whether `sign_data` yields a bearer token for the `Authorization` header
+    /// rather than a `CB-ACCESS-SIGN` signature.
+    fn is_bearer(&self) -> bool {
+        false
+    }
 }
 
 impl CoinbaseTradeSigner for ApiCred {
     fn sign_data<'a, 'b: 'a, 'c: 'b>(
         &'c self,
-        timestamp: u32,
+        timestamp: u64,
         method: &'b str,
-        url_path: &'b str,
+        request_target: &'b str,
         json_payload: &'b str,
     ) -> TradeSignResult<'a> {
         Box::pin(async move {
@@ -34,7 +45,7 @@ impl CoinbaseTradeSigner for ApiCred {
                 &self.secret,
                 timestamp,
                 method,
-                url_path,
+                request_target,
                 json_payload,
             ))
         })
@@ -45,7 +56,13 @@ impl CoinbaseTradeSigner for ApiCred {
     }
 }
 
-fn sign(secret: &str, timestamp: u32, method: &str, url_path: &str, json_payload: &str) -> String {
+fn sign(
+    secret: &str,
+    timestamp: u64,
+    method: &str,
+    request_target: &str,
+    json_payload: &str,
+) -> String {
     use hmac::Hmac;
     use hmac::Mac;
     use sha2::Sha256;
@@ -53,9 +70,9 @@ fn sign(secret: &str, timestamp: u32, method: &str, url_path: &str, json_payload
     let mut mac =
         Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
 
-    mac.update(ArrStr::from_u32(timestamp).as_ref());
+    mac.update(ArrStr::from_u64(timestamp).as_ref());
     mac.update(method.as_bytes());
-    mac.update(url_path.as_bytes());
+    mac.update(request_target.as_bytes());
     mac.update(json_payload.as_bytes());
 
     let payload = mac.finalize().into_bytes();
@@ -84,13 +101,8 @@ impl<const N: usize> ArrStr<N> {
 }
 
 impl ArrStr<0> {
-    // fn from_u64(v: u64) -> ArrStr<20> {
-    //     // 20 - the length of u64::max_value.
-    //     ArrStr::new(v).unwrap()
-    // }
-
-    fn from_u32(v: u32) -> ArrStr<10> {
-        // 10 - the length of u32::max_value.
+    fn from_u64(v: u64) -> ArrStr<20> {
+        // 20 - the length of u64::max_value.
         ArrStr::new(v).unwrap()
     }
 }
@@ -106,3 +118,47 @@ impl<const N: usize> AsRef<str> for ArrStr<N> {
         unsafe { from_utf8_unchecked(&self.buf[..self.len]) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/api/v3/brokerage/orders";
+    const PAYLOAD: &str = r#"{"product_id":"BTC-USD"}"#;
+
+    #[test]
+    fn test_arr_str_from_u64() {
+        let s = ArrStr::from_u64(0);
+        assert_eq!(s.as_ref() as &str, "0");
+        let s = ArrStr::from_u64(u64::MAX);
+        assert_eq!(s.as_ref() as &str, "18446744073709551615");
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign("secret", 1700000000, "POST", PATH, PAYLOAD),
+            "85450fb57cff2b0b806fc794acde2a85b8c7de00b9fac8b2a5c8025fb3bcb7e0"
+        );
+        // Millisecond timestamps do not fit into u32.
+        assert_eq!(
+            sign("secret", 1700000000123, "POST", PATH, PAYLOAD),
+            "5e357418d61cf071c7138b1948a2b24fb41206f3e0a1c691f61d67916c6b9f1b"
+        );
+        assert_eq!(
+            sign("secret", u64::MAX, "POST", PATH, PAYLOAD),
+            "060b83e4c0212a4079c3611fd3a3b614e8500873b5dcf462267e52370d82066f"
+        );
+    }
+
+    #[test]
+    fn test_sign_get_with_query() {
+        let signature = sign("secret", 1700000000, "GET", "/orders?status=open", "");
+        assert_eq!(
+            signature,
+            "c0b4e3fa172f972abb163c02db62b82f7b9f60cbc954a0ca4aae59881bfe0ec3"
+        );
+        // The query is a part of the signed payload.
+        assert_ne!(signature, sign("secret", 1700000000, "GET", "/orders", ""));
+    }
+}
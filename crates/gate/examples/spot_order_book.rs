@@ -0,0 +1,77 @@
+use ccx_gate::GateApi;
+use ccx_gate::api::spot::SpotOrderBookRequest;
+use ccx_gate::util::GateApiCred;
+use ccx_gate::util::OrderBookUpdater;
+use ccx_gate::websocket::order_book::OrderBookUpdateRequest;
+use ccx_gate::websocket::order_book::UpdateInterval;
+use ccx_gate::websocket::request::WsRequestEvent;
+use ccx_gate::websocket::response::Event;
+use ccx_gate::websocket::response::EventInner;
+use futures::StreamExt;
+
+const PAIR: &str = "BTC_USDT";
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenvy::dotenv();
+
+    env_logger::init();
+
+    let api = GateApi::<GateApiCred>::from_env();
+
+    loop {
+        let (tx, mut rx) = api.websocket().await.unwrap().split();
+        tx.order_book_update(
+            WsRequestEvent::Subscribe,
+            OrderBookUpdateRequest {
+                pair: PAIR.into(),
+                interval: UpdateInterval::Ms100,
+            },
+        )
+        .await
+        .unwrap();
+        tx.trades(WsRequestEvent::Subscribe, &[PAIR]).await.unwrap();
+
+        // Updates received meanwhile wait in the channel and are applied after the snapshot.
+        let snapshot = api
+            .spot()
+            .order_book(&SpotOrderBookRequest {
+                with_id: Some(true),
+                ..SpotOrderBookRequest::currency_pair(PAIR.into())
+            })
+            .await
+            .unwrap();
+        let mut updater = OrderBookUpdater::new();
+        updater.init(snapshot).unwrap();
+
+        loop {
+            let Some(response) = rx.next().await else {
+                log::warn!("connection closed");
+                break;
+            };
+            match response.event {
+                Event::OrderBookUpdate(EventInner::Update(Ok(diff))) => {
+                    if let Err(e) = updater.push_diff(diff) {
+                        log::warn!("resync: {e}");
+                        break;
+                    }
+                    if let Some(state) = updater.state().filter(|state| state.is_ready()) {
+                        println!(
+                            "{PAIR} #{}: bid {:?} ask {:?}",
+                            state.last_update_id(),
+                            state.next_bid(),
+                            state.next_ask(),
+                        );
+                    }
+                }
+                Event::Trades(EventInner::Update(Ok(trade))) => {
+                    println!(
+                        "{PAIR} trade: {:?} {} @ {}",
+                        trade.side, trade.amount, trade.price
+                    );
+                }
+                event => log::debug!("{event:?}"),
+            }
+        }
+    }
+}
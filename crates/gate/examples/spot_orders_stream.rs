@@ -0,0 +1,18 @@
+use ccx_gate::GateApi;
+use ccx_gate::util::GateApiCred;
+use ccx_gate::websocket::spot::SpotWebsocket;
+use futures::StreamExt;
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenvy::dotenv();
+
+    env_logger::init();
+
+    let api = GateApi::<GateApiCred>::from_env();
+
+    let mut orders = SpotWebsocket::orders(&api, &["!all"]).await.unwrap();
+    while let Some(update) = orders.next().await {
+        dbg!(update).ok();
+    }
+}
@@ -504,6 +504,49 @@ pub enum GateApiError {
     #[error("Internal server error {0:?}")]
     #[serde(rename = "CLIENT_ERROR")]
     ClientError(GateErrorInfo),
+
+    /// A label missing from the list above.
+    #[error("{label}: {message}")]
+    #[serde(skip)]
+    Other {
+        label: SmartString<32>,
+        message: SmartString<104>,
+    },
+}
+
+impl GateApiError {
+    /// Parses the body of a failed response, keeping unknown labels as [`GateApiError::Other`].
+    pub fn from_body(body: &[u8]) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        struct UnknownError {
+            label: SmartString<32>,
+            #[serde(default)]
+            message: SmartString<104>,
+        }
+
+        serde_json::from_slice(body).or_else(|e| {
+            let UnknownError { label, message } = serde_json::from_slice(body).map_err(|_| e)?;
+            Ok(GateApiError::Other { label, message })
+        })
+    }
+
+    pub fn is_balance_not_enough(&self) -> bool {
+        matches!(self, GateApiError::BalanceNotEnough(_))
+    }
+
+    pub fn is_order_not_found(&self) -> bool {
+        matches!(self, GateApiError::OrderNotFound(_))
+    }
+
+    /// The request, e.g. a withdrawal with a reused `withdraw_order_id`, was already made.
+    pub fn is_duplicate_request(&self) -> bool {
+        matches!(
+            self,
+            GateApiError::DuplicateRequest(_)
+                | GateApiError::OrderExists(_)
+                | GateApiError::RepeatedCreation(_)
+        )
+    }
 }
 
 #[cfg(test)]
@@ -527,4 +570,33 @@ mod tests {
 
         assert_eq!(resp, GateApiError::InvalidSignature(sample));
     }
+
+    #[test]
+    fn test_from_body() {
+        let resp = GateApiError::from_body(
+            br#"{"label":"BALANCE_NOT_ENOUGH","message":"Not enough balance"}"#,
+        )
+        .unwrap();
+        assert!(resp.is_balance_not_enough());
+        assert!(!resp.is_order_not_found());
+
+        let resp =
+            GateApiError::from_body(br#"{"label":"DUPLICATE_REQUEST","message":"Duplicate"}"#)
+                .unwrap();
+        assert!(resp.is_duplicate_request());
+
+        let resp =
+            GateApiError::from_body(br#"{"label":"NEW_LABEL","message":"Something went wrong"}"#)
+                .unwrap();
+        assert_eq!(
+            resp,
+            GateApiError::Other {
+                label: "NEW_LABEL".into(),
+                message: "Something went wrong".to_fmt(),
+            }
+        );
+        assert_eq!(resp.to_string(), "NEW_LABEL: Something went wrong");
+
+        assert!(GateApiError::from_body(b"<html></html>").is_err());
+    }
 }
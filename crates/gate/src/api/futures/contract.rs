@@ -0,0 +1,183 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::Serialize;
+use smart_string::SmartString;
+
+use crate::api::ApiMethod;
+use crate::api::ApiVersion;
+use crate::api::PublicRequest;
+use crate::api::Request;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractRequest;
+
+impl Request for ContractRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Contract;
+}
+
+impl PublicRequest for ContractRequest {}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskLimitTiersRequest {
+    pub contract: SmartString,
+}
+
+impl Request for RiskLimitTiersRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Vec<RiskLimitTier>;
+}
+
+impl PublicRequest for RiskLimitTiersRequest {}
+
+/// Perpetual futures contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contract {
+    /// Contract name, e.g. `BTC_USDT`.
+    pub name: SmartString,
+    /// Value of one contract in the base currency.
+    pub quanto_multiplier: Decimal,
+    pub leverage_min: Decimal,
+    /// Max leverage of the lowest risk limit tier.
+    pub leverage_max: Decimal,
+    /// Maintenance rate of the lowest risk limit tier.
+    pub maintenance_rate: Decimal,
+    pub mark_price: Decimal,
+    /// Minimum order size, in contracts.
+    pub order_size_min: i64,
+    /// Maximum order size, in contracts.
+    pub order_size_max: i64,
+    pub risk_limit_base: Decimal,
+    pub risk_limit_step: Decimal,
+    pub risk_limit_max: Decimal,
+    pub in_delisting: bool,
+}
+
+/// Risk limit tier of a contract.
+///
+/// The larger the position, the higher the tier and the lower the leverage allowed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskLimitTier {
+    pub tier: u32,
+    /// Max position value of the tier.
+    pub risk_limit: Decimal,
+    pub initial_rate: Decimal,
+    pub maintenance_rate: Decimal,
+    pub leverage_max: Decimal,
+}
+
+impl RiskLimitTier {
+    /// Highest leverage allowed by any of `tiers`, the one of the lowest tier.
+    pub fn max_leverage(tiers: &[RiskLimitTier]) -> Option<Decimal> {
+        tiers.iter().map(|tier| tier.leverage_max).max()
+    }
+}
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::futures::FuturesApi;
+    use crate::api::futures::Settle;
+    use crate::client::rest::RequestError;
+    use crate::client::signer::GateSigner;
+
+    impl<S: GateSigner> FuturesApi<S> {
+        /// Get a single contract
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/contracts/{contract}`
+        pub async fn contract(
+            &self,
+            settle: Settle,
+            contract: &str,
+        ) -> Result<Contract, RequestError> {
+            let settle = settle.as_str();
+            let path = format!("/futures/{settle}/contracts/{contract}");
+            self.0.request(&path, &ContractRequest).await
+        }
+
+        /// List risk limit tiers of a contract
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/risk_limit_tiers`
+        pub async fn risk_limit_tiers(
+            &self,
+            settle: Settle,
+            contract: &str,
+        ) -> Result<Vec<RiskLimitTier>, RequestError> {
+            let request = RiskLimitTiersRequest {
+                contract: contract.into(),
+            };
+            let path = format!("/futures/{}/risk_limit_tiers", settle.as_str());
+            self.0.request(&path, &request).await
+        }
+
+        /// Highest leverage allowed for a contract
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/risk_limit_tiers`
+        ///
+        /// # Description
+        /// Derived from the risk limit tiers of the contract. Falls back to the
+        /// `leverage_max` of the contract when Gate lists no tiers for it.
+        pub async fn max_leverage_for(
+            &self,
+            settle: Settle,
+            contract: &str,
+        ) -> Result<Decimal, RequestError> {
+            let tiers = self.risk_limit_tiers(settle, contract).await?;
+            match RiskLimitTier::max_leverage(&tiers) {
+                Some(leverage) => Ok(leverage),
+                None => Ok(self.contract(settle, contract).await?.leverage_max),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_contract() {
+        let json = r#"{
+            "name": "BTC_USDT",
+            "type": "direct",
+            "quanto_multiplier": "0.0001",
+            "maintenance_rate": "0.005",
+            "mark_type": "index",
+            "mark_price": "37985.6",
+            "order_price_round": "0.1",
+            "order_size_min": 1,
+            "risk_limit_step": "1000000",
+            "leverage_min": "1",
+            "leverage_max": "100",
+            "risk_limit_base": "1000000",
+            "risk_limit_max": "8000000",
+            "order_size_max": 1000000,
+            "in_delisting": false,
+            "orders_limit": 50
+        }"#;
+        let contract: Contract = serde_json::from_str(json).unwrap();
+        assert_eq!(contract.name, "BTC_USDT");
+        assert_eq!(contract.quanto_multiplier, dec!(0.0001));
+        assert_eq!(contract.leverage_max, dec!(100));
+        assert_eq!(contract.order_size_max, 1000000);
+    }
+
+    #[test]
+    fn test_max_leverage() {
+        let json = r#"[
+            {"tier": 1, "risk_limit": "500000", "initial_rate": "0.01", "maintenance_rate": "0.005", "leverage_max": "100", "contract": "BTC_USDT", "deduction": "0"},
+            {"tier": 2, "risk_limit": "1000000", "initial_rate": "0.02", "maintenance_rate": "0.01", "leverage_max": "50", "contract": "BTC_USDT", "deduction": "2500"}
+        ]"#;
+        let tiers: Vec<RiskLimitTier> = serde_json::from_str(json).unwrap();
+        assert_eq!(tiers[1].risk_limit, dec!(1000000));
+        assert_eq!(RiskLimitTier::max_leverage(&tiers), Some(dec!(100)));
+        assert_eq!(RiskLimitTier::max_leverage(&[]), None);
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use super::FuturesOrder;
+use super::Position;
+
+/// Open positions and orders that make a checked leverage or position mode change refuse.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FuturesExposure {
+    /// Positions with a non-zero size.
+    pub positions: Vec<Position>,
+    pub open_orders: Vec<FuturesOrder>,
+}
+
+impl FuturesExposure {
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty() && self.open_orders.is_empty()
+    }
+}
+
+impl fmt::Display for FuturesExposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} open position(s)", self.positions.len())?;
+        for position in &self.positions {
+            write!(f, " {}:{}", position.contract, position.size)?;
+        }
+        write!(f, ", {} open order(s)", self.open_orders.len())
+    }
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use rust_decimal::Decimal;
+    use thiserror::Error;
+
+    use super::*;
+    use crate::api::futures::FuturesAccount;
+    use crate::api::futures::FuturesApi;
+    use crate::api::futures::ListFuturesOrdersRequest;
+    use crate::api::futures::ListPositionsRequest;
+    use crate::api::futures::Settle;
+    use crate::api::futures::UpdateLeverageRequest;
+    use crate::client::rest::RequestError;
+    use crate::client::signer::GateSigner;
+
+    #[derive(Debug, Error)]
+    pub enum FuturesGuardError {
+        #[error("Refused with {0}")]
+        Exposed(FuturesExposure),
+        #[error("Request error: {0}")]
+        Request(#[from] RequestError),
+    }
+
+    impl<S: GateSigner> FuturesApi<S> {
+        /// Open positions and orders, of one contract or of all of them
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/positions`, `GET /futures/{settle}/orders`
+        pub async fn exposure(
+            &self,
+            settle: Settle,
+            contract: Option<&str>,
+        ) -> Result<FuturesExposure, RequestError> {
+            let request = ListPositionsRequest {
+                holding: Some(true),
+            };
+            let mut positions = self.list_positions(settle, &request).await?;
+            positions.retain(|position| {
+                position.is_open() && contract.is_none_or(|contract| position.contract == contract)
+            });
+            let request = ListFuturesOrdersRequest::open(contract);
+            let open_orders = self.list_orders(settle, &request).await?;
+            Ok(FuturesExposure {
+                positions,
+                open_orders,
+            })
+        }
+
+        /// Update position leverage unless a position or an order of the contract is open
+        ///
+        /// # Description
+        /// Refuses with [`FuturesGuardError::Exposed`] holding the current exposure, unless
+        /// `force` is set, in which case it is the same as [`Self::update_leverage`].
+        ///
+        /// The check is best-effort: it is made with separate requests before the change, so
+        /// a position opened or an order placed in between is not detected.
+        pub async fn set_leverage_checked(
+            &self,
+            settle: Settle,
+            contract: &str,
+            leverage: Decimal,
+            force: bool,
+        ) -> Result<Position, FuturesGuardError> {
+            if !force {
+                let exposure = self.exposure(settle, Some(contract)).await?;
+                if !exposure.is_empty() {
+                    return Err(FuturesGuardError::Exposed(exposure));
+                }
+            }
+            let request = UpdateLeverageRequest {
+                leverage,
+                cross_leverage_limit: None,
+            };
+            Ok(self.update_leverage(settle, contract, &request).await?)
+        }
+
+        /// Switch the position mode unless a position or an order of any contract is open
+        ///
+        /// # Description
+        /// Refuses with [`FuturesGuardError::Exposed`] holding the current exposure, unless
+        /// `force` is set, in which case it is the same as [`Self::set_dual_mode`].
+        ///
+        /// The check is best-effort, see [`Self::set_leverage_checked`].
+        pub async fn set_dual_mode_checked(
+            &self,
+            settle: Settle,
+            enabled: bool,
+            force: bool,
+        ) -> Result<FuturesAccount, FuturesGuardError> {
+            if !force {
+                let exposure = self.exposure(settle, None).await?;
+                if !exposure.is_empty() {
+                    return Err(FuturesGuardError::Exposed(exposure));
+                }
+            }
+            Ok(self.set_dual_mode(settle, enabled).await?)
+        }
+    }
+}
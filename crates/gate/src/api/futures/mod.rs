@@ -0,0 +1,35 @@
+mod contract;
+mod guard;
+mod order;
+mod position;
+
+pub use contract::*;
+pub use guard::*;
+pub use order::*;
+pub use position::*;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::GateApi;
+
+/// Futures trading
+#[derive(ref_cast::RefCast, Clone)]
+#[repr(transparent)]
+pub struct FuturesApi<S>(GateApi<S>);
+
+/// Settle currency of perpetual futures, the `{settle}` part of the endpoint paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Settle {
+    Usdt,
+    Btc,
+}
+
+impl Settle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Settle::Usdt => "usdt",
+            Settle::Btc => "btc",
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::DateTime;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::TimestampSecondsWithFrac;
+use serde_with::formats::Flexible;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use smart_string::SmartString;
+
+use crate::api::ApiMethod;
+use crate::api::ApiVersion;
+use crate::api::PrivateRequest;
+use crate::api::Request;
+
+/// Request list of futures orders
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct ListFuturesOrdersRequest {
+    /// Orders of all contracts if not specified.
+    pub contract: Option<SmartString>,
+    pub status: FuturesOrderStatus,
+    /// Maximum number of records to be returned.
+    pub limit: Option<u32>,
+}
+
+impl ListFuturesOrdersRequest {
+    pub fn open(contract: Option<&str>) -> Self {
+        ListFuturesOrdersRequest {
+            contract: contract.map(Into::into),
+            status: FuturesOrderStatus::Open,
+            limit: None,
+        }
+    }
+}
+
+impl Request for ListFuturesOrdersRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Vec<FuturesOrder>;
+}
+
+impl PrivateRequest for ListFuturesOrdersRequest {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FuturesOrderStatus {
+    Open,
+    Finished,
+}
+
+/// Futures order.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FuturesOrder {
+    pub id: i64,
+    pub contract: SmartString,
+    /// Order size in contracts, negative for sell.
+    pub size: i64,
+    /// Size left to fill.
+    pub left: i64,
+    /// Order price, 0 for market orders.
+    pub price: Decimal,
+    pub status: FuturesOrderStatus,
+    /// Custom order text.
+    pub text: Option<SmartString>,
+    #[serde(default)]
+    pub is_reduce_only: bool,
+    #[serde(default)]
+    pub is_close: bool,
+    #[serde_as(as = "TimestampSecondsWithFrac<f64, Flexible>")]
+    pub create_time: DateTime<Utc>,
+}
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::futures::FuturesApi;
+    use crate::api::futures::Settle;
+    use crate::client::rest::RequestError;
+    use crate::client::signer::GateSigner;
+
+    impl<S: GateSigner> FuturesApi<S> {
+        /// List futures orders
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/orders`
+        pub async fn list_orders(
+            &self,
+            settle: Settle,
+            request: &ListFuturesOrdersRequest,
+        ) -> Result<Vec<FuturesOrder>, RequestError> {
+            let path = format!("/futures/{}/orders", settle.as_str());
+            self.0.signed_request(&path, request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_order() {
+        let json = r#"{
+            "id": 15675394,
+            "user": 100000,
+            "contract": "BTC_USDT",
+            "create_time": 1546569968.5,
+            "size": 6024,
+            "iceberg": 0,
+            "left": 6024,
+            "price": "3765",
+            "fill_price": "0",
+            "tif": "gtc",
+            "is_reduce_only": false,
+            "is_close": false,
+            "is_liq": false,
+            "text": "t-my-custom-id",
+            "status": "open"
+        }"#;
+        let order: FuturesOrder = serde_json::from_str(json).unwrap();
+        assert_eq!(order.id, 15675394);
+        assert_eq!(order.price, dec!(3765));
+        assert_eq!(order.status, FuturesOrderStatus::Open);
+        assert_eq!(
+            order.create_time,
+            DateTime::from_timestamp_millis(1546569968500).unwrap()
+        );
+    }
+}
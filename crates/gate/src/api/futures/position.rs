@@ -0,0 +1,281 @@
+use ccx_api_lib::serde_util::plain_decimal;
+use chrono::DateTime;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::TimestampSeconds;
+use serde_with::formats::Flexible;
+use serde_with::serde_as;
+use serde_with::skip_serializing_none;
+use smart_string::SmartString;
+
+use crate::api::ApiMethod;
+use crate::api::ApiVersion;
+use crate::api::PrivateRequest;
+use crate::api::Request;
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListPositionsRequest {
+    /// Return only the positions with a non-zero size.
+    pub holding: Option<bool>,
+}
+
+impl Request for ListPositionsRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Vec<Position>;
+}
+
+impl PrivateRequest for ListPositionsRequest {}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GetPositionRequest;
+
+impl Request for GetPositionRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Position;
+}
+
+impl PrivateRequest for GetPositionRequest {}
+
+/// Gate reads the parameters from the query string.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateLeverageRequest {
+    /// New leverage, 0 for cross margin.
+    #[serde(serialize_with = "plain_decimal::serialize")]
+    pub leverage: Decimal,
+    /// Leverage limit of the cross margin, when `leverage` is 0.
+    #[serde(serialize_with = "plain_decimal::option::serialize")]
+    pub cross_leverage_limit: Option<Decimal>,
+}
+
+impl Request for UpdateLeverageRequest {
+    const METHOD: ApiMethod = ApiMethod::Post;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    const QUERY_PARAMS: bool = true;
+    type Response = Position;
+}
+
+impl PrivateRequest for UpdateLeverageRequest {}
+
+/// Gate reads the parameters from the query string.
+#[derive(Debug, Clone, Serialize)]
+pub struct SetDualModeRequest {
+    pub dual_mode: bool,
+}
+
+impl Request for SetDualModeRequest {
+    const METHOD: ApiMethod = ApiMethod::Post;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    const QUERY_PARAMS: bool = true;
+    type Response = FuturesAccount;
+}
+
+impl PrivateRequest for SetDualModeRequest {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionMode {
+    /// Single position mode.
+    Single,
+    /// Long side in dual position mode.
+    DualLong,
+    /// Short side in dual position mode.
+    DualShort,
+}
+
+/// Futures position.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Position {
+    pub contract: SmartString,
+    /// Position size in contracts, negative for short.
+    pub size: i64,
+    /// Position leverage, 0 for cross margin.
+    pub leverage: Decimal,
+    /// Current risk limit.
+    pub risk_limit: Decimal,
+    /// Max leverage allowed by the current risk limit.
+    pub leverage_max: Decimal,
+    /// Position value in the settle currency.
+    pub value: Decimal,
+    pub margin: Decimal,
+    pub entry_price: Decimal,
+    pub liq_price: Decimal,
+    pub mark_price: Decimal,
+    pub unrealised_pnl: Decimal,
+    /// Number of open orders of the contract.
+    #[serde(default)]
+    pub pending_orders: u32,
+    pub mode: PositionMode,
+    /// Leverage limit of the cross margin.
+    pub cross_leverage_limit: Option<Decimal>,
+    #[serde_as(as = "Option<TimestampSeconds<i64, Flexible>>")]
+    #[serde(default)]
+    pub update_time: Option<DateTime<Utc>>,
+}
+
+impl Position {
+    pub fn is_open(&self) -> bool {
+        self.size != 0
+    }
+}
+
+/// Futures account of a settle currency.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FuturesAccount {
+    pub currency: SmartString,
+    /// Balance including the unrealised PnL.
+    pub total: Decimal,
+    pub unrealised_pnl: Decimal,
+    pub position_margin: Decimal,
+    pub order_margin: Decimal,
+    pub available: Decimal,
+    pub in_dual_mode: bool,
+}
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::futures::FuturesApi;
+    use crate::api::futures::Settle;
+    use crate::client::rest::RequestError;
+    use crate::client::signer::GateSigner;
+
+    impl<S: GateSigner> FuturesApi<S> {
+        /// List positions
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/positions`
+        ///
+        /// # Description
+        /// In dual position mode both sides of a contract are listed.
+        pub async fn list_positions(
+            &self,
+            settle: Settle,
+            request: &ListPositionsRequest,
+        ) -> Result<Vec<Position>, RequestError> {
+            let path = format!("/futures/{}/positions", settle.as_str());
+            self.0.signed_request(&path, request).await
+        }
+
+        /// Get a single position
+        ///
+        /// # Endpoint
+        /// `GET /futures/{settle}/positions/{contract}`
+        ///
+        /// # Description
+        /// Single position mode only.
+        pub async fn position(
+            &self,
+            settle: Settle,
+            contract: &str,
+        ) -> Result<Position, RequestError> {
+            let settle = settle.as_str();
+            let path = format!("/futures/{settle}/positions/{contract}");
+            self.0.signed_request(&path, &GetPositionRequest).await
+        }
+
+        /// Update position leverage
+        ///
+        /// # Endpoint
+        /// `POST /futures/{settle}/positions/{contract}/leverage`
+        ///
+        /// # Description
+        /// Single position mode only. Gate rejects the change when it would bring an open
+        /// position over the risk limit; see [`Self::set_leverage_checked`] for a version
+        /// refusing while anything is open.
+        pub async fn update_leverage(
+            &self,
+            settle: Settle,
+            contract: &str,
+            request: &UpdateLeverageRequest,
+        ) -> Result<Position, RequestError> {
+            let path = format!("/futures/{}/positions/{contract}/leverage", settle.as_str());
+            self.0.signed_request(&path, request).await
+        }
+
+        /// Enable or disable dual position mode
+        ///
+        /// # Endpoint
+        /// `POST /futures/{settle}/dual_mode`
+        ///
+        /// # Description
+        /// Applies to all contracts of the settle currency. Gate rejects the change while
+        /// any position or order is open; see [`Self::set_dual_mode_checked`].
+        pub async fn set_dual_mode(
+            &self,
+            settle: Settle,
+            enabled: bool,
+        ) -> Result<FuturesAccount, RequestError> {
+            let request = SetDualModeRequest { dual_mode: enabled };
+            let path = format!("/futures/{}/dual_mode", settle.as_str());
+            self.0.signed_request(&path, &request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_position() {
+        let json = r#"{
+            "user": 10000,
+            "contract": "BTC_USDT",
+            "size": -9440,
+            "leverage": "0",
+            "risk_limit": "100",
+            "leverage_max": "100",
+            "maintenance_rate": "0.005",
+            "value": "2.497143098997",
+            "margin": "4.431548146258",
+            "entry_price": "3779.55",
+            "liq_price": "99999999",
+            "mark_price": "3780.32",
+            "unrealised_pnl": "-0.000507486844",
+            "realised_pnl": "0.045543982432",
+            "adl_ranking": 5,
+            "pending_orders": 16,
+            "close_order": {"id": 232323, "price": "3779", "is_liq": false},
+            "mode": "single",
+            "cross_leverage_limit": "0",
+            "update_time": 1684994406,
+            "open_time": 1684994406
+        }"#;
+        let position: Position = serde_json::from_str(json).unwrap();
+        assert_eq!(position.size, -9440);
+        assert!(position.is_open());
+        assert_eq!(position.leverage, dec!(0));
+        assert_eq!(position.pending_orders, 16);
+        assert_eq!(position.mode, PositionMode::Single);
+        assert_eq!(
+            position.update_time,
+            DateTime::from_timestamp(1684994406, 0)
+        );
+    }
+
+    #[test]
+    fn test_serialize_query() {
+        let request = UpdateLeverageRequest {
+            leverage: dec!(10),
+            cross_leverage_limit: None,
+        };
+        assert_eq!(
+            crate::api::to_query_string(&request).unwrap(),
+            "leverage=10"
+        );
+        let request = SetDualModeRequest { dual_mode: true };
+        assert_eq!(
+            crate::api::to_query_string(&request).unwrap(),
+            "dual_mode=true"
+        );
+    }
+}
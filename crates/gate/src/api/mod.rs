@@ -1,5 +1,7 @@
 mod error;
+pub mod futures;
 mod method;
+mod query;
 mod request;
 pub mod spot;
 mod version;
@@ -8,17 +10,24 @@ pub mod withdrawal;
 
 pub use error::*;
 pub use method::*;
+pub use query::*;
 pub use request::*;
 pub use version::*;
 
 pub const API_BASE: &str = "https://api.gateio.ws/api/";
 pub const STREAM_BASE: &str = "wss://api.gateio.ws/ws/v4/";
 
+pub const API_BASE_TESTNET: &str = "https://api-testnet.gateapi.io/api/";
+pub const STREAM_BASE_TESTNET: &str = "wss://ws-testnet.gate.com/v4/ws/spot";
+
 #[cfg(feature = "with_network")]
 pub use with_network::*;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use ::futures::Stream;
+    use ::futures::StreamExt;
+    use ::futures::stream;
     use ccx_api_lib::Proxy;
     use ref_cast::RefCast;
     use spot::SpotApi;
@@ -26,10 +35,14 @@ mod with_network {
     use withdrawal::WithdrawalApi;
 
     pub use super::*;
+    use crate::api::futures::FuturesApi;
     use crate::client::GateSigner;
+    use crate::client::RateLimiter;
+    use crate::client::config::ApiEnvironment;
     use crate::client::config::CCX_GATE_API_PREFIX;
     use crate::client::config::GateApiConfig;
     use crate::client::rest::RequestError;
+    use crate::client::rest::ResponseMeta;
     use crate::client::rest::RestClient;
     use crate::client::websocket::WebsocketStream;
     use crate::error::GateResult;
@@ -42,23 +55,35 @@ mod with_network {
 
     impl<S> GateApi<S> {
         pub fn new(signer: S, proxy: Option<Proxy>) -> GateApi<S> {
-            let api_base = API_BASE.parse().unwrap();
-            let stream_base = STREAM_BASE.parse().unwrap();
-            GateApi::with_config(GateApiConfig::new(signer, api_base, stream_base, proxy))
+            GateApi::with_config(GateApiConfig::live(signer, proxy))
+        }
+
+        /// Api talking to the testnet endpoints.
+        pub fn testnet(signer: S, proxy: Option<Proxy>) -> GateApi<S> {
+            GateApi::with_config(GateApiConfig::testnet(signer, proxy))
         }
 
+        /// Reads config from env vars with names like:
+        /// "CCX_GATE_API_KEY", "CCX_GATE_API_SECRET", and "CCX_GATE_API_TESTNET"
+        ///
+        /// Requests are routed through a SOCKS5 proxy when "CCX_GATE_API_PROXY_HOST" and
+        /// "CCX_GATE_API_PROXY_PORT" are set ("CCX_GATE_API_PROXY_USERNAME" and
+        /// "CCX_GATE_API_PROXY_PASSWORD" are optional).
         pub fn from_env() -> GateApi<GateApiCred> {
             Self::from_env_with_prefix(CCX_GATE_API_PREFIX)
         }
 
+        /// Reads config from env vars with names like:
+        /// "${prefix}_KEY", "${prefix}_SECRET", "${prefix}_TESTNET", and "${prefix}_PROXY_*"
         pub fn from_env_with_prefix(prefix: &str) -> GateApi<GateApiCred> {
-            // FIXME prefix
+            let environment = ApiEnvironment::from_env_with_prefix(prefix);
             let proxy = Proxy::from_env_with_prefix(prefix);
-            log::debug!(
-                "from_env_with_prefix proxy :: {:?}",
-                proxy.as_ref().map(|p| (&p.host, p.port))
-            );
-            GateApi::new(GateApiCred::from_env_with_prefix(prefix), proxy)
+            log::debug!("from_env_with_prefix proxy :: {proxy:?}");
+            GateApi::with_config(GateApiConfig::with_environment(
+                GateApiCred::from_env_with_prefix(prefix),
+                environment,
+                proxy,
+            ))
         }
 
         pub fn with_config(config: GateApiConfig<S>) -> GateApi<S> {
@@ -66,6 +91,15 @@ mod with_network {
             GateApi { client }
         }
 
+        /// Same as [`Self::with_config`], but signed requests wait for `rate_limiter`.
+        pub fn with_rate_limiter(
+            config: GateApiConfig<S>,
+            rate_limiter: RateLimiter,
+        ) -> GateApi<S> {
+            let client = RestClient::with_rate_limiter(config, rate_limiter);
+            GateApi { client }
+        }
+
         /// Unsigned request. For signed see [Self::signed_request]
         pub async fn request<R: PublicRequest>(
             &self,
@@ -79,11 +113,26 @@ mod with_network {
                 .await?)
         }
 
+        /// Api sharing endpoints, proxy and rate limiter with `self`, but signing
+        /// requests with `signer`.
+        ///
+        /// Meant for trading on behalf of several accounts from one process.
+        pub fn clone_with_signer<S2>(&self, signer: S2) -> GateApi<S2> {
+            GateApi {
+                client: self.client.clone_with_signer(signer),
+            }
+        }
+
         /// Spot trading
         pub fn spot(&self) -> &SpotApi<S> {
             RefCast::ref_cast(self)
         }
 
+        /// Futures trading
+        pub fn futures(&self) -> &FuturesApi<S> {
+            RefCast::ref_cast(self)
+        }
+
         /// Wallet operations
         pub fn wallet(&self) -> &WalletApi<S> {
             RefCast::ref_cast(self)
@@ -105,13 +154,50 @@ mod with_network {
             path: &str,
             request: &R,
         ) -> Result<R::Response, RequestError> {
+            Ok(self.signed_request_with_meta(path, request).await?.0)
+        }
+
+        /// Same as [`Self::signed_request`], but also returns the rate limit state
+        /// reported by Gate.io in the response headers.
+        pub async fn signed_request_with_meta<R: PrivateRequest>(
+            &self,
+            path: &str,
+            request: &R,
+        ) -> Result<(R::Response, ResponseMeta), RequestError> {
+            if let Some(rate_limiter) = self.client.rate_limiter() {
+                rate_limiter.acquire(self.client.signer().key()).await;
+            }
             let signed = self
                 .client
                 .prepare_rest(path, request)
                 .with_current_timestamp()
                 .sign()
                 .await?;
-            Ok(signed.call().await?)
+            Ok(signed.call_with_meta().await?)
+        }
+
+        /// Walks all pages of a list request, yielding records one by one.
+        ///
+        /// Stops after the first page shorter than [`PagedRequest::page_size`] or after an error.
+        pub fn paged_request<'a, R: PrivateRequest + PagedRequest + 'a>(
+            &'a self,
+            path: &'a str,
+            request: R,
+        ) -> impl Stream<Item = Result<R::Item, RequestError>> + 'a {
+            stream::unfold(Some(request), move |request| async move {
+                let mut request = request?;
+                let page = match self.signed_request(path, &request).await {
+                    Ok(page) => page,
+                    Err(e) => return Some((vec![Err(e)], None)),
+                };
+                let next =
+                    (!page.is_empty() && page.len() >= request.page_size() as usize).then(|| {
+                        request.next_page(page.len());
+                        request
+                    });
+                Some((page.into_iter().map(Ok).collect(), next))
+            })
+            .flat_map(stream::iter)
         }
     }
 }
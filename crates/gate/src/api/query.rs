@@ -0,0 +1,169 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde_json::Value;
+
+/// Encodes a request as a URL query string.
+///
+/// Fields keep their declaration order, `None` fields are skipped and sequences are emitted
+/// as repeated keys (`k=a&k=b`). Use [`crate::util::CommaSeparated`] for parameters that expect
+/// a single comma-joined value.
+///
+/// The output is what is both sent and signed, so it must be deterministic.
+pub fn to_query_string<T: Serialize>(request: &T) -> Result<String, serde_json::Error> {
+    let json = serde_json::to_string(request)?;
+    let OrderedFields(fields) = serde_json::from_str(&json)?;
+
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in &fields {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    if let Some(item) = scalar_to_string(item)? {
+                        query.append_pair(key, &item);
+                    }
+                }
+            }
+            value => {
+                if let Some(value) = scalar_to_string(value)? {
+                    query.append_pair(key, &value);
+                }
+            }
+        }
+    }
+    Ok(query.finish())
+}
+
+fn scalar_to_string(value: &Value) -> Result<Option<String>, serde_json::Error> {
+    use serde::ser::Error;
+
+    Ok(match value {
+        Value::Null => None,
+        Value::Bool(v) => Some(v.to_string()),
+        Value::Number(v) => Some(v.to_string()),
+        Value::String(v) => Some(v.clone()),
+        Value::Array(_) | Value::Object(_) => Err(serde_json::Error::custom(
+            "nested values are not supported in a query string",
+        ))?,
+    })
+}
+
+/// Top-level fields of a serialized request in declaration order.
+struct OrderedFields(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for OrderedFields {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = OrderedFields;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a struct or nothing")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(OrderedFields(vec![]))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(OrderedFields(fields))
+            }
+        }
+
+        deserializer.deserialize_any(FieldsVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_with::serde_as;
+    use serde_with::skip_serializing_none;
+
+    use super::*;
+    use crate::client::signer::sign;
+    use crate::util::CommaSeparated;
+
+    #[serde_as]
+    #[skip_serializing_none]
+    #[derive(Serialize)]
+    struct CommaRequest {
+        #[serde_as(as = "CommaSeparated<String>")]
+        currency_pairs: Vec<String>,
+        account: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct RepeatedRequest {
+        limit: u32,
+        currency_pair: Vec<String>,
+        page: Option<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct EmptyRequest;
+
+    #[test]
+    fn test_comma_separated() {
+        let request = CommaRequest {
+            currency_pairs: vec!["BTC_USDT".into(), "ETH_USDT".into()],
+            account: None,
+        };
+        let query = to_query_string(&request).unwrap();
+        assert_eq!(query, "currency_pairs=BTC_USDT%2CETH_USDT");
+        assert_eq!(
+            sign(
+                "secret",
+                "GET",
+                "/api/v4/spot/batch_fee",
+                &query,
+                "",
+                "1541993715"
+            ),
+            "d0108063d6a9738b492346e2c725ac227bc79c87d26d5a334f18256fa8359dd8\
+             0a4a30f2d5ebab4de2bc6f51a128f2659dc0aadbdd64820369858f7b79423f4c"
+        );
+    }
+
+    #[test]
+    fn test_repeated_keys() {
+        let request = RepeatedRequest {
+            limit: 10,
+            currency_pair: vec!["BTC_USDT".into(), "ETH_USDT".into()],
+            page: None,
+        };
+        let query = to_query_string(&request).unwrap();
+        assert_eq!(
+            query,
+            "limit=10&currency_pair=BTC_USDT&currency_pair=ETH_USDT"
+        );
+        assert_eq!(
+            sign(
+                "secret",
+                "GET",
+                "/api/v4/spot/batch_fee",
+                &query,
+                "",
+                "1541993715"
+            ),
+            "b2d22887730852fa3ac9c1c6a378906f597438b387d12c075bca7e4ce878a9d8\
+             c6015e1e6d519753f801d37aa544c3264286a006582fce6649e19f6e87302cad"
+        );
+    }
+
+    #[test]
+    fn test_empty_request() {
+        assert_eq!(to_query_string(&EmptyRequest).unwrap(), "");
+    }
+}
@@ -7,6 +7,9 @@ use crate::api::ApiVersion;
 pub trait Request: Serialize {
     const METHOD: ApiMethod;
     const VERSION: ApiVersion;
+    /// Whether a `POST` or `PUT` request sends its parameters in the query string with
+    /// an empty body, like `GET` and `DELETE` requests do.
+    const QUERY_PARAMS: bool = false;
 
     type Response: de::DeserializeOwned;
 }
@@ -16,3 +19,14 @@ pub trait PublicRequest: Request {}
 
 /// Request requires signature
 pub trait PrivateRequest: Request {}
+
+/// List request that can be walked page by page.
+pub trait PagedRequest: Request<Response = Vec<Self::Item>> + Clone {
+    type Item: de::DeserializeOwned;
+
+    /// Number of records per page: the limit set by the caller or the endpoint default.
+    fn page_size(&self) -> u32;
+
+    /// Points the request to the page following the one just received.
+    fn next_page(&mut self, received: usize);
+}
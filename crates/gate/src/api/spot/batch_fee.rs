@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::serde_as;
+use smart_string::SmartString;
+
+use crate::api::ApiMethod;
+use crate::api::ApiVersion;
+use crate::api::PrivateRequest;
+use crate::api::Request;
+use crate::util::CommaSeparated;
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpotBatchFeeRequest {
+    /// A request can only query up to 50 currency pairs
+    #[serde_as(as = "CommaSeparated<SmartString>")]
+    pub currency_pairs: Vec<SmartString>,
+}
+
+impl Request for SpotBatchFeeRequest {
+    const METHOD: ApiMethod = ApiMethod::Get;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = HashMap<SmartString, SpotFee>;
+}
+
+impl PrivateRequest for SpotBatchFeeRequest {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpotFee {
+    /// User ID
+    pub user_id: i64,
+    /// Taker fee rate
+    pub taker_fee: Decimal,
+    /// Maker fee rate
+    pub maker_fee: Decimal,
+    /// If GT deduction is enabled
+    pub gt_discount: bool,
+    /// Taker fee rate if using GT deduction. It will be 0 if GT deduction is disabled
+    pub gt_taker_fee: Decimal,
+    /// Maker fee rate if using GT deduction. It will be 0 if GT deduction is disabled
+    pub gt_maker_fee: Decimal,
+    /// Loan fee rate of margin lending
+    pub loan_fee: Decimal,
+    /// Point type. 0 - Initial version. 1 - new version since 202009
+    pub point_type: SmartString,
+    /// Currency pair
+    pub currency_pair: SmartString,
+    /// Deduction types for rates, 1 - GT deduction, 2 - Point card deduction,
+    /// 3 - VIP rates
+    pub debit_fee: i32,
+}
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use super::*;
+    use crate::api::spot::SpotApi;
+    use crate::client::rest::RequestError;
+    use crate::client::signer::GateSigner;
+
+    impl<S: GateSigner> SpotApi<S> {
+        /// # Query a batch of user trading fee rates
+        ///
+        /// ## Parameters
+        ///
+        /// * `currency_pairs` - A request can only query up to 50 currency pairs
+        pub async fn batch_fee(
+            &self,
+            currency_pairs: Vec<SmartString>,
+        ) -> Result<<SpotBatchFeeRequest as Request>::Response, RequestError> {
+            let request = SpotBatchFeeRequest { currency_pairs };
+            self.0.signed_request("/spot/batch_fee", &request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::api::to_query_string;
+
+    #[test]
+    fn test_serialize_query() {
+        let request = SpotBatchFeeRequest {
+            currency_pairs: vec!["BTC_USDT".into(), "ETH_USDT".into()],
+        };
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pairs=BTC_USDT%2CETH_USDT"
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{
+  "BTC_USDT": {
+    "user_id": 10001,
+    "taker_fee": "0.002",
+    "maker_fee": "0.002",
+    "gt_discount": false,
+    "gt_taker_fee": "0",
+    "gt_maker_fee": "0",
+    "loan_fee": "0.18",
+    "point_type": "1",
+    "currency_pair": "BTC_USDT",
+    "debit_fee": 3
+  }
+}"#;
+        let res: <SpotBatchFeeRequest as Request>::Response = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            res.get("BTC_USDT"),
+            Some(&SpotFee {
+                user_id: 10001,
+                taker_fee: dec!(0.002),
+                maker_fee: dec!(0.002),
+                gt_discount: false,
+                gt_taker_fee: dec!(0),
+                gt_maker_fee: dec!(0),
+                loan_fee: dec!(0.18),
+                point_type: "1".into(),
+                currency_pair: "BTC_USDT".into(),
+                debit_fee: 3,
+            })
+        );
+    }
+}
@@ -13,6 +13,7 @@ use crate::api::ApiMethod;
 use crate::api::ApiVersion;
 use crate::api::PublicRequest;
 use crate::api::Request;
+use crate::api::spot::OrderBookInterval;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct AllCurrencyPairsRequest;
@@ -81,6 +82,23 @@ pub struct CurrencyPair {
     pub buy_start: Option<DateTime<Utc>>,
 }
 
+impl CurrencyPair {
+    /// Order book intervals accepted for this pair, from no aggregation to the coarsest.
+    ///
+    /// Only [`OrderBookInterval::NONE`] when the precision is unknown.
+    pub fn valid_intervals(&self) -> Vec<OrderBookInterval> {
+        let mut intervals = vec![OrderBookInterval::NONE];
+        if let Some(precision) = self.precision {
+            intervals.extend(
+                (0..=precision)
+                    .rev()
+                    .map(|scale| OrderBookInterval::new_unchecked(Decimal::new(1, scale))),
+            );
+        }
+        intervals
+    }
+}
+
 /// How currency pair can be traded
 #[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
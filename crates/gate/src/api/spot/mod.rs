@@ -1,4 +1,5 @@
 mod accounts;
+mod batch_fee;
 mod currency;
 mod currency_pair;
 pub mod order;
@@ -6,6 +7,7 @@ pub mod order_book;
 mod tickers;
 
 pub use accounts::*;
+pub use batch_fee::*;
 pub use currency::*;
 pub use currency_pair::*;
 pub use order_book::*;
@@ -18,3 +20,11 @@ use super::GateApi;
 #[derive(RefCast, Clone)]
 #[repr(transparent)]
 pub struct SpotApi<S>(GateApi<S>);
+
+#[cfg(feature = "with_network")]
+impl<S> SpotApi<S> {
+    /// See [`GateApi::clone_with_signer`].
+    pub fn clone_with_signer<S2>(&self, signer: S2) -> SpotApi<S2> {
+        SpotApi(self.0.clone_with_signer(signer))
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+use serde_with::skip_serializing_none;
+use smart_string::SmartString;
+
+use super::Order;
+use super::create::AccountType;
+use crate::api::ApiMethod;
+use crate::api::ApiVersion;
+use crate::api::PrivateRequest;
+use crate::api::Request;
+
+/// Params for cancelling an order
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelOrderParams {
+    /// Currency pair of the order.
+    pub currency_pair: SmartString<15>,
+    /// Operation account.
+    ///
+    /// Defaults to spot, portfolio and margin account if not specified.
+    ///
+    /// Set to `cross_margin` to operate against margin account.
+    /// Portfolio margin account must set to `cross_margin` only
+    pub account: Option<AccountType>,
+}
+
+impl CancelOrderParams {
+    pub fn new(currency_pair: &str) -> Self {
+        Self {
+            currency_pair: currency_pair.into(),
+            account: None,
+        }
+    }
+}
+
+impl Request for CancelOrderParams {
+    const METHOD: ApiMethod = ApiMethod::Delete;
+    const VERSION: ApiVersion = ApiVersion::V4;
+    type Response = Order;
+}
+
+impl PrivateRequest for CancelOrderParams {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::spot::order::OrderStatus;
+    use crate::api::spot::order::get::GetOrderParams;
+    use crate::api::spot::order::list::ListOrdersRequest;
+    use crate::api::to_query_string;
+
+    #[test]
+    fn test_serialize_account() {
+        let mut request = CancelOrderParams::new("BTC_USDT");
+        assert_eq!(to_query_string(&request).unwrap(), "currency_pair=BTC_USDT");
+
+        request.account = Some(AccountType::CrossMargin);
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pair=BTC_USDT&account=cross_margin"
+        );
+
+        let request = GetOrderParams {
+            currency_pair: Some("BTC_USDT".into()),
+            account: Some(AccountType::CrossMargin),
+        };
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pair=BTC_USDT&account=cross_margin"
+        );
+
+        let mut request = ListOrdersRequest::new("BTC_USDT", OrderStatus::Open);
+        request.account = Some(AccountType::Unified);
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pair=BTC_USDT&status=open&account=unified"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use smart_string::SmartString;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ClientOrderIdError {
+    #[error("Client order id is empty")]
+    Empty,
+    #[error("Client order id is {0} bytes long, at most {max} allowed", max = ClientOrderId::MAX_LEN)]
+    TooLong(usize),
+    #[error("Client order id contains invalid character {0:?}")]
+    InvalidChar(char),
+}
+
+/// Custom order id, sent as the order `text`.
+///
+/// Gate requires the text to start with `t-`; the part after the prefix is limited to
+/// 28 bytes of `0-9`, `A-Z`, `a-z`, `_`, `-` and `.`.
+/// The prefix is added automatically when missing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientOrderId(SmartString<30>);
+
+impl ClientOrderId {
+    pub const PREFIX: &'static str = "t-";
+    /// Max length excluding the prefix.
+    pub const MAX_LEN: usize = 28;
+
+    pub fn new(id: &str) -> Result<Self, ClientOrderIdError> {
+        let id = id.strip_prefix(Self::PREFIX).unwrap_or(id);
+        if id.is_empty() {
+            return Err(ClientOrderIdError::Empty);
+        }
+        if id.len() > Self::MAX_LEN {
+            return Err(ClientOrderIdError::TooLong(id.len()));
+        }
+        if let Some(c) = id
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        {
+            return Err(ClientOrderIdError::InvalidChar(c));
+        }
+
+        let text = format!("{}{id}", Self::PREFIX);
+        Ok(ClientOrderId(text.as_str().into()))
+    }
+
+    /// The id without the `t-` prefix.
+    pub fn id(&self) -> &str {
+        &self.0.as_str()[Self::PREFIX.len()..]
+    }
+
+    /// The order text, i.e. the id with the `t-` prefix.
+    pub fn as_text(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn into_text(self) -> SmartString<30> {
+        self.0
+    }
+}
+
+impl FromStr for ClientOrderId {
+    type Err = ClientOrderIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ClientOrderId::new(s)
+    }
+}
+
+impl fmt::Display for ClientOrderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_text())
+    }
+}
+
+impl Serialize for ClientOrderId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_text())
+    }
+}
+
+impl<'de> Deserialize<'de> for ClientOrderId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        ClientOrderId::new(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_order_id_prefix() {
+        let id = ClientOrderId::new("abc_123.x-y").unwrap();
+        assert_eq!(id.id(), "abc_123.x-y");
+        assert_eq!(id.as_text(), "t-abc_123.x-y");
+        assert_eq!(ClientOrderId::new("t-abc_123.x-y").unwrap(), id);
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""t-abc_123.x-y""#);
+        assert_eq!(
+            serde_json::from_str::<ClientOrderId>(r#""t-abc_123.x-y""#).unwrap(),
+            id
+        );
+        assert_eq!(
+            serde_json::from_value::<ClientOrderId>(serde_json::json!("t-abc_123.x-y")).unwrap(),
+            id
+        );
+        assert_eq!(
+            serde_json::from_str::<ClientOrderId>(r#""t-abc_123.x\u002dy""#).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn test_client_order_id_validation() {
+        let max = "a".repeat(ClientOrderId::MAX_LEN);
+        assert!(ClientOrderId::new(&max).is_ok());
+        assert_eq!(
+            ClientOrderId::new(&format!("{max}a")),
+            Err(ClientOrderIdError::TooLong(29))
+        );
+        assert_eq!(ClientOrderId::new(""), Err(ClientOrderIdError::Empty));
+        assert_eq!(ClientOrderId::new("t-"), Err(ClientOrderIdError::Empty));
+        assert_eq!(
+            ClientOrderId::new("order 1"),
+            Err(ClientOrderIdError::InvalidChar(' '))
+        );
+        assert_eq!(
+            ClientOrderId::new("ордер"),
+            Err(ClientOrderIdError::InvalidChar('о'))
+        );
+    }
+}
@@ -1,14 +1,31 @@
+use ccx_api_lib::serde_util::none_as_empty_str;
+use ccx_api_lib::serde_util::plain_decimal;
+use chrono::DateTime;
+use chrono::Utc;
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde_with::TimestampMilliSeconds;
+use serde_with::TimestampSeconds;
+use serde_with::formats::Flexible;
+use serde_with::serde_as;
 use serde_with::skip_serializing_none;
 use smart_string::SmartString;
+use thiserror::Error;
 
+use super::FinishAs;
 use super::Order;
+use super::OrderStatus;
+use super::client_order_id::ClientOrderId;
 use crate::api::ApiMethod;
 use crate::api::ApiVersion;
+use crate::api::GateApiError;
 use crate::api::PrivateRequest;
 use crate::api::Request;
+use crate::api::spot::CurrencyPair;
+#[cfg(feature = "with_network")]
+use crate::client::rest::RequestError;
 
 /// Represents the structure for creating an order.
 #[skip_serializing_none]
@@ -25,15 +42,24 @@ pub struct CreateOrderRequest {
     pub side: OrderSide,
 
     /// The amount of the order. For limit orders, it refers to the base currency.
+    #[serde(serialize_with = "plain_decimal::serialize")]
     pub amount: Decimal,
 
     /// The price of the order. Required for limit orders. Optional.
+    #[serde(serialize_with = "plain_decimal::option::serialize")]
     pub price: Option<Decimal>,
 
     /// Time in force for the order (e.g., gtc, ioc, poc, fok). Optional.
     pub time_in_force: Option<TimeInForce>,
 
     /// The amount to display for iceberg orders. Null or 0 for normal orders. Optional.
+    ///
+    /// Gate may return an empty string for normal orders, which is read as `None`.
+    #[serde(
+        serialize_with = "plain_decimal::option::serialize",
+        deserialize_with = "none_as_empty_str::deserialize",
+        default
+    )]
     pub iceberg: Option<Decimal>,
 
     /// Enables automatic borrowing if the balance is insufficient. Used in margin or cross margin accounts. Optional.
@@ -75,16 +101,307 @@ impl CreateOrderRequest {
             order_type: None,
         }
     }
+
+    /// Limit order, `gtc` unless [`CreateOrderRequest::time_in_force`] is set.
+    pub fn limit(currency_pair: &str, side: OrderSide, amount: Decimal, price: Decimal) -> Self {
+        Self {
+            order_type: Some(OrderType::Limit),
+            price: Some(price),
+            ..Self::new(currency_pair, side, amount)
+        }
+    }
+
+    /// Market order with its amount in the currency that Gate reads for the side.
+    ///
+    /// Gate takes `amount` of a market buy in the quote currency and of a market sell in
+    /// the base currency. `price` is left unset, market orders must not have one, and
+    /// `time_in_force` is `ioc`.
+    pub fn market(currency_pair: &str, amount: MarketAmount) -> Self {
+        let (side, amount) = match amount {
+            MarketAmount::Buy { spend_quote } => (OrderSide::Buy, spend_quote),
+            MarketAmount::Sell { sell_base } => (OrderSide::Sell, sell_base),
+        };
+        Self {
+            order_type: Some(OrderType::Market),
+            time_in_force: Some(TimeInForce::ImmediateOrCancelled),
+            ..Self::new(currency_pair, side, amount)
+        }
+    }
+
+    /// Sets user-defined information, see also [`CreateOrderRequest::with_client_id`].
+    pub fn text(mut self, text: impl Into<SmartString<30>>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn account(mut self, account: AccountType) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Sets `text` to the given client order id.
+    pub fn with_client_id(mut self, id: ClientOrderId) -> Self {
+        self.text = Some(id.into_text());
+        self
+    }
+
+    /// Makes the order an iceberg order displaying `visible_amount`.
+    ///
+    /// A zero amount disables iceberg, in which case the field is omitted from the request.
+    /// When `pair` is given, the visible amount is also checked against its minimum base
+    /// amount and amount precision.
+    pub fn with_iceberg(
+        mut self,
+        visible_amount: Decimal,
+        pair: Option<&CurrencyPair>,
+    ) -> Result<Self, IcebergError> {
+        if visible_amount.is_zero() {
+            self.iceberg = None;
+            return Ok(self);
+        }
+        if visible_amount.is_sign_negative() {
+            return Err(IcebergError::Negative(visible_amount));
+        }
+        if visible_amount >= self.amount {
+            return Err(IcebergError::NotLessThanAmount {
+                iceberg: visible_amount,
+                amount: self.amount,
+            });
+        }
+        if let Some(pair) = pair {
+            if let Some(min_base_amount) = pair.min_base_amount.filter(|min| visible_amount < *min)
+            {
+                return Err(IcebergError::BelowMinBaseAmount {
+                    iceberg: visible_amount,
+                    min_base_amount,
+                });
+            }
+            if let Some(amount_precision) = pair
+                .amount_precision
+                .filter(|precision| visible_amount.normalize().scale() > *precision)
+            {
+                return Err(IcebergError::ExceedsAmountPrecision {
+                    iceberg: visible_amount,
+                    amount_precision,
+                });
+            }
+        }
+        self.iceberg = Some(visible_amount);
+        Ok(self)
+    }
+
+    /// Checks the fields that depend on each other:
+    ///
+    /// * `price` is set for limit orders, which is also the default order type;
+    /// * `price` is omitted and `time_in_force` is `ioc` or `fok` for market orders;
+    /// * `iceberg` is less than `amount`;
+    /// * `auto_borrow` is only enabled for margin, cross margin or unified accounts;
+    /// * `auto_repay` is only enabled for cross margin or unified accounts.
+    #[cfg(feature = "with_network")]
+    pub fn validate(&self) -> Result<(), Box<RequestError>> {
+        let is_limit = matches!(self.order_type, None | Some(OrderType::Limit));
+        if is_limit && self.price.is_none() {
+            return Err(RequestError::mandatory_field_omitted("price").into());
+        }
+        if matches!(self.order_type, Some(OrderType::Market)) {
+            if self.price.is_some() {
+                return Err(RequestError::invalid_request(
+                    "price must be omitted for market orders",
+                )
+                .into());
+            }
+            if let Some(
+                time_in_force @ (TimeInForce::GoodTillCancelled | TimeInForce::PendingOrCancelled),
+            ) = self.time_in_force
+            {
+                return Err(RequestError::invalid_request(format!(
+                    "{time_in_force:?} is not supported for market orders"
+                ))
+                .into());
+            }
+        }
+        if let Some(iceberg) = self.iceberg.filter(|iceberg| *iceberg >= self.amount) {
+            let error = IcebergError::NotLessThanAmount {
+                iceberg,
+                amount: self.amount,
+            };
+            return Err(RequestError::invalid_request(error.to_string()).into());
+        }
+        let account = self.account.unwrap_or(AccountType::Spot);
+        if self.auto_borrow == Some(true)
+            && !matches!(
+                account,
+                AccountType::Margin | AccountType::CrossMargin | AccountType::Unified
+            )
+        {
+            return Err(RequestError::invalid_request(format!(
+                "auto_borrow is not supported for {account:?} account"
+            ))
+            .into());
+        }
+        if self.auto_repay == Some(true)
+            && !matches!(account, AccountType::CrossMargin | AccountType::Unified)
+        {
+            return Err(RequestError::invalid_request(format!(
+                "auto_repay is not supported for {account:?} account"
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Amount of a market order, see [`CreateOrderRequest::market`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketAmount {
+    /// Buy for `spend_quote` of the quote currency.
+    Buy { spend_quote: Decimal },
+
+    /// Sell `sell_base` of the base currency.
+    Sell { sell_base: Decimal },
+}
+
+/// Iceberg amount rejected by [`CreateOrderRequest::with_iceberg`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IcebergError {
+    #[error("Iceberg amount {0} is negative")]
+    Negative(Decimal),
+    #[error("Iceberg amount {iceberg} must be less than order amount {amount}")]
+    NotLessThanAmount { iceberg: Decimal, amount: Decimal },
+    #[error("Iceberg amount {iceberg} is below min base amount {min_base_amount}")]
+    BelowMinBaseAmount {
+        iceberg: Decimal,
+        min_base_amount: Decimal,
+    },
+    #[error("Iceberg amount {iceberg} exceeds amount precision {amount_precision}")]
+    ExceedsAmountPrecision {
+        iceberg: Decimal,
+        amount_precision: u32,
+    },
 }
 
 impl Request for CreateOrderRequest {
     const METHOD: ApiMethod = ApiMethod::Post;
     const VERSION: ApiVersion = ApiVersion::V4;
-    type Response = Order;
+    type Response = CreateOrderOutcome;
 }
 
 impl PrivateRequest for CreateOrderRequest {}
 
+/// Response to an order creation. The field set depends on [`ActionMode`].
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum CreateOrderOutcome {
+    /// `ACK` mode: only key order fields.
+    Ack(OrderAck),
+
+    /// `RESULT` mode: the order without clearing information.
+    Result(OrderResult),
+
+    /// `FULL` mode (default): the complete order.
+    Full(Box<Order>),
+
+    /// The order was not placed even though the request succeeded (`succeeded: false`),
+    /// e.g. a failed auto-borrow.
+    Rejected(GateApiError),
+}
+
+impl CreateOrderOutcome {
+    /// Order ID unless the order was rejected.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            CreateOrderOutcome::Ack(ack) => Some(ack.id.as_str()),
+            CreateOrderOutcome::Result(result) => Some(result.id.as_str()),
+            CreateOrderOutcome::Full(order) => Some(order.id.as_str()),
+            CreateOrderOutcome::Rejected(_) => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CreateOrderOutcome {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let value = Value::deserialize(deserializer)?;
+        let outcome = if value.get("succeeded") == Some(&Value::Bool(false)) {
+            GateApiError::deserialize(value).map(CreateOrderOutcome::Rejected)
+        } else if value.get("fee").is_some() {
+            Order::deserialize(value).map(|order| CreateOrderOutcome::Full(Box::new(order)))
+        } else if value.get("status").is_some() {
+            OrderResult::deserialize(value).map(CreateOrderOutcome::Result)
+        } else {
+            OrderAck::deserialize(value).map(CreateOrderOutcome::Ack)
+        };
+        outcome.map_err(D::Error::custom)
+    }
+}
+
+/// Order created in `ACK` mode.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct OrderAck {
+    /// Order ID.
+    pub id: SmartString<15>,
+
+    /// User-defined information.
+    pub text: Option<SmartString<30>>,
+
+    /// Creation time of the order.
+    #[serde_as(as = "TimestampSeconds<i64, Flexible>")]
+    pub create_time: DateTime<Utc>,
+}
+
+/// Order created in `RESULT` mode.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct OrderResult {
+    /// Order fields derived from order creation request
+    #[serde(flatten)]
+    pub request: CreateOrderRequest,
+
+    /// Order ID.
+    pub id: SmartString<15>,
+
+    /// Creation time of the order.
+    #[serde(rename = "create_time_ms")]
+    #[serde_as(as = "TimestampMilliSeconds<i64, Flexible>")]
+    pub create_time: DateTime<Utc>,
+
+    /// Last modification time of the order.
+    #[serde(rename = "update_time_ms")]
+    #[serde_as(as = "TimestampMilliSeconds<i64, Flexible>")]
+    pub update_time: DateTime<Utc>,
+
+    /// Order status.
+    pub status: OrderStatus,
+
+    /// Amount left to fill.
+    pub left: Option<Decimal>,
+
+    /// Amount traded to fill.
+    pub filled_amount: Option<Decimal>,
+
+    /// Total filled in quote currency.
+    pub filled_total: Option<Decimal>,
+
+    /// Average fill price.
+    pub avg_deal_price: Option<Decimal>,
+
+    /// Order completion status.
+    pub finish_as: Option<FinishAs>,
+}
+
 /// Represents the different types of accounts.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -268,4 +585,383 @@ mod tests {
         // Assert that the serialized JSON matches the expected JSON
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn serialize_create_order_request_plain_decimals() {
+        let order = CreateOrderRequest {
+            price: Some(dec!(0.00000022)),
+            // Negative zero, e.g. left by a rounding, is sent without the sign.
+            iceberg: Some(-dec!(0.00)),
+            ..CreateOrderRequest::new(
+                "SHIB_USDT",
+                OrderSide::Buy,
+                Decimal::from_scientific("1.5e6").unwrap(),
+            )
+        };
+        let serialized = serde_json::to_string(&order).expect("Serialization failed");
+        assert_eq!(
+            r#"{"currency_pair":"SHIB_USDT","side":"buy","amount":"1500000","price":"0.00000022","iceberg":"0.00"}"#,
+            serialized
+        );
+    }
+
+    #[test]
+    fn build_limit_order() {
+        let order = CreateOrderRequest::limit("BTC_USDT", OrderSide::Buy, dec!(0.001), dec!(65000))
+            .text("t-abc123")
+            .time_in_force(TimeInForce::GoodTillCancelled)
+            .account(AccountType::Unified);
+        let expected = CreateOrderRequest {
+            text: Some("t-abc123".into()),
+            order_type: Some(OrderType::Limit),
+            account: Some(AccountType::Unified),
+            price: Some(dec!(65000)),
+            time_in_force: Some(TimeInForce::GoodTillCancelled),
+            ..CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.001))
+        };
+        assert_eq!(expected, order);
+    }
+
+    #[test]
+    fn serialize_market_orders() {
+        let buy = CreateOrderRequest::market(
+            "BTC_USDT",
+            MarketAmount::Buy {
+                spend_quote: dec!(100),
+            },
+        );
+        let expected = r#"{
+  "currency_pair": "BTC_USDT",
+  "side": "buy",
+  "amount": "100",
+  "time_in_force": "ioc",
+  "type": "market"
+}"#;
+        assert_eq!(expected, serde_json::to_string_pretty(&buy).unwrap());
+
+        let sell = CreateOrderRequest::market(
+            "BTC_USDT",
+            MarketAmount::Sell {
+                sell_base: dec!(0.001),
+            },
+        );
+        let expected = r#"{
+  "currency_pair": "BTC_USDT",
+  "side": "sell",
+  "amount": "0.001",
+  "time_in_force": "ioc",
+  "type": "market"
+}"#;
+        assert_eq!(expected, serde_json::to_string_pretty(&sell).unwrap());
+    }
+
+    fn pair() -> CurrencyPair {
+        serde_json::from_str(
+            r#"{
+  "id": "BTC_USDT",
+  "base": "BTC",
+  "quote": "USDT",
+  "fee": "0.2",
+  "min_base_amount": "0.0001",
+  "min_quote_amount": "3",
+  "amount_precision": 4,
+  "precision": 1,
+  "trade_status": "tradable",
+  "sell_start": 1516378650,
+  "buy_start": 1516378650
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_iceberg_enabled() {
+        let order = CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.5))
+            .with_iceberg(dec!(0.1), Some(&pair()))
+            .unwrap();
+        assert_eq!(order.iceberg, Some(dec!(0.1)));
+        let serialized = serde_json::to_value(&order).unwrap();
+        assert_eq!(serialized["iceberg"], "0.1");
+
+        let order = CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.5))
+            .with_iceberg(dec!(0.00001), None)
+            .unwrap();
+        assert_eq!(order.iceberg, Some(dec!(0.00001)));
+    }
+
+    #[test]
+    fn with_iceberg_disabled() {
+        let order = CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.5))
+            .with_iceberg(dec!(0.1), None)
+            .unwrap()
+            .with_iceberg(dec!(0), Some(&pair()))
+            .unwrap();
+        assert_eq!(order.iceberg, None);
+        let serialized = serde_json::to_value(&order).unwrap();
+        assert!(serialized.get("iceberg").is_none());
+    }
+
+    #[test]
+    fn with_iceberg_invalid() {
+        let order = || CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.5));
+        assert_eq!(
+            order().with_iceberg(dec!(-0.1), None).unwrap_err(),
+            IcebergError::Negative(dec!(-0.1))
+        );
+        assert_eq!(
+            order().with_iceberg(dec!(0.5), None).unwrap_err(),
+            IcebergError::NotLessThanAmount {
+                iceberg: dec!(0.5),
+                amount: dec!(0.5),
+            }
+        );
+        assert_eq!(
+            order()
+                .with_iceberg(dec!(0.00005), Some(&pair()))
+                .unwrap_err(),
+            IcebergError::BelowMinBaseAmount {
+                iceberg: dec!(0.00005),
+                min_base_amount: dec!(0.0001),
+            }
+        );
+        assert_eq!(
+            order()
+                .with_iceberg(dec!(0.00015), Some(&pair()))
+                .unwrap_err(),
+            IcebergError::ExceedsAmountPrecision {
+                iceberg: dec!(0.00015),
+                amount_precision: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_iceberg_empty_string() {
+        let json = r#"{
+  "currency_pair": "BTC_USDT",
+  "side": "buy",
+  "amount": "0.001",
+  "iceberg": ""
+}"#;
+        let request: CreateOrderRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.iceberg, None);
+
+        let json = r#"{
+  "currency_pair": "BTC_USDT",
+  "side": "buy",
+  "amount": "0.001",
+  "iceberg": "0.0005"
+}"#;
+        let request: CreateOrderRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.iceberg, Some(dec!(0.0005)));
+    }
+
+    #[cfg(feature = "with_network")]
+    #[test]
+    fn validate_create_order_request() {
+        let limit = CreateOrderRequest {
+            price: Some(dec!(65000)),
+            ..CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.001))
+        };
+        assert!(limit.validate().is_ok());
+
+        let no_price = CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.001));
+        assert!(matches!(
+            *no_price.validate().unwrap_err(),
+            RequestError::MandatoryFieldOmitted("price")
+        ));
+        let market = CreateOrderRequest {
+            order_type: Some(OrderType::Market),
+            ..no_price
+        };
+        assert!(market.validate().is_ok());
+        let market_with_price = CreateOrderRequest {
+            price: Some(dec!(65000)),
+            ..market.clone()
+        };
+        assert!(matches!(
+            *market_with_price.validate().unwrap_err(),
+            RequestError::InvalidRequest(_)
+        ));
+        let market_gtc = CreateOrderRequest {
+            time_in_force: Some(TimeInForce::GoodTillCancelled),
+            ..market.clone()
+        };
+        assert!(matches!(
+            *market_gtc.validate().unwrap_err(),
+            RequestError::InvalidRequest(_)
+        ));
+        let market_buy = CreateOrderRequest::market(
+            "BTC_USDT",
+            MarketAmount::Buy {
+                spend_quote: dec!(100),
+            },
+        );
+        assert!(market_buy.validate().is_ok());
+
+        let iceberg = CreateOrderRequest {
+            iceberg: Some(dec!(0.001)),
+            ..limit.clone()
+        };
+        assert_eq!(
+            iceberg.validate().unwrap_err().to_string(),
+            "Invalid request: Iceberg amount 0.001 must be less than order amount 0.001"
+        );
+
+        let auto_borrow = CreateOrderRequest {
+            auto_borrow: Some(true),
+            ..limit.clone()
+        };
+        assert!(matches!(
+            *auto_borrow.validate().unwrap_err(),
+            RequestError::InvalidRequest(_)
+        ));
+        let margin = CreateOrderRequest {
+            account: Some(AccountType::Margin),
+            ..auto_borrow
+        };
+        assert!(margin.validate().is_ok());
+
+        let auto_repay = CreateOrderRequest {
+            auto_repay: Some(true),
+            ..margin.clone()
+        };
+        assert!(matches!(
+            *auto_repay.validate().unwrap_err(),
+            RequestError::InvalidRequest(_)
+        ));
+        let cross_margin = CreateOrderRequest {
+            account: Some(AccountType::CrossMargin),
+            ..auto_repay
+        };
+        assert!(cross_margin.validate().is_ok());
+    }
+
+    #[test]
+    fn deserialize_create_order_outcome_ack() {
+        let json = r#"{
+  "id": "1852454420",
+  "text": "t-abc123",
+  "create_time": "1710488334"
+}"#;
+
+        let expected = CreateOrderOutcome::Ack(OrderAck {
+            id: "1852454420".into(),
+            text: Some("t-abc123".into()),
+            create_time: DateTime::from_timestamp(1710488334, 0).unwrap(),
+        });
+        assert_eq!(expected, serde_json::from_str(json).unwrap());
+    }
+
+    #[test]
+    fn deserialize_create_order_outcome_result() {
+        let json = r#"{
+  "id": "1852454420",
+  "text": "t-abc123",
+  "amend_text": "-",
+  "create_time": "1710488334",
+  "update_time": "1710488334",
+  "create_time_ms": 1710488334073,
+  "update_time_ms": 1710488334074,
+  "status": "open",
+  "currency_pair": "BTC_USDT",
+  "type": "limit",
+  "account": "spot",
+  "side": "buy",
+  "amount": "0.001",
+  "price": "65000",
+  "time_in_force": "gtc",
+  "iceberg": "0",
+  "left": "0.001",
+  "filled_amount": "0",
+  "filled_total": "0",
+  "avg_deal_price": "0",
+  "finish_as": "open"
+}"#;
+
+        let expected = CreateOrderOutcome::Result(OrderResult {
+            request: CreateOrderRequest {
+                text: Some("t-abc123".into()),
+                order_type: Some(OrderType::Limit),
+                account: Some(AccountType::Spot),
+                price: Some(dec!(65000)),
+                time_in_force: Some(TimeInForce::GoodTillCancelled),
+                iceberg: Some(dec!(0)),
+                ..CreateOrderRequest::new("BTC_USDT", OrderSide::Buy, dec!(0.001))
+            },
+            id: "1852454420".into(),
+            create_time: DateTime::from_timestamp_millis(1710488334073).unwrap(),
+            update_time: DateTime::from_timestamp_millis(1710488334074).unwrap(),
+            status: OrderStatus::Open,
+            left: Some(dec!(0.001)),
+            filled_amount: Some(dec!(0)),
+            filled_total: Some(dec!(0)),
+            avg_deal_price: Some(dec!(0)),
+            finish_as: Some(FinishAs::Open),
+        });
+        assert_eq!(expected, serde_json::from_str(json).unwrap());
+    }
+
+    #[test]
+    fn deserialize_create_order_outcome_full() {
+        let json = r#"{
+  "id": "1852454420",
+  "text": "t-abc123",
+  "amend_text": "-",
+  "create_time": "1710488334",
+  "update_time": "1710488334",
+  "create_time_ms": 1710488334073,
+  "update_time_ms": 1710488334074,
+  "status": "closed",
+  "currency_pair": "BTC_USDT",
+  "type": "limit",
+  "account": "spot",
+  "side": "buy",
+  "amount": "0.001",
+  "price": "65000",
+  "time_in_force": "gtc",
+  "iceberg": "0",
+  "left": "0",
+  "filled_amount": "0.001",
+  "fill_price": "63.4693",
+  "filled_total": "63.4693",
+  "avg_deal_price": "63.4693",
+  "fee": "0.00000022",
+  "fee_currency": "BTC",
+  "point_fee": "0",
+  "gt_fee": "0",
+  "gt_maker_fee": "0",
+  "gt_taker_fee": "0",
+  "gt_discount": false,
+  "rebated_fee": "0",
+  "rebated_fee_currency": "USDT",
+  "finish_as": "filled"
+}"#;
+
+        let outcome: CreateOrderOutcome = serde_json::from_str(json).unwrap();
+        assert_eq!(outcome.id(), Some("1852454420"));
+        let CreateOrderOutcome::Full(order) = outcome else {
+            panic!("expected full order, got {outcome:?}");
+        };
+        assert_eq!(order.fee, Some(dec!(0.00000022)));
+        assert_eq!(order.status, OrderStatus::Closed);
+        assert_eq!(order.finish_as, FinishAs::Filled);
+    }
+
+    #[test]
+    fn deserialize_create_order_outcome_rejected() {
+        let json = r#"{
+  "text": "t-abc123",
+  "succeeded": false,
+  "label": "BALANCE_NOT_ENOUGH",
+  "message": "Not enough balance"
+}"#;
+
+        let outcome: CreateOrderOutcome = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            outcome,
+            CreateOrderOutcome::Rejected(GateApiError::BalanceNotEnough(_))
+        ));
+        assert_eq!(outcome.id(), None);
+    }
 }
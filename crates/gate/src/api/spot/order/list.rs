@@ -12,6 +12,7 @@ use super::create::AccountType;
 use super::create::OrderSide;
 use crate::api::ApiMethod;
 use crate::api::ApiVersion;
+use crate::api::PagedRequest;
 use crate::api::PrivateRequest;
 use crate::api::Request;
 
@@ -76,3 +77,35 @@ impl Request for ListOrdersRequest {
 }
 
 impl PrivateRequest for ListOrdersRequest {}
+
+impl PagedRequest for ListOrdersRequest {
+    type Item = Order;
+
+    fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(100)
+    }
+
+    fn next_page(&mut self, _received: usize) {
+        self.page = Some(self.page.unwrap_or(1) + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_page() {
+        let mut request = ListOrdersRequest::new("BTC_USDT", OrderStatus::Closed);
+        assert_eq!(request.page_size(), 100);
+        request.next_page(100);
+        assert_eq!(request.page, Some(2));
+
+        request.limit = Some(10);
+        request.next_page(10);
+        assert_eq!(request.page_size(), 10);
+        assert_eq!(request.page, Some(3));
+        let query = crate::api::to_query_string(&request).unwrap();
+        assert!(query.contains("page=3&limit=10"), "{query}");
+    }
+}
@@ -1,9 +1,12 @@
+pub mod cancel;
+pub mod client_order_id;
 pub mod create;
 pub mod get;
 pub mod list;
 
 use chrono::DateTime;
 use chrono::Utc;
+use client_order_id::ClientOrderId;
 use create::CreateOrderRequest;
 use displaydoc::Display;
 use rust_decimal::Decimal;
@@ -16,7 +19,10 @@ use smart_string::SmartString;
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use cancel::CancelOrderParams;
+    use create::CreateOrderOutcome;
     use create::CreateOrderRequest;
+    use futures::Stream;
     use get::GetOrderParams;
     use list::ListOrdersRequest;
 
@@ -30,10 +36,17 @@ mod with_network {
         ///
         /// # Endpoint
         /// `POST /spot/orders`
+        ///
+        /// # Description
+        /// The shape of the response depends on `action_mode`.
+        /// Note that the order may be rejected with HTTP 200 (see [`CreateOrderOutcome::Rejected`]).
+        ///
+        /// The request is checked with [`CreateOrderRequest::validate`] before it is sent.
         pub async fn create_order(
             &self,
             request: &CreateOrderRequest,
-        ) -> Result<Order, RequestError> {
+        ) -> Result<CreateOrderOutcome, RequestError> {
+            request.validate().map_err(|e| *e)?;
             self.0.signed_request("/spot/orders", request).await
         }
 
@@ -52,6 +65,21 @@ mod with_network {
             self.0.signed_request("/spot/orders", request).await
         }
 
+        /// List orders of all pages
+        ///
+        /// # Endpoint
+        /// `GET /spot/orders`
+        ///
+        /// # Description
+        /// Same as [`Self::list_orders`], but advances `page` until a short page is returned.
+        /// `limit` of the request is used as the page size.
+        pub fn list_orders_paged(
+            &self,
+            request: &ListOrdersRequest,
+        ) -> impl Stream<Item = Result<Order, RequestError>> + '_ {
+            self.0.paged_request("/spot/orders", request.clone())
+        }
+
         /// Get a single order
         ///
         /// # Endpoint
@@ -67,6 +95,67 @@ mod with_network {
             let path = format!("/spot/orders/{id}");
             self.0.signed_request(&path, params).await
         }
+
+        /// Get a single order by its client order id
+        ///
+        /// # Endpoint
+        /// `GET /spot/orders/{text}`
+        ///
+        /// # Description
+        /// Gate accepts the custom order text (`t-` prefixed) in place of the order id.
+        pub async fn get_order_by_text(
+            &self,
+            client_id: &ClientOrderId,
+            params: &GetOrderParams,
+        ) -> Result<Order, RequestError> {
+            self.get_order(client_id.as_text(), params).await
+        }
+
+        /// Find an order by its client order id
+        ///
+        /// # Endpoint
+        /// `GET /spot/orders/{text}`
+        ///
+        /// # Description
+        /// Same as [`Self::get_order_by_text`], but returns `None` instead of an error when
+        /// Gate does not know the order. Meant for checking whether a [`Self::create_order`]
+        /// call whose response was lost has landed before retrying it.
+        ///
+        /// Gate requires the order `text` to start with `t-`, which [`ClientOrderId`] takes
+        /// care of. An order is only found by its text while it is open and for 60 seconds
+        /// after it is finished, so the check must be done right after the failed call.
+        pub async fn find_order_by_text(
+            &self,
+            currency_pair: &str,
+            client_id: &ClientOrderId,
+        ) -> Result<Option<Order>, RequestError> {
+            let params = GetOrderParams {
+                currency_pair: Some(currency_pair.into()),
+                account: None,
+            };
+            match self.get_order_by_text(client_id, &params).await {
+                Ok(order) => Ok(Some(order)),
+                Err(e) if e.is_order_not_found() => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
+
+        /// Cancel a single order
+        ///
+        /// # Endpoint
+        /// `DELETE /spot/orders/{order_id}`
+        ///
+        /// # Description
+        /// Spot, portfolio and margin orders are cancelled by default.
+        /// Cross margin orders can only be cancelled with `account` set to `cross_margin`.
+        pub async fn cancel_order(
+            &self,
+            id: &str,
+            params: &CancelOrderParams,
+        ) -> Result<Order, RequestError> {
+            let path = format!("/spot/orders/{id}");
+            self.0.signed_request(&path, params).await
+        }
     }
 }
 
@@ -147,6 +236,19 @@ pub struct Order {
     pub finish_as: FinishAs,
 }
 
+impl Order {
+    /// Client order id with the `t-` prefix stripped.
+    ///
+    /// Returns `None` if the order has no custom text (e.g. `web`, `apiv4`).
+    pub fn client_id(&self) -> Option<&str> {
+        self.request
+            .text
+            .as_ref()?
+            .as_str()
+            .strip_prefix(ClientOrderId::PREFIX)
+    }
+}
+
 /// Represents the status of an order.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -291,4 +393,48 @@ mod tests {
         // Assert that the original and deserialized orders are the same
         assert_eq!(expected, serde_json::from_str(json).unwrap());
     }
+
+    #[test]
+    fn client_id_round_trip() {
+        let client_id = ClientOrderId::new("grid-7_buy.1").unwrap();
+        let request = CreateOrderRequest::new("BTC_USDT", create::OrderSide::Buy, dec!(0.001))
+            .with_client_id(client_id.clone());
+        let request = serde_json::to_value(&request).unwrap();
+        assert_eq!(request["text"], "t-grid-7_buy.1");
+
+        let mut order = serde_json::json!({
+            "id": "1852454420",
+            "create_time_ms": 1710488334073u64,
+            "update_time_ms": 1710488334074u64,
+            "status": "open",
+            "finish_as": "open",
+        });
+        order
+            .as_object_mut()
+            .unwrap()
+            .extend(request.as_object().unwrap().clone());
+        let order: Order = serde_json::from_value(order).unwrap();
+        assert_eq!(order.client_id(), Some(client_id.id()));
+        assert_eq!(
+            ClientOrderId::new(order.request.text.as_ref().unwrap().as_str()).unwrap(),
+            client_id
+        );
+    }
+
+    #[test]
+    fn client_id_without_prefix() {
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "id": "1852454420",
+            "text": "apiv4",
+            "currency_pair": "BTC_USDT",
+            "side": "buy",
+            "amount": "1",
+            "create_time_ms": 1710488334073u64,
+            "update_time_ms": 1710488334074u64,
+            "status": "open",
+            "finish_as": "open",
+        }))
+        .unwrap();
+        assert_eq!(order.client_id(), None);
+    }
 }
@@ -4,17 +4,20 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
 use serde_with::TimestampMilliSeconds;
 use serde_with::formats::Flexible;
 use serde_with::serde_as;
 use serde_with::skip_serializing_none;
 use smallvec::SmallVec;
 use smart_string::SmartString;
+use thiserror::Error;
 
 use crate::api::ApiMethod;
 use crate::api::ApiVersion;
 use crate::api::PublicRequest;
 use crate::api::Request;
+use crate::api::spot::CurrencyPair;
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Default)]
@@ -23,7 +26,7 @@ pub struct SpotOrderBookRequest {
     pub currency_pair: SmartString,
     /// Order depth. 0 means no aggregation is applied. default to 0
     #[serde(rename = "interval")]
-    pub order_depth: Option<Decimal>,
+    pub order_depth: Option<OrderBookInterval>,
     pub limit: Option<u32>,
     pub with_id: Option<bool>,
 }
@@ -41,6 +44,74 @@ impl SpotOrderBookRequest {
 
 impl PublicRequest for SpotOrderBookRequest {}
 
+/// Price grouping of the order book, sent as `interval`.
+///
+/// Gate accepts zero (no aggregation) and powers of ten from the pair's tick
+/// (`10^-precision`) up to `1`, see [`CurrencyPair::valid_intervals`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderBookInterval(Decimal);
+
+impl OrderBookInterval {
+    /// No aggregation.
+    pub const NONE: OrderBookInterval = OrderBookInterval(Decimal::ZERO);
+
+    /// Validates `interval` against the precision of `pair`.
+    pub fn new(interval: Decimal, pair: &CurrencyPair) -> Result<Self, OrderBookIntervalError> {
+        if interval.is_zero() {
+            return Ok(Self::NONE);
+        }
+        let precision = pair
+            .precision
+            .ok_or(OrderBookIntervalError::UnknownPrecision)?;
+        let interval = interval.normalize();
+        if interval > Decimal::ONE {
+            return Err(OrderBookIntervalError::TooCoarse(interval));
+        }
+        if interval.mantissa() != 1 {
+            return Err(OrderBookIntervalError::NotPowerOfTen(interval));
+        }
+        if interval.scale() > precision {
+            return Err(OrderBookIntervalError::FinerThanTick {
+                interval,
+                tick: Decimal::new(1, precision),
+            });
+        }
+        Ok(OrderBookInterval(interval))
+    }
+
+    /// Skips validation, e.g. when no pair snapshot is at hand.
+    pub fn new_unchecked(interval: Decimal) -> Self {
+        OrderBookInterval(interval.normalize())
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl Serialize for OrderBookInterval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0.normalize())
+    }
+}
+
+/// Order book interval rejected by [`OrderBookInterval::new`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OrderBookIntervalError {
+    #[error("Currency pair precision is unknown")]
+    UnknownPrecision,
+    #[error("Order book interval {0} is not a power of ten")]
+    NotPowerOfTen(Decimal),
+    #[error("Order book interval {0} is coarser than 1")]
+    TooCoarse(Decimal),
+    #[error("Order book interval {interval} is finer than tick {tick}")]
+    FinerThanTick { interval: Decimal, tick: Decimal },
+}
+
 impl Request for SpotOrderBookRequest {
     const METHOD: ApiMethod = ApiMethod::Get;
     const VERSION: ApiVersion = ApiVersion::V4;
@@ -118,6 +189,75 @@ mod tests {
     use smallvec::smallvec;
 
     use super::*;
+    use crate::api::to_query_string;
+
+    fn pair(precision: u32) -> CurrencyPair {
+        serde_json::from_str(&format!(
+            r#"{{"id": "BTC_USDT", "precision": {precision}, "amount_precision": 4}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_interval_validation() {
+        for (precision, valid, finer) in [
+            (2, dec!(0.01), dec!(0.001)),
+            (4, dec!(0.0001), dec!(0.00001)),
+            (8, dec!(0.00000001), dec!(0.000000001)),
+        ] {
+            let pair = pair(precision);
+            assert_eq!(OrderBookInterval::new(valid, &pair).unwrap().value(), valid);
+            assert_eq!(
+                OrderBookInterval::new(dec!(1), &pair).unwrap().value(),
+                dec!(1)
+            );
+            assert!(OrderBookInterval::new(dec!(0), &pair).unwrap().is_none());
+            assert_eq!(
+                OrderBookInterval::new(finer, &pair),
+                Err(OrderBookIntervalError::FinerThanTick {
+                    interval: finer,
+                    tick: valid,
+                })
+            );
+            assert_eq!(
+                OrderBookInterval::new(dec!(0.05), &pair),
+                Err(OrderBookIntervalError::NotPowerOfTen(dec!(0.05)))
+            );
+            assert_eq!(
+                OrderBookInterval::new(dec!(10), &pair),
+                Err(OrderBookIntervalError::TooCoarse(dec!(10)))
+            );
+            for interval in pair.valid_intervals() {
+                assert_eq!(
+                    OrderBookInterval::new(interval.value(), &pair),
+                    Ok(interval)
+                );
+            }
+        }
+        assert_eq!(pair(2).valid_intervals().len(), 4);
+        assert_eq!(pair(8).valid_intervals().len(), 10);
+    }
+
+    #[test]
+    fn test_serialize_interval() {
+        let request = SpotOrderBookRequest {
+            order_depth: Some(OrderBookInterval::new(dec!(0.010000), &pair(4)).unwrap()),
+            ..SpotOrderBookRequest::currency_pair("BTC_USDT".into())
+        };
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pair=BTC_USDT&interval=0.01"
+        );
+
+        let request = SpotOrderBookRequest {
+            order_depth: Some(OrderBookInterval::NONE),
+            ..SpotOrderBookRequest::currency_pair("BTC_USDT".into())
+        };
+        assert_eq!(
+            to_query_string(&request).unwrap(),
+            "currency_pair=BTC_USDT&interval=0"
+        );
+    }
 
     #[test]
     fn test_deserialize() {
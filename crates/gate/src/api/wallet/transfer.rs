@@ -1,3 +1,4 @@
+use ccx_api_lib::serde_util::plain_decimal;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde::Serialize;
@@ -17,6 +18,7 @@ pub struct WalletTransferRequest {
     /// Account to transfer to
     pub to: WalletAccountEnum,
     /// Transfer amount
+    #[serde(serialize_with = "plain_decimal::serialize")]
     pub amount: Decimal,
     /// Margin currency pair. Required if transfer from or to margin account
     pub currency_pair: Option<SmartString>,
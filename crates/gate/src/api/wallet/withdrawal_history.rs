@@ -11,6 +11,7 @@ use smart_string::SmartString;
 
 use crate::api::ApiMethod;
 use crate::api::ApiVersion;
+use crate::api::PagedRequest;
 use crate::api::PrivateRequest;
 use crate::api::Request;
 use crate::api::withdrawal::WithdrawalWithdrawStatus;
@@ -21,6 +22,8 @@ use crate::api::withdrawal::WithdrawalWithdrawStatus;
 pub struct WalletWithdrawalHistoryRequest {
     /// Filter by currency. Return all currency records if not specified
     pub currency: Option<SmartString>,
+    /// Filter by client order id
+    pub withdraw_order_id: Option<SmartString<32>>,
     /// Time range beginning, default to 7 days before current time
     #[serde_as(as = "Option<TimestampSeconds<i64>>")]
     pub from: Option<DateTime<Utc>>,
@@ -41,6 +44,18 @@ impl Request for WalletWithdrawalHistoryRequest {
 
 impl PrivateRequest for WalletWithdrawalHistoryRequest {}
 
+impl PagedRequest for WalletWithdrawalHistoryRequest {
+    type Item = WalletWithdrawalHistoryResponse;
+
+    fn page_size(&self) -> u32 {
+        self.limit.map_or(100, |limit| limit as u32)
+    }
+
+    fn next_page(&mut self, received: usize) {
+        self.offset = Some(self.offset.unwrap_or(0) + received as u64);
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct WalletWithdrawalHistoryResponse {
@@ -71,6 +86,8 @@ pub struct WalletWithdrawalHistoryResponse {
 
 #[cfg(feature = "with_network")]
 mod with_network {
+    use futures::Stream;
+
     use super::*;
     use crate::api::wallet::WalletApi;
     use crate::client::rest::RequestError;
@@ -95,6 +112,17 @@ mod with_network {
         ) -> Result<<WalletWithdrawalHistoryRequest as Request>::Response, RequestError> {
             self.0.signed_request("/wallet/withdrawals", request).await
         }
+
+        /// Withdrawal records of all pages, see [`Self::withdrawal_history`].
+        ///
+        /// Pages of `limit` records are requested until a short page is returned.
+        pub fn withdrawal_history_paged(
+            &self,
+            request: &WalletWithdrawalHistoryRequest,
+        ) -> impl Stream<Item = Result<WalletWithdrawalHistoryResponse, RequestError>> + '_
+        {
+            self.0.paged_request("/wallet/withdrawals", request.clone())
+        }
     }
 }
 
@@ -139,4 +167,18 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn test_next_page() {
+        let mut request = WalletWithdrawalHistoryRequest {
+            limit: Some(50),
+            ..Default::default()
+        };
+        assert_eq!(request.page_size(), 50);
+        request.next_page(50);
+        assert_eq!(request.offset, Some(50));
+        request.next_page(50);
+        assert_eq!(request.offset, Some(100));
+        assert_eq!(WalletWithdrawalHistoryRequest::default().page_size(), 100);
+    }
 }
@@ -1,3 +1,4 @@
+use ccx_api_lib::serde_util::plain_decimal;
 use chrono::DateTime;
 use chrono::Utc;
 use rust_decimal::Decimal;
@@ -13,6 +14,9 @@ use crate::api::ApiMethod;
 use crate::api::ApiVersion;
 use crate::api::PrivateRequest;
 use crate::api::Request;
+use crate::api::wallet::WalletWithdrawalHistoryResponse;
+#[cfg(feature = "with_network")]
+use crate::client::rest::RequestError;
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -20,6 +24,7 @@ pub struct WithdrawalWithdrawRequest {
     /// Client order id, up to 32 length and can only include 0-9, A-Z, a-z, underscore(_), hyphen(-) or dot(.)
     pub withdraw_order_id: Option<SmartString<32>>,
     /// Currency amount
+    #[serde(serialize_with = "plain_decimal::serialize")]
     pub amount: Decimal,
     /// Currency name
     pub currency: SmartString,
@@ -31,6 +36,68 @@ pub struct WithdrawalWithdrawRequest {
     pub chain: SmartString,
 }
 
+impl WithdrawalWithdrawRequest {
+    /// Max length of `withdraw_order_id` in bytes.
+    pub const WITHDRAW_ORDER_ID_MAX_LEN: usize = 32;
+    /// Max length of `address` in bytes.
+    pub const ADDRESS_MAX_LEN: usize = 66;
+
+    /// Builds a request, checking `withdraw_order_id` and `address` before they are
+    /// stored in the length-limited fields:
+    ///
+    /// * `withdraw_order_id` is not empty, is up to 32 bytes long and only includes 0-9,
+    ///   A-Z, a-z, underscore(_), hyphen(-) or dot(.);
+    /// * `address` is up to 66 bytes long.
+    #[cfg(feature = "with_network")]
+    pub fn new(
+        currency: &str,
+        chain: &str,
+        amount: Decimal,
+        address: Option<&str>,
+        withdraw_order_id: Option<&str>,
+        memo: Option<&str>,
+    ) -> Result<Self, Box<RequestError>> {
+        if let Some(id) = withdraw_order_id {
+            if id.is_empty() {
+                return Err(RequestError::invalid_request("withdraw_order_id is empty").into());
+            }
+            if id.len() > Self::WITHDRAW_ORDER_ID_MAX_LEN {
+                return Err(RequestError::invalid_request(format!(
+                    "withdraw_order_id is {} bytes long, at most {} allowed",
+                    id.len(),
+                    Self::WITHDRAW_ORDER_ID_MAX_LEN
+                ))
+                .into());
+            }
+            if let Some(c) = id
+                .chars()
+                .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            {
+                return Err(RequestError::invalid_request(format!(
+                    "withdraw_order_id contains invalid character {c:?}"
+                ))
+                .into());
+            }
+        }
+        if let Some(address) = address.filter(|a| a.len() > Self::ADDRESS_MAX_LEN) {
+            return Err(RequestError::invalid_request(format!(
+                "address is {} bytes long, at most {} allowed",
+                address.len(),
+                Self::ADDRESS_MAX_LEN
+            ))
+            .into());
+        }
+        Ok(WithdrawalWithdrawRequest {
+            withdraw_order_id: withdraw_order_id.map(Into::into),
+            amount,
+            currency: currency.into(),
+            address: address.map(Into::into),
+            memo: memo.map(Into::into),
+            chain: chain.into(),
+        })
+    }
+}
+
 impl Request for WithdrawalWithdrawRequest {
     const METHOD: ApiMethod = ApiMethod::Post;
     const VERSION: ApiVersion = ApiVersion::V4;
@@ -122,18 +189,65 @@ impl WithdrawalWithdrawStatus {
         )
     }
 
+    /// The withdrawal was not and will not be executed.
+    pub fn is_failed(&self) -> bool {
+        matches!(
+            self,
+            WithdrawalWithdrawStatus::Cancel
+                | WithdrawalWithdrawStatus::Fail
+                | WithdrawalWithdrawStatus::Invalid
+        )
+    }
+
     pub fn needs_confirmation(&self) -> bool {
         matches!(self, WithdrawalWithdrawStatus::Manual)
     }
 }
 
+/// Result of `WithdrawalApi::withdraw_idempotent`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotentWithdrawal {
+    /// A withdrawal with the same `withdraw_order_id` was found; nothing was submitted.
+    Existing(WalletWithdrawalHistoryResponse),
+    /// No live withdrawal with the same `withdraw_order_id` was found; the request was submitted.
+    Submitted(WithdrawalWithdrawResponse),
+}
+
+impl IdempotentWithdrawal {
+    pub fn id(&self) -> &str {
+        match self {
+            IdempotentWithdrawal::Existing(record) => record.id.as_str(),
+            IdempotentWithdrawal::Submitted(record) => record.id.as_str(),
+        }
+    }
+
+    pub fn status(&self) -> WithdrawalWithdrawStatus {
+        match self {
+            IdempotentWithdrawal::Existing(record) => record.status,
+            IdempotentWithdrawal::Submitted(record) => record.status,
+        }
+    }
+
+    pub fn is_submitted(&self) -> bool {
+        matches!(self, IdempotentWithdrawal::Submitted(_))
+    }
+}
+
 #[cfg(feature = "with_network")]
 mod with_network {
+    use chrono::Duration;
+    use futures::TryStreamExt;
+
     use super::*;
+    use crate::api::wallet::WalletWithdrawalHistoryRequest;
     use crate::api::withdrawal::WithdrawalApi;
     use crate::client::rest::RequestError;
     use crate::client::signer::GateSigner;
 
+    /// How far back [`WithdrawalApi::withdraw_idempotent`] and [`WithdrawalApi::get_withdrawal`]
+    /// look for a withdrawal. Gate.io limits the history range to 30 days.
+    const LOOKBACK_DAYS: i64 = 30;
+
     impl<S: GateSigner> WithdrawalApi<S> {
         /// # Withdraw
         ///
@@ -156,6 +270,79 @@ mod with_network {
         ) -> Result<<WithdrawalWithdrawRequest as Request>::Response, RequestError> {
             self.0.signed_request("/withdrawals", request).await
         }
+
+        /// # Withdraw unless already withdrawn
+        ///
+        /// Looks up withdrawals of the last 30 days with the same `withdraw_order_id` first and
+        /// returns the existing record if any of them is not failed
+        /// (see [`WithdrawalWithdrawStatus::is_failed`]). Otherwise submits the request.
+        ///
+        /// This is a client-side, best-effort check: it does not protect against concurrent
+        /// calls with the same `withdraw_order_id`, nor against withdrawals older than 30 days.
+        ///
+        /// Requests without `withdraw_order_id` are submitted as is.
+        pub async fn withdraw_idempotent(
+            &self,
+            request: &WithdrawalWithdrawRequest,
+        ) -> Result<IdempotentWithdrawal, RequestError> {
+            if let Some(withdraw_order_id) = &request.withdraw_order_id {
+                let to = Utc::now();
+                let lookup = WalletWithdrawalHistoryRequest {
+                    currency: Some(request.currency.clone()),
+                    withdraw_order_id: Some(withdraw_order_id.clone()),
+                    from: Some(to - Duration::days(LOOKBACK_DAYS)),
+                    to: Some(to),
+                    ..Default::default()
+                };
+                let existing = self
+                    .0
+                    .wallet()
+                    .withdrawal_history(&lookup)
+                    .await?
+                    .into_iter()
+                    // The filter is applied on our side too in case it is ignored by the API.
+                    .filter(|record| record.withdraw_order_id.as_ref() == Some(withdraw_order_id))
+                    .find(|record| !record.status.is_failed());
+                if let Some(record) = existing {
+                    log::debug!(
+                        "withdraw_idempotent :: {withdraw_order_id} already exists: {} ({:?})",
+                        record.id,
+                        record.status,
+                    );
+                    return Ok(IdempotentWithdrawal::Existing(record));
+                }
+            }
+
+            Ok(IdempotentWithdrawal::Submitted(
+                self.withdraw(request).await?,
+            ))
+        }
+
+        /// # Get a withdrawal by record id
+        ///
+        /// Gate.io has no endpoint for a single withdrawal, so the withdrawal records of
+        /// the last 30 days are paged through until `id` is found. Returns `None` if it is not.
+        ///
+        /// Meant for polling a withdrawal until [`WithdrawalWithdrawStatus::is_finished`].
+        pub async fn get_withdrawal(
+            &self,
+            id: &str,
+        ) -> Result<Option<WalletWithdrawalHistoryResponse>, RequestError> {
+            let to = Utc::now();
+            let request = WalletWithdrawalHistoryRequest {
+                from: Some(to - Duration::days(LOOKBACK_DAYS)),
+                to: Some(to),
+                ..Default::default()
+            };
+            let records = self.0.wallet().withdrawal_history_paged(&request);
+            futures::pin_mut!(records);
+            while let Some(record) = records.try_next().await? {
+                if record.id.as_str() == id {
+                    return Ok(Some(record));
+                }
+            }
+            Ok(None)
+        }
     }
 }
 
@@ -197,6 +384,53 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "with_network")]
+    #[test]
+    fn test_new_request() {
+        let request = WithdrawalWithdrawRequest::new(
+            "USDT",
+            "TRX",
+            dec!(10),
+            Some("TXYZopYRdj2D9XRtbG411XZZ3kM5VkAeBf"),
+            Some("order_1-2.3"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(request.withdraw_order_id, Some("order_1-2.3".into()));
+        assert_eq!(
+            request.address,
+            Some("TXYZopYRdj2D9XRtbG411XZZ3kM5VkAeBf".into())
+        );
+
+        let new = |address: &str, withdraw_order_id: &str| {
+            WithdrawalWithdrawRequest::new(
+                "USDT",
+                "TRX",
+                dec!(10),
+                Some(address),
+                Some(withdraw_order_id),
+                None,
+            )
+        };
+        let max_id = "a".repeat(WithdrawalWithdrawRequest::WITHDRAW_ORDER_ID_MAX_LEN);
+        let max_address = "a".repeat(WithdrawalWithdrawRequest::ADDRESS_MAX_LEN);
+        assert!(new(&max_address, &max_id).is_ok());
+        assert!(new(&max_address, &format!("{max_id}a")).is_err());
+        assert!(new(&format!("{max_address}a"), &max_id).is_err());
+        assert!(new("addr", "").is_err());
+        assert!(new("addr", "order 1").is_err());
+        assert!(new("addr", "order#1").is_err());
+    }
+
+    #[test]
+    fn test_is_failed() {
+        assert!(WithdrawalWithdrawStatus::Cancel.is_failed());
+        assert!(WithdrawalWithdrawStatus::Invalid.is_failed());
+        assert!(!WithdrawalWithdrawStatus::Done.is_failed());
+        assert!(!WithdrawalWithdrawStatus::Request.is_failed());
+        assert!(!WithdrawalWithdrawStatus::Manual.is_failed());
+    }
+
     #[test]
     fn test_example_real_response() {
         let json = r#"{"id":"w50000000","currency":"USDT","amount":"2.63","address":"Txxx","memo":null,"status":"REQUEST","chain":"TRX","withdraw_order_id":"47eaed6f32f24cb7a765fef1966e775b"}"#;
@@ -3,8 +3,51 @@ pub use ccx_api_lib::Proxy;
 use ccx_api_lib::env_var_with_prefix;
 use url::Url;
 
+use crate::api::API_BASE;
+use crate::api::API_BASE_TESTNET;
+use crate::api::STREAM_BASE;
+use crate::api::STREAM_BASE_TESTNET;
+
 pub static CCX_GATE_API_PREFIX: &str = "CCX_GATE_API";
 
+/// Gate.io deployment the client talks to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ApiEnvironment {
+    /// Production API.
+    #[default]
+    Live,
+    /// Spot testnet, both REST and websocket.
+    Testnet,
+    /// Arbitrary REST and websocket endpoints, e.g. a local mock server.
+    Custom { api_base: Url, stream_base: Url },
+}
+
+impl ApiEnvironment {
+    /// Reads "${prefix}_TESTNET" env var; "1" selects the testnet.
+    pub fn from_env_with_prefix(prefix: &str) -> Self {
+        match env_var_with_prefix(prefix, "TESTNET").as_deref() {
+            Some("1") => ApiEnvironment::Testnet,
+            _ => ApiEnvironment::Live,
+        }
+    }
+
+    pub fn api_base(&self) -> Url {
+        match self {
+            ApiEnvironment::Live => API_BASE.parse().unwrap(),
+            ApiEnvironment::Testnet => API_BASE_TESTNET.parse().unwrap(),
+            ApiEnvironment::Custom { api_base, .. } => api_base.clone(),
+        }
+    }
+
+    pub fn stream_base(&self) -> Url {
+        match self {
+            ApiEnvironment::Live => STREAM_BASE.parse().unwrap(),
+            ApiEnvironment::Testnet => STREAM_BASE_TESTNET.parse().unwrap(),
+            ApiEnvironment::Custom { stream_base, .. } => stream_base.clone(),
+        }
+    }
+}
+
 /// API config.
 #[derive(Clone)]
 pub struct GateApiConfig<S> {
@@ -32,7 +75,73 @@ impl<S> GateApiConfig<S> {
         }
     }
 
+    pub fn with_environment(signer: S, environment: ApiEnvironment, proxy: Option<Proxy>) -> Self {
+        let api_base = environment.api_base();
+        let stream_base = environment.stream_base();
+        GateApiConfig::new(signer, api_base, stream_base, proxy)
+    }
+
+    /// Production endpoints.
+    pub fn live(signer: S, proxy: Option<Proxy>) -> Self {
+        GateApiConfig::with_environment(signer, ApiEnvironment::Live, proxy)
+    }
+
+    /// Spot testnet endpoints.
+    pub fn testnet(signer: S, proxy: Option<Proxy>) -> Self {
+        GateApiConfig::with_environment(signer, ApiEnvironment::Testnet, proxy)
+    }
+
+    /// Custom REST base URL (e.g. `https://example.com/api/`); the websocket endpoint is
+    /// derived from the same host.
+    pub fn custom_base(signer: S, api_base: Url, proxy: Option<Proxy>) -> Self {
+        let mut stream_base = api_base.clone();
+        let scheme = match api_base.scheme() {
+            "http" => "ws",
+            _ => "wss",
+        };
+        let _ = stream_base.set_scheme(scheme);
+        stream_base.set_path("/ws/v4/");
+        let environment = ApiEnvironment::Custom {
+            api_base,
+            stream_base,
+        };
+        GateApiConfig::with_environment(signer, environment, proxy)
+    }
+
+    /// Routes both REST and websocket connections through the proxy.
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
     pub fn env_var(postfix: &str) -> Option<String> {
         env_var_with_prefix(CCX_GATE_API_PREFIX, postfix)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_bases() {
+        let config = GateApiConfig::live((), None);
+        assert_eq!(config.api_base.as_str(), API_BASE);
+        assert_eq!(config.stream_base.as_str(), STREAM_BASE);
+
+        let config = GateApiConfig::testnet((), None);
+        assert_eq!(
+            config.api_base.as_str(),
+            "https://api-testnet.gateapi.io/api/"
+        );
+        assert_eq!(
+            config.stream_base.as_str(),
+            "wss://ws-testnet.gate.com/v4/ws/spot"
+        );
+
+        let api_base = "http://127.0.0.1:8080/api/".parse().unwrap();
+        let config = GateApiConfig::custom_base((), api_base, None);
+        assert_eq!(config.api_base.as_str(), "http://127.0.0.1:8080/api/");
+        assert_eq!(config.stream_base.as_str(), "ws://127.0.0.1:8080/ws/v4/");
+    }
+}
@@ -1,9 +1,12 @@
 pub mod config;
 // pub mod nonce;
+pub mod rate_limiter;
 pub mod rest;
 pub mod signer;
 pub mod websocket;
 
 // pub use nonce::Nonce;
+pub use rate_limiter::RateLimiter;
 pub use rest::RestClient;
 pub use signer::GateSigner;
+pub use signer::GateWsSigner;
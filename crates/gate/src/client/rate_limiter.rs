@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use smart_string::SmartString;
+
+/// Fixed-window limiter with an independent window per API key.
+///
+/// Gate.io limits private endpoints per API key, so clients signing with different
+/// credentials must not consume each other's quota. Clones share the same state.
+///
+/// Requests are not throttled unless the limiter is passed to
+/// [`RestClient::with_rate_limiter`](super::RestClient::with_rate_limiter). The documented
+/// limits differ per endpoint group (e.g. spot order placement and private reads), so `limit`
+/// and `interval` should match the strictest group the client calls.
+#[derive(Clone)]
+pub struct RateLimiter {
+    limit: u32,
+    interval: Duration,
+    windows: Arc<Mutex<HashMap<SmartString<64>, Window>>>,
+}
+
+#[derive(Clone, Copy)]
+struct Window {
+    start: Instant,
+    used: u32,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, interval: Duration) -> Self {
+        assert!(limit > 0, "Rate limit must be positive");
+        RateLimiter {
+            limit,
+            interval,
+            windows: Default::default(),
+        }
+    }
+
+    /// Waits until a request signed with `key` may be sent.
+    pub async fn acquire(&self, key: &str) {
+        if let Some(delay) = self.reserve(key, Instant::now()) {
+            log::debug!("RateLimiter: key {key:?} :: sleep for {delay:?}");
+            actix_rt::time::sleep(delay).await;
+        }
+    }
+
+    /// Reserves a slot for `key` and returns the delay before it becomes available.
+    ///
+    /// Slots beyond the limit are queued into the following windows.
+    pub(crate) fn reserve(&self, key: &str, now: Instant) -> Option<Duration> {
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(key.into()).or_insert(Window {
+            start: now,
+            used: 0,
+        });
+
+        let reserved = window.used.div_ceil(self.limit).max(1);
+        if now >= window.start + self.interval * reserved {
+            *window = Window {
+                start: now,
+                used: 0,
+            };
+        }
+
+        let slot_window = window.used / self.limit;
+        window.used += 1;
+        let delay = (window.start + self.interval * slot_window).saturating_duration_since(now);
+        (!delay.is_zero()).then_some(delay)
+    }
+
+    /// Number of slots used in the current window of `key`.
+    pub fn used(&self, key: &str) -> u32 {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(key)
+            .map_or(0, |window| window.used)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_within_limit() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        assert_eq!(limiter.reserve("a", now), None);
+        assert_eq!(limiter.reserve("a", now), None);
+        assert_eq!(
+            limiter.reserve("a", now + Duration::from_secs(4)),
+            Some(Duration::from_secs(6))
+        );
+        // The queued slot has passed, a new window starts.
+        assert_eq!(limiter.reserve("a", now + Duration::from_secs(20)), None);
+        assert_eq!(limiter.used("a"), 1);
+    }
+
+    #[test]
+    fn test_keys_are_isolated() {
+        let limiter = RateLimiter::new(1, Duration::from_secs(10));
+        let shared = limiter.clone();
+        let now = Instant::now();
+        assert_eq!(limiter.reserve("a", now), None);
+        assert_eq!(shared.reserve("a", now), Some(Duration::from_secs(10)));
+        assert_eq!(shared.reserve("b", now), None);
+        assert_eq!(limiter.used("a"), 2);
+        assert_eq!(limiter.used("b"), 1);
+        assert_eq!(limiter.used("c"), 0);
+    }
+}
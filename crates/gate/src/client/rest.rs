@@ -1,6 +1,8 @@
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Instant;
 
+use awc::http::header::HeaderMap;
 use ccx_api_lib::ClientRequest;
 use ccx_api_lib::Method;
 use ccx_api_lib::PayloadError;
@@ -12,11 +14,13 @@ use smart_string::SmartString;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::rate_limiter::RateLimiter;
 use super::websocket::WebsocketStream;
 use crate::api::ApiMethod;
 use crate::api::GateApiError;
 use crate::api::PrivateRequest;
 use crate::api::Request;
+use crate::api::to_query_string;
 use crate::client::config::GateApiConfig;
 use crate::client::signer::GateSigner;
 use crate::client::signer::SignError;
@@ -40,6 +44,43 @@ pub enum RequestError {
     Sign(#[from] SignError),
     #[error("Call error: {0}")]
     Call(#[from] CallError),
+    #[error("Mandatory field omitted: {0}")]
+    MandatoryFieldOmitted(&'static str),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+impl RequestError {
+    pub fn mandatory_field_omitted(field: &'static str) -> Self {
+        RequestError::MandatoryFieldOmitted(field)
+    }
+
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        RequestError::InvalidRequest(message.into())
+    }
+
+    /// The error returned by the exchange, if the request reached it.
+    pub fn api_error(&self) -> Option<&GateApiError> {
+        match self {
+            RequestError::Call(CallError::GateApi(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn is_balance_not_enough(&self) -> bool {
+        self.api_error()
+            .is_some_and(GateApiError::is_balance_not_enough)
+    }
+
+    pub fn is_order_not_found(&self) -> bool {
+        self.api_error()
+            .is_some_and(GateApiError::is_order_not_found)
+    }
+
+    pub fn is_duplicate_request(&self) -> bool {
+        self.api_error()
+            .is_some_and(GateApiError::is_duplicate_request)
+    }
 }
 
 /// API client.
@@ -57,6 +98,7 @@ impl<S> Clone for RestClient<S> {
 
 struct ClientInner<S> {
     config: GateApiConfig<S>,
+    rate_limiter: Option<RateLimiter>,
 }
 
 pub struct GateRequest<R, S> {
@@ -82,20 +124,59 @@ pub struct GateSignedRequest<R> {
 }
 
 impl<S> RestClient<S> {
+    /// Client without a rate limiter, see [`Self::with_rate_limiter`].
     pub fn new(config: GateApiConfig<S>) -> Self {
-        let inner = Arc::new(ClientInner { config });
+        Self::with_inner(config, None)
+    }
+
+    /// Client waiting for `rate_limiter` before every signed request.
+    pub fn with_rate_limiter(config: GateApiConfig<S>, rate_limiter: RateLimiter) -> Self {
+        Self::with_inner(config, Some(rate_limiter))
+    }
+
+    fn with_inner(config: GateApiConfig<S>, rate_limiter: Option<RateLimiter>) -> Self {
+        let inner = Arc::new(ClientInner {
+            config,
+            rate_limiter,
+        });
         Self { inner }
     }
 
+    /// Client with the same endpoints, proxy and rate limiter, but signing with `signer`.
+    ///
+    /// The rate limiter keeps a separate window per API key, so clients sharing it
+    /// with different credentials do not consume each other's quota.
+    pub fn clone_with_signer<S2>(&self, signer: S2) -> RestClient<S2> {
+        let GateApiConfig {
+            api_base,
+            stream_base,
+            proxy,
+            ..
+        } = &self.inner.config;
+        let config =
+            GateApiConfig::new(signer, api_base.clone(), stream_base.clone(), proxy.clone());
+        RestClient::with_inner(config, self.inner.rate_limiter.clone())
+    }
+
+    pub fn signer(&self) -> &S {
+        &self.inner.config.signer
+    }
+
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.rate_limiter.as_ref()
+    }
+
     /// REST and Websocket client from `awc` crate
     pub(super) fn client(&self) -> awc::Client {
         make_client(false, self.inner.config.proxy.as_ref())
     }
 
     pub fn prepare_rest<R: Request>(&self, path: &str, request: &R) -> GateRequest<R, S> {
-        let body = match R::METHOD {
-            ApiMethod::Get | ApiMethod::Delete => "".to_string(),
-            ApiMethod::Post | ApiMethod::Put => serde_json::to_string(request).unwrap(),
+        let query_params =
+            R::QUERY_PARAMS || matches!(R::METHOD, ApiMethod::Get | ApiMethod::Delete);
+        let body = match query_params {
+            true => "".to_string(),
+            false => serde_json::to_string(request).unwrap(),
         };
         let method = match R::METHOD {
             ApiMethod::Get => Method::GET,
@@ -106,18 +187,22 @@ impl<S> RestClient<S> {
         let version = R::VERSION.as_str();
         let url_base = self.inner.config.api_base.as_str();
         let slash = if url_base.ends_with('/') { "" } else { "/" };
-        let url: SmartString<254> = format_args!("{url_base}{slash}{version}{path}").to_fmt();
+        let mut url = format!("{url_base}{slash}{version}{path}");
+
+        if query_params {
+            let query = to_query_string(request).unwrap();
+            if !query.is_empty() {
+                url.push('?');
+                url.push_str(&query);
+            }
+        }
 
-        let mut req = self
+        let req = self
             .client()
             .request(method, url.as_str())
             .append_header(("Accept", "application/json"))
             .append_header(("Content-Type", "application/json"));
 
-        if let ApiMethod::Get = R::METHOD {
-            req = req.query(request).unwrap();
-        }
-
         let api_client = self.clone();
 
         GateRequest {
@@ -182,7 +267,7 @@ impl<R: Request, S> GateRequest<R, S> {
 
         Ok(match is_success {
             true => serde_json::from_slice::<R::Response>(&body)?,
-            false => Err(serde_json::from_slice::<GateApiError>(&body)?)?,
+            false => Err(GateApiError::from_body(&body)?)?,
         })
     }
 }
@@ -253,6 +338,11 @@ impl<R: Request + PrivateRequest, S: GateSigner> GatePreparedRequest<R, S> {
 
 impl<R: Request> GateSignedRequest<R> {
     pub async fn call(self) -> Result<R::Response, CallError> {
+        Ok(self.call_with_meta().await?.0)
+    }
+
+    /// Same as [`Self::call`], but also returns the rate limit headers of the response.
+    pub async fn call_with_meta(self) -> Result<(R::Response, ResponseMeta), CallError> {
         let Self {
             request,
             body,
@@ -266,6 +356,7 @@ impl<R: Request> GateSignedRequest<R> {
         let tm = Instant::now();
         let mut res = request.send_body(body).await?;
         let is_success = res.status().is_success();
+        let meta = ResponseMeta::from_headers(res.headers());
         let d1 = tm.elapsed();
         let body = res.body().limit(16 * 1024 * 1024).await?;
         let d2 = tm.elapsed() - d1;
@@ -275,15 +366,174 @@ impl<R: Request> GateSignedRequest<R> {
             d1.as_secs_f64() * 1000.0,
             d2.as_secs_f64() * 1000.0,
         );
+        log::debug!("[{request_id}]  Rate limit: {meta:?}");
 
         if cfg!(debug_assertions) {
             let body = String::from_utf8_lossy(&body);
             log::debug!("[{request_id}]  Response body: {:?}", body);
         }
 
-        Ok(match is_success {
+        let response = match is_success {
             true => serde_json::from_slice::<R::Response>(&body)?,
-            false => Err(serde_json::from_slice::<GateApiError>(&body)?)?,
-        })
+            false => Err(GateApiError::from_body(&body)?)?,
+        };
+        Ok((response, meta))
+    }
+}
+
+/// Rate limit state reported in the response headers.
+///
+/// Gate.io reports it on spot endpoints; fields are `None` when a header is missing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// Requests left in the current window (`X-Gate-RateLimit-Requests-Remain`).
+    pub requests_remain: Option<u32>,
+    /// Requests allowed per window (`X-Gate-RateLimit-Limit`).
+    pub limit: Option<u32>,
+    /// When the window resets, ms (`X-Gate-RateLimit-Reset-Timestamp`).
+    pub reset_timestamp: Option<i64>,
+}
+
+impl ResponseMeta {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        fn parse<T: FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        ResponseMeta {
+            requests_remain: parse(headers, "X-Gate-RateLimit-Requests-Remain"),
+            limit: parse(headers, "X-Gate-RateLimit-Limit"),
+            reset_timestamp: parse(headers, "X-Gate-RateLimit-Reset-Timestamp"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::spot::order::get::GetOrderParams;
+    use crate::client::config::ApiEnvironment;
+    use crate::util::GateApiCred;
+
+    fn cred(key: &str, secret: &str) -> GateApiCred {
+        GateApiCred::new(Some(key.to_string()), Some(secret.to_string()))
+    }
+
+    async fn signed_headers(client: &RestClient<GateApiCred>) -> (String, String) {
+        let signed = client
+            .prepare_rest("/spot/orders/12345", &GetOrderParams::default())
+            .with_current_timestamp()
+            .sign()
+            .await
+            .unwrap();
+        let header = |name: &str| {
+            signed
+                .request
+                .headers()
+                .get(name)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        (header("KEY"), header("SIGN"))
+    }
+
+    #[actix_rt::test]
+    async fn test_clone_with_signer() {
+        let config =
+            GateApiConfig::with_environment(cred("key-a", "secret-a"), ApiEnvironment::Live, None);
+        let rate_limiter = RateLimiter::new(10, std::time::Duration::from_secs(1));
+        let client_a = RestClient::with_rate_limiter(config, rate_limiter);
+        let client_b = client_a.clone_with_signer(cred("key-b", "secret-b"));
+
+        assert_eq!(
+            client_a.inner.config.api_base,
+            client_b.inner.config.api_base
+        );
+
+        let (key_a, sign_a) = signed_headers(&client_a).await;
+        let (key_b, sign_b) = signed_headers(&client_b).await;
+        assert_eq!(key_a, "key-a");
+        assert_eq!(key_b, "key-b");
+        assert_ne!(sign_a, sign_b);
+
+        // The limiter is shared, but the windows are per key.
+        let rate_limiter = client_b.rate_limiter().unwrap();
+        client_a
+            .rate_limiter()
+            .unwrap()
+            .acquire(client_a.signer().key())
+            .await;
+        assert_eq!(rate_limiter.used("key-a"), 1);
+        assert_eq!(rate_limiter.used("key-b"), 0);
+    }
+
+    #[test]
+    fn test_response_meta_from_headers() {
+        use awc::http::header::HeaderName;
+        use awc::http::header::HeaderValue;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("x-gate-ratelimit-requests-remain", "9"),
+            ("x-gate-ratelimit-limit", "10"),
+            ("x-gate-ratelimit-reset-timestamp", "1736408263764"),
+        ] {
+            headers.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        assert_eq!(
+            ResponseMeta::from_headers(&headers),
+            ResponseMeta {
+                requests_remain: Some(9),
+                limit: Some(10),
+                reset_timestamp: Some(1736408263764),
+            }
+        );
+
+        headers.remove("x-gate-ratelimit-limit");
+        headers.insert(
+            HeaderName::from_static("x-gate-ratelimit-requests-remain"),
+            HeaderValue::from_static("n/a"),
+        );
+        assert_eq!(
+            ResponseMeta::from_headers(&headers),
+            ResponseMeta {
+                requests_remain: None,
+                limit: None,
+                reset_timestamp: Some(1736408263764),
+            }
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_query_params_post() {
+        use rust_decimal_macros::dec;
+
+        use crate::api::futures::UpdateLeverageRequest;
+
+        let config =
+            GateApiConfig::with_environment(cred("key", "secret"), ApiEnvironment::Live, None);
+        let rate_limiter = RateLimiter::new(10, std::time::Duration::from_secs(1));
+        let client = RestClient::with_rate_limiter(config, rate_limiter);
+        let request = UpdateLeverageRequest {
+            leverage: dec!(10),
+            cross_leverage_limit: None,
+        };
+        let prepared = client.prepare_rest("/futures/usdt/positions/BTC_USDT/leverage", &request);
+        assert_eq!(prepared.request.get_method(), Method::POST);
+        assert_eq!(
+            prepared
+                .request
+                .get_uri()
+                .path_and_query()
+                .unwrap()
+                .as_str(),
+            "/api/v4/futures/usdt/positions/BTC_USDT/leverage?leverage=10"
+        );
+        assert_eq!(prepared.body, "");
     }
 }
@@ -18,6 +18,7 @@ use crate::client::RestClient;
 use crate::error::GateError;
 use crate::error::GateResult;
 use crate::websocket::order_book::OrderBookRequest;
+use crate::websocket::order_book::OrderBookUpdateRequest;
 use crate::websocket::request::WsRequest;
 use crate::websocket::request::WsRequestEvent;
 use crate::websocket::response::Event;
@@ -150,6 +151,12 @@ impl Websocket {
                 log::warn!("Websocket client failed to send ping, stopping!");
                 ctx.stop()
             };
+            // Gate answers the application level ping with `spot.pong`.
+            let ping = serde_json::to_string(&WsRequest::ping()).expect("json encode");
+            if let Err(_msg) = act.sink.write(ws::Message::Text(ping.into())) {
+                log::warn!("Websocket client failed to send spot.ping, stopping!");
+                ctx.stop()
+            };
         });
     }
 }
@@ -205,4 +212,26 @@ impl WebsocketStreamTx {
             .await
             .map_err(|_e| GateError::IoError(io::ErrorKind::ConnectionAborted.into()))
     }
+
+    /// Subscribe or unsubscribe from tickers of `currency_pairs`
+    pub async fn tickers(&self, event: WsRequestEvent, currency_pairs: &[&str]) -> GateResult<()> {
+        let pairs = currency_pairs.iter().map(|&pair| pair.into()).collect();
+        self.send(WsRequest::tickers(event, pairs)).await
+    }
+
+    /// Subscribe or unsubscribe from public trades of `currency_pairs`
+    pub async fn trades(&self, event: WsRequestEvent, currency_pairs: &[&str]) -> GateResult<()> {
+        let pairs = currency_pairs.iter().map(|&pair| pair.into()).collect();
+        self.send(WsRequest::trades(event, pairs)).await
+    }
+
+    /// Subscribe or unsubscribe from changed order book levels
+    pub async fn order_book_update(
+        &self,
+        event: WsRequestEvent,
+        payload: OrderBookUpdateRequest,
+    ) -> GateResult<()> {
+        self.send(WsRequest::order_book_update(event, payload))
+            .await
+    }
 }
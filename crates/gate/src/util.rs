@@ -1,4 +1,12 @@
+mod comma_separated;
+pub mod csv_export;
 pub mod maybe_str;
+#[cfg(feature = "with_network")]
+mod order_book;
+
+pub use comma_separated::CommaSeparated;
+#[cfg(feature = "with_network")]
+pub use order_book::*;
 
 use ccx_api_lib::env_var_with_prefix;
 use serde::Deserialize;
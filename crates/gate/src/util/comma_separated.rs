@@ -0,0 +1,103 @@
+use std::fmt;
+use std::fmt::Write;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serializer;
+use serde::de::Error;
+use serde_with::DeserializeAs;
+use serde_with::SerializeAs;
+
+/// `serde_as` adapter for list parameters which Gate.io expects as a single comma-joined
+/// value, e.g. `currency_pairs=BTC_USDT,ETH_USDT`.
+///
+/// ```
+/// use serde::Serialize;
+/// use serde_with::serde_as;
+/// use ccx_gate::util::CommaSeparated;
+///
+/// #[serde_as]
+/// #[derive(Serialize)]
+/// struct Request {
+///     #[serde_as(as = "CommaSeparated<String>")]
+///     currency_pairs: Vec<String>,
+/// }
+/// ```
+///
+/// Sequences serialized without this adapter are sent as repeated keys instead
+/// (`currency_pair=BTC_USDT&currency_pair=ETH_USDT`).
+pub struct CommaSeparated<T>(PhantomData<T>);
+
+impl<I, T> SerializeAs<I> for CommaSeparated<T>
+where
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+    T: fmt::Display,
+{
+    fn serialize_as<S>(source: &I, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = String::new();
+        for (i, item) in source.into_iter().enumerate() {
+            if i > 0 {
+                buf.push(',');
+            }
+            write!(buf, "{item}").map_err(serde::ser::Error::custom)?;
+        }
+        serializer.serialize_str(&buf)
+    }
+}
+
+impl<'de, T> DeserializeAs<'de, Vec<T>> for CommaSeparated<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            return Ok(vec![]);
+        }
+        s.split(',')
+            .map(|item| item.parse().map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use serde_with::serde_as;
+
+    use super::*;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        #[serde_as(as = "CommaSeparated<String>")]
+        pairs: Vec<String>,
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let example = Example {
+            pairs: vec!["BTC_USDT".into(), "ETH_USDT".into()],
+        };
+        let json = serde_json::to_string(&example).unwrap();
+        assert_eq!(json, r#"{"pairs":"BTC_USDT,ETH_USDT"}"#);
+        assert_eq!(serde_json::from_str::<Example>(&json).unwrap(), example);
+    }
+
+    #[test]
+    fn test_empty() {
+        let example = Example { pairs: vec![] };
+        let json = serde_json::to_string(&example).unwrap();
+        assert_eq!(json, r#"{"pairs":""}"#);
+        assert_eq!(serde_json::from_str::<Example>(&json).unwrap(), example);
+    }
+}
@@ -0,0 +1,592 @@
+//! Deposit and withdrawal history export to CSV for accounting tools.
+//!
+//! Records are written one by one as they are fetched, so the size of the exported range
+//! does not affect memory usage.
+
+use std::collections::HashMap;
+use std::io;
+use std::str::FromStr;
+
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal::RoundingStrategy;
+use serde::Serialize;
+use smart_string::SmartString;
+use thiserror::Error;
+
+use crate::api::wallet::WalletDepositsResponse;
+use crate::api::wallet::WalletWithdrawalHistoryResponse;
+#[cfg(feature = "with_network")]
+use crate::client::rest::RequestError;
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[cfg(feature = "with_network")]
+    #[error("Request error: {0}")]
+    Request(#[from] RequestError),
+    #[error("Invalid record {id}: {reason}")]
+    InvalidRecord { id: SmartString, reason: String },
+}
+
+impl ExportError {
+    fn invalid_record(id: &str, reason: impl Into<String>) -> Self {
+        ExportError::InvalidRecord {
+            id: id.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    Deposit,
+    Withdrawal,
+}
+
+impl TransferDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferDirection::Deposit => "deposit",
+            TransferDirection::Withdrawal => "withdrawal",
+        }
+    }
+}
+
+/// Deposit or withdrawal normalized for export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferRecord {
+    pub timestamp: DateTime<Utc>,
+    pub direction: TransferDirection,
+    pub currency: SmartString,
+    pub chain: SmartString,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub txid: SmartString,
+    pub address: SmartString,
+    pub memo: SmartString,
+    pub status: SmartString,
+    pub client_id: Option<SmartString>,
+}
+
+impl TryFrom<&WalletDepositsResponse> for TransferRecord {
+    type Error = ExportError;
+
+    fn try_from(deposit: &WalletDepositsResponse) -> Result<Self, Self::Error> {
+        let timestamp = i64::from_str(deposit.timestamp.as_str())
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .ok_or_else(|| ExportError::invalid_record(deposit.id.as_str(), "invalid timestamp"))?;
+        let amount = Decimal::from_str(deposit.amount.as_str())
+            .map_err(|e| ExportError::invalid_record(deposit.id.as_str(), e.to_string()))?;
+        Ok(TransferRecord {
+            timestamp,
+            direction: TransferDirection::Deposit,
+            currency: deposit.currency.clone(),
+            chain: deposit.chain.clone(),
+            amount,
+            fee: Decimal::ZERO,
+            txid: deposit.txid.clone(),
+            address: deposit.address.clone(),
+            memo: deposit.memo.clone(),
+            status: status_str(&deposit.status),
+            client_id: deposit.withdraw_order_id.clone(),
+        })
+    }
+}
+
+impl TryFrom<&WalletWithdrawalHistoryResponse> for TransferRecord {
+    type Error = ExportError;
+
+    fn try_from(withdrawal: &WalletWithdrawalHistoryResponse) -> Result<Self, Self::Error> {
+        let timestamp = withdrawal.timestamp.ok_or_else(|| {
+            ExportError::invalid_record(withdrawal.id.as_str(), "missing timestamp")
+        })?;
+        Ok(TransferRecord {
+            timestamp,
+            direction: TransferDirection::Withdrawal,
+            currency: withdrawal.currency.clone(),
+            chain: withdrawal.chain.clone(),
+            amount: withdrawal.amount,
+            fee: withdrawal.fee,
+            txid: withdrawal
+                .txid
+                .as_ref()
+                .map_or("", |txid| txid.as_str())
+                .into(),
+            address: withdrawal.address.as_str().into(),
+            memo: withdrawal.memo.clone().unwrap_or_default(),
+            status: status_str(&withdrawal.status),
+            client_id: withdrawal
+                .withdraw_order_id
+                .as_ref()
+                .map(|id| id.as_str().into()),
+        })
+    }
+}
+
+/// Status as it is named by the API, e.g. `DONE`.
+fn status_str(status: &impl Serialize) -> SmartString {
+    match serde_json::to_value(status) {
+        Ok(serde_json::Value::String(status)) => status.as_str().into(),
+        _ => SmartString::new(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumn {
+    Timestamp,
+    Direction,
+    Currency,
+    Chain,
+    Amount,
+    Fee,
+    Txid,
+    Address,
+    Memo,
+    Status,
+    ClientId,
+}
+
+impl CsvColumn {
+    pub const DEFAULT: [CsvColumn; 11] = [
+        CsvColumn::Timestamp,
+        CsvColumn::Direction,
+        CsvColumn::Currency,
+        CsvColumn::Chain,
+        CsvColumn::Amount,
+        CsvColumn::Fee,
+        CsvColumn::Txid,
+        CsvColumn::Address,
+        CsvColumn::Memo,
+        CsvColumn::Status,
+        CsvColumn::ClientId,
+    ];
+
+    pub fn header(&self) -> &'static str {
+        match self {
+            CsvColumn::Timestamp => "timestamp",
+            CsvColumn::Direction => "direction",
+            CsvColumn::Currency => "currency",
+            CsvColumn::Chain => "chain",
+            CsvColumn::Amount => "amount",
+            CsvColumn::Fee => "fee",
+            CsvColumn::Txid => "txid",
+            CsvColumn::Address => "address",
+            CsvColumn::Memo => "memo",
+            CsvColumn::Status => "status",
+            CsvColumn::ClientId => "client_id",
+        }
+    }
+}
+
+/// Fixed number of decimal places per currency.
+///
+/// Amounts are rounded half away from zero and never use exponent notation. Currencies without
+/// configured precision are written as is, with trailing zeros removed.
+#[derive(Debug, Clone, Default)]
+pub struct DecimalFormat {
+    default_dp: Option<u32>,
+    currency_dp: HashMap<SmartString, u32>,
+}
+
+impl DecimalFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn default_dp(mut self, dp: u32) -> Self {
+        self.default_dp = Some(dp);
+        self
+    }
+
+    pub fn currency_dp(mut self, currency: &str, dp: u32) -> Self {
+        self.currency_dp.insert(currency.into(), dp);
+        self
+    }
+
+    pub fn format(&self, currency: &str, value: Decimal) -> String {
+        match self.currency_dp.get(currency).copied().or(self.default_dp) {
+            Some(dp) => {
+                let value =
+                    value.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+                let dp = dp as usize;
+                format!("{value:.dp$}")
+            }
+            None => value.normalize().to_string(),
+        }
+    }
+}
+
+/// Streaming RFC 4180 CSV writer of [`TransferRecord`]s.
+///
+/// Text fields starting with `=`, `+`, `-` or `@` are prefixed with `'` so that spreadsheets
+/// show them as text rather than evaluating them.
+pub struct TransferCsvWriter<W> {
+    writer: W,
+    columns: Vec<CsvColumn>,
+    decimal_format: DecimalFormat,
+}
+
+impl<W: io::Write> TransferCsvWriter<W> {
+    /// Writer with [`CsvColumn::DEFAULT`] columns and [`DecimalFormat::default`].
+    pub fn new(writer: W) -> Self {
+        TransferCsvWriter {
+            writer,
+            columns: CsvColumn::DEFAULT.to_vec(),
+            decimal_format: DecimalFormat::default(),
+        }
+    }
+
+    pub fn columns(mut self, columns: impl Into<Vec<CsvColumn>>) -> Self {
+        self.columns = columns.into();
+        self
+    }
+
+    pub fn decimal_format(mut self, decimal_format: DecimalFormat) -> Self {
+        self.decimal_format = decimal_format;
+        self
+    }
+
+    pub fn write_header(&mut self) -> io::Result<()> {
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b",")?;
+            }
+            write_field(&mut self.writer, column.header())?;
+        }
+        self.writer.write_all(b"\r\n")
+    }
+
+    pub fn write_record(&mut self, record: &TransferRecord) -> io::Result<()> {
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b",")?;
+            }
+            let mut value = match column {
+                CsvColumn::Timestamp => record.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+                CsvColumn::Direction => record.direction.as_str().to_string(),
+                CsvColumn::Currency => record.currency.to_string(),
+                CsvColumn::Chain => record.chain.to_string(),
+                CsvColumn::Amount => self.decimal_format.format(&record.currency, record.amount),
+                CsvColumn::Fee => self.decimal_format.format(&record.currency, record.fee),
+                CsvColumn::Txid => record.txid.to_string(),
+                CsvColumn::Address => record.address.to_string(),
+                CsvColumn::Memo => record.memo.to_string(),
+                CsvColumn::Status => record.status.to_string(),
+                CsvColumn::ClientId => record
+                    .client_id
+                    .as_ref()
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            };
+            if !matches!(column, CsvColumn::Amount | CsvColumn::Fee) {
+                neutralize_formula(&mut value);
+            }
+            write_field(&mut self.writer, &value)?;
+        }
+        self.writer.write_all(b"\r\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Prefixes text that spreadsheets would evaluate as a formula with `'`.
+///
+/// Memos, addresses and client ids are set by third parties, so a value like
+/// `=HYPERLINK(...)` must not reach the spreadsheet as a formula.
+fn neutralize_formula(value: &mut String) {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        value.insert(0, '\'');
+    }
+}
+
+/// Quotes the field if it contains a delimiter, a quote, a line break or surrounding spaces.
+fn write_field(writer: &mut impl io::Write, value: &str) -> io::Result<()> {
+    let needs_quotes =
+        value.contains([',', '"', '\r', '\n']) || value.starts_with(' ') || value.ends_with(' ');
+    if !needs_quotes {
+        return writer.write_all(value.as_bytes());
+    }
+    writer.write_all(b"\"")?;
+    writer.write_all(value.replace('"', "\"\"").as_bytes())?;
+    writer.write_all(b"\"")
+}
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use chrono::Duration;
+    use smart_string::DisplayExt;
+
+    use super::*;
+    use crate::api::wallet::WalletApi;
+    use crate::api::wallet::WalletWithdrawalHistoryRequest;
+    use crate::client::signer::GateSigner;
+
+    /// History endpoints reject time ranges longer than 30 days.
+    const EXPORT_WINDOW_DAYS: i64 = 30;
+    const EXPORT_PAGE_LIMIT: u32 = 100;
+
+    impl<S: GateSigner> WalletApi<S> {
+        /// Export deposits and withdrawals made within `from..to` to CSV.
+        ///
+        /// The header is written first. The range is walked in 30-day windows; within a window
+        /// deposits go before withdrawals. Each page is written as soon as it is received.
+        ///
+        /// Returns the number of exported records.
+        pub async fn export_transfers_csv<W: io::Write>(
+            &self,
+            csv: &mut TransferCsvWriter<W>,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<u64, ExportError> {
+            csv.write_header()?;
+
+            let mut count = 0;
+            let mut window_start = from;
+            while window_start < to {
+                let window_end = (window_start + Duration::days(EXPORT_WINDOW_DAYS)).min(to);
+                // Both bounds are inclusive.
+                let last_second = window_end - Duration::seconds(1);
+
+                let mut offset = 0;
+                loop {
+                    let page = self
+                        .deposits(
+                            None,
+                            Some(window_start.timestamp().to_fmt()),
+                            Some(last_second.timestamp().to_fmt()),
+                            Some(EXPORT_PAGE_LIMIT),
+                            Some(offset),
+                        )
+                        .await?;
+                    for deposit in &page {
+                        csv.write_record(&TransferRecord::try_from(deposit)?)?;
+                    }
+                    count += page.len() as u64;
+                    if page.len() < EXPORT_PAGE_LIMIT as usize {
+                        break;
+                    }
+                    offset += EXPORT_PAGE_LIMIT;
+                }
+
+                let mut offset = 0;
+                loop {
+                    let request = WalletWithdrawalHistoryRequest {
+                        from: Some(window_start),
+                        to: Some(last_second),
+                        limit: Some(EXPORT_PAGE_LIMIT.into()),
+                        offset: Some(offset),
+                        ..Default::default()
+                    };
+                    let page = self.withdrawal_history(&request).await?;
+                    for withdrawal in &page {
+                        csv.write_record(&TransferRecord::try_from(withdrawal)?)?;
+                    }
+                    count += page.len() as u64;
+                    if page.len() < EXPORT_PAGE_LIMIT as usize {
+                        break;
+                    }
+                    offset += u64::from(EXPORT_PAGE_LIMIT);
+                }
+
+                window_start = window_end;
+            }
+
+            csv.flush()?;
+            Ok(count)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::api::wallet::WalletDepositsStatus;
+    use crate::api::withdrawal::WithdrawalWithdrawStatus;
+
+    fn fixture() -> Vec<TransferRecord> {
+        vec![
+            TransferRecord {
+                timestamp: DateTime::from_timestamp(1542000000, 0).unwrap(),
+                direction: TransferDirection::Deposit,
+                currency: "USDT".into(),
+                chain: "TRX".into(),
+                amount: dec!(222.61),
+                fee: dec!(0),
+                txid: "128988928203223323290".into(),
+                address: "TXmVpin5vq5gdZsciyyjdZgKRUju4st1wM".into(),
+                memo: "".into(),
+                status: "DONE".into(),
+                client_id: None,
+            },
+            TransferRecord {
+                timestamp: DateTime::from_timestamp(1542003600, 0).unwrap(),
+                direction: TransferDirection::Withdrawal,
+                currency: "XRP".into(),
+                chain: "XRP".into(),
+                amount: dec!(1000.125),
+                fee: dec!(0.25),
+                txid: "".into(),
+                address: "rPEPPER7kfTD9w2To4CQk6UCfuHM9c6GDY".into(),
+                memo: r#"tag 123, "invoice" #42"#.into(),
+                status: "PEND".into(),
+                client_id: Some("order_123456".into()),
+            },
+            TransferRecord {
+                timestamp: DateTime::from_timestamp(1542007200, 0).unwrap(),
+                direction: TransferDirection::Withdrawal,
+                currency: "BTC".into(),
+                chain: "BTC".into(),
+                amount: dec!(0.00000001),
+                fee: dec!(0.0005000),
+                txid: "f2b1a7e0".into(),
+                address: "1HkxtBAMrA3tP5ENnYY2CZortjZvFDH5Cs".into(),
+                memo: "line one\nline two".into(),
+                status: "DONE".into(),
+                client_id: None,
+            },
+        ]
+    }
+
+    fn export(mut csv: TransferCsvWriter<Vec<u8>>) -> Vec<u8> {
+        csv.write_header().unwrap();
+        for record in fixture() {
+            csv.write_record(&record).unwrap();
+        }
+        csv.into_inner()
+    }
+
+    #[test]
+    fn test_export_default_golden() {
+        let produced = export(TransferCsvWriter::new(Vec::new()));
+        let golden = include_bytes!("testdata/transfers_default.csv");
+        assert_eq!(
+            String::from_utf8_lossy(&produced),
+            String::from_utf8_lossy(golden)
+        );
+        assert_eq!(produced, golden);
+    }
+
+    #[test]
+    fn test_export_custom_golden() {
+        let columns = [
+            CsvColumn::ClientId,
+            CsvColumn::Timestamp,
+            CsvColumn::Currency,
+            CsvColumn::Amount,
+            CsvColumn::Fee,
+            CsvColumn::Memo,
+        ];
+        let decimal_format = DecimalFormat::new()
+            .default_dp(2)
+            .currency_dp("BTC", 8)
+            .currency_dp("XRP", 6);
+        let csv = TransferCsvWriter::new(Vec::new())
+            .columns(columns)
+            .decimal_format(decimal_format);
+        let produced = export(csv);
+        let golden = include_bytes!("testdata/transfers_custom.csv");
+        assert_eq!(
+            String::from_utf8_lossy(&produced),
+            String::from_utf8_lossy(golden)
+        );
+        assert_eq!(produced, golden);
+    }
+
+    #[test]
+    fn test_formula_neutralized() {
+        let record = TransferRecord {
+            txid: "-2+3".into(),
+            address: "@SUM(A1:A9)".into(),
+            memo: "=HYPERLINK(\"http://x\",\"a,b\")".into(),
+            client_id: Some("+1".into()),
+            ..fixture()[0].clone()
+        };
+        let columns = [
+            CsvColumn::Amount,
+            CsvColumn::Txid,
+            CsvColumn::Address,
+            CsvColumn::Memo,
+            CsvColumn::ClientId,
+        ];
+        let mut csv = TransferCsvWriter::new(Vec::new()).columns(columns);
+        csv.write_record(&TransferRecord {
+            amount: dec!(-1),
+            ..record
+        })
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(csv.into_inner()).unwrap(),
+            "-1,'-2+3,'@SUM(A1:A9),\"'=HYPERLINK(\"\"http://x\"\",\"\"a,b\"\")\",'+1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_decimal_format() {
+        let format = DecimalFormat::new().currency_dp("USDT", 2);
+        assert_eq!(format.format("USDT", dec!(1.005)), "1.01");
+        assert_eq!(format.format("USDT", dec!(-1.005)), "-1.01");
+        assert_eq!(format.format("USDT", dec!(3)), "3.00");
+        assert_eq!(format.format("BTC", dec!(0.000000010)), "0.00000001");
+        assert_eq!(
+            format.format("BTC", dec!(0.00000000000000000001)),
+            "0.00000000000000000001"
+        );
+    }
+
+    #[test]
+    fn test_record_from_deposit() {
+        let deposit = WalletDepositsResponse {
+            id: "210496".into(),
+            timestamp: "1542000000".into(),
+            withdraw_order_id: None,
+            currency: "USDT".into(),
+            address: "TXmVpin5vq5gdZsciyyjdZgKRUju4st1wM".into(),
+            txid: "128988928203223323290".into(),
+            amount: "222.61".into(),
+            memo: "".into(),
+            status: WalletDepositsStatus::Done,
+            chain: "TRX".into(),
+        };
+        assert_eq!(TransferRecord::try_from(&deposit).unwrap(), fixture()[0]);
+
+        let invalid = WalletDepositsResponse {
+            amount: "n/a".into(),
+            ..deposit
+        };
+        assert!(matches!(
+            TransferRecord::try_from(&invalid),
+            Err(ExportError::InvalidRecord { .. })
+        ));
+    }
+
+    #[test]
+    fn test_record_from_withdrawal() {
+        let withdrawal = WalletWithdrawalHistoryResponse {
+            id: "210497".into(),
+            txid: None,
+            withdraw_order_id: Some("order_123456".into()),
+            timestamp: DateTime::from_timestamp(1542003600, 0),
+            amount: dec!(1000.125),
+            fee: dec!(0.25),
+            currency: "XRP".into(),
+            address: "rPEPPER7kfTD9w2To4CQk6UCfuHM9c6GDY".into(),
+            memo: Some(r#"tag 123, "invoice" #42"#.into()),
+            status: WithdrawalWithdrawStatus::Pend,
+            chain: "XRP".into(),
+        };
+        assert_eq!(TransferRecord::try_from(&withdrawal).unwrap(), fixture()[1]);
+    }
+}
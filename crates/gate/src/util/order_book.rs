@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use ccx_api_lib::DiffSequence;
+use ccx_api_lib::OrderBookDesynced;
+use rust_decimal::Decimal;
+
+use crate::api::spot::PriceAndAmount;
+use crate::api::spot::SpotOrderBookResponse;
+use crate::error::GateError;
+use crate::websocket::order_book::OrderBookUpdate;
+
+/// Local order book kept in sync with the `spot.order_book_update` channel.
+///
+/// Updates received before the snapshot are buffered. The snapshot must be requested
+/// with `with_id`, see [`crate::api::spot::SpotOrderBookRequest`]. When the updates do not
+/// continue the book, [`GateError::OrderBookDesynced`] is returned and the updater goes back
+/// to buffering, so a new snapshot has to be passed to `init`.
+pub enum OrderBookUpdater {
+    Preparing { buffer: Vec<OrderBookUpdate> },
+    Ready { state: OrderBookState },
+}
+
+pub struct OrderBookState {
+    sequence: DiffSequence,
+    /// When the snapshot or the latest update was applied.
+    last_update_instant: Instant,
+    asks: BTreeMap<Decimal, Decimal>,
+    bids: BTreeMap<Decimal, Decimal>,
+}
+
+impl OrderBookUpdater {
+    pub fn new() -> Self {
+        OrderBookUpdater::Preparing { buffer: vec![] }
+    }
+
+    pub fn state(&self) -> Option<&OrderBookState> {
+        match self {
+            OrderBookUpdater::Preparing { .. } => None,
+            OrderBookUpdater::Ready { state } => Some(state),
+        }
+    }
+
+    /// Whether the snapshot has been received and an update following it was applied.
+    pub fn is_ready(&self) -> bool {
+        self.state().is_some_and(OrderBookState::is_ready)
+    }
+
+    pub fn push_diff(&mut self, update: OrderBookUpdate) -> Result<(), Box<GateError>> {
+        match self {
+            OrderBookUpdater::Preparing { buffer } => buffer.push(update),
+            OrderBookUpdater::Ready { state } => match state.check(&update) {
+                Ok(true) => state.apply(update),
+                Ok(false) => {}
+                Err(e) => {
+                    *self = OrderBookUpdater::Preparing {
+                        buffer: vec![update],
+                    };
+                    Err(GateError::from(e))?
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Applies the buffered updates on top of `snapshot`.
+    ///
+    /// Fails with [`GateError::OrderBookDesynced`] when the snapshot is older than the
+    /// buffered updates, which are kept for the next snapshot.
+    pub fn init(&mut self, snapshot: SpotOrderBookResponse) -> Result<(), Box<GateError>> {
+        match self {
+            OrderBookUpdater::Preparing { buffer } => {
+                let mut state = OrderBookState::new(snapshot)?;
+                let mut diffs = std::mem::take(buffer).into_iter();
+                while let Some(diff) = diffs.next() {
+                    match state.check(&diff) {
+                        Ok(true) => state.apply(diff),
+                        Ok(false) => {}
+                        Err(e) => {
+                            *buffer = std::iter::once(diff).chain(diffs).collect();
+                            return Err(GateError::from(e).into());
+                        }
+                    }
+                }
+                *self = OrderBookUpdater::Ready { state };
+                Ok(())
+            }
+            OrderBookUpdater::Ready { .. } => {
+                log::warn!("OrderBookUpdater already initialized");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Default for OrderBookUpdater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderBookState {
+    /// Fails if the snapshot was requested without `with_id`.
+    pub fn new(snapshot: SpotOrderBookResponse) -> Result<Self, Box<GateError>> {
+        let last_update_id = snapshot
+            .id
+            .as_deref()
+            .ok_or_else(|| GateError::other("order book snapshot has no id, request it with_id"))?
+            .parse()
+            .map_err(|e| GateError::other(format!("invalid order book snapshot id: {e}")))?;
+        Ok(OrderBookState {
+            sequence: DiffSequence::new(last_update_id),
+            last_update_instant: Instant::now(),
+            asks: levels(&snapshot.asks).collect(),
+            bids: levels(&snapshot.bids).collect(),
+        })
+    }
+
+    /// Update id of the snapshot or of the latest applied update.
+    pub fn last_update_id(&self) -> u64 {
+        self.sequence.last_update_id()
+    }
+
+    /// Whether an update following the snapshot was applied, i.e. the book is in sync
+    /// with the stream.
+    pub fn is_ready(&self) -> bool {
+        self.sequence.is_synced()
+    }
+
+    /// When the snapshot or the latest update was applied.
+    pub fn last_update_instant(&self) -> Instant {
+        self.last_update_instant
+    }
+
+    /// Time elapsed since the snapshot or the latest update was applied.
+    pub fn age(&self) -> Duration {
+        self.last_update_instant.elapsed()
+    }
+
+    /// Whether the book has not been updated for longer than `max_age`.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+
+    pub fn asks(&self) -> &BTreeMap<Decimal, Decimal> {
+        &self.asks
+    }
+
+    pub fn bids(&self) -> &BTreeMap<Decimal, Decimal> {
+        &self.bids
+    }
+
+    pub fn next_ask(&self) -> Option<(&Decimal, &Decimal)> {
+        self.asks.iter().next()
+    }
+
+    pub fn next_bid(&self) -> Option<(&Decimal, &Decimal)> {
+        self.bids.iter().next_back()
+    }
+
+    pub fn spread(&self) -> Decimal {
+        let ask = self.next_ask().map(|(p, _)| p).cloned().unwrap_or_default();
+        let bid = self.next_bid().map(|(p, _)| p).cloned().unwrap_or_default();
+        ask - bid
+    }
+
+    pub fn update(&mut self, diff: OrderBookUpdate) -> Result<(), Box<GateError>> {
+        if self.check(&diff).map_err(GateError::from)? {
+            self.apply(diff);
+        }
+        Ok(())
+    }
+
+    /// Whether `diff` continues the book, `false` for an outdated update.
+    fn check(&mut self, diff: &OrderBookUpdate) -> Result<bool, OrderBookDesynced> {
+        self.sequence
+            .accept(diff.first_update_id, diff.last_update_id)
+    }
+
+    fn apply(&mut self, diff: OrderBookUpdate) {
+        self.last_update_instant = Instant::now();
+
+        for e in diff.asks {
+            if e.amount.is_zero() {
+                self.asks.remove(&e.price);
+            } else {
+                self.asks.insert(e.price, e.amount);
+            }
+        }
+        for e in diff.bids {
+            if e.amount.is_zero() {
+                self.bids.remove(&e.price);
+            } else {
+                self.bids.insert(e.price, e.amount);
+            }
+        }
+    }
+}
+
+fn levels(levels: &[PriceAndAmount]) -> impl Iterator<Item = (Decimal, Decimal)> + '_ {
+    levels.iter().map(|v| (v.price, v.amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use rust_decimal_macros::dec;
+    use smallvec::smallvec;
+
+    use super::*;
+
+    fn snapshot(id: Option<&str>) -> SpotOrderBookResponse {
+        SpotOrderBookResponse {
+            id: id.map(Into::into),
+            current: DateTime::from_timestamp_millis(1623898993123).unwrap(),
+            update: DateTime::from_timestamp_millis(1623898993121).unwrap(),
+            asks: smallvec![
+                PriceAndAmount::from((dec!(101), dec!(1))),
+                PriceAndAmount::from((dec!(102), dec!(3))),
+            ],
+            bids: smallvec![
+                PriceAndAmount::from((dec!(99), dec!(1))),
+                PriceAndAmount::from((dec!(98), dec!(3))),
+            ],
+        }
+    }
+
+    fn diff(first_update_id: u64, last_update_id: u64) -> OrderBookUpdate {
+        OrderBookUpdate {
+            update_time_ms: 0,
+            event_time: 0,
+            currency_pair: "BTC_USDT".into(),
+            first_update_id,
+            last_update_id,
+            bids: vec![(dec!(99), dec!(0)).into()],
+            asks: vec![(dec!(101), dec!(2)).into()],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_without_id() {
+        assert!(OrderBookState::new(snapshot(None)).is_err());
+        assert!(OrderBookUpdater::new().init(snapshot(Some("x"))).is_err());
+    }
+
+    #[test]
+    fn test_readiness() {
+        let mut updater = OrderBookUpdater::new();
+        updater.push_diff(diff(8, 10)).unwrap();
+        assert!(!updater.is_ready());
+
+        updater.init(snapshot(Some("10"))).unwrap();
+        // The buffered update is outdated, so the book is not in sync yet.
+        assert!(!updater.is_ready());
+        let state = updater.state().unwrap();
+        assert_eq!(state.last_update_id(), 10);
+        assert_eq!(state.next_bid(), Some((&dec!(99), &dec!(1))));
+        assert_eq!(state.spread(), dec!(2));
+
+        updater.push_diff(diff(9, 12)).unwrap();
+        assert!(updater.is_ready());
+        let state = updater.state().unwrap();
+        assert_eq!(state.last_update_id(), 12);
+        assert_eq!(state.next_ask(), Some((&dec!(101), &dec!(2))));
+        assert_eq!(state.next_bid(), Some((&dec!(98), &dec!(3))));
+
+        updater.push_diff(diff(13, 13)).unwrap();
+        assert_eq!(updater.state().unwrap().last_update_id(), 13);
+    }
+
+    #[test]
+    fn test_gap() {
+        // The stream is ahead of the snapshot.
+        let mut updater = OrderBookUpdater::new();
+        updater.push_diff(diff(12, 14)).unwrap();
+        assert!(matches!(
+            *updater.init(snapshot(Some("10"))).unwrap_err(),
+            GateError::OrderBookDesynced(OrderBookDesynced {
+                expected: 11,
+                first_update_id: 12,
+            })
+        ));
+        // The buffered update is kept for a newer snapshot.
+        updater.init(snapshot(Some("12"))).unwrap();
+        assert_eq!(updater.state().unwrap().last_update_id(), 14);
+
+        // An update got lost.
+        assert!(matches!(
+            *updater.push_diff(diff(16, 17)).unwrap_err(),
+            GateError::OrderBookDesynced(OrderBookDesynced {
+                expected: 15,
+                first_update_id: 16,
+            })
+        ));
+        assert!(updater.state().is_none());
+        updater.push_diff(diff(18, 18)).unwrap();
+        updater.init(snapshot(Some("16"))).unwrap();
+        assert_eq!(updater.state().unwrap().last_update_id(), 18);
+    }
+}
@@ -1,3 +1,6 @@
 pub mod order_book;
+pub mod orders;
 pub mod request;
 pub mod response;
+pub mod spot;
+pub mod trades;
@@ -24,6 +24,22 @@ impl Serialize for OrderBookRequest {
     }
 }
 
+/// Order book update WebSocket request payload
+#[derive(Debug, Clone)]
+pub struct OrderBookUpdateRequest {
+    pub pair: SmartString<12>,
+    pub interval: UpdateInterval,
+}
+
+impl Serialize for OrderBookUpdateRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(2))?;
+        seq.serialize_element(&self.pair)?;
+        seq.serialize_element(&self.interval)?;
+        seq.end()
+    }
+}
+
 /// Order book level
 #[derive(Debug, Serialize, Clone, Copy)]
 pub enum Level {
@@ -55,6 +71,17 @@ pub enum Interval {
     Ms1000,
 }
 
+/// Order book update interval of the `spot.order_book_update` channel
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum UpdateInterval {
+    /// 20 ms, top 20 levels only
+    #[serde(rename = "20ms")]
+    Ms20,
+    /// 100 ms
+    #[serde(rename = "100ms")]
+    Ms100,
+}
+
 /// Represents a snapshot of the order book.
 #[derive(Debug, Clone, Deserialize)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -77,3 +104,38 @@ pub struct OrderBookSnapshot {
     /// Top level asks in the current snapshot, sorted by price from low to high.
     pub asks: Vec<PriceAndAmount>,
 }
+
+/// Changed levels of the order book between two update IDs.
+///
+/// A level with zero amount has to be removed from the book.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct OrderBookUpdate {
+    /// Order book update time in milliseconds.
+    #[serde(rename = "t")]
+    pub update_time_ms: i64,
+
+    /// Event time in seconds.
+    #[serde(rename = "E")]
+    pub event_time: i64,
+
+    /// Currency pair.
+    #[serde(rename = "s")]
+    pub currency_pair: SmartString<12>,
+
+    /// First update ID since the previous notification.
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+
+    /// Last update ID in this notification.
+    #[serde(rename = "u")]
+    pub last_update_id: u64,
+
+    /// Changed bids.
+    #[serde(rename = "b")]
+    pub bids: Vec<PriceAndAmount>,
+
+    /// Changed asks.
+    #[serde(rename = "a")]
+    pub asks: Vec<PriceAndAmount>,
+}
@@ -0,0 +1,50 @@
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::de::Error;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::api::spot::order::Order;
+
+/// Order change pushed by the `spot.orders` channel.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct OrderUpdate {
+    /// What happened to the order.
+    pub event: OrderEvent,
+    /// The order after the change.
+    pub order: Order,
+}
+
+/// Order event of the `spot.orders` channel.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderEvent {
+    /// Order created.
+    Put,
+    /// Order filled partially.
+    Update,
+    /// Order filled completely or cancelled, see [`Order::finish_as`].
+    Finish,
+}
+
+impl<'de> Deserialize<'de> for OrderUpdate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = Map::<String, Value>::deserialize(deserializer)?;
+        let event = fields
+            .get("event")
+            .ok_or_else(|| D::Error::missing_field("event"))?;
+        let event = OrderEvent::deserialize(event).map_err(D::Error::custom)?;
+        // The channel sends no `status`, it follows from the event.
+        if !fields.contains_key("status") {
+            let status = match (event, fields.get("finish_as").and_then(Value::as_str)) {
+                (OrderEvent::Finish, Some("filled")) => "closed",
+                (OrderEvent::Finish, _) => "cancelled",
+                _ => "open",
+            };
+            fields.insert("status".into(), status.into());
+        }
+        let order = Order::deserialize(Value::Object(fields)).map_err(D::Error::custom)?;
+        Ok(OrderUpdate { event, order })
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::Utc;
 use serde::Serialize;
 use serde_with::skip_serializing_none;
+use smart_string::SmartString;
 
 use super::order_book::OrderBookRequest;
+use super::order_book::OrderBookUpdateRequest;
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize)]
@@ -14,6 +16,28 @@ pub struct WsRequest {
     /// Channel-dependent fields of request
     #[serde(flatten)]
     inner: WsRequestInner,
+    /// Authentication of private channel requests
+    pub auth: Option<WsAuth>,
+}
+
+/// Authentication of a private channel request, see [`crate::client::GateWsSigner::sign_ws`].
+#[derive(Debug, Clone, Serialize)]
+pub struct WsAuth {
+    pub method: &'static str,
+    #[serde(rename = "KEY")]
+    pub key: String,
+    #[serde(rename = "SIGN")]
+    pub sign: SmartString<128>,
+}
+
+impl WsAuth {
+    pub fn api_key(key: impl Into<String>, sign: SmartString<128>) -> Self {
+        Self {
+            method: "api_key",
+            key: key.into(),
+            sign,
+        }
+    }
 }
 
 impl WsRequest {
@@ -30,6 +54,79 @@ impl WsRequest {
     pub fn order_book(event: WsRequestEvent, payload: OrderBookRequest) -> Self {
         WsRequestInner::OrderBook { event, payload }.into()
     }
+
+    /// Notify the latest ticker of the subscribed currency pairs.
+    ///
+    /// <https://www.gate.io/docs/developers/apiv4/ws/en/#tickers-channel>
+    pub fn tickers(event: WsRequestEvent, currency_pairs: Vec<SmartString<15>>) -> Self {
+        WsRequestInner::Tickers {
+            event,
+            payload: currency_pairs,
+        }
+        .into()
+    }
+
+    /// Notify public trades of the subscribed currency pairs.
+    ///
+    /// <https://www.gate.io/docs/developers/apiv4/ws/en/#public-trades-channel>
+    pub fn trades(event: WsRequestEvent, currency_pairs: Vec<SmartString<15>>) -> Self {
+        WsRequestInner::Trades {
+            event,
+            payload: currency_pairs,
+        }
+        .into()
+    }
+
+    /// Periodically notify order book changes, to be applied to a snapshot
+    /// requested with `with_id`, see [`crate::util::OrderBookUpdater`].
+    ///
+    /// <https://www.gate.io/docs/developers/apiv4/ws/en/#changed-order-book-levels>
+    pub fn order_book_update(event: WsRequestEvent, payload: OrderBookUpdateRequest) -> Self {
+        WsRequestInner::OrderBookUpdate { event, payload }.into()
+    }
+
+    /// Notify changes of orders created in the subscribed currency pairs.
+    /// Private channel, the request must be signed with [`Self::with_auth`].
+    ///
+    /// `"!all"` subscribes to all currency pairs.
+    ///
+    /// <https://www.gate.io/docs/developers/apiv4/ws/en/#orders-channel>
+    pub fn orders(event: WsRequestEvent, currency_pairs: Vec<SmartString<15>>) -> Self {
+        WsRequestInner::Orders {
+            event,
+            payload: currency_pairs,
+        }
+        .into()
+    }
+
+    pub fn with_auth(mut self, auth: WsAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Channel name, a part of the signed string of private channel requests.
+    pub fn channel(&self) -> &'static str {
+        match self.inner {
+            WsRequestInner::Ping => "spot.ping",
+            WsRequestInner::Tickers { .. } => "spot.tickers",
+            WsRequestInner::Trades { .. } => "spot.trades",
+            WsRequestInner::OrderBook { .. } => "spot.order_book",
+            WsRequestInner::OrderBookUpdate { .. } => "spot.order_book_update",
+            WsRequestInner::Orders { .. } => "spot.orders",
+        }
+    }
+
+    /// Event name, a part of the signed string of private channel requests.
+    pub fn event(&self) -> Option<WsRequestEvent> {
+        match self.inner {
+            WsRequestInner::Ping => None,
+            WsRequestInner::Tickers { event, .. }
+            | WsRequestInner::Trades { event, .. }
+            | WsRequestInner::OrderBook { event, .. }
+            | WsRequestInner::OrderBookUpdate { event, .. }
+            | WsRequestInner::Orders { event, .. } => Some(event),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -39,12 +136,22 @@ pub enum WsRequestEvent {
     Unsubscribe,
 }
 
+impl WsRequestEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WsRequestEvent::Subscribe => "subscribe",
+            WsRequestEvent::Unsubscribe => "unsubscribe",
+        }
+    }
+}
+
 impl From<WsRequestInner> for WsRequest {
     fn from(inner: WsRequestInner) -> Self {
         Self {
             time: Utc::now().timestamp(),
             id: None,
             inner,
+            auth: None,
         }
     }
 }
@@ -54,21 +161,44 @@ impl From<WsRequestInner> for WsRequest {
 enum WsRequestInner {
     #[serde(rename = "spot.ping")]
     Ping,
+    #[serde(rename = "spot.tickers")]
+    Tickers {
+        event: WsRequestEvent,
+        payload: Vec<SmartString<15>>,
+    },
+    #[serde(rename = "spot.trades")]
+    Trades {
+        event: WsRequestEvent,
+        payload: Vec<SmartString<15>>,
+    },
     #[serde(rename = "spot.order_book")]
     OrderBook {
         event: WsRequestEvent,
         payload: OrderBookRequest,
     },
+    #[serde(rename = "spot.order_book_update")]
+    OrderBookUpdate {
+        event: WsRequestEvent,
+        payload: OrderBookUpdateRequest,
+    },
+    #[serde(rename = "spot.orders")]
+    Orders {
+        event: WsRequestEvent,
+        payload: Vec<SmartString<15>>,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use similar_asserts::assert_eq;
 
+    use super::WsAuth;
     use super::WsRequestEvent;
     use crate::websocket::order_book::Interval;
     use crate::websocket::order_book::Level;
     use crate::websocket::order_book::OrderBookRequest;
+    use crate::websocket::order_book::OrderBookUpdateRequest;
+    use crate::websocket::order_book::UpdateInterval;
     use crate::websocket::request::WsRequest;
 
     #[test]
@@ -104,4 +234,57 @@ mod tests {
         request.time = 1724168425;
         assert_eq!(expected, serde_json::to_string_pretty(&request).unwrap());
     }
+
+    #[test]
+    fn serialize_public_channels() {
+        let mut request = WsRequest::tickers(WsRequestEvent::Subscribe, vec!["BTC_USDT".into()]);
+        request.time = 1724168425;
+        assert_eq!(
+            r#"{"time":1724168425,"channel":"spot.tickers","event":"subscribe","payload":["BTC_USDT"]}"#,
+            serde_json::to_string(&request).unwrap()
+        );
+
+        let mut request = WsRequest::trades(WsRequestEvent::Unsubscribe, vec!["BTC_USDT".into()]);
+        request.time = 1724168425;
+        assert_eq!(
+            r#"{"time":1724168425,"channel":"spot.trades","event":"unsubscribe","payload":["BTC_USDT"]}"#,
+            serde_json::to_string(&request).unwrap()
+        );
+
+        let mut request = WsRequest::order_book_update(
+            WsRequestEvent::Subscribe,
+            OrderBookUpdateRequest {
+                pair: "BTC_USDT".into(),
+                interval: UpdateInterval::Ms100,
+            },
+        );
+        request.time = 1724168425;
+        assert_eq!(request.channel(), "spot.order_book_update");
+        assert_eq!(
+            r#"{"time":1724168425,"channel":"spot.order_book_update","event":"subscribe","payload":["BTC_USDT","100ms"]}"#,
+            serde_json::to_string(&request).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialize_orders() {
+        let expected = r#"{
+  "time": 1611541000,
+  "channel": "spot.orders",
+  "event": "subscribe",
+  "payload": [
+    "BTC_USDT"
+  ],
+  "auth": {
+    "method": "api_key",
+    "KEY": "key",
+    "SIGN": "sign"
+  }
+}"#;
+        let mut request = WsRequest::orders(WsRequestEvent::Subscribe, vec!["BTC_USDT".into()])
+            .with_auth(WsAuth::api_key("key", "sign".into()));
+        request.time = 1611541000;
+        assert_eq!(request.channel(), "spot.orders");
+        assert_eq!(expected, serde_json::to_string_pretty(&request).unwrap());
+    }
 }
@@ -3,11 +3,16 @@ use std::fmt::Display;
 use ccx_api_lib::serde_util::none_as_empty_str;
 use serde::Deserialize;
 use serde::Deserializer;
+use serde::de::DeserializeOwned;
 use serde::de::Error;
 use serde_json::value::RawValue;
 use serde_repr::Deserialize_repr;
 
 use super::order_book::OrderBookSnapshot;
+use super::order_book::OrderBookUpdate;
+use super::orders::OrderUpdate;
+use super::trades::Trade;
+use crate::api::spot::SpotTicker;
 
 pub type WsResult<T> = Result<T, WsErr>;
 
@@ -28,8 +33,16 @@ pub struct WsResponse {
 pub enum Event {
     /// Check if connection to server is still alive
     Pong(WsResult<()>),
+    /// Notify about the latest ticker
+    Tickers(EventInner<SpotTicker>),
+    /// Notify about public trades
+    Trades(EventInner<Trade>),
     /// Periodically notify about top bids and asks snapshot with limited levels
     OrderBook(EventInner<OrderBookSnapshot>),
+    /// Periodically notify about changed order book levels
+    OrderBookUpdate(EventInner<OrderBookUpdate>),
+    /// Notify about changes of own orders
+    Orders(EventInner<Vec<OrderUpdate>>),
 }
 
 impl<'de> Deserialize<'de> for WsResponse {
@@ -45,20 +58,20 @@ impl<'de> Deserialize<'de> for WsResponse {
             result: Option<Box<RawValue>>,
         }
 
-        #[derive(Deserialize)]
-        #[serde(rename_all = "lowercase")]
-        enum EventKind {
-            Subscribe,
-            Unsubscribe,
-            Update,
-        }
-
         #[derive(Deserialize)]
         enum Channel {
             #[serde(rename = "spot.pong")]
             Pong,
+            #[serde(rename = "spot.tickers")]
+            Tickers,
+            #[serde(rename = "spot.trades")]
+            Trades,
             #[serde(rename = "spot.order_book")]
             OrderBook,
+            #[serde(rename = "spot.order_book_update")]
+            OrderBookUpdate,
+            #[serde(rename = "spot.orders")]
+            Orders,
         }
 
         let WsResponseInternal {
@@ -76,25 +89,48 @@ impl<'de> Deserialize<'de> for WsResponse {
             _ => Ok(serde_json::from_str("{}").unwrap()),
         };
         let event = match (channel, event) {
-            (Channel::Pong, _) => Ok(Event::Pong(result.map(|_| ()))),
-            (Channel::OrderBook, Some(EventKind::Subscribe)) => {
-                Ok(Event::OrderBook(EventInner::Subscribe(result.map(|_| ()))))
+            (Channel::Pong, _) => Event::Pong(result.map(|_| ())),
+            (_, None) => Err(D::Error::missing_field("event"))?,
+            (Channel::Tickers, Some(kind)) => Event::Tickers(event_inner::<_, D>(kind, result)?),
+            (Channel::Trades, Some(kind)) => Event::Trades(event_inner::<_, D>(kind, result)?),
+            (Channel::OrderBook, Some(kind)) => {
+                Event::OrderBook(event_inner::<_, D>(kind, result)?)
             }
-            (Channel::OrderBook, Some(EventKind::Unsubscribe)) => Ok(Event::OrderBook(
-                EventInner::Unsubscribe(result.map(|_| ())),
-            )),
-            (Channel::OrderBook, Some(EventKind::Update)) => {
-                Ok(Event::OrderBook(EventInner::Update(match result {
-                    Ok(json) => Ok(serde_json::from_str(json.get()).map_err(D::Error::custom)?),
-                    Err(err) => Err(err),
-                })))
+            (Channel::OrderBookUpdate, Some(kind)) => {
+                Event::OrderBookUpdate(event_inner::<_, D>(kind, result)?)
             }
-            (_, None) => Err(D::Error::missing_field("event")),
-        }?;
+            (Channel::Orders, Some(kind)) => Event::Orders(event_inner::<_, D>(kind, result)?),
+        };
         Ok(WsResponse { time, id, event })
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum EventKind {
+    Subscribe,
+    Unsubscribe,
+    Update,
+}
+
+fn event_inner<'de, T, D>(
+    kind: EventKind,
+    result: WsResult<Box<RawValue>>,
+) -> Result<EventInner<T>, D::Error>
+where
+    T: DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    Ok(match kind {
+        EventKind::Subscribe => EventInner::Subscribe(result.map(|_| ())),
+        EventKind::Unsubscribe => EventInner::Unsubscribe(result.map(|_| ())),
+        EventKind::Update => EventInner::Update(match result {
+            Ok(json) => Ok(serde_json::from_str(json.get()).map_err(D::Error::custom)?),
+            Err(err) => Err(err),
+        }),
+    })
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum EventInner<T> {
@@ -138,11 +174,18 @@ mod tests {
     use similar_asserts::assert_eq;
 
     use super::Event;
+    use crate::api::spot::order::FinishAs;
+    use crate::api::spot::order::OrderStatus;
+    use crate::api::spot::order::create::OrderSide;
     use crate::websocket::order_book::OrderBookSnapshot;
+    use crate::websocket::order_book::OrderBookUpdate;
+    use crate::websocket::orders::OrderEvent;
+    use crate::websocket::orders::OrderUpdate;
     use crate::websocket::response::EventInner;
     use crate::websocket::response::WsErr;
     use crate::websocket::response::WsErrCode::ServerError;
     use crate::websocket::response::WsResponse;
+    use crate::websocket::trades::Trade;
 
     #[test]
     fn deserialize_pong_success() {
@@ -233,6 +276,183 @@ mod tests {
         assert_eq!(expected, serde_path_to_error::deserialize(jd).unwrap());
     }
 
+    #[test]
+    fn deserialize_tickers() {
+        let json = r#"{
+  "time": 1606291803,
+  "channel": "spot.tickers",
+  "event": "update",
+  "result": {
+    "currency_pair": "BTC_USDT",
+    "last": "19106.55",
+    "lowest_ask": "19108.71",
+    "highest_bid": "19106.55",
+    "change_percentage": "3.66",
+    "base_volume": "2811.3042155865",
+    "quote_volume": "53441606.52411221454674732293",
+    "high_24h": "19417.74",
+    "low_24h": "18434.21"
+  }
+}"#;
+        let jd = &mut serde_json::Deserializer::from_str(json);
+        let response: WsResponse = serde_path_to_error::deserialize(jd).unwrap();
+        let Event::Tickers(EventInner::Update(Ok(ticker))) = response.event else {
+            panic!("unexpected event: {:?}", response.event);
+        };
+        assert_eq!(ticker.currency_pair, "BTC_USDT");
+        assert_eq!(ticker.last, Some(dec!(19106.55)));
+        assert_eq!(ticker.lowest_ask, Some(dec!(19108.71)));
+        assert_eq!(ticker.high_24h, Some(dec!(19417.74)));
+        assert_eq!(ticker.etf_net_value, None);
+    }
+
+    #[test]
+    fn deserialize_trades() {
+        let json = r#"{
+  "time": 1606292218,
+  "channel": "spot.trades",
+  "event": "update",
+  "result": {
+    "id": 309143071,
+    "create_time": 1606292218,
+    "create_time_ms": "1606292218213.4578",
+    "side": "sell",
+    "currency_pair": "GT_USDT",
+    "amount": "16.4700000000",
+    "price": "0.4705000000",
+    "range": "2390902-2390902"
+  }
+}"#;
+        let expected = WsResponse::new(
+            1606292218,
+            Event::Trades(EventInner::Update(Ok(Trade {
+                id: 309143071,
+                create_time: 1606292218,
+                create_time_ms: dec!(1606292218213.4578),
+                side: OrderSide::Sell,
+                currency_pair: "GT_USDT".into(),
+                amount: dec!(16.47),
+                price: dec!(0.4705),
+                range: Some("2390902-2390902".into()),
+            }))),
+        );
+        let jd = &mut serde_json::Deserializer::from_str(json);
+        assert_eq!(expected, serde_path_to_error::deserialize(jd).unwrap());
+    }
+
+    #[test]
+    fn deserialize_order_book_update() {
+        let json = r#"{
+  "time": 1606294781,
+  "channel": "spot.order_book_update",
+  "event": "update",
+  "result": {
+    "t": 1606294781123,
+    "e": "depthUpdate",
+    "E": 1606294781,
+    "s": "BTC_USDT",
+    "U": 48776301,
+    "u": 48776306,
+    "b": [
+      ["19137.74", "0.0001"],
+      ["19088.37", "0"]
+    ],
+    "a": [
+      ["19137.75", "0.6135"]
+    ]
+  }
+}"#;
+        let expected = WsResponse::new(
+            1606294781,
+            Event::OrderBookUpdate(EventInner::Update(Ok(OrderBookUpdate {
+                update_time_ms: 1606294781123,
+                event_time: 1606294781,
+                currency_pair: "BTC_USDT".into(),
+                first_update_id: 48776301,
+                last_update_id: 48776306,
+                bids: vec![
+                    (dec!(19137.74), dec!(0.0001)).into(),
+                    (dec!(19088.37), dec!(0)).into(),
+                ],
+                asks: vec![(dec!(19137.75), dec!(0.6135)).into()],
+            }))),
+        );
+        let jd = &mut serde_json::Deserializer::from_str(json);
+        assert_eq!(expected, serde_path_to_error::deserialize(jd).unwrap());
+    }
+
+    #[test]
+    fn deserialize_orders() {
+        let json = r#"{
+  "time": 1694655225,
+  "time_ms": 1694655225315,
+  "channel": "spot.orders",
+  "event": "update",
+  "result": [
+    {
+      "id": "399123456",
+      "text": "t-testtext",
+      "create_time": "1694655225",
+      "update_time": "1694655225",
+      "currency_pair": "BTC_USDT",
+      "type": "limit",
+      "account": "spot",
+      "side": "sell",
+      "amount": "0.0001",
+      "price": "26253.3",
+      "time_in_force": "gtc",
+      "left": "0",
+      "filled_total": "2.62533",
+      "avg_deal_price": "26253.3",
+      "fee": "0.00525066",
+      "fee_currency": "USDT",
+      "point_fee": "0",
+      "gt_fee": "0",
+      "rebated_fee": "0",
+      "rebated_fee_currency": "USDT",
+      "create_time_ms": "1694655225315",
+      "update_time_ms": "1694655225320",
+      "user": 3497082,
+      "event": "finish",
+      "stp_id": 0,
+      "stp_act": "-",
+      "finish_as": "filled",
+      "biz_info": "-",
+      "amend_text": "-"
+    }
+  ]
+}"#;
+        let jd = &mut serde_json::Deserializer::from_str(json);
+        let response: WsResponse = serde_path_to_error::deserialize(jd).unwrap();
+        let Event::Orders(EventInner::Update(Ok(updates))) = response.event else {
+            panic!("unexpected event: {:?}", response.event);
+        };
+        assert_eq!(updates.len(), 1);
+        let OrderUpdate { event, order } = &updates[0];
+        assert_eq!(*event, OrderEvent::Finish);
+        assert_eq!(order.id.as_str(), "399123456");
+        assert_eq!(order.client_id(), Some("testtext"));
+        assert_eq!(order.status, OrderStatus::Closed);
+        assert_eq!(order.finish_as, FinishAs::Filled);
+        assert_eq!(order.request.price, Some(dec!(26253.3)));
+        assert_eq!(order.filled_total, Some(dec!(2.62533)));
+        assert_eq!(order.update_time.timestamp_millis(), 1694655225320);
+    }
+
+    #[test]
+    fn deserialize_orders_subscribe() {
+        let json = r#"{
+  "time": 1611541000,
+  "channel": "spot.orders",
+  "event": "subscribe",
+  "error": null,
+  "result": {"status": "success"}
+}"#;
+        let expected = WsResponse::new(1611541000, Event::Orders(EventInner::Subscribe(Ok(()))));
+        let jd = &mut serde_json::Deserializer::from_str(json);
+        assert_eq!(expected, serde_path_to_error::deserialize(jd).unwrap());
+    }
+
     impl WsResponse {
         fn new(time: i64, event: Event) -> Self {
             Self {
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::Stream;
+use futures::StreamExt;
+use futures::channel::mpsc;
+
+use super::orders::OrderUpdate;
+use super::request::WsAuth;
+use super::request::WsRequest;
+use super::request::WsRequestEvent;
+use super::response::Event;
+use super::response::EventInner;
+use super::response::WsResponse;
+use super::response::WsResult;
+use crate::api::GateApi;
+use crate::client::GateWsSigner;
+use crate::client::websocket::WebsocketStreamTx;
+use crate::error::GateError;
+use crate::error::GateResult;
+
+/// Push-based updates of own spot orders (`spot.orders` channel).
+///
+/// Yields every order change, a failed subscription is yielded as an error.
+/// The stream ends when the connection is closed.
+pub struct SpotWebsocket {
+    tx: WebsocketStreamTx,
+    rx: mpsc::UnboundedReceiver<WsResponse>,
+    pending: VecDeque<OrderUpdate>,
+}
+
+impl SpotWebsocket {
+    /// Connects and subscribes to the orders of `currency_pairs`, `"!all"` for all pairs.
+    pub async fn orders<S: GateWsSigner>(
+        api: &GateApi<S>,
+        currency_pairs: &[&str],
+    ) -> GateResult<Self> {
+        let (tx, rx) = api.websocket().await?.split();
+        let request = WsRequest::orders(
+            WsRequestEvent::Subscribe,
+            currency_pairs.iter().map(|&pair| pair.into()).collect(),
+        );
+        tx.send(sign(api.client.signer(), request).await?).await?;
+        Ok(SpotWebsocket {
+            tx,
+            rx,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Stops the orders updates of `currency_pairs`.
+    pub async fn unsubscribe_orders<S: GateWsSigner>(
+        &self,
+        signer: &S,
+        currency_pairs: &[&str],
+    ) -> GateResult<()> {
+        let request = WsRequest::orders(
+            WsRequestEvent::Unsubscribe,
+            currency_pairs.iter().map(|&pair| pair.into()).collect(),
+        );
+        self.tx.send(sign(signer, request).await?).await
+    }
+}
+
+async fn sign<S: GateWsSigner>(signer: &S, request: WsRequest) -> GateResult<WsRequest> {
+    let event = request.event().map_or("", |event| event.as_str());
+    let sign = signer
+        .sign_ws(request.channel(), event, request.time)
+        .await
+        .map_err(|e| GateError::Other(format!("Sign error: {e}")))?;
+    Ok(request.with_auth(WsAuth::api_key(signer.key(), sign)))
+}
+
+impl Stream for SpotWebsocket {
+    type Item = WsResult<OrderUpdate>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Poll::Ready(Some(Ok(update)));
+            }
+            let response = match self.rx.poll_next_unpin(cx) {
+                Poll::Ready(Some(response)) => response,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            match response.event {
+                Event::Orders(EventInner::Update(Ok(updates))) => self.pending.extend(updates),
+                Event::Orders(
+                    EventInner::Subscribe(Err(e))
+                    | EventInner::Unsubscribe(Err(e))
+                    | EventInner::Update(Err(e)),
+                ) => return Poll::Ready(Some(Err(e))),
+                event => log::debug!("spot.orders: skipping {event:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use smart_string::SmartString;
+
+use crate::api::spot::order::create::OrderSide;
+
+/// Public trade of the `spot.trades` channel.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct Trade {
+    /// Trade ID.
+    pub id: u64,
+    /// Trade time in seconds.
+    pub create_time: i64,
+    /// Trade time in milliseconds, with a fractional part.
+    pub create_time_ms: Decimal,
+    /// Taker side.
+    pub side: OrderSide,
+    /// Currency pair.
+    pub currency_pair: SmartString<15>,
+    /// Trade amount.
+    pub amount: Decimal,
+    /// Trade price.
+    pub price: Decimal,
+    /// Range of the market trade IDs of this trade, like `2390902-2390902`.
+    pub range: Option<String>,
+}
@@ -0,0 +1,84 @@
+//! Minimal HTTP server mocking the Gate.io API, shared by the integration tests.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+
+use ccx_gate::GateApi;
+use ccx_gate::client::config::GateApiConfig;
+use ccx_gate::util::GateApiCred;
+use futures::channel::mpsc;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+/// `200 OK` response of the mock.
+pub struct Response {
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    pub fn json(body: impl Into<String>) -> Self {
+        Response {
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// Serves `responses` in order, one per connection, and reports the request lines.
+///
+/// The listener is closed afterwards, so any extra request fails to connect.
+pub async fn serve(
+    listener: TcpListener,
+    responses: Vec<Response>,
+    requests: mpsc::UnboundedSender<String>,
+) {
+    for response in responses {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+            let mut chunk = [0; 1024];
+            let n = stream.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection closed before the request was read");
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let request = String::from_utf8_lossy(&buf);
+        let _ = requests.unbounded_send(request.lines().next().unwrap_or_default().to_string());
+
+        let mut head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            response.body.len()
+        );
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.write_all(response.body.as_bytes()).await.unwrap();
+        stream.shutdown().await.unwrap();
+    }
+}
+
+/// Starts [`serve`] on a local port.
+///
+/// Returns the address and the request lines, which end once all the responses are served.
+pub async fn start(responses: Vec<Response>) -> (SocketAddr, mpsc::UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (requests_tx, requests_rx) = mpsc::unbounded();
+    actix_rt::spawn(serve(listener, responses, requests_tx));
+    (addr, requests_rx)
+}
+
+/// Api talking to the mock at `addr`, signing with a dummy key.
+pub fn api(addr: SocketAddr) -> GateApi<GateApiCred> {
+    let api_base = format!("http://{addr}/api/").parse().unwrap();
+    let cred = GateApiCred::new(Some("key".to_string()), Some("secret".to_string()));
+    GateApi::with_config(GateApiConfig::custom_base(cred, api_base, None))
+}
@@ -0,0 +1,169 @@
+//! Checks that the checked leverage and position mode changes refuse while exposed.
+#![cfg(feature = "with_network")]
+
+use ccx_gate::api::futures::FuturesGuardError;
+use ccx_gate::api::futures::Settle;
+use futures::StreamExt;
+use rust_decimal_macros::dec;
+
+mod common;
+
+use common::Response;
+
+fn position(contract: &str, size: i64) -> String {
+    format!(
+        r#"{{
+            "contract": "{contract}",
+            "size": {size},
+            "leverage": "10",
+            "risk_limit": "1000000",
+            "leverage_max": "100",
+            "value": "0",
+            "margin": "0",
+            "entry_price": "0",
+            "liq_price": "0",
+            "mark_price": "37985.6",
+            "unrealised_pnl": "0",
+            "mode": "single"
+        }}"#
+    )
+}
+
+const OPEN_ORDER: &str = r#"{
+    "id": 15675394,
+    "contract": "ETH_USDT",
+    "create_time": 1546569968.123,
+    "size": 10,
+    "left": 10,
+    "price": "2000",
+    "status": "open"
+}"#;
+
+const ACCOUNT: &str = r#"{
+    "currency": "USDT",
+    "total": "100",
+    "unrealised_pnl": "0",
+    "position_margin": "0",
+    "order_margin": "0",
+    "available": "100",
+    "in_dual_mode": true
+}"#;
+
+#[actix_rt::test]
+async fn test_set_leverage_refused_with_open_position() {
+    let positions = format!("[{},{}]", position("BTC_USDT", -5), position("ETH_USDT", 3));
+    let (addr, requests) =
+        common::start(vec![Response::json(positions), Response::json("[]")]).await;
+    let api = common::api(addr);
+
+    let res = api
+        .futures()
+        .set_leverage_checked(Settle::Usdt, "BTC_USDT", dec!(20), false)
+        .await;
+    let Err(FuturesGuardError::Exposed(exposure)) = res else {
+        panic!("unexpected result: {res:?}");
+    };
+    assert_eq!(exposure.positions.len(), 1);
+    assert_eq!(exposure.positions[0].contract, "BTC_USDT");
+    assert_eq!(exposure.positions[0].size, -5);
+    assert!(exposure.open_orders.is_empty());
+
+    let requests: Vec<String> = requests.collect().await;
+    assert_eq!(
+        requests,
+        [
+            "GET /api/v4/futures/usdt/positions?holding=true HTTP/1.1",
+            "GET /api/v4/futures/usdt/orders?contract=BTC_USDT&status=open HTTP/1.1",
+        ]
+    );
+}
+
+#[actix_rt::test]
+async fn test_set_leverage_when_flat() {
+    let (addr, requests) = common::start(vec![
+        Response::json(format!("[{}]", position("ETH_USDT", 3))),
+        Response::json("[]"),
+        Response::json(position("BTC_USDT", 0)),
+    ])
+    .await;
+    let api = common::api(addr);
+
+    let position = api
+        .futures()
+        .set_leverage_checked(Settle::Usdt, "BTC_USDT", dec!(10), false)
+        .await
+        .unwrap();
+    assert_eq!(position.leverage, dec!(10));
+
+    let requests: Vec<String> = requests.collect().await;
+    assert_eq!(
+        requests.last().unwrap(),
+        "POST /api/v4/futures/usdt/positions/BTC_USDT/leverage?leverage=10 HTTP/1.1"
+    );
+}
+
+#[actix_rt::test]
+async fn test_set_leverage_forced() {
+    let (addr, requests) = common::start(vec![Response::json(position("BTC_USDT", -5))]).await;
+    let api = common::api(addr);
+
+    api.futures()
+        .set_leverage_checked(Settle::Usdt, "BTC_USDT", dec!(10), true)
+        .await
+        .unwrap();
+
+    let requests: Vec<String> = requests.collect().await;
+    assert_eq!(
+        requests,
+        ["POST /api/v4/futures/usdt/positions/BTC_USDT/leverage?leverage=10 HTTP/1.1"]
+    );
+}
+
+#[actix_rt::test]
+async fn test_set_dual_mode_refused_with_open_order() {
+    let (addr, requests) = common::start(vec![
+        Response::json("[]"),
+        Response::json(format!("[{OPEN_ORDER}]")),
+    ])
+    .await;
+    let api = common::api(addr);
+
+    let res = api
+        .futures()
+        .set_dual_mode_checked(Settle::Usdt, true, false)
+        .await;
+    let Err(FuturesGuardError::Exposed(exposure)) = res else {
+        panic!("unexpected result: {res:?}");
+    };
+    assert!(exposure.positions.is_empty());
+    assert_eq!(exposure.open_orders[0].contract, "ETH_USDT");
+    assert_eq!(exposure.to_string(), "0 open position(s), 1 open order(s)");
+
+    let requests: Vec<String> = requests.collect().await;
+    assert_eq!(
+        requests,
+        [
+            "GET /api/v4/futures/usdt/positions?holding=true HTTP/1.1",
+            "GET /api/v4/futures/usdt/orders?status=open HTTP/1.1",
+        ]
+    );
+}
+
+#[actix_rt::test]
+async fn test_set_dual_mode_forced() {
+    let (addr, requests) = common::start(vec![Response::json(ACCOUNT)]).await;
+    let api = common::api(addr);
+
+    let account = api
+        .futures()
+        .set_dual_mode_checked(Settle::Usdt, true, true)
+        .await
+        .unwrap();
+    assert!(account.in_dual_mode);
+
+    let requests: Vec<String> = requests.collect().await;
+    assert_eq!(
+        requests,
+        ["POST /api/v4/futures/usdt/dual_mode?dual_mode=true HTTP/1.1"]
+    );
+}
@@ -0,0 +1,55 @@
+//! Checks that paged list helpers walk pages until a short page is returned.
+#![cfg(feature = "with_network")]
+
+use ccx_gate::api::spot::order::OrderStatus;
+use ccx_gate::api::spot::order::list::ListOrdersRequest;
+use futures::StreamExt;
+
+mod common;
+
+use common::Response;
+
+fn order(id: u32) -> String {
+    format!(
+        r#"{{
+            "id": "{id}",
+            "currency_pair": "BTC_USDT",
+            "side": "buy",
+            "amount": "0.001",
+            "create_time_ms": 1710488334073,
+            "update_time_ms": 1710488334074,
+            "status": "closed",
+            "finish_as": "filled"
+        }}"#
+    )
+}
+
+#[actix_rt::test]
+async fn test_list_orders_paged() {
+    let pages = [vec![1, 2], vec![3, 4], vec![5]].map(|page| {
+        let orders: Vec<String> = page.into_iter().map(order).collect();
+        Response::json(format!("[{}]", orders.join(",")))
+    });
+    let (addr, requests_rx) = common::start(pages.into()).await;
+    let api = common::api(addr);
+
+    let mut request = ListOrdersRequest::new("BTC_USDT", OrderStatus::Closed);
+    request.limit = Some(2);
+    let ids: Vec<String> = api
+        .spot()
+        .list_orders_paged(&request)
+        .map(|order| order.unwrap().id.to_string())
+        .collect()
+        .await;
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+
+    let requests: Vec<String> = requests_rx.collect().await;
+    assert_eq!(
+        requests,
+        [
+            "GET /api/v4/spot/orders?currency_pair=BTC_USDT&status=closed&limit=2 HTTP/1.1",
+            "GET /api/v4/spot/orders?currency_pair=BTC_USDT&status=closed&page=2&limit=2 HTTP/1.1",
+            "GET /api/v4/spot/orders?currency_pair=BTC_USDT&status=closed&page=3&limit=2 HTTP/1.1",
+        ]
+    );
+}
@@ -0,0 +1,132 @@
+//! Checks that the client is routed through the SOCKS5 proxy configured via env vars.
+#![cfg(feature = "with_network")]
+
+use std::net::SocketAddr;
+
+use ccx_gate::GateApi;
+use ccx_gate::client::config::CCX_GATE_API_PREFIX;
+use ccx_gate::client::config::GateApiConfig;
+use ccx_gate::client::config::Proxy;
+use ccx_gate::util::GateApiCred;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use futures::channel::oneshot;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+mod common;
+
+use common::Response;
+
+const PROXY_USERNAME: &str = "gate";
+const PROXY_PASSWORD: &str = "secret";
+/// Not resolvable, so the request can only reach the server through the proxy.
+const API_HOST: &str = "api.gate.invalid";
+
+const CURRENCIES: &str = r#"[{
+    "currency": "GT",
+    "delisted": false,
+    "withdraw_disabled": false,
+    "withdraw_delayed": false,
+    "deposit_disabled": false,
+    "trade_disabled": false,
+    "chain": "GT"
+}]"#;
+
+/// Minimal SOCKS5 proxy with username/password auth. Connects every CONNECT request
+/// to `upstream` and reports the requested target.
+async fn serve_proxy(listener: TcpListener, upstream: SocketAddr, target: oneshot::Sender<String>) {
+    let (mut client, _) = listener.accept().await.unwrap();
+
+    // Greeting: VER, NMETHODS, METHODS.
+    let mut head = [0; 2];
+    client.read_exact(&mut head).await.unwrap();
+    assert_eq!(head[0], 5);
+    let mut methods = vec![0; head[1] as usize];
+    client.read_exact(&mut methods).await.unwrap();
+    assert!(
+        methods.contains(&2),
+        "username/password auth is not offered"
+    );
+    client.write_all(&[5, 2]).await.unwrap();
+
+    // Auth: VER, ULEN, UNAME, PLEN, PASSWD.
+    let mut ver_len = [0; 2];
+    client.read_exact(&mut ver_len).await.unwrap();
+    let mut username = vec![0; ver_len[1] as usize];
+    client.read_exact(&mut username).await.unwrap();
+    let mut len = [0; 1];
+    client.read_exact(&mut len).await.unwrap();
+    let mut password = vec![0; len[0] as usize];
+    client.read_exact(&mut password).await.unwrap();
+    let authorized = username == PROXY_USERNAME.as_bytes() && password == PROXY_PASSWORD.as_bytes();
+    client
+        .write_all(&[1, if authorized { 0 } else { 1 }])
+        .await
+        .unwrap();
+    assert!(authorized, "invalid proxy credentials");
+
+    // Request: VER, CMD, RSV, ATYP=DOMAINNAME, LEN, HOST, PORT.
+    let mut request = [0; 4];
+    client.read_exact(&mut request).await.unwrap();
+    assert_eq!(request, [5, 1, 0, 3]);
+    client.read_exact(&mut len).await.unwrap();
+    let mut host = vec![0; len[0] as usize];
+    client.read_exact(&mut host).await.unwrap();
+    let port = client.read_u16().await.unwrap();
+    let _ = target.send(format!("{}:{port}", String::from_utf8_lossy(&host)));
+
+    let mut server = TcpStream::connect(upstream).await.unwrap();
+    client
+        .write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0])
+        .await
+        .unwrap();
+    let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+}
+
+#[actix_rt::test]
+async fn test_requests_are_routed_through_proxy() {
+    let api_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let api_addr = api_listener.local_addr().unwrap();
+    let proxy_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_addr = proxy_listener.local_addr().unwrap();
+
+    let (requests_tx, requests_rx) = mpsc::unbounded();
+    let (target_tx, target_rx) = oneshot::channel();
+    let response = Response::json(CURRENCIES);
+    actix_rt::spawn(common::serve(api_listener, vec![response], requests_tx));
+    actix_rt::spawn(serve_proxy(proxy_listener, api_addr, target_tx));
+
+    // SAFETY: this is the only test in the binary touching the environment.
+    unsafe {
+        std::env::set_var(format!("{CCX_GATE_API_PREFIX}_PROXY_HOST"), "127.0.0.1");
+        std::env::set_var(
+            format!("{CCX_GATE_API_PREFIX}_PROXY_PORT"),
+            proxy_addr.port().to_string(),
+        );
+        std::env::set_var(
+            format!("{CCX_GATE_API_PREFIX}_PROXY_USERNAME"),
+            PROXY_USERNAME,
+        );
+        std::env::set_var(
+            format!("{CCX_GATE_API_PREFIX}_PROXY_PASSWORD"),
+            PROXY_PASSWORD,
+        );
+    }
+    let proxy = Proxy::from_env_with_prefix(CCX_GATE_API_PREFIX);
+    assert!(proxy.is_some());
+
+    let api_base = format!("http://{API_HOST}:8080/api/").parse().unwrap();
+    let config = GateApiConfig::custom_base(GateApiCred::new(None, None), api_base, proxy);
+    let api = GateApi::with_config(config);
+
+    let currencies = api.spot().list_currencies().await.unwrap();
+    assert_eq!(currencies.len(), 1);
+    assert_eq!(currencies[0].currency.to_string(), "GT");
+
+    assert_eq!(target_rx.await.unwrap(), format!("{API_HOST}:8080"));
+    let requests: Vec<String> = requests_rx.collect().await;
+    assert_eq!(requests, ["GET /api/v4/spot/currencies HTTP/1.1"]);
+}
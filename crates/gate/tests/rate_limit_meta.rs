@@ -0,0 +1,45 @@
+//! Checks that rate limit headers are surfaced to the caller.
+#![cfg(feature = "with_network")]
+
+use ccx_gate::api::spot::order::get::GetOrderParams;
+use ccx_gate::client::rest::ResponseMeta;
+
+mod common;
+
+use common::Response;
+
+const ORDER: &str = r#"{
+    "id": "12345",
+    "text": "t-abc123",
+    "currency_pair": "BTC_USDT",
+    "side": "buy",
+    "amount": "0.001",
+    "create_time_ms": 1710488334073,
+    "update_time_ms": 1710488334074,
+    "status": "open",
+    "finish_as": "open"
+}"#;
+
+#[actix_rt::test]
+async fn test_signed_request_with_meta() {
+    let response = Response::json(ORDER)
+        .with_header("X-Gate-RateLimit-Requests-Remain", "7")
+        .with_header("X-Gate-RateLimit-Limit", "10")
+        .with_header("X-Gate-RateLimit-Reset-Timestamp", "1736408263764");
+    let (addr, _requests) = common::start(vec![response]).await;
+    let api = common::api(addr);
+
+    let (order, meta) = api
+        .signed_request_with_meta("/spot/orders/12345", &GetOrderParams::default())
+        .await
+        .unwrap();
+    assert_eq!(order.id.as_str(), "12345");
+    assert_eq!(
+        meta,
+        ResponseMeta {
+            requests_remain: Some(7),
+            limit: Some(10),
+            reset_timestamp: Some(1736408263764),
+        }
+    );
+}
@@ -0,0 +1,81 @@
+//! Checks that `withdraw_idempotent` does not resubmit a known withdrawal.
+#![cfg(feature = "with_network")]
+
+use ccx_gate::api::withdrawal::IdempotentWithdrawal;
+use ccx_gate::api::withdrawal::WithdrawalWithdrawRequest;
+use ccx_gate::api::withdrawal::WithdrawalWithdrawStatus;
+use futures::StreamExt;
+use rust_decimal_macros::dec;
+
+mod common;
+
+use common::Response;
+
+fn withdrawal(status: &str) -> String {
+    format!(
+        r#"{{
+            "id": "w1001",
+            "timestamp": "1710488334",
+            "withdraw_order_id": "payout-42",
+            "currency": "USDT",
+            "address": "Txxx",
+            "txid": null,
+            "amount": "10",
+            "fee": "1",
+            "memo": null,
+            "status": "{status}",
+            "chain": "TRX"
+        }}"#
+    )
+}
+
+async fn withdraw(bodies: Vec<String>) -> (IdempotentWithdrawal, Vec<String>) {
+    let (addr, requests_rx) = common::start(bodies.into_iter().map(Response::json).collect()).await;
+    let api = common::api(addr);
+
+    let request = WithdrawalWithdrawRequest {
+        withdraw_order_id: Some("payout-42".into()),
+        amount: dec!(10),
+        currency: "USDT".into(),
+        address: Some("Txxx".into()),
+        memo: None,
+        chain: "TRX".into(),
+    };
+    let withdrawal = api
+        .withdrawal()
+        .withdraw_idempotent(&request)
+        .await
+        .unwrap();
+    // Paths only, the query carries the generated timestamps.
+    let requests = requests_rx.map(|line| line.split('?').next().unwrap().to_string());
+    (withdrawal, requests.collect().await)
+}
+
+#[actix_rt::test]
+async fn test_duplicate_is_not_submitted() {
+    let (withdrawal, requests) = withdraw(vec![format!("[{}]", withdrawal("PEND"))]).await;
+
+    assert!(!withdrawal.is_submitted());
+    assert_eq!(withdrawal.id(), "w1001");
+    assert_eq!(withdrawal.status(), WithdrawalWithdrawStatus::Pend);
+    assert_eq!(requests, ["GET /api/v4/wallet/withdrawals"]);
+}
+
+#[actix_rt::test]
+async fn test_failed_duplicate_is_resubmitted() {
+    let (withdrawal, requests) = withdraw(vec![
+        format!("[{}]", withdrawal("CANCEL")),
+        withdrawal("REQUEST"),
+    ])
+    .await;
+
+    assert!(withdrawal.is_submitted());
+    assert_eq!(withdrawal.status(), WithdrawalWithdrawStatus::Request);
+    assert_eq!(
+        requests,
+        [
+            "GET /api/v4/wallet/withdrawals",
+            "POST /api/v4/withdrawals HTTP/1.1"
+        ]
+    );
+}
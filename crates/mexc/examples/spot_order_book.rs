@@ -0,0 +1,28 @@
+use ccx_mexc::ApiCred;
+use ccx_mexc::MexcError;
+use ccx_mexc::SpotApi;
+use futures::StreamExt;
+
+#[actix_rt::main]
+async fn main() {
+    let _ = dotenv::dotenv();
+    env_logger::init();
+
+    let mexc_spot = SpotApi::<ApiCred>::from_env();
+
+    let res = async move {
+        let mut stream = mexc_spot.order_book_stream("BTCUSDT").await?;
+        while let Some(update) = stream.next().await {
+            match update {
+                Ok(update) if update.is_ready => println!(
+                    "{} #{:?}: bid {:?} / ask {:?}",
+                    update.symbol, update.last_update_id, update.best_bid, update.best_ask
+                ),
+                Ok(update) => println!("{}: syncing", update.symbol),
+                Err(e) => log::warn!("order book stream: {e}"),
+            }
+        }
+        Ok::<(), MexcError>(())
+    };
+    println!("Execution stopped with: {:?}", res.await);
+}
@@ -15,11 +15,13 @@ mod account;
 mod market_data;
 mod user_data_stream;
 mod wallet;
+mod websocket_market;
 
 pub use self::account::*;
 pub use self::market_data::*;
 pub use self::user_data_stream::*;
 pub use self::wallet::*;
+pub use self::websocket_market::*;
 use crate::client::MexcSigner;
 
 pub const API_BASE: &str = "https://api.mexc.com/";
@@ -66,18 +68,34 @@ mod with_network {
                 Url::parse(API_BASE).unwrap(),
                 Url::parse(STREAM_BASE).unwrap(),
             );
+            SpotApi::with_base_urls(signer, api_base, stream_base, proxy)
+        }
+
+        /// Custom REST and websocket base URLs, e.g. a local mock server.
+        ///
+        /// The proxy applies to both REST and websocket connections.
+        pub fn with_base_urls(
+            signer: S,
+            api_base: Url,
+            stream_base: Url,
+            proxy: Option<Proxy>,
+        ) -> Self {
             SpotApi::with_config(Config::new(signer, api_base, stream_base, proxy))
         }
 
         /// Reads config from env vars with names like:
         /// "CCX_MEXC_API_KEY", "CCX_MEXC_API_SECRET"
+        ///
+        /// Requests are routed through a SOCKS5 proxy when "CCX_MEXC_API_PROXY_HOST" and
+        /// "CCX_MEXC_API_PROXY_PORT" are set ("CCX_MEXC_API_PROXY_USERNAME" and
+        /// "CCX_MEXC_API_PROXY_PASSWORD" are optional).
         pub fn from_env() -> SpotApi<ApiCred> {
             let proxy = Proxy::from_env_with_prefix(CCX_MEXC_API_PREFIX);
             SpotApi::new(ApiCred::from_env_with_prefix(CCX_MEXC_API_PREFIX), proxy)
         }
 
         /// Reads config from env vars with names like:
-        /// "${prefix}_KEY", "${prefix}_SECRET"
+        /// "${prefix}_KEY", "${prefix}_SECRET", and "${prefix}_PROXY_*"
         pub fn from_env_with_prefix(prefix: &str) -> SpotApi<ApiCred> {
             let proxy = Proxy::from_env_with_prefix(prefix);
             SpotApi::new(ApiCred::from_env_with_prefix(prefix), proxy)
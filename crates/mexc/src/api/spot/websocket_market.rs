@@ -0,0 +1,276 @@
+use super::prelude::*;
+use crate::util::Ask;
+use crate::util::Bid;
+
+/// State of the local order book after a diff from the stream, see [`MexcWebsocket`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderBookUpdate {
+    pub symbol: Atom,
+    /// Whether the book is in sync with the stream. The best bid and ask are not
+    /// reliable until then.
+    pub is_ready: bool,
+    /// Version of the latest applied diff, `None` while the snapshot is not applied.
+    pub last_update_id: Option<u64>,
+    pub best_bid: Option<Bid>,
+    pub best_ask: Option<Ask>,
+}
+
+#[cfg(feature = "with_network")]
+pub use with_network::*;
+
+#[cfg(feature = "with_network")]
+mod with_network {
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    use ::awc::BoxedSocket;
+    use actix_codec::Framed;
+    use actix_http::ws;
+    use actix_http::ws::Codec;
+    use ccx_api_lib::SnapshotBackoff;
+    use futures::FutureExt;
+    use futures::SinkExt;
+    use futures::Stream;
+    use futures::StreamExt;
+    use futures::channel::mpsc;
+    use futures::future::Fuse;
+    use futures::future::FusedFuture;
+    use futures::future::LocalBoxFuture;
+
+    use super::*;
+    use crate::util::OrderBook;
+    use crate::util::OrderBookUpdater;
+    use crate::ws_stream::IncreaseDepth;
+    use crate::ws_stream::PushCommand;
+    use crate::ws_stream::PushMessage;
+    use crate::ws_stream::increase_depth_channel;
+
+    /// MEXC closes connections which send nothing for a minute.
+    const PING_INTERVAL: Duration = Duration::from_secs(20);
+    const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+    const SNAPSHOT_LIMIT: u16 = 1000;
+
+    type Connection = Framed<BoxedSocket, Codec>;
+
+    /// Order book of a symbol kept in sync with
+    /// `spot@public.increase.depth.v3.api@<SYMBOL>`, see [`SpotApi::order_book_stream`].
+    ///
+    /// The channel is pushed as JSON text frames, see [`crate::ws_stream::Push`].
+    /// Binary (protobuf) frames are only sent for `.pb` channels and are ignored.
+    ///
+    /// Yields an update after every diff. Errors, e.g. a failed snapshot request or a lost
+    /// connection, are passed through without ending the stream. The connection is
+    /// reestablished when the server closes it and the book is then synced again.
+    pub struct MexcWebsocket {
+        rx: mpsc::UnboundedReceiver<MexcResult<OrderBookUpdate>>,
+        task: actix_rt::task::JoinHandle<()>,
+    }
+
+    impl<S> SpotApi<S>
+    where
+        S: crate::client::MexcSigner,
+        S: Unpin + 'static,
+    {
+        /// Local order book of `symbol`.
+        ///
+        /// Subscribes to the diff depth channel and applies the diffs on top of a REST
+        /// snapshot. When a diff does not continue the book a new snapshot is fetched.
+        /// Snapshots requested one after another are delayed increasingly, up to a minute.
+        pub async fn order_book_stream(&self, symbol: &str) -> MexcResult<MexcWebsocket>
+        where
+            S: Clone,
+        {
+            let symbol = symbol.to_uppercase();
+            let connection = connect(self, &symbol).await?;
+            let (tx, rx) = mpsc::unbounded();
+            let task = actix_rt::spawn(run_order_book_stream(self.clone(), symbol, connection, tx));
+            Ok(MexcWebsocket { rx, task })
+        }
+    }
+
+    async fn connect<S>(api: &SpotApi<S>, symbol: &str) -> MexcResult<Connection>
+    where
+        S: crate::client::MexcSigner,
+        S: Unpin + 'static,
+    {
+        let mut connection = api.client.push_connection().await?;
+        let cmd = PushCommand::Subscribe(vec![increase_depth_channel(symbol)]);
+        send(&mut connection, &cmd).await?;
+        Ok(connection)
+    }
+
+    async fn send(connection: &mut Connection, cmd: &PushCommand) -> MexcResult<()> {
+        let msg = serde_json::to_string(cmd)?;
+        log::debug!("Sending to server: `{}`", msg);
+        connection.send(ws::Message::Text(msg.into())).await?;
+        Ok(())
+    }
+
+    async fn run_order_book_stream<S>(
+        api: SpotApi<S>,
+        symbol: String,
+        mut connection: Connection,
+        tx: mpsc::UnboundedSender<MexcResult<OrderBookUpdate>>,
+    ) where
+        S: crate::client::MexcSigner,
+        S: Unpin + 'static,
+    {
+        let mut backoff = SnapshotBackoff::new();
+        loop {
+            let e = match sync_order_book(&api, &symbol, &mut connection, &mut backoff, &tx).await {
+                Ok(()) => return,
+                Err(e) => e,
+            };
+            if tx.unbounded_send(Err(e)).is_err() {
+                return;
+            }
+
+            connection = loop {
+                log::warn!("{symbol} order book stream disconnected, reconnecting");
+                actix_rt::time::sleep(RECONNECT_DELAY).await;
+                if tx.is_closed() {
+                    return;
+                }
+                match connect(&api, &symbol).await {
+                    Ok(connection) => break connection,
+                    Err(e) => {
+                        if tx.unbounded_send(Err(e)).is_err() {
+                            return;
+                        }
+                    }
+                }
+            };
+        }
+    }
+
+    /// Returns `Ok` when the downstream is gone and `Err` when the connection is lost.
+    async fn sync_order_book<S>(
+        api: &SpotApi<S>,
+        symbol: &str,
+        connection: &mut Connection,
+        backoff: &mut SnapshotBackoff,
+        tx: &mpsc::UnboundedSender<MexcResult<OrderBookUpdate>>,
+    ) -> MexcResult<()>
+    where
+        S: crate::client::MexcSigner,
+        S: Unpin + 'static,
+    {
+        let mut updater = OrderBookUpdater::new();
+        let mut ping = actix_rt::time::interval(PING_INTERVAL);
+        // At most one snapshot is requested at a time, diffs are buffered meanwhile.
+        let mut snapshot: Fuse<LocalBoxFuture<'_, MexcResult<OrderBook>>> = Fuse::terminated();
+        loop {
+            futures::select! {
+                frame = connection.next().fuse() => {
+                    let text = match frame {
+                        Some(Ok(ws::Frame::Text(text))) => text,
+                        Some(Ok(ws::Frame::Ping(msg))) => {
+                            connection.send(ws::Message::Pong(msg)).await?;
+                            continue;
+                        }
+                        Some(Ok(ws::Frame::Pong(_))) => continue,
+                        Some(Ok(ws::Frame::Binary(_))) => {
+                            log::warn!("unexpected binary message (ignored)");
+                            continue;
+                        }
+                        Some(Ok(ws::Frame::Close(reason))) => {
+                            Err(MexcError::other(format!("closed by server: {reason:?}")))?
+                        }
+                        Some(Ok(ws::Frame::Continuation(_))) => {
+                            Err(MexcError::other("unexpected continuation frame"))?
+                        }
+                        Some(Err(e)) => Err(e)?,
+                        None => Err(MexcError::other("connection closed"))?,
+                    };
+
+                    let diff = match serde_json::from_slice::<PushMessage<IncreaseDepth>>(&text) {
+                        Ok(PushMessage::Push(push)) => push.into(),
+                        Ok(PushMessage::Response(response)) => {
+                            if response.is_error() {
+                                let e = MexcError::other(response.msg);
+                                if tx.unbounded_send(Err(e)).is_err() {
+                                    return Ok(());
+                                }
+                            } else {
+                                log::debug!("{:?}", response);
+                            }
+                            continue;
+                        }
+                        Err(e) => {
+                            log::error!(
+                                "Failed to deserialize server message: {:?}: {}",
+                                e,
+                                String::from_utf8_lossy(&text)
+                            );
+                            continue;
+                        }
+                    };
+
+                    // The updater goes back to buffering on a gap.
+                    if let Err(e) = updater.push_diff(diff) {
+                        log::warn!("{symbol} order book: {e}, resyncing");
+                    }
+                }
+                _ = ping.tick().fuse() => {
+                    send(connection, &PushCommand::Ping).await?;
+                    continue;
+                }
+                res = snapshot => match res {
+                    // A snapshot older than the buffered diffs keeps them for the next one.
+                    Ok(book) => {
+                        if let Err(e) = updater.init(book) {
+                            log::debug!("{symbol} order book: {e}");
+                        }
+                    }
+                    Err(e) => {
+                        if tx.unbounded_send(Err(e)).is_err() {
+                            return Ok(());
+                        }
+                    }
+                },
+            }
+
+            if updater.state().is_none() && snapshot.is_terminated() {
+                let delay = backoff.next_delay();
+                snapshot = async move {
+                    actix_rt::time::sleep(delay).await;
+                    Ok(api.depth(symbol, Some(SNAPSHOT_LIMIT))?.await?.into())
+                }
+                .boxed_local()
+                .fuse();
+            }
+
+            let state = updater.state();
+            let update = OrderBookUpdate {
+                symbol: symbol.into(),
+                is_ready: updater.is_ready(),
+                last_update_id: state.map(|state| state.last_update_id()),
+                best_bid: state
+                    .and_then(|state| state.next_bid())
+                    .map(|(&price, &qty)| Bid { price, qty }),
+                best_ask: state
+                    .and_then(|state| state.next_ask())
+                    .map(|(&price, &qty)| Ask { price, qty }),
+            };
+            if tx.unbounded_send(Ok(update)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    impl Stream for MexcWebsocket {
+        type Item = MexcResult<OrderBookUpdate>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_next_unpin(cx)
+        }
+    }
+
+    impl Drop for MexcWebsocket {
+        fn drop(&mut self) {
+            self.task.abort();
+        }
+    }
+}
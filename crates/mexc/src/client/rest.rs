@@ -1,11 +1,14 @@
 use std::sync::Arc;
 use std::time::Instant;
 
+use ::awc::BoxedSocket;
+use actix_codec::Framed;
 use actix_http::BoxedPayloadStream;
 use actix_http::Method;
 use actix_http::Payload;
 use actix_http::Uri;
 use actix_http::encoding::Decoder;
+use actix_http::ws::Codec;
 use ccx_api_lib::Client;
 use ccx_api_lib::ClientRequest;
 use ccx_api_lib::ClientResponse;
@@ -106,6 +109,16 @@ where
         let url = self.inner.config.stream_base.clone();
         WebsocketStream::connect(self.clone(), url).await
     }
+
+    /// Raw connection to the stream endpoint speaking the native MEXC protocol, see
+    /// [`crate::ws_stream::PushCommand`].
+    pub(crate) async fn push_connection(&self) -> MexcResult<Framed<BoxedSocket, Codec>> {
+        let url = self.inner.config.stream_base.as_str();
+        log::debug!("Connecting WS: {}", url);
+        let (response, connection) = self.client_h1().ws(url).connect().await?;
+        log::debug!("{:?}", response);
+        Ok(connection)
+    }
 }
 
 impl<S> RequestBuilder<S>
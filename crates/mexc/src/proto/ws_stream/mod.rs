@@ -10,6 +10,7 @@ mod kline;
 mod mini_ticker;
 mod order_trade;
 mod orderbook_diff;
+mod push;
 mod ticker;
 mod trade;
 mod ws_kline;
@@ -22,6 +23,7 @@ pub use kline::*;
 pub use mini_ticker::*;
 pub use order_trade::*;
 pub use orderbook_diff::*;
+pub use push::*;
 pub use ticker::*;
 pub use trade::*;
 pub use ws_kline::*;
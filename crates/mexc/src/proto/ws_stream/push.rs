@@ -0,0 +1,218 @@
+//! Native MEXC v3 websocket protocol.
+//!
+//! Requests are `{"method": "SUBSCRIPTION", "params": [<channel>, ..]}`, the server
+//! answers them with `{"id": 0, "code": 0, "msg": <channel or PONG>}`.
+//!
+//! Channels without the `.pb` suffix, e.g. `spot@public.increase.depth.v3.api@<SYMBOL>`,
+//! are pushed as JSON text frames `{"c": <channel>, "d": <data>, "s": <symbol>, "t": <time>}`.
+//! Protobuf `.pb` channels come in binary frames and are not decoded by this crate.
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::de;
+
+use super::OrderBookDiffEvent;
+use crate::Atom;
+use crate::Decimal;
+use crate::util::Ask;
+use crate::util::Bid;
+
+pub const INCREASE_DEPTH_CHANNEL: &str = "spot@public.increase.depth.v3.api";
+
+/// `spot@public.increase.depth.v3.api@<SYMBOL>`
+pub fn increase_depth_channel(symbol: &str) -> String {
+    format!("{INCREASE_DEPTH_CHANNEL}@{}", symbol.to_uppercase())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(tag = "method", content = "params")]
+pub enum PushCommand {
+    #[serde(rename = "SUBSCRIPTION")]
+    Subscribe(Vec<String>),
+    #[serde(rename = "UNSUBSCRIPTION")]
+    Unsubscribe(Vec<String>),
+    #[serde(rename = "PING")]
+    Ping,
+}
+
+/// Any text frame of the native protocol.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PushMessage<T> {
+    Push(Push<T>),
+    Response(PushResponse),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct PushResponse {
+    pub id: u64,
+    pub code: i32,
+    /// The channel name for a successful subscription, `PONG` for a ping and the
+    /// reason otherwise.
+    pub msg: String,
+}
+
+impl PushResponse {
+    pub fn is_pong(&self) -> bool {
+        self.msg == "PONG"
+    }
+
+    /// MEXC reports failed subscriptions with code 0 and a `Not Subscribed` message.
+    pub fn is_error(&self) -> bool {
+        self.code != 0 || self.msg.starts_with("Not Subscribed")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct Push<T> {
+    /// Channel.
+    #[serde(rename = "c")]
+    pub channel: String,
+    /// Data.
+    #[serde(rename = "d")]
+    pub data: T,
+    /// Symbol.
+    #[serde(rename = "s")]
+    pub symbol: Atom,
+    /// Event time.
+    #[serde(rename = "t")]
+    pub time: u64,
+}
+
+/// Data of `spot@public.increase.depth.v3.api@<SYMBOL>`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct IncreaseDepth {
+    #[serde(default)]
+    pub asks: Vec<PushDepthLevel>,
+    #[serde(default)]
+    pub bids: Vec<PushDepthLevel>,
+    /// Event type.
+    #[serde(rename = "e")]
+    pub event_type: String,
+    /// Version, consecutive pushes increase it by one.
+    #[serde(rename = "r", deserialize_with = "version")]
+    pub version: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub struct PushDepthLevel {
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    /// Zero means the level is removed.
+    #[serde(rename = "v")]
+    pub qty: Decimal,
+}
+
+/// A push carries a single version, so it becomes a diff with
+/// `first_update_id == final_update_id` which [`crate::util::OrderBookUpdater`] applies
+/// against the `lastUpdateId` of the REST snapshot.
+impl From<Push<IncreaseDepth>> for OrderBookDiffEvent {
+    fn from(push: Push<IncreaseDepth>) -> Self {
+        let Push {
+            data, symbol, time, ..
+        } = push;
+        OrderBookDiffEvent {
+            event_type: (),
+            event_time: time,
+            symbol,
+            first_update_id: data.version,
+            final_update_id: data.version,
+            bids: data
+                .bids
+                .into_iter()
+                .map(|v| Bid {
+                    price: v.price,
+                    qty: v.qty,
+                })
+                .collect(),
+            asks: data
+                .asks
+                .into_iter()
+                .map(|v| Ask {
+                    price: v.price,
+                    qty: v.qty,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Version {
+        Number(u64),
+        String(String),
+    }
+
+    match Version::deserialize(deserializer)? {
+        Version::Number(v) => Ok(v),
+        Version::String(v) => v.parse().map_err(de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_command() {
+        let cmd = PushCommand::Subscribe(vec![increase_depth_channel("btcusdt")]);
+        assert_eq!(
+            serde_json::to_string(&cmd).unwrap(),
+            r#"{"method":"SUBSCRIPTION","params":["spot@public.increase.depth.v3.api@BTCUSDT"]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&PushCommand::Ping).unwrap(),
+            r#"{"method":"PING"}"#
+        );
+    }
+
+    #[test]
+    fn test_decode_increase_depth() {
+        let json = r#"{
+            "c": "spot@public.increase.depth.v3.api@BTCUSDT",
+            "d": {
+                "asks": [{"p": "20290.89", "v": "0.00000000"}],
+                "e": "spot@public.increase.depth.v3.api",
+                "r": "3407459756"
+            },
+            "s": "BTCUSDT",
+            "t": 1661932660144
+        }"#;
+        let msg: PushMessage<IncreaseDepth> = serde_json::from_str(json).unwrap();
+        let PushMessage::Push(push) = msg else {
+            panic!("unexpected message: {msg:?}");
+        };
+        let diff = OrderBookDiffEvent::from(push);
+        assert_eq!(diff.first_update_id, 3407459756);
+        assert_eq!(diff.final_update_id, 3407459756);
+        assert_eq!(diff.symbol, Atom::from("BTCUSDT"));
+        assert!(diff.bids.is_empty());
+        assert_eq!(
+            diff.asks,
+            vec![Ask {
+                price: dec!(20290.89),
+                qty: dec!(0),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_decode_response() {
+        let json = r#"{"id":0,"code":0,"msg":"spot@public.increase.depth.v3.api@BTCUSDT"}"#;
+        let msg: PushMessage<IncreaseDepth> = serde_json::from_str(json).unwrap();
+        let PushMessage::Response(response) = msg else {
+            panic!("unexpected message: {msg:?}");
+        };
+        assert!(!response.is_error());
+        assert!(!response.is_pong());
+
+        let json = r#"{"id":0,"code":0,"msg":"Not Subscribed successfully! [spot@public.increase.depth.v3.api@BTCUSDT].  Reason： Blocked! "}"#;
+        let response: PushResponse = serde_json::from_str(json).unwrap();
+        assert!(response.is_error());
+    }
+}
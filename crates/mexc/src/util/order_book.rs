@@ -1,5 +1,9 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
 
+use ccx_api_lib::DiffSequence;
+use ccx_api_lib::OrderBookDesynced;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::Zero;
 use serde::Deserialize;
@@ -7,16 +11,23 @@ use serde::Serialize;
 
 use crate::MexcError;
 use crate::MexcResult;
+use crate::api::spot::OrderSide;
 use crate::ws_stream::OrderBookDiffEvent;
 
+/// Local order book maintained from a depth snapshot and the diff stream.
+///
+/// Diffs are buffered until [`init`](Self::init) gets a snapshot. When the diffs do not
+/// continue the book, [`MexcError::OrderBookDesynced`] is returned and the updater goes back
+/// to buffering, so a new snapshot has to be passed to `init`.
 pub enum OrderBookUpdater {
     Preparing { buffer: Vec<OrderBookDiffEvent> },
     Ready { state: OrderBookState },
 }
 
 pub struct OrderBookState {
-    last_update_id: u64,
-    dirty: bool,
+    sequence: DiffSequence,
+    /// When the snapshot or the latest diff was applied.
+    last_update_instant: Instant,
     asks: BTreeMap<Decimal, Decimal>,
     bids: BTreeMap<Decimal, Decimal>,
 }
@@ -27,6 +38,19 @@ pub struct Fill {
     pub exhausted: bool,
 }
 
+/// Expected execution of a market order against the book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MarketImpact {
+    /// Volume-weighted average price of the filled amount.
+    pub vwap: Decimal,
+    /// Distance of `vwap` from the touch price in basis points, positive when worse.
+    pub slippage_bps: Decimal,
+    /// Number of price levels the order reaches into.
+    pub levels_consumed: usize,
+    /// Whether the book can not cover the whole amount.
+    pub exhausted: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct OrderBook {
     pub last_update_id: u64,
@@ -58,20 +82,46 @@ impl OrderBookUpdater {
         }
     }
 
+    /// Whether the snapshot has been received and a diff following it was applied.
+    pub fn is_ready(&self) -> bool {
+        self.state().is_some_and(OrderBookState::is_ready)
+    }
+
     pub fn push_diff(&mut self, update: OrderBookDiffEvent) -> MexcResult<()> {
         match self {
             OrderBookUpdater::Preparing { buffer } => buffer.push(update),
-            OrderBookUpdater::Ready { state } => state.update(update)?,
+            OrderBookUpdater::Ready { state } => match state.check(&update) {
+                Ok(true) => state.apply(update),
+                Ok(false) => {}
+                Err(e) => {
+                    *self = OrderBookUpdater::Preparing {
+                        buffer: vec![update],
+                    };
+                    Err(e)?
+                }
+            },
         }
         Ok(())
     }
 
+    /// Applies the buffered diffs on top of `snapshot`.
+    ///
+    /// Fails with [`MexcError::OrderBookDesynced`] when the snapshot is older than the
+    /// buffered diffs, which are kept for the next snapshot.
     pub fn init(&mut self, snapshot: OrderBook) -> MexcResult<()> {
         match self {
             OrderBookUpdater::Preparing { buffer } => {
                 let mut state = OrderBookState::new(snapshot);
-                for diff in buffer.drain(..) {
-                    state.update(diff)?;
+                let mut diffs = std::mem::take(buffer).into_iter();
+                while let Some(diff) = diffs.next() {
+                    match state.check(&diff) {
+                        Ok(true) => state.apply(diff),
+                        Ok(false) => {}
+                        Err(e) => {
+                            *buffer = std::iter::once(diff).chain(diffs).collect();
+                            return Err(e.into());
+                        }
+                    }
                 }
                 *self = OrderBookUpdater::Ready { state };
                 Ok(())
@@ -93,13 +143,53 @@ impl Default for OrderBookUpdater {
 impl OrderBookState {
     pub fn new(snapshot: OrderBook) -> Self {
         OrderBookState {
-            last_update_id: snapshot.last_update_id,
-            dirty: true,
-            asks: snapshot.asks.iter().map(|v| (v.price, v.qty)).collect(),
-            bids: snapshot.bids.iter().map(|v| (v.price, v.qty)).collect(),
+            sequence: DiffSequence::new(snapshot.last_update_id),
+            last_update_instant: Instant::now(),
+            // A zero quantity means there is no level, like in diffs.
+            asks: snapshot
+                .asks
+                .iter()
+                .filter(|v| !v.qty.is_zero())
+                .map(|v| (v.price, v.qty))
+                .collect(),
+            bids: snapshot
+                .bids
+                .iter()
+                .filter(|v| !v.qty.is_zero())
+                .map(|v| (v.price, v.qty))
+                .collect(),
         }
     }
 
+    /// Update id of the snapshot or of the latest applied diff.
+    pub fn last_update_id(&self) -> u64 {
+        self.sequence.last_update_id()
+    }
+
+    /// Whether a diff following the snapshot was applied, i.e. the book is in sync with
+    /// the stream.
+    pub fn is_ready(&self) -> bool {
+        self.sequence.is_synced()
+    }
+
+    /// When the snapshot or the latest diff was applied.
+    pub fn last_update_instant(&self) -> Instant {
+        self.last_update_instant
+    }
+
+    /// Time elapsed since the snapshot or the latest diff was applied.
+    pub fn age(&self) -> Duration {
+        self.last_update_instant.elapsed()
+    }
+
+    /// Whether the book has not been updated for longer than `max_age`.
+    ///
+    /// A quiet market and a stalled stream look the same here, so consumers should not
+    /// quote off a stale book either way.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+
     pub fn asks(&self) -> &BTreeMap<Decimal, Decimal> {
         &self.asks
     }
@@ -117,51 +207,21 @@ impl OrderBookState {
     }
 
     pub fn ask_avg(&self) -> Option<(Decimal, Decimal)> {
-        // lowest 10 ask levels
-        let levels = self.asks.iter().take(10);
-
-        let mut total_price = Decimal::zero();
-        let mut total_volume = Decimal::zero();
-        let mut count = 0;
-
-        for (price, volume) in levels {
-            total_price += price * volume;
-            total_volume += volume;
-            count += 1;
-        }
-
-        if count == 0 || total_volume == Decimal::zero() {
-            return None;
-        }
-
-        Some((
-            total_price / total_volume,
-            total_volume / Decimal::from(count),
-        ))
+        self.ask_avg_n(10)
     }
 
     pub fn bid_avg(&self) -> Option<(Decimal, Decimal)> {
-        // highest 10 bid levels
-        let levels = self.bids.iter().rev().take(10);
-
-        let mut total_price = Decimal::zero();
-        let mut total_volume = Decimal::zero();
-        let mut count = 0;
-
-        for (price, volume) in levels {
-            total_price += price * volume;
-            total_volume += volume;
-            count += 1;
-        }
+        self.bid_avg_n(10)
+    }
 
-        if count == 0 || total_volume == Decimal::zero() {
-            return None;
-        }
+    /// Volume-weighted average price and average volume over the lowest `n` ask levels.
+    pub fn ask_avg_n(&self, n: usize) -> Option<(Decimal, Decimal)> {
+        levels_avg(self.asks.iter().take(n))
+    }
 
-        Some((
-            total_price / total_volume,
-            total_volume / Decimal::from(count),
-        ))
+    /// Volume-weighted average price and average volume over the highest `n` bid levels.
+    pub fn bid_avg_n(&self, n: usize) -> Option<(Decimal, Decimal)> {
+        levels_avg(self.bids.iter().rev().take(n))
     }
 
     pub fn ask_volume(&self, price_limit: &Decimal) -> Fill {
@@ -202,6 +262,47 @@ impl OrderBookState {
         }
     }
 
+    /// Expected execution of a market order for `amount` of the base asset.
+    ///
+    /// Returns `None` when the side the order takes from is empty.
+    pub fn market_impact(&self, side: OrderSide, amount: Decimal) -> Option<MarketImpact> {
+        let (touch, levels): (_, Box<dyn Iterator<Item = (&Decimal, &Decimal)>>) = match side {
+            OrderSide::Buy => (self.next_ask()?.0, Box::new(self.asks.iter())),
+            OrderSide::Sell => (self.next_bid()?.0, Box::new(self.bids.iter().rev())),
+        };
+        let touch = *touch;
+
+        let mut remaining = amount;
+        let mut quote_value = Decimal::zero();
+        let mut levels_consumed = 0;
+        for (price, volume) in levels {
+            if remaining <= Decimal::zero() {
+                break;
+            }
+            let qty = remaining.min(*volume);
+            quote_value += qty * price;
+            remaining -= qty;
+            levels_consumed += 1;
+        }
+
+        let filled = amount - remaining.max(Decimal::zero());
+        let vwap = if filled.is_zero() {
+            touch
+        } else {
+            quote_value / filled
+        };
+        let slippage = match side {
+            OrderSide::Buy => vwap - touch,
+            OrderSide::Sell => touch - vwap,
+        };
+        Some(MarketImpact {
+            vwap,
+            slippage_bps: slippage / touch * Decimal::from(10_000),
+            levels_consumed,
+            exhausted: remaining > Decimal::zero(),
+        })
+    }
+
     pub fn spread(&self) -> Decimal {
         let ask = self.next_ask().map(|(p, _)| p).cloned().unwrap_or_default();
         let bid = self.next_bid().map(|(p, _)| p).cloned().unwrap_or_default();
@@ -209,37 +310,20 @@ impl OrderBookState {
     }
 
     pub fn update(&mut self, diff: OrderBookDiffEvent) -> MexcResult<()> {
-        /*
-           Drop any event where final_update_id is <= lastUpdateId in the snapshot.
-
-           The first processed event should have
-               first_update_id <= lastUpdateId+1 AND final_update_id >= lastUpdateId+1.
-
-           While listening to the stream, each new event's first_update_id should be equal
-               to the previous event's final_update_id + 1.
-        */
-        let next_id = self.last_update_id + 1;
-        if self.dirty {
-            if diff.final_update_id < next_id {
-                // Ignore an old update.
-                return Ok(());
-            }
-            if diff.first_update_id > next_id {
-                Err(MexcError::other(format!(
-                    "first_update_id > next_id:   {};   {}",
-                    diff.first_update_id, next_id
-                )))?
-            }
-            // ^^ ensures diff.first_update_id <= next_id && diff.final_update_id > next_id
-            self.dirty = false;
-        } else if diff.first_update_id != next_id {
-            Err(MexcError::other(format!(
-                "first_update_id != next_id:   {};   {}",
-                diff.first_update_id, next_id
-            )))?
+        if self.check(&diff)? {
+            self.apply(diff);
         }
+        Ok(())
+    }
 
-        self.last_update_id = diff.final_update_id;
+    /// Whether `diff` continues the book, `false` for an outdated diff.
+    fn check(&mut self, diff: &OrderBookDiffEvent) -> Result<bool, OrderBookDesynced> {
+        self.sequence
+            .accept(diff.first_update_id, diff.final_update_id)
+    }
+
+    fn apply(&mut self, diff: OrderBookDiffEvent) {
+        self.last_update_instant = Instant::now();
 
         for e in diff.asks {
             if e.qty.is_zero() {
@@ -255,6 +339,214 @@ impl OrderBookState {
                 self.bids.insert(e.price, e.qty);
             }
         }
-        Ok(())
+    }
+}
+
+fn levels_avg<'a>(
+    levels: impl Iterator<Item = (&'a Decimal, &'a Decimal)>,
+) -> Option<(Decimal, Decimal)> {
+    let mut total_price = Decimal::zero();
+    let mut total_volume = Decimal::zero();
+    let mut count = 0;
+
+    for (price, volume) in levels {
+        total_price += price * volume;
+        total_volume += volume;
+        count += 1;
+    }
+
+    if count == 0 || total_volume == Decimal::zero() {
+        return None;
+    }
+
+    Some((
+        total_price / total_volume,
+        total_volume / Decimal::from(count),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn book() -> OrderBookState {
+        OrderBookState::new(OrderBook {
+            last_update_id: 1,
+            bids: vec![
+                Bid {
+                    price: dec!(99),
+                    qty: dec!(1),
+                },
+                Bid {
+                    price: dec!(98),
+                    qty: dec!(3),
+                },
+            ]
+            .into(),
+            asks: vec![
+                Ask {
+                    price: dec!(101),
+                    qty: dec!(1),
+                },
+                Ask {
+                    price: dec!(102),
+                    qty: dec!(3),
+                },
+            ]
+            .into(),
+        })
+    }
+
+    fn diff(first_update_id: u64, final_update_id: u64) -> OrderBookDiffEvent {
+        OrderBookDiffEvent {
+            event_type: (),
+            event_time: 0,
+            symbol: "BTCUSDT".into(),
+            first_update_id,
+            final_update_id,
+            bids: vec![],
+            asks: vec![Ask {
+                price: dec!(101),
+                qty: dec!(2),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_snapshot_zero_levels() {
+        let book = OrderBookState::new(OrderBook {
+            last_update_id: 1,
+            bids: vec![
+                Bid {
+                    price: dec!(99.5),
+                    qty: dec!(0),
+                },
+                Bid {
+                    price: dec!(99),
+                    qty: dec!(1),
+                },
+            ]
+            .into(),
+            asks: vec![
+                Ask {
+                    price: dec!(100),
+                    qty: dec!(0),
+                },
+                Ask {
+                    price: dec!(101),
+                    qty: dec!(1),
+                },
+            ]
+            .into(),
+        });
+        assert_eq!(book.asks().len(), 1);
+        assert_eq!(book.bids().len(), 1);
+        assert_eq!(book.next_ask(), Some((&dec!(101), &dec!(1))));
+        assert_eq!(book.next_bid(), Some((&dec!(99), &dec!(1))));
+        assert_eq!(book.spread(), dec!(2));
+    }
+
+    #[test]
+    fn test_staleness() {
+        let mut book = book();
+        assert!(!book.is_stale(Duration::from_secs(60)));
+
+        let old = Instant::now() - Duration::from_secs(10);
+        book.last_update_instant = old;
+        assert!(book.age() >= Duration::from_secs(10));
+        assert!(book.is_stale(Duration::from_secs(5)));
+        assert!(!book.is_stale(Duration::from_secs(60)));
+
+        // An outdated diff is not applied and does not refresh the book.
+        book.update(diff(1, 1)).unwrap();
+        assert_eq!(book.last_update_instant(), old);
+
+        book.update(diff(2, 3)).unwrap();
+        assert!(book.last_update_instant() > old);
+        assert!(!book.is_stale(Duration::from_secs(5)));
+        assert_eq!(book.last_update_id(), 3);
+        assert_eq!(book.next_ask(), Some((&dec!(101), &dec!(2))));
+    }
+
+    #[test]
+    fn test_readiness() {
+        let mut updater = OrderBookUpdater::new();
+        updater.push_diff(diff(1, 1)).unwrap();
+        assert!(!updater.is_ready());
+
+        updater
+            .init(OrderBook {
+                last_update_id: 1,
+                bids: Box::new([]),
+                asks: Box::new([]),
+            })
+            .unwrap();
+        // The buffered diff is outdated, so the book is not in sync yet.
+        assert!(!updater.is_ready());
+        assert_eq!(updater.state().unwrap().last_update_id(), 1);
+
+        updater.push_diff(diff(2, 4)).unwrap();
+        assert!(updater.is_ready());
+        assert_eq!(updater.state().unwrap().last_update_id(), 4);
+
+        // A diff got lost, the updater waits for a new snapshot.
+        assert!(matches!(
+            updater.push_diff(diff(6, 7)),
+            Err(MexcError::OrderBookDesynced(OrderBookDesynced {
+                expected: 5,
+                first_update_id: 6,
+            }))
+        ));
+        assert!(updater.state().is_none());
+        updater
+            .init(OrderBook {
+                last_update_id: 5,
+                bids: Box::new([]),
+                asks: Box::new([]),
+            })
+            .unwrap();
+        assert_eq!(updater.state().unwrap().last_update_id(), 7);
+    }
+
+    #[test]
+    fn test_market_impact() {
+        let book = book();
+
+        let impact = book.market_impact(OrderSide::Buy, dec!(2)).unwrap();
+        assert_eq!(impact.vwap, dec!(101.5));
+        assert_eq!(impact.slippage_bps.round_dp(2), dec!(49.50));
+        assert_eq!(impact.levels_consumed, 2);
+        assert!(!impact.exhausted);
+
+        let impact = book.market_impact(OrderSide::Sell, dec!(0.5)).unwrap();
+        assert_eq!(impact.vwap, dec!(99));
+        assert!(impact.slippage_bps.is_zero());
+        assert_eq!(impact.levels_consumed, 1);
+
+        let impact = book.market_impact(OrderSide::Sell, dec!(5)).unwrap();
+        assert_eq!(impact.vwap, dec!(98.25));
+        assert_eq!(impact.levels_consumed, 2);
+        assert!(impact.exhausted);
+
+        let empty = OrderBookState::new(OrderBook {
+            last_update_id: 1,
+            bids: Box::new([]),
+            asks: Box::new([]),
+        });
+        assert_eq!(empty.market_impact(OrderSide::Buy, dec!(1)), None);
+    }
+
+    #[test]
+    fn test_avg_n_levels() {
+        let book = book();
+        assert_eq!(book.ask_avg_n(1), Some((dec!(101), dec!(1))));
+        assert_eq!(book.ask_avg_n(2), Some((dec!(101.75), dec!(2))));
+        assert_eq!(book.bid_avg_n(1), Some((dec!(99), dec!(1))));
+        assert_eq!(book.bid_avg_n(2), Some((dec!(98.25), dec!(2))));
+        assert_eq!(book.ask_avg_n(0), None);
+        assert_eq!(book.ask_avg(), book.ask_avg_n(25));
+        assert_eq!(book.bid_avg(), book.bid_avg_n(25));
     }
 }